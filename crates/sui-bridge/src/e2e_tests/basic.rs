@@ -194,7 +194,8 @@ async fn test_add_new_coins_on_sui_and_eth() {
         vec![token_price],
         1, // seq num
     )
-    .await;
+    .await
+    .unwrap();
     let new_token_erc_address = bridge_test_cluster.contracts().ka;
     let eth_action = BridgeAction::AddTokensOnEvmAction(AddTokensOnEvmAction {
         nonce: 0,
@@ -1020,7 +1020,7 @@ impl TestClusterWrapperBuilder {
                 token_prices,
                 0,
             );
-            let action = action.await;
+            let action = action.await.unwrap();
             info!("register tokens took {:?} secs", timer.elapsed().as_secs());
             let sig_map = self
                 .bridge_authority_keys
@@ -1449,7 +1449,7 @@ async fn deposit_eth_to_sui_package(
             .await
             .unwrap(),
     );
-    let tx = wallet_context.sign_transaction(&tx_data);
+    let tx = wallet_context.sign_transaction(&tx_data).unwrap();
     wallet_context.execute_transaction_may_fail(tx).await
 }
 
@@ -253,7 +253,7 @@ pub async fn publish_and_register_coins_return_add_coins_on_sui_action(
     token_ids: Vec<u8>,
     token_prices: Vec<u64>,
     nonce: u64,
-) -> BridgeAction {
+) -> anyhow::Result<BridgeAction> {
     assert!(token_ids.len() == token_packages_dir.len());
     assert!(token_prices.len() == token_packages_dir.len());
     let sui_client = wallet_context.get_client().await.unwrap();
@@ -280,7 +280,7 @@ pub async fn publish_and_register_coins_return_add_coins_on_sui_action(
         let tx = TestTransactionBuilder::new(sender, gas, rgp)
             .publish(token_package_dir.to_path_buf())
             .build();
-        let tx = wallet_context.sign_transaction(&tx);
+        let tx = wallet_context.sign_transaction(&tx)?;
         let api_clone = quorum_driver_api.clone();
         publish_tokens_tasks.push(tokio::spawn(async move {
             api_clone.execute_transaction_block(
@@ -355,7 +355,7 @@ pub async fn publish_and_register_coins_return_add_coins_on_sui_action(
             .unwrap()
             .unwrap();
         let tx = TransactionData::new_programmable(sender, vec![gas], pt, 1_000_000_000, rgp);
-        let signed_tx = wallet_context.sign_transaction(&tx);
+        let signed_tx = wallet_context.sign_transaction(&tx)?;
         let api_clone = quorum_driver_api.clone();
         register_tasks.push(async move {
             api_clone
@@ -375,14 +375,14 @@ pub async fn publish_and_register_coins_return_add_coins_on_sui_action(
         );
     }
 
-    BridgeAction::AddTokensOnSuiAction(AddTokensOnSuiAction {
+    Ok(BridgeAction::AddTokensOnSuiAction(AddTokensOnSuiAction {
         nonce,
         chain_id: BridgeChainId::SuiCustom,
         native: false,
         token_ids,
         token_type_names,
         token_prices,
-    })
+    }))
 }
 
 pub async fn wait_for_server_to_be_up(server_url: String, timeout_sec: u64) -> anyhow::Result<()> {
@@ -7,11 +7,13 @@ use crate::checkpoints::CheckpointStore;
 use crate::par_index_live_object_set::LiveObjectIndexer;
 use crate::par_index_live_object_set::ParMakeLiveObjectIndexer;
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -24,23 +26,34 @@ use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::SuiAddress;
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::visitor as DFV;
+use sui_types::effects::TransactionEffects;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::effects::TransactionEvents;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::layout_resolver::LayoutResolver;
 use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::BackingPackageStore;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::storage::TransactionIndexKey;
+use sui_types::storage::TransactionIndexKind;
+use sui_types::transaction::TransactionData;
+use sui_types::transaction::TransactionDataAPI;
 use tracing::{debug, info};
-use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::rocks::{default_db_options, DBMap, DBOptions, MetricConf};
+use typed_store::rocksdb::compaction_filter::Decision;
+use typed_store::rocksdb::MergeOperands;
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::TypedStoreError;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 4;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
@@ -65,6 +78,8 @@ pub struct OwnerIndexInfo {
     // object_id of the object is a part of the Key
     pub version: SequenceNumber,
     pub type_: MoveObjectType,
+    /// The balance of the object, if it is a `0x2::coin::Coin<T>`.
+    pub balance: Option<u64>,
 }
 
 impl OwnerIndexInfo {
@@ -72,6 +87,7 @@ impl OwnerIndexInfo {
         Self {
             version: object.version(),
             type_: object.type_().expect("packages cannot be owned").to_owned(),
+            balance: object.is_coin().then(|| object.get_coin_value_unsafe()),
         }
     }
 }
@@ -103,6 +119,71 @@ impl CoinIndexInfo {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BalanceKey {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct OwnedCoinKey {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+    pub object_id: ObjectID,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct OwnedCoinInfo {
+    // owner, coin_type and object_id of the coin are a part of the Key
+    pub version: SequenceNumber,
+    pub balance: u64,
+}
+
+/// A change to the total balance and number of the coins of a single type owned by an address.
+///
+/// The `balance` table's merge operator sums these deltas, so stored values hold the totals.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub struct BalanceIndexInfo {
+    pub balance_delta: i128,
+    pub coin_count_delta: i64,
+}
+
+impl BalanceIndexInfo {
+    fn new(object: &Object) -> Self {
+        Self {
+            balance_delta: object.get_coin_value_unsafe() as i128,
+            coin_count_delta: 1,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            balance_delta: self.balance_delta + other.balance_delta,
+            coin_count_delta: self.coin_count_delta + other.coin_count_delta,
+        }
+    }
+
+    fn invert(self) -> Self {
+        Self {
+            balance_delta: -self.balance_delta,
+            coin_count_delta: -self.coin_count_delta,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.balance_delta == 0 && self.coin_count_delta == 0
+    }
+}
+
+impl From<BalanceIndexInfo> for BalanceInfo {
+    fn from(info: BalanceIndexInfo) -> Self {
+        Self {
+            balance: info.balance_delta.clamp(0, u64::MAX as i128) as u64,
+            coin_count: info.coin_count_delta.max(0) as u64,
+        }
+    }
+}
+
 /// RocksDB tables for the RpcIndexStore
 ///
 /// Anytime a new table is added, or and existing one has it's schema changed, make sure to also
@@ -127,6 +208,18 @@ struct IndexStoreTables {
     /// Only contains entries for transactions which have yet to be pruned from the main database.
     transactions: DBMap<TransactionDigest, TransactionInfo>,
 
+    /// An index of transactions by their sender, the addresses they affect, the packages they
+    /// call and the packages defining the events they emit.
+    ///
+    /// Allows an efficient iterator to list the transactions matching a filter in a range of
+    /// checkpoints, without loading every transaction in those checkpoints. Only contains entries
+    /// for transactions which have yet to be pruned from the main database.
+    transaction_index: DBMap<TransactionIndexKey, TransactionDigest>,
+
+    /// The keys written to `transaction_index` for each transaction, so that they can be pruned
+    /// along with it.
+    transaction_index_keys: DBMap<TransactionDigest, Vec<TransactionIndexKey>>,
+
     /// An index of object ownership.
     ///
     /// Allows an efficient iterator to list all objects currently owned by a specific user
@@ -144,6 +237,20 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of the balances of each coin type owned by an address.
+    ///
+    /// Allows looking up an account's balance of a coin type, or listing all of its balances,
+    /// without iterating over all of its objects. Entries are updated by merging in
+    /// `BalanceIndexInfo` deltas, and are removed during compaction once they reach zero.
+    #[default_options_override_fn = "balance_table_options"]
+    balance: DBMap<BalanceKey, BalanceIndexInfo>,
+
+    /// An index of the coins owned by an address, by coin type.
+    ///
+    /// Allows an efficient iterator to list the coins of a specific type owned by an account,
+    /// without iterating over all of its objects.
+    owned_coin: DBMap<OwnedCoinKey, OwnedCoinInfo>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -214,8 +321,49 @@ impl IndexStoreTables {
                 };
 
                 self.transactions
-                    .multi_insert(contents.iter().map(|digests| (digests.transaction, info)))
-                    .map_err(StorageError::from)
+                    .multi_insert(contents.iter().map(|digests| (digests.transaction, info)))?;
+
+                let transaction_digests: Vec<_> =
+                    contents.iter().map(|digests| digests.transaction).collect();
+                let transactions = authority_store
+                    .multi_get_transaction_blocks(&transaction_digests)
+                    .map_err(StorageError::custom)?;
+                let effects = authority_store
+                    .multi_get_effects(contents.iter().map(|digests| &digests.effects))
+                    .map_err(StorageError::custom)?;
+
+                let mut batch = self.transaction_index.batch();
+                for (i, (transaction, effects)) in transactions.into_iter().zip(effects).enumerate()
+                {
+                    let digest = transaction_digests[i];
+                    let transaction = transaction.ok_or_else(|| {
+                        StorageError::missing(format!("missing transaction {digest}"))
+                    })?;
+                    let effects = effects.ok_or_else(|| {
+                        StorageError::missing(format!("missing effects for transaction {digest}"))
+                    })?;
+                    let events = effects
+                        .events_digest()
+                        .map(|events_digest| authority_store.get_events(events_digest))
+                        .transpose()?
+                        .flatten();
+
+                    let keys = transaction_index_keys(
+                        checkpoint.sequence_number,
+                        i as u64,
+                        transaction.transaction_data(),
+                        &effects,
+                        events.as_ref(),
+                    );
+
+                    batch.insert_batch(
+                        &self.transaction_index,
+                        keys.iter().map(|key| (*key, digest)),
+                    )?;
+                    batch.insert_batch(&self.transaction_index_keys, [(digest, keys)])?;
+                }
+
+                batch.write().map_err(StorageError::from)
             })?;
 
             info!(
@@ -259,11 +407,21 @@ impl IndexStoreTables {
     ) -> Result<(), TypedStoreError> {
         let mut batch = self.transactions.batch();
 
-        let transactions_to_prune = checkpoint_contents_to_prune
+        let transactions_to_prune: Vec<_> = checkpoint_contents_to_prune
             .iter()
-            .flat_map(|contents| contents.iter().map(|digests| digests.transaction));
+            .flat_map(|contents| contents.iter().map(|digests| digests.transaction))
+            .collect();
 
-        batch.delete_batch(&self.transactions, transactions_to_prune)?;
+        let index_keys_to_prune = self
+            .transaction_index_keys
+            .multi_get(&transactions_to_prune)?;
+
+        batch.delete_batch(
+            &self.transaction_index,
+            index_keys_to_prune.into_iter().flatten().flatten(),
+        )?;
+        batch.delete_batch(&self.transaction_index_keys, &transactions_to_prune)?;
+        batch.delete_batch(&self.transactions, &transactions_to_prune)?;
 
         batch.write()
     }
@@ -294,13 +452,72 @@ impl IndexStoreTables {
                     .iter()
                     .map(|digests| (digests.transaction, info)),
             )?;
+
+            for (i, tx) in checkpoint.transactions.iter().enumerate() {
+                let digest = *tx.transaction.digest();
+                let keys = transaction_index_keys(
+                    checkpoint.checkpoint_summary.sequence_number,
+                    i as u64,
+                    tx.transaction.transaction_data(),
+                    &tx.effects,
+                    tx.events.as_ref(),
+                );
+
+                batch.insert_batch(
+                    &self.transaction_index,
+                    keys.iter().map(|key| (*key, digest)),
+                )?;
+                batch.insert_batch(&self.transaction_index_keys, [(digest, keys)])?;
+            }
         }
 
         // object indexes
         {
             let mut coin_index = HashMap::new();
+            let mut balance_deltas = HashMap::new();
 
             for tx in &checkpoint.transactions {
+                // balance indexing
+                //
+                // every coin that is removed, or changed, is subtracted from its previous owner's
+                // balance, and every changed coin is added to its new owner's balance.
+                let removed_coins = tx.removed_objects_pre_version().chain(
+                    tx.changed_objects()
+                        .filter_map(|(_, old_object)| old_object),
+                );
+                for (key, delta) in removed_coins.filter_map(try_create_balance_info) {
+                    let entry: &mut BalanceIndexInfo = balance_deltas.entry(key).or_default();
+                    *entry = entry.merge(delta.invert());
+                }
+
+                let changed_coins = tx.changed_objects().map(|(object, _)| object);
+                for (key, delta) in changed_coins.filter_map(try_create_balance_info) {
+                    let entry: &mut BalanceIndexInfo = balance_deltas.entry(key).or_default();
+                    *entry = entry.merge(delta);
+                }
+
+                // owned coin indexing
+                //
+                // every coin that is removed, or changed, is removed from its previous owner's
+                // coins, and every changed coin is added to its new owner's coins (in that order, so
+                // that a coin that stays with the same owner is re-inserted).
+                let removed_coins = tx.removed_objects_pre_version().chain(
+                    tx.changed_objects()
+                        .filter_map(|(_, old_object)| old_object),
+                );
+                batch.delete_batch(
+                    &self.owned_coin,
+                    removed_coins
+                        .filter_map(try_create_owned_coin_info)
+                        .map(|(key, _)| key),
+                )?;
+
+                let changed_coins = tx.changed_objects().map(|(object, _)| object);
+                batch.insert_batch(
+                    &self.owned_coin,
+                    changed_coins.filter_map(try_create_owned_coin_info),
+                )?;
+
                 // determine changes from removed objects
                 for removed_object in tx.removed_objects_pre_version() {
                     match removed_object.owner() {
@@ -390,6 +607,9 @@ impl IndexStoreTables {
             }
 
             batch.insert_batch(&self.coin, coin_index)?;
+
+            balance_deltas.retain(|_, delta| !delta.is_zero());
+            batch.partial_merge_batch(&self.balance, balance_deltas)?;
         }
 
         debug!(
@@ -407,6 +627,48 @@ impl IndexStoreTables {
         self.transactions.get(digest)
     }
 
+    fn transactions_iter(
+        &self,
+        from: TransactionIndexKey,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<impl Iterator<Item = (TransactionIndexKey, TransactionDigest)> + '_, TypedStoreError>
+    {
+        let to = TransactionIndexKey {
+            kind: from.kind,
+            checkpoint: to_checkpoint,
+            transaction_index: u64::MAX,
+        };
+        Ok(self.transaction_index.range_iter(from..=to))
+    }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        let key = BalanceKey {
+            owner: owner.to_owned(),
+            coin_type: coin_type.to_owned(),
+        };
+        self.balance.get(&key)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = (BalanceKey, BalanceIndexInfo)> + '_, TypedStoreError> {
+        // `TypeTag::Bool` has the smallest serialized form, so it sorts before every coin type.
+        let lower_bound = BalanceKey {
+            owner,
+            coin_type: cursor.unwrap_or(TypeTag::Bool),
+        };
+        Ok(self
+            .balance
+            .iter_with_bounds(Some(lower_bound), None)
+            .take_while(move |(key, _)| key.owner == owner))
+    }
+
     fn owner_iter(
         &self,
         owner: SuiAddress,
@@ -419,6 +681,27 @@ impl IndexStoreTables {
             .iter_with_bounds(Some(lower_bound), Some(upper_bound)))
     }
 
+    fn owned_coin_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<ObjectID>,
+    ) -> Result<impl Iterator<Item = (OwnedCoinKey, OwnedCoinInfo)> + '_, TypedStoreError> {
+        let lower_bound = OwnedCoinKey {
+            owner,
+            coin_type: coin_type.clone(),
+            object_id: cursor.unwrap_or(ObjectID::ZERO),
+        };
+        let upper_bound = OwnedCoinKey {
+            owner,
+            coin_type,
+            object_id: ObjectID::MAX,
+        };
+        Ok(self
+            .owned_coin
+            .iter_with_bounds(Some(lower_bound), Some(upper_bound)))
+    }
+
     fn dynamic_field_iter(
         &self,
         parent: ObjectID,
@@ -560,6 +843,31 @@ impl RpcIndexStore {
         self.tables.get_transaction_info(digest)
     }
 
+    pub fn transactions_iter(
+        &self,
+        from: TransactionIndexKey,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<impl Iterator<Item = (TransactionIndexKey, TransactionDigest)> + '_, TypedStoreError>
+    {
+        self.tables.transactions_iter(from, to_checkpoint)
+    }
+
+    pub fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        self.tables.get_balance(owner, coin_type)
+    }
+
+    pub fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = (BalanceKey, BalanceIndexInfo)> + '_, TypedStoreError> {
+        self.tables.balance_iter(owner, cursor)
+    }
+
     pub fn owner_iter(
         &self,
         owner: SuiAddress,
@@ -568,6 +876,15 @@ impl RpcIndexStore {
         self.tables.owner_iter(owner, cursor)
     }
 
+    pub fn owned_coin_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<ObjectID>,
+    ) -> Result<impl Iterator<Item = (OwnedCoinKey, OwnedCoinInfo)> + '_, TypedStoreError> {
+        self.tables.owned_coin_iter(owner, coin_type, cursor)
+    }
+
     pub fn dynamic_field_iter(
         &self,
         parent: ObjectID,
@@ -585,6 +902,65 @@ impl RpcIndexStore {
     }
 }
 
+/// The keys to add to the `transaction_index` for the transaction at position `transaction_index`
+/// in `checkpoint`.
+fn transaction_index_keys(
+    checkpoint: CheckpointSequenceNumber,
+    transaction_index: u64,
+    transaction: &TransactionData,
+    effects: &TransactionEffects,
+    events: Option<&TransactionEvents>,
+) -> Vec<TransactionIndexKey> {
+    let sender = transaction.sender();
+    let mut kinds = BTreeSet::from([
+        TransactionIndexKind::Sender(sender),
+        TransactionIndexKind::AffectedAddress(sender),
+    ]);
+
+    let owners = effects
+        .old_object_metadata()
+        .into_iter()
+        .map(|(_, owner)| owner)
+        .chain(
+            effects
+                .all_changed_objects()
+                .into_iter()
+                .map(|(_, owner, _)| owner),
+        );
+
+    for owner in owners {
+        match owner {
+            Owner::AddressOwner(address) => {
+                kinds.insert(TransactionIndexKind::AffectedAddress(address));
+            }
+            Owner::ConsensusV2 { authenticator, .. } => {
+                let address = *authenticator.as_single_owner();
+                kinds.insert(TransactionIndexKind::AffectedAddress(address));
+            }
+            Owner::ObjectOwner(_) | Owner::Shared { .. } | Owner::Immutable => {}
+        }
+    }
+
+    for (package, _, _) in transaction.move_calls() {
+        kinds.insert(TransactionIndexKind::MoveCallPackage(*package));
+    }
+
+    for event in events.into_iter().flat_map(|events| &events.data) {
+        kinds.insert(TransactionIndexKind::EventPackage(
+            event.type_.address.into(),
+        ));
+    }
+
+    kinds
+        .into_iter()
+        .map(|kind| TransactionIndexKey {
+            kind,
+            checkpoint,
+            transaction_index,
+        })
+        .collect()
+}
+
 fn try_create_dynamic_field_info(
     object: &Object,
     resolver: &mut dyn LayoutResolver,
@@ -627,6 +1003,80 @@ fn try_create_dynamic_field_info(
     }))
 }
 
+/// The `balance` table key and delta for adding `object` to its owner's balance, if it is a coin
+/// owned by an address.
+fn try_create_balance_info(object: &Object) -> Option<(BalanceKey, BalanceIndexInfo)> {
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    let coin_type = object.coin_type_maybe()?;
+    let key = BalanceKey {
+        owner: *owner,
+        coin_type,
+    };
+
+    Some((key, BalanceIndexInfo::new(object)))
+}
+
+/// The `owned_coin` table entry for `object`, if it is a coin owned by an address.
+fn try_create_owned_coin_info(object: &Object) -> Option<(OwnedCoinKey, OwnedCoinInfo)> {
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    let coin_type = object.coin_type_maybe()?;
+    let key = OwnedCoinKey {
+        owner: *owner,
+        coin_type,
+        object_id: object.id(),
+    };
+
+    let info = OwnedCoinInfo {
+        version: object.version(),
+        balance: object.get_coin_value_unsafe(),
+    };
+
+    Some((key, info))
+}
+
+fn balance_table_options() -> DBOptions {
+    let mut options = default_db_options();
+    options
+        .options
+        .set_merge_operator_associative("balance_merge", balance_delta_merge_operator);
+    options
+        .options
+        .set_compaction_filter("balance_zero_filter", balance_compaction_filter);
+    options
+}
+
+/// Sums the `BalanceIndexInfo` deltas merged into a `balance` table entry.
+fn balance_delta_merge_operator(
+    _key: &[u8],
+    existing_value: Option<&[u8]>,
+    operands: &MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut total: BalanceIndexInfo = match existing_value {
+        Some(value) => bcs::from_bytes(value).ok()?,
+        None => BalanceIndexInfo::default(),
+    };
+
+    for operand in operands.iter() {
+        total = total.merge(bcs::from_bytes(operand).ok()?);
+    }
+
+    bcs::to_bytes(&total).ok()
+}
+
+/// Removes `balance` table entries once the owner no longer has any coins of that type.
+fn balance_compaction_filter(_level: u32, _key: &[u8], value: &[u8]) -> Decision {
+    match bcs::from_bytes::<BalanceIndexInfo>(value) {
+        Ok(info) if info.is_zero() => Decision::Remove,
+        _ => Decision::Keep,
+    }
+}
+
 fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinIndexInfo)> {
     use sui_types::coin::CoinMetadata;
     use sui_types::coin::TreasuryCap;
@@ -701,6 +1151,18 @@ impl LiveObjectIndexer for RpcLiveObjectIndexer<'_> {
                 let owner_info = OwnerIndexInfo::new(&object);
                 self.batch
                     .insert_batch(&self.tables.owner, [(owner_key, owner_info)])?;
+
+                // Balance Index
+                if let Some((key, delta)) = try_create_balance_info(&object) {
+                    self.batch
+                        .partial_merge_batch(&self.tables.balance, [(key, delta)])?;
+                }
+
+                // Owned Coin Index
+                if let Some((key, info)) = try_create_owned_coin_info(&object) {
+                    self.batch
+                        .insert_batch(&self.tables.owned_coin, [(key, info)])?;
+                }
             }
 
             // Dynamic Field Index
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use parking_lot::Mutex;
 use std::sync::Arc;
+use sui_types::base_types::MoveObjectType;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::TransactionDigest;
@@ -22,12 +24,14 @@ use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
 use sui_types::storage::AccountOwnedObjectInfo;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
 use sui_types::storage::ObjectStore;
 use sui_types::storage::RpcIndexes;
 use sui_types::storage::RpcStateReader;
+use sui_types::storage::TransactionIndexKey;
 use sui_types::storage::WriteStore;
 use sui_types::storage::{ObjectKey, ReadStore};
 use sui_types::transaction::VerifiedTransaction;
@@ -38,6 +42,8 @@ use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::rpc_index::CoinIndexInfo;
+use crate::rpc_index::OwnedCoinInfo;
+use crate::rpc_index::OwnedCoinKey;
 use crate::rpc_index::OwnerIndexInfo;
 use crate::rpc_index::OwnerIndexKey;
 use crate::rpc_index::RpcIndexStore;
@@ -487,12 +493,20 @@ impl RpcIndexes for RpcIndexStore {
         cursor: Option<ObjectID>,
     ) -> Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>> {
         let iter = self.owner_iter(owner, cursor)?.map(
-            |(OwnerIndexKey { owner, object_id }, OwnerIndexInfo { version, type_ })| {
+            |(
+                OwnerIndexKey { owner, object_id },
+                OwnerIndexInfo {
+                    version,
+                    type_,
+                    balance,
+                },
+            )| {
                 AccountOwnedObjectInfo {
                     owner,
                     object_id,
                     version,
                     type_,
+                    balance,
                 }
             },
         );
@@ -500,6 +514,32 @@ impl RpcIndexes for RpcIndexStore {
         Ok(Box::new(iter) as _)
     }
 
+    fn owned_coins_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &TypeTag,
+        cursor: Option<ObjectID>,
+    ) -> Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>> {
+        let iter = self.owned_coin_iter(owner, coin_type.clone(), cursor)?.map(
+            |(
+                OwnedCoinKey {
+                    owner,
+                    coin_type,
+                    object_id,
+                },
+                OwnedCoinInfo { version, balance },
+            )| AccountOwnedObjectInfo {
+                owner,
+                object_id,
+                version,
+                type_: MoveObjectType::coin(coin_type),
+                balance: Some(balance),
+            },
+        );
+
+        Ok(Box::new(iter) as _)
+    }
+
     fn dynamic_field_iter(
         &self,
         parent: ObjectID,
@@ -528,4 +568,39 @@ impl RpcIndexes for RpcIndexStore {
             )
             .pipe(Ok)
     }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &TypeTag,
+    ) -> sui_types::storage::error::Result<Option<BalanceInfo>> {
+        self.get_balance(owner, coin_type)?
+            .map(BalanceInfo::from)
+            .pipe(Ok)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> sui_types::storage::error::Result<Box<dyn Iterator<Item = (TypeTag, BalanceInfo)> + '_>>
+    {
+        let iter = self
+            .balance_iter(*owner, cursor)?
+            .map(|(key, info)| (key.coin_type, BalanceInfo::from(info)));
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn transactions_iter(
+        &self,
+        from: TransactionIndexKey,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = (TransactionIndexKey, TransactionDigest)> + '_>,
+    > {
+        let iter = self.transactions_iter(from, to_checkpoint)?;
+
+        Ok(Box::new(iter) as _)
+    }
 }
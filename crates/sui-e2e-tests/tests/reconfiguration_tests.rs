@@ -96,7 +96,7 @@ async fn test_transaction_expiration() {
     // Expired transaction returns an error
     let mut expired_data = data.clone();
     *expired_data.expiration_mut_for_testing() = TransactionExpiration::Epoch(0);
-    let expired_transaction = test_cluster.wallet.sign_transaction(&expired_data);
+    let expired_transaction = test_cluster.wallet.sign_transaction(&expired_data).unwrap();
     let result = test_cluster
         .wallet
         .execute_transaction_may_fail(expired_transaction)
@@ -108,7 +108,7 @@ async fn test_transaction_expiration() {
 
     // Non expired transaction signed without issue
     *data.expiration_mut_for_testing() = TransactionExpiration::Epoch(10);
-    let transaction = test_cluster.wallet.sign_transaction(&data);
+    let transaction = test_cluster.wallet.sign_transaction(&data).unwrap();
     test_cluster
         .wallet
         .execute_transaction_may_fail(transaction)
@@ -127,21 +127,27 @@ async fn reconfig_with_revert_end_to_end_test() {
 
     // gas1 transaction is committed
     let gas1 = gas_objects.pop().unwrap();
-    let tx = test_cluster.wallet.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas1, rgp)
-            .transfer_sui(None, sender)
-            .build(),
-    );
+    let tx = test_cluster
+        .wallet
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas1, rgp)
+                .transfer_sui(None, sender)
+                .build(),
+        )
+        .unwrap();
     let effects1 = test_cluster.execute_transaction(tx).await;
     assert_eq!(0, effects1.effects.unwrap().executed_epoch());
 
     // gas2 transaction is (most likely) reverted
     let gas2 = gas_objects.pop().unwrap();
-    let tx = test_cluster.wallet.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas2, rgp)
-            .transfer_sui(None, sender)
-            .build(),
-    );
+    let tx = test_cluster
+        .wallet
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas2, rgp)
+                .transfer_sui(None, sender)
+                .build(),
+        )
+        .unwrap();
     let net = test_cluster
         .fullnode_handle
         .sui_node
@@ -315,11 +321,14 @@ async fn test_expired_locks() {
     let gas_object = accounts_and_objs[0].1[0];
 
     let transfer_sui = |amount| {
-        test_cluster.wallet.sign_transaction(
-            &TestTransactionBuilder::new(sender, gas_object, gas_price)
-                .transfer_sui(Some(amount), receiver)
-                .build(),
-        )
+        test_cluster
+            .wallet
+            .sign_transaction(
+                &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                    .transfer_sui(Some(amount), receiver)
+                    .build(),
+            )
+            .unwrap()
     };
 
     let t1 = transfer_sui(1);
@@ -0,0 +1,209 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::GetBalanceRequest;
+use sui_rpc_api::proto::node::v2alpha::ListBalancesRequest;
+use sui_rpc_api::proto::node::v2alpha::ListOwnedCoinsRequest;
+use sui_sdk_types::Address;
+use sui_sdk_types::TypeTag;
+use sui_test_transaction_builder::make_transfer_sui_transaction;
+use sui_types::base_types::SuiAddress;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn get_balance_and_list_coins() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let owner = test_cluster.get_address_0();
+    let gas_coins = test_cluster
+        .wallet
+        .get_all_gas_objects_owned_by_address(owner)
+        .await
+        .unwrap();
+    let owner = Address::from(owner);
+    let sui: TypeTag = "0x2::sui::SUI".parse().unwrap();
+
+    let balance = grpc_client
+        .get_balance(GetBalanceRequest {
+            owner: Some(owner.into()),
+            coin_type: Some(sui.clone().into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .balance
+        .unwrap();
+    assert_eq!(balance.coin_type, Some(sui.clone().into()));
+    assert_eq!(balance.coin_count, Some(gas_coins.len() as u64));
+    assert!(balance.balance.unwrap() > 0);
+
+    // The only coins owned by the account are gas coins
+    let balances = grpc_client
+        .list_balances(ListBalancesRequest {
+            owner: Some(owner.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balances.balances, vec![balance.clone()]);
+    assert!(balances.next_page_token.is_none());
+
+    // Page through the account's coins one at a time
+    let mut coins = vec![];
+    let mut page_token = None;
+    loop {
+        let response = grpc_client
+            .list_owned_coins(ListOwnedCoinsRequest {
+                owner: Some(owner.into()),
+                coin_type: Some(sui.clone().into()),
+                page_size: Some(1),
+                page_token,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.coins.len() <= 1);
+        coins.extend(response.coins);
+
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(coins.len(), gas_coins.len());
+    assert_eq!(
+        coins.iter().map(|coin| coin.balance.unwrap()).sum::<u64>(),
+        balance.balance.unwrap()
+    );
+    for coin in &coins {
+        assert_eq!(coin.coin_type, Some(sui.clone().into()));
+        let object_id =
+            sui_sdk_types::ObjectId::try_from(coin.object_id.as_ref().unwrap()).unwrap();
+        assert!(gas_coins
+            .iter()
+            .any(|(id, _, _)| sui_sdk_types::ObjectId::from(*id) == object_id));
+    }
+
+    // An account with no coins has a zero balance
+    let empty = grpc_client
+        .get_balance(GetBalanceRequest {
+            owner: Some(Address::ZERO.into()),
+            coin_type: Some(sui.into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .balance
+        .unwrap();
+    assert_eq!(empty.balance, Some(0));
+    assert_eq!(empty.coin_count, Some(0));
+
+    let error = grpc_client
+        .list_owned_coins(ListOwnedCoinsRequest {
+            owner: Some(owner.into()),
+            coin_type: None,
+            page_size: None,
+            page_token: Some("not a page token".to_owned()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
+
+#[sim_test]
+async fn balance_follows_transfers() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let sui: TypeTag = "0x2::sui::SUI".parse().unwrap();
+
+    for _ in 0..2 {
+        let txn =
+            make_transfer_sui_transaction(&test_cluster.wallet, Some(recipient), Some(9)).await;
+        test_cluster
+            .wallet
+            .execute_transaction_must_succeed(txn)
+            .await;
+    }
+
+    // The balance index is updated when the transactions' checkpoints are executed.
+    let balance = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        loop {
+            let balance = grpc_client
+                .get_balance(GetBalanceRequest {
+                    owner: Some(Address::from(recipient).into()),
+                    coin_type: Some(sui.clone().into()),
+                })
+                .await
+                .unwrap()
+                .into_inner()
+                .balance
+                .unwrap();
+
+            if balance.coin_count == Some(2) {
+                break balance;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(balance.balance, Some(18));
+
+    let balances = grpc_client
+        .list_balances(ListBalancesRequest {
+            owner: Some(Address::from(recipient).into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balances.balances, vec![balance]);
+    assert!(balances.next_page_token.is_none());
+
+    // The coins received by the transfers are listed by their type.
+    let coins = grpc_client
+        .list_owned_coins(ListOwnedCoinsRequest {
+            owner: Some(Address::from(recipient).into()),
+            coin_type: Some(sui.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(coins.coins.len(), 2);
+    assert!(coins.coins.iter().all(|coin| coin.balance == Some(9)));
+    assert!(coins.next_page_token.is_none());
+
+    // ...and not under any other type.
+    let other: TypeTag = "0x2::coin::Coin<0x2::sui::SUI>".parse().unwrap();
+    let coins = grpc_client
+        .list_owned_coins(ListOwnedCoinsRequest {
+            owner: Some(Address::from(recipient).into()),
+            coin_type: Some(other.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(coins.coins.is_empty());
+}
@@ -369,6 +369,7 @@ async fn subscribe_checkpoint() {
         read_mask: Some(prost_types::FieldMask {
             paths: vec!["sequence_number".to_owned()],
         }),
+        filter: None,
     };
 
     let mut stream = client
@@ -402,3 +403,74 @@ async fn subscribe_checkpoint() {
 
     assert!(count >= 50);
 }
+
+#[sim_test]
+async fn subscribe_checkpoint_with_filter() {
+    use sui_rpc_api::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+    use sui_rpc_api::proto::node::v2alpha::CheckpointFilter;
+    use sui_rpc_api::proto::node::v2alpha::SubscribeCheckpointsRequest;
+    use tokio_stream::StreamExt;
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let accounts_and_objs = test_cluster
+        .wallet
+        .get_all_accounts_and_gas_objects()
+        .await
+        .unwrap();
+    let receiver = accounts_and_objs[1].0;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let request = SubscribeCheckpointsRequest {
+        read_mask: Some(FieldMask::from_paths([
+            "sequence_number",
+            "transactions.digest",
+        ])),
+        filter: Some(CheckpointFilter {
+            affected_addresses: vec![sui_sdk_types::Address::from(receiver).into()],
+            ..Default::default()
+        }),
+    };
+
+    let mut stream = client
+        .subscribe_checkpoints(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    // The only transaction affecting the receiver is the transfer, so every checkpoint is either
+    // empty, or contains only the transfer.
+    let mut count = 0;
+    let mut found = false;
+    while let Some(item) = stream.next().await {
+        let checkpoint = item.unwrap().checkpoint.unwrap();
+        for transaction in checkpoint.transactions {
+            assert_eq!(transaction.digest, Some(transaction_digest.into()));
+            found = true;
+        }
+
+        count += 1;
+        if found || count > 50 {
+            break;
+        }
+    }
+
+    assert!(found);
+
+    // Invalid filters are rejected
+    let request = SubscribeCheckpointsRequest {
+        read_mask: None,
+        filter: Some(CheckpointFilter {
+            event_types: vec!["not a type".to_owned()],
+            ..Default::default()
+        }),
+    };
+
+    let error = client.subscribe_checkpoints(request).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::stake_with_validator;
+use sui_macros::sim_test;
+use sui_rpc_api::field_mask::FieldMask;
+use sui_rpc_api::field_mask::FieldMaskUtil;
+use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2::GetTransactionRequest;
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient as AlphaNodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::{EventFilter, ListEventsRequest};
+use test_cluster::TestClusterBuilder;
+use tokio_stream::StreamExt;
+
+#[sim_test]
+async fn list_events() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let mut alpha_client = AlphaNodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let checkpoint = grpc_client
+        .get_transaction(
+            GetTransactionRequest::new(transaction_digest)
+                .with_read_mask(FieldMask::from_paths(["checkpoint"])),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .checkpoint
+        .unwrap();
+
+    let request = ListEventsRequest {
+        start_checkpoint: Some(checkpoint),
+        end_checkpoint: Some(checkpoint),
+        filter: Some(EventFilter {
+            event_type: Some("0x3::validator::StakingRequestEvent".to_owned()),
+            ..Default::default()
+        }),
+        read_mask: None,
+    };
+
+    let mut stream = alpha_client
+        .list_events(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut responses = vec![];
+    while let Some(response) = stream.next().await {
+        responses.push(response.unwrap());
+    }
+
+    assert_eq!(responses.len(), 1);
+    let response = &responses[0];
+    assert_eq!(response.checkpoint, Some(checkpoint));
+    assert_eq!(response.transaction_digest, Some(transaction_digest.into()));
+
+    // `event_bcs` is not part of the default read mask
+    assert!(response.event.is_some());
+    assert!(response.event_bcs.is_none());
+
+    // Filtering by module also matches the event
+    let request = ListEventsRequest {
+        start_checkpoint: Some(checkpoint),
+        end_checkpoint: Some(checkpoint),
+        filter: Some(EventFilter {
+            event_type: Some("0x3::validator".to_owned()),
+            ..Default::default()
+        }),
+        read_mask: Some(FieldMask::from_paths(["event_bcs"])),
+    };
+
+    let mut stream = alpha_client
+        .list_events(request)
+        .await
+        .unwrap()
+        .into_inner();
+    let response = stream.next().await.unwrap().unwrap();
+    assert!(response.transaction_digest.is_none());
+    assert!(response.event.is_none());
+    assert!(response.event_bcs.is_some());
+
+    // Filters served from the indexes can cover more checkpoints than a full scan
+    let request = ListEventsRequest {
+        start_checkpoint: Some(checkpoint),
+        end_checkpoint: Some(checkpoint + 500),
+        filter: Some(EventFilter {
+            event_type: Some("0x3::validator::StakingRequestEvent".to_owned()),
+            ..Default::default()
+        }),
+        read_mask: None,
+    };
+
+    let mut stream = alpha_client
+        .list_events(request)
+        .await
+        .unwrap()
+        .into_inner();
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.transaction_digest, Some(transaction_digest.into()));
+
+    let request = ListEventsRequest {
+        start_checkpoint: Some(checkpoint),
+        end_checkpoint: Some(checkpoint + 500),
+        ..Default::default()
+    };
+    let error = alpha_client.list_events(request).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+    // Invalid event types are rejected
+    let request = ListEventsRequest {
+        start_checkpoint: Some(checkpoint),
+        filter: Some(EventFilter {
+            event_type: Some("not a type".to_owned()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let error = alpha_client.list_events(request).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
@@ -1,10 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod balances;
 mod checkpoints;
 mod coin_info;
 mod committee;
+mod events;
 mod execute;
+mod move_package;
 mod node_info;
 mod objects;
 mod resolve;
@@ -19,11 +22,15 @@ async fn transfer_coin(
     let receiver = accounts_and_objs[1].0;
     let gas_object = accounts_and_objs[0].1[0];
     let object_to_send = accounts_and_objs[0].1[1];
-    let txn = context.sign_transaction(
-        &sui_test_transaction_builder::TestTransactionBuilder::new(sender, gas_object, gas_price)
+    let txn = context
+        .sign_transaction(
+            &sui_test_transaction_builder::TestTransactionBuilder::new(
+                sender, gas_object, gas_price,
+            )
             .transfer(object_to_send, receiver)
             .build(),
-    );
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
     resp.digest.into()
 }
@@ -38,11 +45,15 @@ async fn stake_with_validator(
     let gas_object = accounts_and_objs[0].1[0];
     let coin_to_stake = accounts_and_objs[0].1[1];
     let validator_address = cluster.swarm.config().validator_configs()[0].sui_address();
-    let txn = context.sign_transaction(
-        &sui_test_transaction_builder::TestTransactionBuilder::new(sender, gas_object, gas_price)
+    let txn = context
+        .sign_transaction(
+            &sui_test_transaction_builder::TestTransactionBuilder::new(
+                sender, gas_object, gas_price,
+            )
             .call_staking(coin_to_stake, validator_address)
             .build(),
-    );
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
     resp.digest.into()
 }
@@ -0,0 +1,170 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::Ability;
+use sui_rpc_api::proto::node::v2alpha::DatatypeKind;
+use sui_rpc_api::proto::node::v2alpha::GetDatatypeRequest;
+use sui_rpc_api::proto::node::v2alpha::GetFunctionRequest;
+use sui_rpc_api::proto::node::v2alpha::GetPackageRequest;
+use sui_rpc_api::proto::node::v2alpha::Reference;
+use sui_rpc_api::proto::node::v2alpha::TypeKind;
+use sui_rpc_api::proto::node::v2alpha::Visibility;
+use sui_sdk_types::ObjectId;
+use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn get_package() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let package = grpc_client
+        .get_package(GetPackageRequest {
+            package_id: Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .package
+        .unwrap();
+
+    assert_eq!(
+        package.storage_id,
+        Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into())
+    );
+    assert_eq!(package.original_id, package.storage_id);
+
+    let coin = package
+        .modules
+        .iter()
+        .find(|module| module.name.as_deref() == Some("coin"))
+        .unwrap();
+    assert!(coin
+        .datatypes
+        .iter()
+        .any(|datatype| datatype.name.as_deref() == Some("Coin")));
+    assert!(coin
+        .functions
+        .iter()
+        .any(|function| function.name.as_deref() == Some("value")));
+
+    let error = grpc_client
+        .get_package(GetPackageRequest {
+            package_id: Some(ObjectId::new([0x12; 32]).into()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::NotFound);
+}
+
+#[sim_test]
+async fn get_datatype() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let datatype = grpc_client
+        .get_datatype(GetDatatypeRequest {
+            package_id: Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("Coin".to_owned()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .datatype
+        .unwrap();
+
+    assert_eq!(datatype.kind(), DatatypeKind::Struct);
+    assert_eq!(
+        datatype.abilities().collect::<Vec<_>>(),
+        vec![Ability::Store, Ability::Key]
+    );
+    assert_eq!(datatype.type_parameters.len(), 1);
+    assert_eq!(datatype.type_parameters[0].is_phantom, Some(true));
+    assert!(datatype.variants.is_empty());
+
+    let fields = datatype
+        .fields
+        .iter()
+        .map(|field| field.name.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["id", "balance"]);
+
+    let balance = datatype.fields[1].r#type.as_ref().unwrap();
+    assert_eq!(balance.kind(), TypeKind::Datatype);
+    assert_eq!(
+        balance.type_name.as_deref(),
+        Some(
+            "0x0000000000000000000000000000000000000000000000000000000000000002::balance::Balance"
+        )
+    );
+    assert_eq!(
+        balance.type_parameter_instantiation[0].kind(),
+        TypeKind::TypeParameter
+    );
+
+    let error = grpc_client
+        .get_datatype(GetDatatypeRequest {
+            package_id: Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("NotAType".to_owned()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::NotFound);
+}
+
+#[sim_test]
+async fn get_function() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let function = grpc_client
+        .get_function(GetFunctionRequest {
+            package_id: Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("value".to_owned()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .function
+        .unwrap();
+
+    assert_eq!(function.visibility(), Visibility::Public);
+    assert_eq!(function.is_entry, Some(false));
+    assert_eq!(function.type_parameters.len(), 1);
+
+    assert_eq!(function.parameters.len(), 1);
+    let parameter = &function.parameters[0];
+    assert_eq!(parameter.reference(), Reference::Immutable);
+    assert_eq!(parameter.body.as_ref().unwrap().kind(), TypeKind::Datatype);
+
+    assert_eq!(function.returns.len(), 1);
+    assert_eq!(function.returns[0].reference, None);
+    assert_eq!(
+        function.returns[0].body.as_ref().unwrap().kind(),
+        TypeKind::U64
+    );
+
+    let error = grpc_client
+        .get_function(GetFunctionRequest {
+            package_id: Some(ObjectId::from(SUI_FRAMEWORK_PACKAGE_ID).into()),
+            module_name: Some("not_a_module".to_owned()),
+            name: Some("value".to_owned()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::NotFound);
+}
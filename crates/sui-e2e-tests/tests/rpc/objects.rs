@@ -8,6 +8,9 @@ use sui_rpc_api::field_mask::FieldMaskUtil;
 use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
 use sui_rpc_api::proto::node::v2::GetObjectRequest;
 use sui_rpc_api::proto::node::v2::GetObjectResponse;
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient as AlphaNodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::BatchGetObjectsRequest;
+use sui_rpc_api::proto::node::v2alpha::ObjectRequest;
 use sui_sdk_types::ObjectId;
 use test_cluster::TestClusterBuilder;
 
@@ -104,3 +107,68 @@ async fn get_object() {
     assert!(object.is_some());
     assert!(object_bcs.is_some());
 }
+
+#[sim_test]
+async fn batch_get_objects() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut alpha_client = AlphaNodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let clock: ObjectId = "0x6".parse().unwrap();
+    let missing: ObjectId = "0x1234".parse().unwrap();
+
+    let response = alpha_client
+        .batch_get_objects(BatchGetObjectsRequest {
+            requests: vec![
+                ObjectRequest {
+                    object_id: Some(clock.into()),
+                    version: None,
+                },
+                ObjectRequest {
+                    object_id: Some(missing.into()),
+                    version: None,
+                },
+                ObjectRequest {
+                    object_id: Some(clock.into()),
+                    version: Some(u64::MAX),
+                },
+            ],
+            read_mask: Some(FieldMask::from_paths(["object_id", "object_bcs"])),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.objects.len(), 3);
+
+    // Results are returned in the order they were requested in
+    let object = response.objects[0].object.as_ref().unwrap();
+    assert!(response.objects[0].error.is_none());
+    assert_eq!(object.object_id, Some(clock.into()));
+    assert!(object.version.is_none());
+    assert!(object.object_bcs.is_some());
+
+    // Objects that can't be found are reported individually
+    for result in &response.objects[1..] {
+        assert!(result.object.is_none());
+        assert_eq!(
+            result.error.as_ref().unwrap().code,
+            Some(tonic::Code::NotFound.into())
+        );
+    }
+
+    // An invalid read_mask fails the whole request
+    let error = alpha_client
+        .batch_get_objects(BatchGetObjectsRequest {
+            requests: vec![ObjectRequest {
+                object_id: Some(clock.into()),
+                version: None,
+            }],
+            read_mask: Some(FieldMask::from_paths(["not_a_field"])),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
@@ -101,7 +101,8 @@ async fn resolve_transaction_simple_transfer() {
 
     let signed_transaction = test_cluster
         .wallet
-        .sign_transaction(&resolved.transaction.try_into().unwrap());
+        .sign_transaction(&resolved.transaction.try_into().unwrap())
+        .unwrap();
     let effects = client
         .execute_transaction(&signed_transaction)
         .await
@@ -239,7 +240,8 @@ async fn resolve_transaction_borrowed_shared_object() {
 
     let signed_transaction = test_cluster
         .wallet
-        .sign_transaction(&resolved.transaction.try_into().unwrap());
+        .sign_transaction(&resolved.transaction.try_into().unwrap())
+        .unwrap();
     let effects = client
         .execute_transaction(&signed_transaction)
         .await
@@ -305,7 +307,8 @@ async fn resolve_transaction_mutable_shared_object() {
 
     let signed_transaction = test_cluster
         .wallet
-        .sign_transaction(&resolved.transaction.try_into().unwrap());
+        .sign_transaction(&resolved.transaction.try_into().unwrap())
+        .unwrap();
     let effects = client
         .execute_transaction(&signed_transaction)
         .await
@@ -7,6 +7,8 @@ use sui_rpc_api::field_mask::FieldMask;
 use sui_rpc_api::field_mask::FieldMaskUtil;
 use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
 use sui_rpc_api::proto::node::v2::{GetTransactionRequest, GetTransactionResponse};
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient as AlphaNodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::BatchGetTransactionsRequest;
 use test_cluster::TestClusterBuilder;
 
 #[sim_test]
@@ -100,3 +102,130 @@ async fn get_transaction() {
     assert!(checkpoint.is_some());
     assert!(timestamp.is_some());
 }
+
+#[sim_test]
+async fn list_transactions() {
+    use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient as AlphaNodeServiceClient;
+    use sui_rpc_api::proto::node::v2alpha::{
+        ListTransactionsRequest, MoveFunctionFilter, TransactionFilter,
+    };
+    use tokio_stream::StreamExt;
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let mut alpha_client = AlphaNodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let checkpoint = grpc_client
+        .get_transaction(
+            GetTransactionRequest::new(transaction_digest)
+                .with_read_mask(FieldMask::from_paths(["checkpoint"])),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .checkpoint
+        .unwrap();
+
+    let request = ListTransactionsRequest {
+        start_checkpoint: Some(0),
+        end_checkpoint: Some(checkpoint),
+        filter: Some(TransactionFilter {
+            move_function: Some(MoveFunctionFilter {
+                package: Some(
+                    sui_sdk_types::ObjectId::from(sui_types::SUI_SYSTEM_PACKAGE_ID).into(),
+                ),
+                module: Some("sui_system".to_owned()),
+                function: Some("request_add_stake".to_owned()),
+            }),
+            ..Default::default()
+        }),
+        read_mask: Some(FieldMask::from_paths(["digest", "effects"])),
+    };
+
+    let mut stream = alpha_client
+        .list_transactions(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut responses = vec![];
+    while let Some(response) = stream.next().await {
+        responses.push(response.unwrap());
+    }
+
+    // Only the staking transaction calls `request_add_stake`
+    assert_eq!(responses.len(), 1);
+    let response = &responses[0];
+    assert_eq!(response.checkpoint, Some(checkpoint));
+
+    let transaction = response.transaction.as_ref().unwrap();
+    assert_eq!(transaction.digest, Some(transaction_digest.into()));
+    assert!(transaction.effects.is_some());
+    assert!(transaction.transaction.is_none());
+    assert!(transaction.events.is_none());
+
+    // Ranges that span too many checkpoints are rejected
+    let request = ListTransactionsRequest {
+        start_checkpoint: Some(0),
+        end_checkpoint: Some(u64::MAX),
+        ..Default::default()
+    };
+    let error = alpha_client.list_transactions(request).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
+
+#[sim_test]
+async fn batch_get_transactions() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+    let missing = sui_sdk_types::TransactionDigest::new([0; 32]);
+
+    let mut alpha_client = AlphaNodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let response = alpha_client
+        .batch_get_transactions(BatchGetTransactionsRequest {
+            digests: vec![missing.into(), transaction_digest.into()],
+            read_mask: Some(FieldMask::from_paths(["digest", "effects"])),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.transactions.len(), 2);
+
+    // Transactions that can't be found are reported individually
+    let result = &response.transactions[0];
+    assert!(result.transaction.is_none());
+    assert_eq!(
+        result.error.as_ref().unwrap().code,
+        Some(tonic::Code::NotFound.into())
+    );
+
+    let result = &response.transactions[1];
+    assert!(result.error.is_none());
+    let transaction = result.transaction.as_ref().unwrap();
+    assert_eq!(transaction.digest, Some(transaction_digest.into()));
+    assert!(transaction.effects.is_some());
+    assert!(transaction.transaction.is_none());
+    assert!(transaction.events.is_none());
+
+    // A missing digest fails the whole request
+    let error = alpha_client
+        .batch_get_transactions(BatchGetTransactionsRequest {
+            digests: vec![transaction_digest.into(), Default::default()],
+            read_mask: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
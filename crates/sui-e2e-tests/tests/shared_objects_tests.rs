@@ -434,7 +434,7 @@ async fn call_shared_object_contract() {
         .build();
     let effects = test_cluster
         .wallet
-        .execute_transaction_may_fail(test_cluster.wallet.sign_transaction(&transaction))
+        .execute_transaction_may_fail(test_cluster.wallet.sign_transaction(&transaction).unwrap())
         .await
         .unwrap()
         .effects
@@ -462,13 +462,16 @@ async fn access_clock_object_test() {
     let test_cluster = TestClusterBuilder::new().build().await;
     let package_id = publish_basics_package(&test_cluster.wallet).await.0;
 
-    let transaction = test_cluster.wallet.sign_transaction(
-        &test_cluster
-            .test_transaction_builder()
-            .await
-            .move_call(package_id, "clock", "get_time", vec![CallArg::CLOCK_IMM])
-            .build(),
-    );
+    let transaction = test_cluster
+        .wallet
+        .sign_transaction(
+            &test_cluster
+                .test_transaction_builder()
+                .await
+                .move_call(package_id, "clock", "get_time", vec![CallArg::CLOCK_IMM])
+                .build(),
+        )
+        .unwrap();
     let digest = *transaction.digest();
     let start = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -547,11 +550,14 @@ async fn shared_object_sync() {
     let (sender, mut objects) = test_cluster.wallet.get_one_account().await.unwrap();
     let rgp = test_cluster.get_reference_gas_price().await;
     // Send a transaction to create a counter, to all but one authority.
-    let create_counter_transaction = test_cluster.wallet.sign_transaction(
-        &TestTransactionBuilder::new(sender, objects.pop().unwrap(), rgp)
-            .call_counter_create(package_id)
-            .build(),
-    );
+    let create_counter_transaction = test_cluster
+        .wallet
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, objects.pop().unwrap(), rgp)
+                .call_counter_create(package_id)
+                .build(),
+        )
+        .unwrap();
     let committee = test_cluster.committee().deref().clone();
     let validators = test_cluster.get_validator_pubkeys();
     let (slow_validators, fast_validators): (Vec<_>, Vec<_>) =
@@ -596,11 +602,14 @@ async fn shared_object_sync() {
     }
 
     // Make a transaction to increment the counter.
-    let increment_counter_transaction = test_cluster.wallet.sign_transaction(
-        &TestTransactionBuilder::new(sender, objects.pop().unwrap(), rgp)
-            .call_counter_increment(package_id, counter_id, counter_initial_shared_version)
-            .build(),
-    );
+    let increment_counter_transaction = test_cluster
+        .wallet
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, objects.pop().unwrap(), rgp)
+                .call_counter_increment(package_id, counter_id, counter_initial_shared_version)
+                .build(),
+        )
+        .unwrap();
 
     // Let's submit the transaction to the original set of validators, except the first.
     let (effects, _) = test_cluster
@@ -625,13 +634,16 @@ async fn replay_shared_object_transaction() {
     let package_id = publish_basics_package(&test_cluster.wallet).await.0;
 
     // Send a transaction to create a counter (only to one authority) -- twice.
-    let create_counter_transaction = test_cluster.wallet.sign_transaction(
-        &test_cluster
-            .test_transaction_builder()
-            .await
-            .call_counter_create(package_id)
-            .build(),
-    );
+    let create_counter_transaction = test_cluster
+        .wallet
+        .sign_transaction(
+            &test_cluster
+                .test_transaction_builder()
+                .await
+                .call_counter_create(package_id)
+                .build(),
+        )
+        .unwrap();
 
     let mut version = None;
     for _ in 0..2 {
@@ -116,6 +116,7 @@ impl FullCluster {
 
         let client_args = ClientArgs {
             local_ingestion_path: Some(temp_dir.path().to_owned()),
+            rpc_api_url: None,
             remote_store_url: None,
         };
 
@@ -0,0 +1,140 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! These tests check that paginating through checkpoints respects the range of checkpoints whose
+//! contents have not been pruned yet.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+use simulacrum::Simulacrum;
+use sui_indexer_alt::config::{ConcurrentLayer, IndexerConfig, PipelineLayer, PrunerLayer};
+use sui_indexer_alt_e2e_tests::FullCluster;
+use sui_indexer_alt_framework::IndexerArgs;
+use sui_indexer_alt_jsonrpc::{
+    config::RpcConfig, data::system_package_task::SystemPackageTaskArgs,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Set-up a cluster where checkpoint contents are pruned, and check that pages of checkpoints
+/// start from the earliest checkpoint that has not been pruned, in both orders, and regardless of
+/// the cursor.
+#[tokio::test]
+async fn test_checkpoints_pruned() {
+    let mut cluster = FullCluster::new_with_configs(
+        Simulacrum::new(),
+        IndexerArgs::default(),
+        SystemPackageTaskArgs::default(),
+        IndexerConfig {
+            pipeline: PipelineLayer {
+                kv_checkpoints: Some(ConcurrentLayer {
+                    pruner: Some(PrunerLayer {
+                        retention: Some(5),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                cp_sequence_numbers: Some(ConcurrentLayer::default()),
+                ..Default::default()
+            },
+            ..IndexerConfig::for_test()
+        },
+        RpcConfig::default(),
+        &prometheus::Registry::new(),
+        CancellationToken::new(),
+    )
+    .await
+    .expect("Failed to create cluster");
+
+    for _ in 0..2 {
+        cluster.create_checkpoint().await;
+    }
+
+    // Grab a cursor (pointing at checkpoint 1) before its checkpoint is pruned.
+    let (page, cursor) = query_checkpoints(&cluster, None, 2, false).await;
+    assert_eq!(page, vec![0, 1]);
+    let cursor = cursor.unwrap();
+
+    for _ in 2..10 {
+        cluster.create_checkpoint().await;
+    }
+
+    // With checkpoint 10 indexed, and a retention of 5, checkpoints 6 to 10 remain.
+    cluster
+        .wait_for_pruner("kv_checkpoints", 6, Duration::from_secs(10))
+        .await
+        .unwrap();
+
+    let expect: Vec<_> = (6..=10).collect();
+    assert_eq!(query_all_checkpoints(&cluster, false).await, expect);
+
+    let expect: Vec<_> = (6..=10).rev().collect();
+    assert_eq!(query_all_checkpoints(&cluster, true).await, expect);
+
+    // A cursor pointing into the pruned range resumes from the first checkpoint that is still
+    // available.
+    let (page, _) = query_checkpoints(&cluster, Some(cursor), 100, false).await;
+    let expect: Vec<_> = (6..=10).collect();
+    assert_eq!(page, expect);
+}
+
+/// Fetch all checkpoints from the RPC on `cluster`, two at a time.
+async fn query_all_checkpoints(cluster: &FullCluster, descending: bool) -> Vec<u64> {
+    let mut next = None;
+    let mut checkpoints = vec![];
+    loop {
+        let (page, cursor) = query_checkpoints(cluster, next, 2, descending).await;
+        checkpoints.extend(page);
+
+        next = cursor;
+        if next.is_none() {
+            break;
+        }
+    }
+
+    checkpoints
+}
+
+/// Query a page of checkpoints from the RPC on `cluster`. `cursor`, `limit`, and `descending`
+/// control the pagination of the request. Returns a list of checkpoint sequence numbers, and a
+/// cursor if a next page exists.
+async fn query_checkpoints(
+    cluster: &FullCluster,
+    cursor: Option<String>,
+    limit: usize,
+    descending: bool,
+) -> (Vec<u64>, Option<String>) {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sui_getCheckpoints",
+        "params": [cursor, limit, descending]
+    });
+
+    let client = Client::new();
+    let response = client
+        .post(cluster.rpc_url())
+        .json(&query)
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response");
+
+    assert!(body["error"].is_null(), "RPC error: {}", body["error"]);
+
+    let mut checkpoints = vec![];
+    for result in body["result"]["data"].as_array().unwrap() {
+        let seq = result["sequenceNumber"].as_str().unwrap();
+        checkpoints.push(seq.parse().unwrap());
+    }
+
+    let has_next_page = body["result"]["hasNextPage"].as_bool().unwrap();
+    let cursor = has_next_page.then(|| body["result"]["nextCursor"].as_str().unwrap().to_owned());
+
+    (checkpoints, cursor)
+}
@@ -0,0 +1,60 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, 2]
+}
+
+//# run-jsonrpc --cursors 1
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2]
+}
+
+//# run-jsonrpc --cursors 3
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, 2, true]
+}
+
+//# run-jsonrpc --cursors 2
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2, true]
+}
+
+//# run-jsonrpc --cursors 100
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2, true]
+}
+
+//# run-jsonrpc --cursors 100
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getLatestCheckpointSequenceNumber",
+  "params": []
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A B --simulator --objects-snapshot-min-checkpoint-lag 2
+
+// 1. Balance of a custom coin, split across multiple coin objects
+// 2. Balance of SUI, when no coin type is given
+// 3. An address with no coins of the requested type has a zero balance
+// 4. Balances are updated when a coin's balance changes within the same balance bucket (A's gas
+//    coin pays for a transaction), and when a coin is sent to another address
+// 5. Invalid coin type
+
+//# publish --sender A
+module Test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext){
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 20, ctx);
+        let c3 = coin::mint(&mut treasury_cap, 3000, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+        transfer::public_transfer(c3, tx_context::sender(ctx));
+    }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "@{Test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}", "@{Test}::fake::FAKE"]
+}
+
+//# programmable --sender A --inputs 42 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "not_a_type"]
+}
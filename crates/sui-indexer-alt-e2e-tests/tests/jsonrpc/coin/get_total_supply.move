@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A --simulator
+
+// 1. Total supply of a custom coin
+// 2. Total supply of SUI is fixed
+// 3. A coin type with no TreasuryCap
+// 4. Invalid coin type
+
+//# publish --sender A
+module Test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext){
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c = coin::mint(&mut treasury_cap, 1000, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c, tx_context::sender(ctx));
+    }
+}
+
+module Test::nocap {
+    public struct NOCAP has drop {}
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["@{Test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["0x2::sui::SUI"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["@{Test}::nocap::NOCAP"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["not_a_type"]
+}
@@ -1,12 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-// This illustrates the "inconsistency" of suix_getCoins, as opposed to graphql's consistency feature.
-// We have a coin with balance 3400 and another coin with balance 12000 at checkpoint 1.
-// We query with limit 2 and get a cursor specifying checkpoint 1 with the 12000 coin.
-// We update the 3400 coin's balance to 1400 at checkpoint 2.
-// We query the coin using the cursor we got from the first query and see a coin with balance 1400.
-// In graphql, the returned coin will be from checkpoint_viewed_at = 1, and has balance 3400.
+// Cursors for suix_getCoins are pinned to the checkpoint that the first page was read at. This
+// test constructs a cursor pinned at a later checkpoint, to show that the cursor's checkpoint
+// determines what later pages see. There are coins with balance 3400 and 12000 at checkpoint 1,
+// and a first page of 2 gives a cursor pinned at checkpoint 1, pointing at the 12000 coin.
+// The 3400 coin's balance drops to 1400 at checkpoint 2, which a cursor pinned at checkpoint 2
+// sees. (See pinned_checkpoint.move for paging with the cursor that the first page returned.)
 
 //# init --protocol-version 70 --addresses Test=0x0 --accounts A B --simulator --objects-snapshot-min-checkpoint-lag 2
 
@@ -36,7 +36,7 @@
 
 //# create-checkpoint
 
-//# run-jsonrpc --cursors bcs(@{obj_1_0},1,4)
+//# run-jsonrpc --cursors bcs(@{obj_1_0},1,4,2)
 {
   "method": "suix_getCoins",
   "params": ["@{A}", null, "@{cursor_0}"]
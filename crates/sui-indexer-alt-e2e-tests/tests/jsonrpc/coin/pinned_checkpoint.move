@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Paging through suix_getCoins with the cursors it returns keeps reading from the checkpoint that
+// the first page was read at, even after the coins change:
+// We have a coin with balance 3400 and another coin with balance 12000 at checkpoint 1.
+// We query with limit 2 and get a cursor pinned at checkpoint 1, pointing at the 12000 coin.
+// We update the 3400 coin's balance to 1400 at checkpoint 2.
+// We query with the cursor returned by the first query, and still see the coin with balance 3400.
+// Cursors pinned at a checkpoint that has not been indexed yet are rejected.
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A B --simulator --objects-snapshot-min-checkpoint-lag 2
+
+//# programmable --sender A --inputs 12000 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 3400 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", null, null, 2]
+}
+
+//# programmable --sender A --inputs object(2,0) 2000 @A
+//> 0: SplitCoins(Input(0), [Input(1)]);
+//> 1: TransferObjects([Result(0)], Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc --cursors bcs(@{obj_1_0},1,4,1)
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", null, "@{cursor_0}"]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_1_0},1,4,100)
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", null, "@{cursor_0}"]
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Epochs that have ended report the number of transactions they contained, and the ongoing epoch
+// reports the number of transactions indexed for it so far.
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# advance-epoch
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# advance-epoch
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": []
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": [null, 1]
+}
+
+//# run-jsonrpc --cursors 0
+{
+  "method": "suix_getEpochs",
+  "params": ["@{cursor_0}", 1]
+}
+
+//# run-jsonrpc --cursors 2
+{
+  "method": "suix_getEpochs",
+  "params": ["@{cursor_0}", null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCurrentEpoch",
+  "params": []
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+// 1. Query events by sender
+// 2. ...by transaction
+// 3. ...by the module that emitted them
+// 4. ...by the module that defines their type
+// 5. ...by their exact type
+// 6. ...by their type, with type parameters
+// 7. Querying a type with the wrong type parameters returns nothing
+
+//# publish
+module P::M {
+  public struct Foo() has copy, drop;
+  public struct Bar<phantom T>() has copy, drop;
+
+  public fun foo() { sui::event::emit(Foo()) }
+  public fun bar<T>() { sui::event::emit(Bar<T>()) }
+}
+
+module P::N {
+  public fun foo_and_bar() {
+    P::M::foo();
+    P::M::bar<u64>();
+  }
+}
+
+//# programmable --sender A
+//> P::M::foo();
+
+//# programmable --sender B
+//> P::M::bar<u64>();
+
+//# programmable --sender A
+//> P::N::foo_and_bar();
+
+//# programmable --sender B
+//> P::M::bar<bool>();
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "@{digest_4}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{P}", "module": "N" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventModule": { "package": "@{P}", "module": "M" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::Foo" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::Bar<u64>" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::Bar<address>" }]
+}
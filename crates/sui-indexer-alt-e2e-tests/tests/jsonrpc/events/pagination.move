@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+// 1. Fetch all events, with the default page size
+// 2. Setting a limit
+// 3. Resuming from a cursor in the middle of a transaction's events
+// 4. Resuming from a cursor at the end of a transaction's events
+// 5. Changing the order
+// 6. Setting the order, cursor and limit
+// 7. Providing a bad cursor
+// 8. Page size too large
+
+//# publish
+module P::M {
+  public struct E(u64) has copy, drop;
+
+  public fun emit(n: u64) {
+    let mut i = 0;
+    while (i < n) {
+      sui::event::emit(E(i));
+      i = i + 1;
+    }
+  }
+}
+
+//# programmable --sender A --inputs 3
+//> P::M::emit(Input(0))
+
+//# programmable --sender A --inputs 2
+//> P::M::emit(Input(0))
+
+//# programmable --sender A --inputs 1
+//> P::M::emit(Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, null, 2]
+}
+
+//# run-jsonrpc --cursors {"tx_sequence_number":2,"event_seq":1}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc --cursors {"tx_sequence_number":2,"event_seq":2}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, null, null, true]
+}
+
+//# run-jsonrpc --cursors {"tx_sequence_number":3,"event_seq":1}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, "@{cursor_0}", 2, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, "i_am_not_a_cursor"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, null, 10000]
+}
@@ -0,0 +1,61 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+// 1. A time range covering the first checkpoint's events only
+// 2. A time range covering the second checkpoint's events only
+// 3. An open-ended time range, past the last checkpoint
+// 4. An empty time range
+// 5. A time range whose start is after its end
+
+//# publish
+module P::M {
+  public struct E(u64) has copy, drop;
+
+  public fun emit(x: u64) { sui::event::emit(E(x)) }
+}
+
+//# advance-clock --duration-ns 1000000000
+
+//# programmable --sender A --inputs 1
+//> P::M::emit(Input(0))
+
+//# create-checkpoint
+
+//# advance-clock --duration-ns 1000000000
+
+//# programmable --sender A --inputs 2
+//> P::M::emit(Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "0", "endTime": "1500" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "1500", "endTime": "2500" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "1500", "endTime": "1000000" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "1000", "endTime": "1000" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "2000", "endTime": "1000" } }]
+}
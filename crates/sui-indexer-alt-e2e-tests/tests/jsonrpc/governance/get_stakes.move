@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Stakes are pending until the epoch after they were requested, and then become active.
+
+//# init --protocol-version 70 --simulator --accounts A B
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# programmable --sender A --inputs 1000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# programmable --sender A --inputs 2000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# advance-clock --duration-ns 1000000
+
+//# advance-epoch
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{B}"]
+}
@@ -0,0 +1,42 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// APYs are recalculated when the epoch changes, and are otherwise served from a cache.
+
+//# init --protocol-version 70 --simulator --accounts A
+
+//# run-jsonrpc
+{
+  "method": "suix_getValidatorsApy",
+  "params": []
+}
+
+//# programmable --sender A --inputs 1000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getValidatorsApy",
+  "params": []
+}
+
+//# advance-epoch
+
+//# advance-epoch
+
+//# advance-epoch
+
+//# run-jsonrpc
+{
+  "method": "suix_getValidatorsApy",
+  "params": []
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getValidatorsApy",
+  "params": []
+}
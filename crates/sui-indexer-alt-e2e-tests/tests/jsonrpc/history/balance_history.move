@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --simulator
+
+// 1. A's SUI balance, split across multiple coin objects, at checkpoint 1
+// 2. After one of the coins is sent to B at checkpoint 2, A's balance at checkpoints 2 and 1 (in
+//    the order requested)...
+// 3. ...and B's balance at checkpoints 1 and 2
+// 4. The same balances, with the coin type given explicitly
+// 5. A checkpoint that has not been indexed yet
+// 6. Invalid coin type
+
+//# programmable --sender A --inputs 12000 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 3400 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{A}", null, ["1"]]
+}
+
+//# programmable --sender A --inputs object(2,0) @B
+//> 0: TransferObjects([Input(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{A}", null, ["2", "1"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{B}", null, ["1", "2"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{B}", "0x2::sui::SUI", ["1", "2"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{A}", null, ["1", "100"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalanceHistory",
+  "params": ["@{A}", "not_a_type", ["1"]]
+}
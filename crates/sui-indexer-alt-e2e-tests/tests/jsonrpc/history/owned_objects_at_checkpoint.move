@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+// 1. All the objects owned by A at checkpoint 1
+// 2. After one of them is transferred at checkpoint 2, A's objects at checkpoint 2...
+// 3. ...and B's objects at checkpoint 2
+// 4. A's objects at checkpoint 1 are unaffected by the transfer
+// 5. ...limited
+// 6. ...limited, with a cursor
+// 7. A checkpoint that has not been indexed yet
+
+//# publish
+module P::M {
+  public struct O has key, store {
+    id: UID,
+  }
+
+  public fun o(ctx: &mut TxContext): O {
+    O { id: object::new(ctx) }
+  }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: P::M::o();
+//> 1: P::M::o();
+//> 2: TransferObjects([Result(0), Result(1)], Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "1", { "showType": true }]
+}
+
+//# programmable --sender A --inputs object(2,0) @B
+//> 0: TransferObjects([Input(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "2", { "showType": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{B}", "2", { "showType": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "1", { "showType": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "1", { "showType": true }, null, 1]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_2_0},1,1)
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "1", { "showType": true }, "@{cursor_0}", 1]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjectsAtCheckpoint",
+  "params": ["@{A}", "100", { "showType": true }]
+}
@@ -75,7 +75,7 @@ module P::M {
   ]
 }
 
-//# run-jsonrpc --cursors bcs(@{obj_5_0},2)
+//# run-jsonrpc --cursors bcs(@{obj_5_0},2,2)
 {
   "method": "suix_getOwnedObjects",
   "params": [
@@ -0,0 +1,162 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! These tests check that fetching the stakes owned by an address fails, rather than silently
+//! returning a partial result, when the address owns more stakes than the RPC is configured to
+//! return.
+
+use std::str::FromStr;
+
+use move_core_types::ident_str;
+use reqwest::Client;
+use serde_json::{json, Value};
+use simulacrum::Simulacrum;
+use sui_indexer_alt::config::IndexerConfig;
+use sui_indexer_alt_e2e_tests::{find_address_owned, FullCluster};
+use sui_indexer_alt_framework::IndexerArgs;
+use sui_indexer_alt_jsonrpc::{
+    config::{GovernanceConfig, RpcConfig},
+    data::system_package_task::SystemPackageTaskArgs,
+};
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::{get_account_key_pair, Signature, Signer},
+    effects::TransactionEffectsAPI,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, Command, ObjectArg, Transaction, TransactionData},
+    SUI_SYSTEM_PACKAGE_ID,
+};
+use tokio_util::sync::CancellationToken;
+
+/// 5 SUI gas budget
+const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
+
+/// 1 SUI, the minimum stake
+const STAKE_AMOUNT: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn test_too_many_stakes() {
+    let mut cluster = FullCluster::new_with_configs(
+        Simulacrum::new(),
+        IndexerArgs::default(),
+        SystemPackageTaskArgs::default(),
+        IndexerConfig::for_test(),
+        RpcConfig {
+            governance: GovernanceConfig { max_stakes: 2 },
+            ..Default::default()
+        },
+        &prometheus::Registry::new(),
+        CancellationToken::new(),
+    )
+    .await
+    .expect("Failed to create cluster");
+
+    let validator = validator_address(&cluster).await;
+    let (a, akp) = get_account_key_pair();
+
+    // Up to the limit, all stakes are returned.
+    for expect in 1..=2 {
+        add_stake(&mut cluster, a, &akp, validator);
+        cluster.create_checkpoint().await;
+
+        let stakes = get_stakes(&cluster, a).await;
+        assert!(stakes["error"].is_null(), "RPC error: {}", stakes["error"]);
+
+        let count: usize = stakes["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pool| pool["stakes"].as_array().unwrap().len())
+            .sum();
+
+        assert_eq!(count, expect);
+    }
+
+    // One more stake exceeds the limit, which is reported as an error.
+    add_stake(&mut cluster, a, &akp, validator);
+    cluster.create_checkpoint().await;
+
+    let stakes = get_stakes(&cluster, a).await;
+    assert!(stakes["result"].is_null(), "Unexpected result: {}", stakes);
+
+    let message = stakes["error"]["message"].as_str().unwrap();
+    assert!(message.contains("more than 2 stakes"), "{message}");
+}
+
+/// Find the address of a validator in `cluster`'s latest system state.
+async fn validator_address(cluster: &FullCluster) -> SuiAddress {
+    let state = query(cluster, "suix_getLatestSuiSystemState", json!([])).await;
+    let address = state["result"]["activeValidators"][0]["suiAddress"]
+        .as_str()
+        .expect("Failed to find validator address");
+
+    SuiAddress::from_str(address).unwrap()
+}
+
+/// Request gas from the "faucet" in `cluster`, and use it to stake [STAKE_AMOUNT] with
+/// `validator` on behalf of `sender` (signed for with `signer`).
+fn add_stake(
+    cluster: &mut FullCluster,
+    sender: SuiAddress,
+    signer: &dyn Signer<Signature>,
+    validator: SuiAddress,
+) {
+    let fx = cluster
+        .request_gas(sender, DEFAULT_GAS_BUDGET + STAKE_AMOUNT)
+        .expect("Failed to request gas");
+
+    let gas = find_address_owned(&fx).expect("Failed to find gas object");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let system = builder.obj(ObjectArg::SUI_SYSTEM_MUT).unwrap();
+    let amount = builder.pure(STAKE_AMOUNT).unwrap();
+    let validator = builder.pure(validator).unwrap();
+    let stake = builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+    builder.programmable_move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        ident_str!("sui_system").to_owned(),
+        ident_str!("request_add_stake").to_owned(),
+        vec![],
+        vec![system, stake, validator],
+    );
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        DEFAULT_GAS_BUDGET,
+        cluster.reference_gas_price(),
+    );
+
+    let (fx, _) = cluster
+        .execute_transaction(Transaction::from_data_and_signer(data, vec![signer]))
+        .expect("Failed to execute transaction");
+
+    assert!(fx.status().is_ok());
+}
+
+/// Fetch the stakes owned by `owner` from the RPC on `cluster`.
+async fn get_stakes(cluster: &FullCluster, owner: SuiAddress) -> Value {
+    query(cluster, "suix_getStakes", json!([owner])).await
+}
+
+/// Send a JSON-RPC request for `method` with `params` to the RPC on `cluster`, and return the
+/// response body.
+async fn query(cluster: &FullCluster, method: &str, params: Value) -> Value {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    Client::new()
+        .post(cluster.rpc_url())
+        .json(&query)
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response")
+}
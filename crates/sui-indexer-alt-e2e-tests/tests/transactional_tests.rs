@@ -105,6 +105,7 @@ async fn cluster(config: &OffChainConfig) -> Arc<OffchainCluster> {
 
     let client_args = ClientArgs {
         local_ingestion_path: Some(config.data_ingestion_path.clone()),
+        rpc_api_url: None,
         remote_store_url: None,
     };
 
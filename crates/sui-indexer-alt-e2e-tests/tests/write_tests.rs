@@ -102,7 +102,7 @@ impl WriteTestCluster {
             .transfer_sui(Some(1_000), recipient)
             .build();
         let tx_digest = tx.digest().to_string();
-        let signed_tx = self.onchain_cluster.wallet.sign_transaction(&tx);
+        let signed_tx = self.onchain_cluster.wallet.sign_transaction(&tx).unwrap();
         let (tx_bytes, sigs) = signed_tx.to_tx_bytes_and_signatures();
         let tx_bytes = tx_bytes.encoded();
         let sigs: Vec<_> = sigs.iter().map(|sig| sig.encoded()).collect();
@@ -119,7 +119,7 @@ impl WriteTestCluster {
             .call_request_remove_validator()
             .build();
         let tx_digest = tx.digest().to_string();
-        let signed_tx = self.onchain_cluster.wallet.sign_transaction(&tx);
+        let signed_tx = self.onchain_cluster.wallet.sign_transaction(&tx).unwrap();
         let (tx_bytes, sigs) = signed_tx.to_tx_bytes_and_signatures();
         let tx_bytes = tx_bytes.encoded();
         let sigs: Vec<_> = sigs.iter().map(|sig| sig.encoded()).collect();
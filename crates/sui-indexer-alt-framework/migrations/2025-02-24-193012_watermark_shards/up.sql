@@ -0,0 +1,29 @@
+-- Tracks the progress of each shard of a concurrent pipeline that has been split across multiple
+-- indexer instances, and which instance is currently responsible for it. The pipeline's overall
+-- watermark (in `watermarks`) is the lowest watermark across all its shards.
+CREATE TABLE IF NOT EXISTS watermark_shards
+(
+    -- The sharded pipeline.
+    pipeline                    TEXT          NOT NULL,
+    -- The shard's index. The shard is responsible for checkpoints whose
+    -- sequence number modulo `shards` is equal to this index.
+    shard                       BIGINT        NOT NULL,
+    -- The number of shards that the pipeline is split into.
+    shards                      BIGINT        NOT NULL,
+    -- Committer watermark for the shard: All data for checkpoints that this
+    -- shard is responsible for, at or before this watermark, has been
+    -- persisted. A `checkpoint_hi_inclusive` of -1 indicates that the shard
+    -- has not committed any data yet.
+    epoch_hi_inclusive          BIGINT        NOT NULL,
+    checkpoint_hi_inclusive     BIGINT        NOT NULL,
+    tx_hi                       BIGINT        NOT NULL,
+    timestamp_ms_hi_inclusive   BIGINT        NOT NULL,
+    -- Identifies the indexer instance that holds the lease on this shard.
+    -- Only the lease owner can update the shard's watermark.
+    lease_owner                 TEXT          NOT NULL,
+    -- If the lease is not renewed by this time (according to the database's
+    -- clock), it is considered abandoned, and another instance can take over
+    -- the shard.
+    lease_expires_at            TIMESTAMP     NOT NULL,
+    PRIMARY KEY (pipeline, shard)
+);
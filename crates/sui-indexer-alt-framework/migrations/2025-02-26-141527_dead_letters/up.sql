@@ -0,0 +1,19 @@
+-- Checkpoints that a concurrent pipeline failed to index (and skipped) because it was configured
+-- to dead-letter failures. Each entry is removed once the checkpoint has been replayed
+-- successfully.
+CREATE TABLE IF NOT EXISTS dead_letters
+(
+    -- The pipeline that failed to index the checkpoint.
+    pipeline                    TEXT          NOT NULL,
+    -- The checkpoint that could not be indexed.
+    cp_sequence_number          BIGINT        NOT NULL,
+    -- The part of the pipeline that failed: 'process' if the checkpoint could
+    -- not be processed, 'commit' if its rows could not be written, or 'replay'
+    -- if it failed again while being replayed.
+    stage                       TEXT          NOT NULL,
+    -- The last error that was encountered.
+    error                       TEXT          NOT NULL,
+    -- When the failure was recorded.
+    created_at                  TIMESTAMP     NOT NULL,
+    PRIMARY KEY (pipeline, cp_sequence_number)
+);
@@ -0,0 +1,13 @@
+-- Live pipelines that have been retired in favour of a shadow pipeline, which
+-- wrote a new version of their tables alongside them. Once a swap has been
+-- recorded, the live pipeline's tables are views over the shadow pipeline's
+-- tables, and the live pipeline's watermark mirrors the shadow pipeline's.
+CREATE TABLE IF NOT EXISTS pipeline_swaps
+(
+    -- The live pipeline that was retired.
+    pipeline                    TEXT          PRIMARY KEY,
+    -- The shadow pipeline that replaced it.
+    replaced_by                 TEXT          NOT NULL,
+    -- When the swap happened.
+    swapped_at                  TIMESTAMP     NOT NULL
+);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS cp_sequence_numbers_unknown_timestamp;
+
+DROP INDEX IF EXISTS cp_sequence_numbers_timestamp_ms;
+
+ALTER TABLE cp_sequence_numbers
+DROP COLUMN IF EXISTS timestamp_ms;
@@ -0,0 +1,15 @@
+-- The timestamp of each checkpoint, so that timestamps can be translated into
+-- checkpoint and transaction bounds with a single indexed lookup. Checkpoints
+-- indexed before this column was added have an unknown (NULL) timestamp, and
+-- queries by time range must not reach them.
+ALTER TABLE cp_sequence_numbers
+ADD COLUMN IF NOT EXISTS timestamp_ms BIGINT;
+
+CREATE INDEX IF NOT EXISTS cp_sequence_numbers_timestamp_ms
+ON cp_sequence_numbers (timestamp_ms, tx_lo);
+
+-- Finds the latest checkpoint with an unknown timestamp, to detect time ranges
+-- that could reach it.
+CREATE INDEX IF NOT EXISTS cp_sequence_numbers_unknown_timestamp
+ON cp_sequence_numbers (cp_sequence_number)
+WHERE timestamp_ms IS NULL;
@@ -204,6 +204,8 @@ mod tests {
 
     #[async_trait::async_trait]
     impl concurrent::Handler for TxCounts {
+        type Store = Db;
+
         async fn commit(
             values: &[Self::Value],
             conn: &mut db::Connection<'_>,
@@ -257,6 +259,7 @@ mod tests {
         let args = Args {
             client_args: ClientArgs {
                 local_ingestion_path: Some(checkpoint_dir.path().to_owned()),
+                rpc_api_url: None,
                 remote_store_url: None,
             },
             indexer_args: IndexerArgs {
@@ -25,16 +25,20 @@ impl Processor for CpSequenceNumbers {
             checkpoint.checkpoint_summary.network_total_transactions as i64;
         let tx_lo = network_total_transactions - checkpoint.transactions.len() as i64;
         let epoch = checkpoint.checkpoint_summary.epoch as i64;
+        let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms as i64;
         Ok(vec![StoredCpSequenceNumbers {
             cp_sequence_number,
             tx_lo,
             epoch,
+            timestamp_ms: Some(timestamp_ms),
         }])
     }
 }
 
 #[async_trait::async_trait]
 impl Handler for CpSequenceNumbers {
+    type Store = db::Db;
+
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
         Ok(diesel::insert_into(cp_sequence_numbers::table)
             .values(values)
@@ -16,6 +16,7 @@ use url::Url;
 
 use crate::ingestion::local_client::LocalIngestionClient;
 use crate::ingestion::remote_client::RemoteIngestionClient;
+use crate::ingestion::rpc_client::RpcIngestionClient;
 use crate::ingestion::Error as IngestionError;
 use crate::ingestion::Result as IngestionResult;
 use crate::metrics::CheckpointLagMetricReporter;
@@ -28,6 +29,10 @@ const MAX_TRANSIENT_RETRY_INTERVAL: Duration = Duration::from_secs(60);
 #[async_trait::async_trait]
 pub(crate) trait IngestionClientTrait: Send + Sync {
     async fn fetch(&self, checkpoint: u64) -> FetchResult;
+
+    /// Start any background tasks the client uses to fetch checkpoints, which will stop when
+    /// `cancel` is cancelled. Fetching works without calling this, but may be slower.
+    fn start(&self, _cancel: CancellationToken) {}
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -44,7 +49,14 @@ pub enum FetchError {
     },
 }
 
-pub type FetchResult = Result<Bytes, FetchError>;
+pub type FetchResult = Result<FetchData, FetchError>;
+
+/// The data returned by an [IngestionClientTrait] implementation, either as the raw bytes of a
+/// checkpoint blob, which still need to be deserialized, or as an already deserialized checkpoint.
+pub enum FetchData {
+    Raw(Bytes),
+    CheckpointData(CheckpointData),
+}
 
 #[derive(Clone)]
 pub struct IngestionClient {
@@ -65,6 +77,11 @@ impl IngestionClient {
         Self::new_impl(client, metrics)
     }
 
+    pub(crate) fn new_rpc(url: Url, metrics: Arc<IndexerMetrics>) -> IngestionResult<Self> {
+        let client = Arc::new(RpcIngestionClient::new(url)?);
+        Ok(Self::new_impl(client, metrics))
+    }
+
     fn new_impl(client: Arc<dyn IngestionClientTrait>, metrics: Arc<IndexerMetrics>) -> Self {
         let checkpoint_lag_reporter = CheckpointLagMetricReporter::new(
             metrics.ingested_checkpoint_timestamp_lag.clone(),
@@ -78,6 +95,12 @@ impl IngestionClient {
         }
     }
 
+    /// Start the client's background tasks, if it has any. They will stop when `cancel` is
+    /// cancelled.
+    pub(crate) fn start(&self, cancel: CancellationToken) {
+        self.client.start(cancel);
+    }
+
     /// Fetch checkpoint data by sequence number.
     ///
     /// This function behaves like `IngestionClient::fetch`, but will repeatedly retry the fetch if
@@ -114,7 +137,7 @@ impl IngestionClient {
     /// Repeatedly retries transient errors with an exponential backoff (up to
     /// [MAX_TRANSIENT_RETRY_INTERVAL]). Transient errors are either defined by the client
     /// implementation that returns a [FetchError::Transient] error variant, or within this
-    /// function if we fail to deserialize the result as [CheckpointData].
+    /// function if we fail to deserialize a [FetchData::Raw] result as [CheckpointData].
     ///
     /// The function will immediately return on:
     ///
@@ -135,7 +158,7 @@ impl IngestionClient {
                     return Err(BE::permanent(IngestionError::Cancelled));
                 }
 
-                let bytes = client.fetch(checkpoint).await.map_err(|err| match err {
+                let fetched = client.fetch(checkpoint).await.map_err(|err| match err {
                     FetchError::NotFound => BE::permanent(IngestionError::NotFound(checkpoint)),
                     FetchError::Permanent(error) => {
                         BE::permanent(IngestionError::FetchError(checkpoint, error))
@@ -147,14 +170,26 @@ impl IngestionClient {
                     ),
                 })?;
 
-                self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
-                let data: CheckpointData = Blob::from_bytes(&bytes).map_err(|e| {
-                    self.metrics.inc_retry(
-                        checkpoint,
-                        "deserialization",
-                        IngestionError::DeserializationError(checkpoint, e),
-                    )
-                })?;
+                let data: CheckpointData = match fetched {
+                    FetchData::Raw(bytes) => {
+                        self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
+                        Blob::from_bytes(&bytes).map_err(|e| {
+                            self.metrics.inc_retry(
+                                checkpoint,
+                                "deserialization",
+                                IngestionError::DeserializationError(checkpoint, e),
+                            )
+                        })?
+                    }
+
+                    FetchData::CheckpointData(data) => {
+                        // The checkpoint was already deserialized by the client, so count the
+                        // size of its BCS serialization, which is what a blob would contain.
+                        let bytes = bcs::serialized_size(&data).unwrap_or_default();
+                        self.metrics.total_ingested_bytes.inc_by(bytes as u64);
+                        data
+                    }
+                };
 
                 Ok(data)
             }
@@ -26,8 +26,9 @@ pub mod error;
 mod local_client;
 mod regulator;
 mod remote_client;
+mod rpc_client;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 
 #[derive(clap::Args, Clone, Debug)]
 pub struct ClientArgs {
@@ -39,6 +40,13 @@ pub struct ClientArgs {
     /// If both remote_store_url and local_ingestion_path are provided, remote_store_url will be used.
     #[clap(long, required = true, group = "source")]
     pub local_ingestion_path: Option<PathBuf>,
+
+    /// URL of a fullnode's RPC API to fetch checkpoints from. Checkpoints are fetched individually
+    /// until the indexer catches up with the node, and are then streamed from it as they are
+    /// executed.
+    /// If remote_store_url or local_ingestion_path are also provided, they will be used instead.
+    #[clap(long, required = true, group = "source")]
+    pub rpc_api_url: Option<Url>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -82,8 +90,10 @@ impl IngestionService {
             IngestionClient::new_remote(url.clone(), metrics.clone())?
         } else if let Some(path) = args.local_ingestion_path.as_ref() {
             IngestionClient::new_local(path.clone(), metrics.clone())
+        } else if let Some(url) = args.rpc_api_url.as_ref() {
+            IngestionClient::new_rpc(url.clone(), metrics.clone())?
         } else {
-            panic!("Either remote_store_url or local_ingestion_path must be provided");
+            panic!("One of remote_store_url, local_ingestion_path or rpc_api_url must be provided");
         };
 
         let subscribers = Vec::new();
@@ -103,6 +113,11 @@ impl IngestionService {
         &self.client
     }
 
+    /// The configuration this service was created with.
+    pub(crate) fn config(&self) -> &IngestionConfig {
+        &self.config
+    }
+
     /// Add a new subscription to the ingestion service. Note that the service is susceptible to
     /// the "slow receiver" problem: If one receiver is slower to process checkpoints than the
     /// checkpoint ingestion rate, it will eventually hold up all receivers.
@@ -155,6 +170,8 @@ impl IngestionService {
             return Err(Error::NoSubscribers);
         }
 
+        client.start(cancel.clone());
+
         let (checkpoint_tx, checkpoint_rx) = mpsc::channel(config.ingest_concurrency);
 
         let regulator = regulator(
@@ -204,6 +221,7 @@ mod tests {
             ClientArgs {
                 remote_store_url: Some(Url::parse(&uri).unwrap()),
                 local_ingestion_path: None,
+                rpc_api_url: None,
             },
             IngestionConfig {
                 checkpoint_buffer_size,
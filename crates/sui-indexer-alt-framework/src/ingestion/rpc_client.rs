@@ -0,0 +1,338 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    pin::pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use sui_rpc_api::Client;
+use tokio::{sync::watch, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tonic::Code;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use crate::ingestion::client::{FetchData, FetchError, FetchResult, IngestionClientTrait};
+use crate::ingestion::Result as IngestionResult;
+use crate::types::full_checkpoint_content::CheckpointData;
+
+/// Maximum number of checkpoints from the live stream to hold on to until they are fetched. If the
+/// indexer is behind the stream, the oldest checkpoints are dropped, and will be fetched
+/// individually instead.
+const MAX_BUFFERED_CHECKPOINTS: usize = 100;
+
+/// How long to wait for a checkpoint that the node does not have yet to show up on the live stream,
+/// before reporting it as not found.
+const STREAM_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait before re-subscribing to the live stream after it fails.
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fetches checkpoints from a fullnode's RPC API. Checkpoints that the node has already executed are
+/// fetched individually, with `GetFullCheckpoint`, while new checkpoints are received from its
+/// `SubscribeCheckpoints` stream as soon as they are executed. This way, the client can backfill
+/// from wherever the indexer is up to, and switch over to the stream once it has caught up with the
+/// tip of the network.
+///
+/// The subscription to the live stream is only opened once the client is started, before that all
+/// checkpoints are fetched individually.
+pub(crate) struct RpcIngestionClient {
+    client: Client,
+
+    /// Checkpoints received from the live stream that have not been fetched yet.
+    buffer: Arc<Mutex<BTreeMap<u64, CheckpointData>>>,
+
+    /// The highest checkpoint received from the live stream so far.
+    stream_hi: watch::Receiver<Option<u64>>,
+
+    /// The sending side of `stream_hi`, which is handed to the subscriber when the client is
+    /// started.
+    stream_hi_tx: Mutex<Option<watch::Sender<Option<u64>>>>,
+
+    /// Background task that maintains the subscription to the live stream, once started.
+    subscriber: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RpcIngestionClient {
+    pub(crate) fn new(url: Url) -> IngestionResult<Self> {
+        let client = Client::new(url.as_str())?;
+        let buffer = Arc::new(Mutex::new(BTreeMap::new()));
+        let (stream_hi_tx, stream_hi) = watch::channel(None);
+
+        Ok(Self {
+            client,
+            buffer,
+            stream_hi,
+            stream_hi_tx: Mutex::new(Some(stream_hi_tx)),
+            subscriber: Mutex::new(None),
+        })
+    }
+
+    /// Remove `checkpoint` from the buffer of checkpoints received from the live stream, if it is
+    /// there.
+    fn take_buffered(&self, checkpoint: u64) -> Option<CheckpointData> {
+        self.buffer.lock().unwrap().remove(&checkpoint)
+    }
+
+    /// Wait (up to [STREAM_WAIT_TIMEOUT]) for `checkpoint` to be received from the live stream. This
+    /// can fail to return the checkpoint even if the stream has moved past it, if the stream
+    /// skipped the checkpoint while re-subscribing.
+    async fn wait_for_stream(&self, checkpoint: u64) -> Option<CheckpointData> {
+        // Nothing will arrive on the stream if the client was never started.
+        if self.stream_hi_tx.lock().unwrap().is_some() {
+            return None;
+        }
+
+        let mut stream_hi = self.stream_hi.clone();
+        let reached = stream_hi.wait_for(|hi| hi.is_some_and(|hi| hi >= checkpoint));
+        match tokio::time::timeout(STREAM_WAIT_TIMEOUT, reached).await {
+            Ok(Ok(_)) => self.take_buffered(checkpoint),
+            // Timed out, or the subscriber stopped.
+            _ => None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IngestionClientTrait for RpcIngestionClient {
+    /// Fetch a checkpoint from the live stream if it has already been received, and from
+    /// `GetFullCheckpoint` otherwise. If the node has not executed the checkpoint yet, waits for it
+    /// to arrive on the stream for a short while, before treating it as not found.
+    ///
+    /// Transient errors include failures to reach the node, the node being overloaded or
+    /// unavailable, and timeouts.
+    async fn fetch(&self, checkpoint: u64) -> FetchResult {
+        if let Some(data) = self.take_buffered(checkpoint) {
+            return Ok(FetchData::CheckpointData(data));
+        }
+
+        match self.client.get_full_checkpoint(checkpoint).await {
+            Ok(data) => Ok(FetchData::CheckpointData(data)),
+
+            Err(status) if status.code() == Code::NotFound => {
+                if let Some(data) = self.wait_for_stream(checkpoint).await {
+                    return Ok(FetchData::CheckpointData(data));
+                }
+
+                debug!(checkpoint, "Checkpoint not found");
+                Err(FetchError::NotFound)
+            }
+
+            Err(status) => match status.code() {
+                Code::Unavailable
+                | Code::DeadlineExceeded
+                | Code::ResourceExhausted
+                | Code::Aborted
+                | Code::Internal
+                | Code::Unknown => Err(FetchError::Transient {
+                    reason: "rpc_error",
+                    error: status.into(),
+                }),
+
+                _ => {
+                    warn!(checkpoint, code = ?status.code(), "Permanent error, giving up!");
+                    Err(FetchError::Permanent(status.into()))
+                }
+            },
+        }
+    }
+
+    /// Subscribe to the node's live checkpoint stream in the background, until `cancel` is
+    /// cancelled. Subsequent calls have no effect.
+    fn start(&self, cancel: CancellationToken) {
+        let Some(stream_hi_tx) = self.stream_hi_tx.lock().unwrap().take() else {
+            return;
+        };
+
+        let subscription = subscribe(self.client.clone(), self.buffer.clone(), stream_hi_tx);
+        let subscriber = tokio::spawn(async move {
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = subscription => {}
+            }
+        });
+
+        *self.subscriber.lock().unwrap() = Some(subscriber);
+    }
+}
+
+impl Drop for RpcIngestionClient {
+    fn drop(&mut self) {
+        if let Some(subscriber) = self.subscriber.lock().unwrap().take() {
+            subscriber.abort();
+        }
+    }
+}
+
+/// Keep a subscription to the node's live checkpoint stream open (re-subscribing whenever it
+/// fails, unless the node does not support subscriptions at all), adding the checkpoints it
+/// receives to `buffer`, and reporting the highest checkpoint received on `stream_hi`.
+async fn subscribe(
+    client: Client,
+    buffer: Arc<Mutex<BTreeMap<u64, CheckpointData>>>,
+    stream_hi: watch::Sender<Option<u64>>,
+) {
+    loop {
+        let stream = match client.subscribe_checkpoints().await {
+            Ok(stream) => stream,
+            Err(status) if status.code() == Code::Unimplemented => {
+                warn!("Checkpoint subscriptions not supported, fetching checkpoints individually");
+                return;
+            }
+
+            Err(status) => {
+                warn!(code = ?status.code(), "Failed to subscribe to checkpoints: {status}");
+                tokio::time::sleep(RESUBSCRIBE_INTERVAL).await;
+                continue;
+            }
+        };
+
+        info!("Subscribed to checkpoints");
+        let mut stream = pin!(stream);
+        while let Some(next) = stream.next().await {
+            let data = match next {
+                Ok(data) => data,
+                Err(status) => {
+                    warn!(code = ?status.code(), "Checkpoint subscription failed: {status}");
+                    break;
+                }
+            };
+
+            buffer_checkpoint(&buffer, &stream_hi, data);
+        }
+
+        tokio::time::sleep(RESUBSCRIBE_INTERVAL).await;
+    }
+}
+
+/// Add a checkpoint received from the live stream to `buffer`, dropping the oldest checkpoints if
+/// the buffer is full, and report it on `stream_hi` if it is the highest checkpoint seen so far.
+fn buffer_checkpoint(
+    buffer: &Mutex<BTreeMap<u64, CheckpointData>>,
+    stream_hi: &watch::Sender<Option<u64>>,
+    data: CheckpointData,
+) {
+    let checkpoint = data.checkpoint_summary.sequence_number;
+
+    {
+        let mut buffer = buffer.lock().unwrap();
+        buffer.insert(checkpoint, data);
+        while buffer.len() > MAX_BUFFERED_CHECKPOINTS {
+            buffer.pop_first();
+        }
+    }
+
+    stream_hi.send_if_modified(|hi| {
+        if hi.is_some_and(|hi| hi >= checkpoint) {
+            false
+        } else {
+            *hi = Some(checkpoint);
+            true
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_storage::blob::Blob;
+
+    use super::*;
+    use crate::ingestion::test_utils::test_checkpoint_data;
+
+    /// A client for a node that is not running, so any request it makes fails.
+    fn test_client() -> RpcIngestionClient {
+        RpcIngestionClient::new(Url::parse("http://localhost:1").unwrap()).unwrap()
+    }
+
+    fn checkpoint(cp: u64) -> CheckpointData {
+        Blob::from_bytes(&test_checkpoint_data(cp)).unwrap()
+    }
+
+    fn sequence_number(data: FetchData) -> u64 {
+        let FetchData::CheckpointData(data) = data else {
+            panic!("Expected deserialized checkpoint data");
+        };
+
+        data.checkpoint_summary.sequence_number
+    }
+
+    #[tokio::test]
+    async fn fetch_buffered_checkpoint() {
+        let client = test_client();
+        let (stream_hi_tx, _) = watch::channel(None);
+        buffer_checkpoint(&client.buffer, &stream_hi_tx, checkpoint(42));
+
+        let data = client.fetch(42).await.unwrap();
+        assert_eq!(sequence_number(data), 42);
+
+        // Buffered checkpoints are only handed out once.
+        assert!(client.buffer.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_unreachable_node_is_transient() {
+        let client = test_client();
+        let error = client.fetch(42).await.unwrap_err();
+        assert!(matches!(error, FetchError::Transient { .. }), "{error}");
+    }
+
+    #[tokio::test]
+    async fn buffer_drops_oldest_checkpoints() {
+        let buffer = Mutex::new(BTreeMap::new());
+        let (stream_hi_tx, stream_hi) = watch::channel(None);
+
+        let total = MAX_BUFFERED_CHECKPOINTS as u64 + 10;
+        for cp in 0..total {
+            buffer_checkpoint(&buffer, &stream_hi_tx, checkpoint(cp));
+        }
+
+        // An older checkpoint does not move the stream's high watermark back.
+        buffer_checkpoint(&buffer, &stream_hi_tx, checkpoint(total - 5));
+
+        let buffer = buffer.lock().unwrap();
+        assert_eq!(buffer.len(), MAX_BUFFERED_CHECKPOINTS);
+        assert_eq!(buffer.first_key_value().unwrap().0, &10);
+        assert_eq!(*stream_hi.borrow(), Some(total - 1));
+    }
+
+    #[tokio::test]
+    async fn wait_for_stream() {
+        let client = test_client();
+        let stream_hi_tx = client.stream_hi_tx.lock().unwrap().take().unwrap();
+
+        let buffer = client.buffer.clone();
+        tokio::spawn(async move {
+            for cp in 0..=10 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                buffer_checkpoint(&buffer, &stream_hi_tx, checkpoint(cp));
+            }
+
+            // Keep the stream open.
+            tokio::time::sleep(STREAM_WAIT_TIMEOUT).await;
+        });
+
+        let data = client.wait_for_stream(5).await.unwrap();
+        assert_eq!(data.checkpoint_summary.sequence_number, 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_stream_timeout() {
+        let client = test_client();
+        let _stream_hi_tx = client.stream_hi_tx.lock().unwrap().take().unwrap();
+        assert!(client.wait_for_stream(5).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn wait_for_stream_not_started() {
+        let client = test_client();
+
+        // Without a subscription, the client does not wait for the stream.
+        let started = tokio::time::Instant::now();
+        assert!(client.wait_for_stream(5).await.is_none());
+        assert!(started.elapsed() < STREAM_WAIT_TIMEOUT);
+    }
+}
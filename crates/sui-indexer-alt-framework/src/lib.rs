@@ -3,7 +3,7 @@
 
 use std::{collections::BTreeSet, sync::Arc};
 
-use anyhow::{ensure, Context};
+use anyhow::{bail, ensure, Context};
 use diesel::{
     migration::{self, Migration, MigrationSource},
     pg::Pg,
@@ -11,13 +11,16 @@ use diesel::{
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
 use ingestion::{client::IngestionClient, ClientArgs, IngestionConfig, IngestionService};
 use metrics::IndexerMetrics;
-use models::watermarks::{CommitterWatermark, PrunerWatermark};
+use models::{pipeline_swaps::StoredPipelineSwap, watermarks::CommitterWatermark};
+use object_store::ObjectStore;
 use pipeline::{
-    concurrent::{self, ConcurrentConfig},
+    concurrent::{self, ConcurrentConfig, PrunerConfig, ShadowConfig},
+    file_sink::{self, FileSinkConfig},
     sequential::{self, SequentialConfig},
     Processor,
 };
 use prometheus::Registry;
+use store::{Connection, DeadLetterConnection, ShardedConnection, Store, TransactionalStore};
 use sui_indexer_alt_metrics::db::DbConnectionStatsCollector;
 use sui_pg_db::{temp::TempDb, Db, DbArgs};
 use task::graceful_shutdown;
@@ -41,6 +44,7 @@ pub(crate) mod metrics;
 pub mod models;
 pub mod pipeline;
 pub mod schema;
+pub mod store;
 pub mod task;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
@@ -67,11 +71,45 @@ pub struct IndexerArgs {
     /// Don't write to the watermark tables for concurrent pipelines.
     #[arg(long)]
     pub skip_watermark: bool,
+
+    /// What the indexer does with the pipelines added to it. This is not a command-line flag:
+    /// indexers expose each mode as a separate command instead.
+    #[arg(skip)]
+    pub mode: Mode,
+}
+
+/// What the indexer does with the pipelines that are added to it.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Index checkpoints from each pipeline's watermark (or the first checkpoint override)
+    /// onwards, keeping watermarks up-to-date.
+    #[default]
+    Index,
+
+    /// Re-index the checkpoints between the first and last checkpoint overrides (which must both
+    /// be set) for concurrent pipelines that are indexing the tip of the network elsewhere. Their
+    /// existing data for the range is replaced a chunk at a time, while their watermarks are left
+    /// untouched.
+    Backfill,
+
+    /// Move concurrent pipelines back so that they resume indexing from `to_checkpoint` the next
+    /// time they run, deleting any data they have committed from that checkpoint onwards. No
+    /// checkpoints are indexed in this mode, and the pipelines must not be running elsewhere while
+    /// they are rewound.
+    Rewind { to_checkpoint: u64 },
+
+    /// Re-index the checkpoints that concurrent pipelines recorded as dead letters (because they
+    /// failed to process or commit them), removing each dead letter once its checkpoint has been
+    /// committed. No other checkpoints are indexed in this mode, and watermarks are left untouched.
+    Replay,
 }
 
-pub struct Indexer {
-    /// Connection pool to the database.
-    db: Db,
+/// An indexer that runs pipelines writing to a store of type `S`. By default, pipelines write to a
+/// Postgres database, but any [Store] can be used, by constructing the indexer with
+/// [Self::with_store].
+pub struct Indexer<S: Store = Db> {
+    /// The store that pipelines write their data and watermarks to.
+    store: S,
 
     /// Prometheus Metrics.
     metrics: Arc<IndexerMetrics>,
@@ -88,6 +126,9 @@ pub struct Indexer {
     /// Don't write to the watermark tables for concurrent pipelines.
     skip_watermark: bool,
 
+    /// Whether pipelines are run normally, backfilled, rewound or replayed.
+    mode: Mode,
+
     /// Optional filter for pipelines to run. If `None`, all pipelines added to the indexer will
     /// run. Any pipelines that are present in this filter but not added to the indexer will yield
     /// a warning when the indexer is run.
@@ -109,10 +150,10 @@ pub struct Indexer {
     handles: Vec<JoinHandle<()>>,
 }
 
-impl Indexer {
-    /// Create a new instance of the indexer framework. `database_url`, `db_args`, `indexer_args,`,
-    /// `client_args`, and `ingestion_config` contain configurations for the following,
-    /// respectively:
+impl Indexer<Db> {
+    /// Create a new instance of the indexer framework, writing to a Postgres database.
+    /// `database_url`, `db_args`, `indexer_args,`, `client_args`, and `ingestion_config` contain
+    /// configurations for the following, respectively:
     ///
     /// - Connecting to the database,
     /// - What is indexed (which checkpoints, which pipelines, whether to update the watermarks
@@ -124,8 +165,9 @@ impl Indexer {
     /// the specific instance of the indexer, generated using diesel's `embed_migrations!` macro.
     /// These migrations will be run as part of initializing the indexer if provided.
     ///
-    /// After initialization, at least one pipeline must be added using [Self::concurrent_pipeline]
-    /// or [Self::sequential_pipeline], before the indexer is started using [Self::run].
+    /// After initialization, at least one pipeline must be added using [Self::concurrent_pipeline],
+    /// [Self::sequential_pipeline], [Self::file_sink_pipeline] or [Self::shadow_pipeline], before
+    /// the indexer is started using [Self::run].
     pub async fn new(
         database_url: Url,
         db_args: DbArgs,
@@ -136,13 +178,6 @@ impl Indexer {
         registry: &Registry,
         cancel: CancellationToken,
     ) -> Result<Self> {
-        let IndexerArgs {
-            first_checkpoint,
-            last_checkpoint,
-            pipeline,
-            skip_watermark,
-        } = indexer_args;
-
         let db = Db::for_write(database_url, db_args)
             .await
             .context("Failed to connect to database")?;
@@ -152,36 +187,19 @@ impl Indexer {
             .await
             .context("Failed to run pending migrations")?;
 
-        let metrics = IndexerMetrics::new(registry);
         registry.register(Box::new(DbConnectionStatsCollector::new(
             Some("indexer_db"),
             db.clone(),
         )))?;
 
-        let ingestion_service = IngestionService::new(
+        Self::with_store(
+            db,
+            indexer_args,
             client_args,
             ingestion_config,
-            metrics.clone(),
-            cancel.clone(),
-        )?;
-
-        Ok(Self {
-            db,
-            metrics,
-            ingestion_service,
-            first_checkpoint,
-            last_checkpoint,
-            skip_watermark,
-            enabled_pipelines: if pipeline.is_empty() {
-                None
-            } else {
-                Some(pipeline.into_iter().collect())
-            },
-            added_pipelines: BTreeSet::new(),
+            registry,
             cancel,
-            first_checkpoint_from_watermark: u64::MAX,
-            handles: vec![],
-        })
+        )
     }
 
     pub async fn new_for_testing(migrations: &'static EmbeddedMigrations) -> (Self, TempDb) {
@@ -193,6 +211,7 @@ impl Indexer {
             ClientArgs {
                 remote_store_url: None,
                 local_ingestion_path: Some(tempdir().unwrap().into_path()),
+                rpc_api_url: None,
             },
             IngestionConfig::default(),
             Some(migrations),
@@ -206,7 +225,308 @@ impl Indexer {
 
     /// The database connection pool used by the indexer.
     pub fn db(&self) -> &Db {
-        &self.db
+        &self.store
+    }
+
+    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
+    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
+    /// the database's schema is up-to-date for both the indexer framework and the specific
+    /// indexer.
+    pub fn migrations(
+        migrations: Option<&'static EmbeddedMigrations>,
+    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
+        struct Migrations(Option<&'static EmbeddedMigrations>);
+        impl MigrationSource<Pg> for Migrations {
+            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
+                let mut migrations = MIGRATIONS.migrations()?;
+                if let Some(more_migrations) = self.0 {
+                    migrations.extend(more_migrations.migrations()?);
+                }
+                Ok(migrations)
+            }
+        }
+
+        Migrations(migrations)
+    }
+
+    /// Adds a concurrent pipeline, `live`, alongside a new version of it, `shadow`, and starts them
+    /// both up. This is used to change the schema of a live pipeline's tables without downtime for
+    /// the readers of those tables.
+    ///
+    /// The shadow pipeline writes to its own tables (created ahead of time by a migration that
+    /// leaves the live pipeline's tables alone), and it is indexed from its own watermark, using
+    /// its own cursor: Ingestion for the rest of the indexer starts from the other pipelines'
+    /// watermarks as usual, and while the shadow pipeline is behind them, it fetches the
+    /// checkpoints it needs for itself, so the live pipeline's tables keep advancing while the
+    /// shadow pipeline backfills.
+    ///
+    /// Once the shadow pipeline has caught up (see [ShadowConfig]), the live pipeline is retired.
+    /// In a single transaction, each of the live pipeline's tables is renamed out of the way (with
+    /// a `_retired` suffix), and the shadow table paired with it in `tables` is renamed to take
+    /// its place. The shadow table's old name is left as a view over the renamed table, only so
+    /// that the shadow pipeline can keep writing to it, under the name its handler uses. From then
+    /// on, the live pipeline's watermark tracks the shadow pipeline's, so readers can keep using
+    /// the live pipeline's tables and watermark unchanged. The retired tables are left for the
+    /// operator to drop, as are the views, once the handler has been updated to write to the live
+    /// tables' names.
+    ///
+    /// Once a live pipeline has been retired, it is skipped whenever it is added to an indexer
+    /// again, and only its shadow pipeline runs. The live pipeline must not be run by any other
+    /// indexer instance while it is being shadowed, because only the instance running the shadow
+    /// pipeline knows to retire it.
+    pub async fn shadow_pipeline<L, H>(
+        &mut self,
+        live: L,
+        live_config: ConcurrentConfig,
+        shadow: H,
+        shadow_config: ConcurrentConfig,
+        tables: &'static [(&'static str, &'static str)],
+        config: ShadowConfig,
+    ) -> Result<()>
+    where
+        L: concurrent::Handler<Store = Db> + Send + Sync + 'static,
+        H: concurrent::Handler<Store = Db> + Send + Sync + 'static,
+    {
+        ensure!(
+            self.mode == Mode::Index,
+            "Shadow pipeline {} cannot be backfilled, rewound or replayed",
+            H::NAME,
+        );
+
+        ensure!(
+            !self.skip_watermark,
+            "Shadow pipeline {} cannot skip watermarks: they decide when it is swapped in",
+            H::NAME,
+        );
+
+        ensure!(
+            live_config.sharding.is_none() && shadow_config.sharding.is_none(),
+            "Shadow pipeline {} and the pipeline it replaces, {}, cannot be sharded",
+            H::NAME,
+            L::NAME,
+        );
+
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
+
+        let swap = StoredPipelineSwap::get(&mut conn, L::NAME)
+            .await
+            .with_context(|| format!("Failed to check whether {} was retired", L::NAME))?;
+
+        drop(conn);
+
+        if let Some(swap) = swap {
+            ensure!(
+                swap.replaced_by == H::NAME,
+                "Pipeline {} was already replaced by {}, not {}",
+                L::NAME,
+                swap.replaced_by,
+                H::NAME,
+            );
+
+            info!(
+                pipeline = L::NAME,
+                replaced_by = H::NAME,
+                "Skipping retired pipeline"
+            );
+
+            if let Some(enabled_pipelines) = &mut self.enabled_pipelines {
+                enabled_pipelines.remove(L::NAME);
+            }
+
+            let start_from_pruner_watermark = H::PRUNING_REQUIRES_PROCESSED_VALUES;
+            let Some(watermark) = self.add_pipeline::<H>(start_from_pruner_watermark).await? else {
+                return Ok(());
+            };
+
+            self.check_first_checkpoint_consistency::<H>(&watermark)?;
+
+            self.handles.push(concurrent::mirror_swap(
+                swap,
+                config,
+                self.store.clone(),
+                self.cancel.clone(),
+            ));
+
+            self.handles.push(concurrent::pipeline(
+                shadow,
+                watermark,
+                shadow_config,
+                false,
+                concurrent::Unsharded,
+                self.store.clone(),
+                self.ingestion_service.subscribe().0,
+                self.metrics.clone(),
+                self.cancel.clone(),
+            ));
+
+            return Ok(());
+        }
+
+        let live_watermark = self
+            .add_pipeline::<L>(L::PRUNING_REQUIRES_PROCESSED_VALUES)
+            .await?;
+
+        // The shadow pipeline is fed from its own cursor, so it should not influence where
+        // ingestion starts for everyone else.
+        let first_checkpoint_from_watermark = self.first_checkpoint_from_watermark;
+        let shadow_watermark = self
+            .add_pipeline::<H>(H::PRUNING_REQUIRES_PROCESSED_VALUES)
+            .await?;
+        self.first_checkpoint_from_watermark = first_checkpoint_from_watermark;
+
+        let (live_watermark, shadow_watermark) = match (live_watermark, shadow_watermark) {
+            (Some(live), Some(shadow)) => (live, shadow),
+            (None, None) => return Ok(()),
+            _ => bail!(
+                "Shadow pipeline {} and the pipeline it replaces, {}, must be enabled together",
+                H::NAME,
+                L::NAME,
+            ),
+        };
+
+        self.check_first_checkpoint_consistency::<L>(&live_watermark)?;
+
+        // Where the shadow pipeline's own cursor starts, following the same rules as
+        // `add_pipeline` uses for the indexer as a whole.
+        let shadow_from = if H::PRUNING_REQUIRES_PROCESSED_VALUES {
+            let mut conn = self
+                .store
+                .connect()
+                .await
+                .context("Failed to connect to store")?;
+
+            conn.pruner_watermark(H::NAME, Default::default())
+                .await
+                .with_context(|| format!("Failed to get pruner watermark for {}", H::NAME))?
+                .map(|w| w.pruner_hi as u64)
+        } else {
+            shadow_watermark
+                .as_ref()
+                .map(|w| w.checkpoint_hi_inclusive as u64 + 1)
+        };
+
+        let shadow_from = shadow_from.unwrap_or(self.first_checkpoint.unwrap_or_default());
+
+        // The live pipeline is fed through a channel that can be closed to retire it, without
+        // closing the channel from the ingestion service.
+        let retire = CancellationToken::new();
+        let (checkpoint_rx, forwarder) = concurrent::retirable(
+            self.ingestion_service.subscribe().0,
+            retire.clone(),
+            self.cancel.clone(),
+        );
+
+        let live = concurrent::pipeline(
+            live,
+            live_watermark,
+            live_config,
+            false,
+            concurrent::Unsharded,
+            self.store.clone(),
+            checkpoint_rx,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        );
+
+        self.handles.push(forwarder);
+        self.handles.push(concurrent::swap_shadow::<L, H>(
+            live,
+            retire,
+            tables,
+            config,
+            self.store.clone(),
+            self.cancel.clone(),
+        ));
+
+        let shadow_rx = concurrent::catch_up(
+            H::NAME,
+            self.ingestion_service.client().clone(),
+            self.ingestion_service.config().ingest_concurrency,
+            shadow_from,
+            self.ingestion_service.subscribe().0,
+            self.cancel.clone(),
+        );
+
+        self.handles.push(concurrent::pipeline(
+            shadow,
+            shadow_watermark,
+            shadow_config,
+            false,
+            concurrent::Unsharded,
+            self.store.clone(),
+            shadow_rx,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        ));
+
+        Ok(())
+    }
+}
+
+impl<S: Store> Indexer<S> {
+    /// Create a new instance of the indexer framework, writing to `store`. Unlike [Indexer::new],
+    /// this does not prepare the store in any way (e.g. by running migrations) -- the store is
+    /// expected to be ready to accept writes from the pipelines that will be added to the indexer.
+    /// `indexer_args`, `client_args` and `ingestion_config` are as for [Indexer::new].
+    pub fn with_store(
+        store: S,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        let IndexerArgs {
+            first_checkpoint,
+            last_checkpoint,
+            pipeline,
+            skip_watermark,
+            mode,
+        } = indexer_args;
+
+        if mode == Mode::Backfill {
+            ensure!(
+                first_checkpoint.is_some() && last_checkpoint.is_some(),
+                "Backfills require both a first and a last checkpoint",
+            );
+        }
+
+        let metrics = IndexerMetrics::new(registry);
+
+        let ingestion_service = IngestionService::new(
+            client_args,
+            ingestion_config,
+            metrics.clone(),
+            cancel.clone(),
+        )?;
+
+        Ok(Self {
+            store,
+            metrics,
+            ingestion_service,
+            first_checkpoint,
+            last_checkpoint,
+            skip_watermark,
+            mode,
+            enabled_pipelines: if pipeline.is_empty() {
+                None
+            } else {
+                Some(pipeline.into_iter().collect())
+            },
+            added_pipelines: BTreeSet::new(),
+            cancel,
+            first_checkpoint_from_watermark: u64::MAX,
+            handles: vec![],
+        })
+    }
+
+    /// The store that the indexer's pipelines write to.
+    pub fn store(&self) -> &S {
+        &self.store
     }
 
     /// The ingestion client used by the indexer to fetch checkpoints.
@@ -234,29 +554,122 @@ impl Indexer {
     /// Concurrent pipelines commit checkpoint data out-of-order to maximise throughput, and they
     /// keep the watermark table up-to-date with the highest point they can guarantee all data
     /// exists for, for their pipeline.
-    pub async fn concurrent_pipeline<H: concurrent::Handler + Send + Sync + 'static>(
+    ///
+    /// When the indexer is backfilling, rewinding or replaying dead letters, the pipeline is
+    /// backfilled, rewound or replayed instead of being started (see [Mode]).
+    ///
+    /// If the pipeline is configured with sharding, it can be run by multiple indexer instances at
+    /// once. Each instance leases one shard of the pipeline's checkpoints, and waits for a shard to
+    /// become available if they have all been leased by other instances.
+    pub async fn concurrent_pipeline<H: concurrent::Handler<Store = S> + Send + Sync + 'static>(
         &mut self,
         handler: H,
-        config: ConcurrentConfig,
-    ) -> Result<()> {
+        mut config: ConcurrentConfig,
+    ) -> Result<()>
+    where
+        S: TransactionalStore,
+        for<'c> S::Connection<'c>: ShardedConnection + DeadLetterConnection,
+    {
         let start_from_pruner_watermark = H::PRUNING_REQUIRES_PROCESSED_VALUES;
         let Some(watermark) = self.add_pipeline::<H>(start_from_pruner_watermark).await? else {
             return Ok(());
         };
 
+        let chunk_size = config
+            .pruner
+            .as_ref()
+            .map_or(PrunerConfig::default().max_chunk_size, |p| p.max_chunk_size);
+
+        let skip_watermark = match self.mode {
+            Mode::Index => self.skip_watermark,
+
+            Mode::Backfill => {
+                // SAFETY: Both bounds are checked to be set when the indexer is created.
+                let first_checkpoint = self.first_checkpoint.unwrap();
+                let last_checkpoint = self.last_checkpoint.unwrap();
+                return concurrent::backfill(
+                    &handler,
+                    &self.store,
+                    self.ingestion_service.client(),
+                    first_checkpoint,
+                    last_checkpoint,
+                    self.ingestion_service.config().ingest_concurrency,
+                    &self.cancel,
+                )
+                .await;
+            }
+
+            Mode::Rewind { to_checkpoint } => {
+                return concurrent::rewind(
+                    &handler,
+                    &self.store,
+                    self.ingestion_service.client(),
+                    to_checkpoint,
+                    chunk_size,
+                    &self.cancel,
+                )
+                .await;
+            }
+
+            Mode::Replay => {
+                return concurrent::replay(
+                    &handler,
+                    &self.store,
+                    self.ingestion_service.client(),
+                    &self.cancel,
+                )
+                .await;
+            }
+        };
+
         // For a concurrent pipeline, if skip_watermark is set, we don't really care about the
         // watermark consistency. first_checkpoint can be anything since we don't update watermark,
         // and writes should be idempotent.
-        if !self.skip_watermark {
+        if !skip_watermark {
             self.check_first_checkpoint_consistency::<H>(&watermark)?;
         }
 
+        if let Some(sharding) = config.sharding.take() {
+            ensure!(
+                sharding.shards > 0 && sharding.lease_ttl_ms > 0,
+                "Sharded pipeline {} needs at least one shard, and a non-zero lease TTL",
+                H::NAME,
+            );
+
+            ensure!(
+                !skip_watermark,
+                "Sharded pipeline {} cannot skip watermarks: they coordinate its shards",
+                H::NAME,
+            );
+
+            ensure!(
+                !H::PRUNING_REQUIRES_PROCESSED_VALUES,
+                "Pipeline {} cannot be sharded: its pruner requires processed values",
+                H::NAME,
+            );
+
+            self.handles.push(concurrent::sharded_pipeline(
+                handler,
+                config,
+                sharding,
+                self.store.clone(),
+                self.ingestion_service.client().clone(),
+                self.ingestion_service.config().ingest_concurrency,
+                self.ingestion_service.subscribe().0,
+                self.metrics.clone(),
+                self.cancel.clone(),
+            ));
+
+            return Ok(());
+        }
+
         self.handles.push(concurrent::pipeline(
             handler,
             watermark,
             config,
-            self.skip_watermark,
-            self.db.clone(),
+            skip_watermark,
+            concurrent::Unsharded,
+            self.store.clone(),
             self.ingestion_service.subscribe().0,
             self.metrics.clone(),
             self.cancel.clone(),
@@ -275,15 +688,24 @@ impl Indexer {
     ///
     /// The pipeline can optionally be configured to lag behind the ingestion service by a fixed
     /// number of checkpoints (configured by `checkpoint_lag`).
-    pub async fn sequential_pipeline<H: sequential::Handler + Send + Sync + 'static>(
+    pub async fn sequential_pipeline<H: sequential::Handler<Store = S> + Send + Sync + 'static>(
         &mut self,
         handler: H,
         config: SequentialConfig,
-    ) -> Result<()> {
+    ) -> Result<()>
+    where
+        S: TransactionalStore,
+    {
         let Some(watermark) = self.add_pipeline::<H>(false).await? else {
             return Ok(());
         };
 
+        ensure!(
+            self.mode == Mode::Index,
+            "Sequential pipeline {} cannot be backfilled, rewound or replayed",
+            H::NAME,
+        );
+
         if self.skip_watermark {
             warn!(
                 pipeline = H::NAME,
@@ -301,7 +723,51 @@ impl Indexer {
             handler,
             watermark,
             config,
-            self.db.clone(),
+            self.store.clone(),
+            checkpoint_rx,
+            watermark_tx,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        ));
+
+        Ok(())
+    }
+
+    /// Adds a new pipeline to this indexer that writes its data to Parquet files in `object_store`
+    /// instead of the indexer's store, and starts it up. Although its tasks have started, they
+    /// will be idle until the ingestion service starts, and serves it checkpoint data.
+    ///
+    /// File sink pipelines gather the rows from contiguous ranges of checkpoints into files (see
+    /// [FileSinkConfig] for how large each file gets), and only update their watermark in the
+    /// indexer's store once the file covering it has been uploaded.
+    pub async fn file_sink_pipeline<H: file_sink::Handler<Store = S> + Send + Sync + 'static>(
+        &mut self,
+        handler: H,
+        object_store: Arc<dyn ObjectStore>,
+        config: FileSinkConfig,
+    ) -> Result<()> {
+        let Some(watermark) = self.add_pipeline::<H>(false).await? else {
+            return Ok(());
+        };
+
+        ensure!(
+            self.mode == Mode::Index,
+            "File sink pipeline {} cannot be backfilled, rewound or replayed",
+            H::NAME,
+        );
+
+        // Files cover contiguous ranges of checkpoints, so the pipeline cannot leave gaps.
+        self.check_first_checkpoint_consistency::<H>(&watermark)?;
+
+        let (checkpoint_rx, watermark_tx) = self.ingestion_service.subscribe();
+
+        self.handles.push(file_sink::pipeline(
+            handler,
+            watermark,
+            config,
+            self.skip_watermark,
+            self.store.clone(),
+            object_store,
             checkpoint_rx,
             watermark_tx,
             self.metrics.clone(),
@@ -346,6 +812,27 @@ impl Indexer {
             );
         }
 
+        // Pipelines are backfilled, rewound or replayed as they are added, so there is nothing left
+        // to run.
+        match self.mode {
+            Mode::Index => {}
+
+            Mode::Backfill => {
+                info!("Pipelines backfilled");
+                return Ok(tokio::spawn(async {}));
+            }
+
+            Mode::Rewind { to_checkpoint } => {
+                info!(to_checkpoint, "Pipelines rewound");
+                return Ok(tokio::spawn(async {}));
+            }
+
+            Mode::Replay => {
+                info!("Dead letters replayed");
+                return Ok(tokio::spawn(async {}));
+            }
+        }
+
         // If an override has been provided, start ingestion from there, otherwise start ingestion
         // from just after the lowest committer watermark across all enabled pipelines.
         let first_checkpoint = self
@@ -375,27 +862,6 @@ impl Indexer {
         }))
     }
 
-    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
-    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
-    /// the database's schema is up-to-date for both the indexer framework and the specific
-    /// indexer.
-    pub fn migrations(
-        migrations: Option<&'static EmbeddedMigrations>,
-    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
-        struct Migrations(Option<&'static EmbeddedMigrations>);
-        impl MigrationSource<Pg> for Migrations {
-            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
-                let mut migrations = MIGRATIONS.migrations()?;
-                if let Some(more_migrations) = self.0 {
-                    migrations.extend(more_migrations.migrations()?);
-                }
-                Ok(migrations)
-            }
-        }
-
-        Migrations(migrations)
-    }
-
     /// Update the indexer's first checkpoint based on the watermark for the pipeline by adding for
     /// handler `H` (as long as it's enabled). Returns `Ok(None)` if the pipeline is disabled,
     /// `Ok(Some(None))` if the pipeline is enabled but its watermark is not found, and
@@ -421,9 +887,14 @@ impl Indexer {
             }
         }
 
-        let mut conn = self.db.connect().await.context("Failed DB connection")?;
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
 
-        let watermark = CommitterWatermark::get(&mut conn, P::NAME)
+        let watermark = conn
+            .committer_watermark(P::NAME)
             .await
             .with_context(|| format!("Failed to get watermark for {}", P::NAME))?;
 
@@ -431,7 +902,7 @@ impl Indexer {
             // If the pruner of this pipeline requires processed values in order to prune,
             // we must start ingestion from just after the pruner watermark,
             // so that we can process all values needed by the pruner.
-            PrunerWatermark::get(&mut conn, P::NAME, Default::default())
+            conn.pruner_watermark(P::NAME, Default::default())
                 .await
                 .with_context(|| format!("Failed to get pruner watermark for {}", P::NAME))?
                 .map(|w| w.pruner_hi as u64)
@@ -452,8 +923,18 @@ impl Indexer {
 
 #[cfg(test)]
 mod tests {
-    use async_trait::async_trait;
+    use std::{collections::BTreeMap, path::Path};
 
+    use arrow_array::{ArrayRef, RecordBatch, UInt64Array};
+    use async_trait::async_trait;
+    use futures::TryStreamExt;
+    use object_store::{memory::InMemory, path::Path as ObjectPath};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use sui_synthetic_ingestion::synthetic_ingestion;
+
+    use crate::models::watermarks::PrunerWatermark;
+    use crate::pipeline::{FailureConfig, FailurePolicy};
+    use crate::store::{MemConnection, MemStore};
     use crate::types::full_checkpoint_content::CheckpointData;
 
     use super::*;
@@ -482,6 +963,8 @@ mod tests {
 
             #[async_trait]
             impl concurrent::Handler for $name {
+                type Store = Db;
+
                 const PRUNING_REQUIRES_PROCESSED_VALUES: bool = $pruning_requires_processed_values;
                 async fn commit(
                     _values: &[Self::Value],
@@ -493,6 +976,178 @@ mod tests {
         };
     }
 
+    #[derive(FieldCount)]
+    struct TxCount {
+        cp_sequence_number: u64,
+        count: u64,
+    }
+
+    /// Test concurrent pipeline that counts the transactions in each checkpoint, and writes them to
+    /// an in-memory store.
+    struct MemTxCounts;
+
+    impl Processor for MemTxCounts {
+        const NAME: &'static str = "mem_tx_counts";
+        type Value = TxCount;
+
+        fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            Ok(vec![TxCount {
+                cp_sequence_number: checkpoint.checkpoint_summary.sequence_number,
+                count: checkpoint.transactions.len() as u64,
+            }])
+        }
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for MemTxCounts {
+        type Store = MemStore<BTreeMap<u64, u64>>;
+
+        const PRUNE_CLEARS_CHECKPOINTS: bool = true;
+
+        async fn commit(
+            values: &[Self::Value],
+            conn: &mut MemConnection<'_, BTreeMap<u64, u64>>,
+        ) -> anyhow::Result<usize> {
+            let counts = conn.data();
+            for value in values {
+                counts.insert(value.cp_sequence_number, value.count);
+            }
+
+            Ok(values.len())
+        }
+
+        async fn prune(
+            &self,
+            from: u64,
+            to_exclusive: u64,
+            conn: &mut MemConnection<'_, BTreeMap<u64, u64>>,
+        ) -> anyhow::Result<usize> {
+            let counts = conn.data();
+            let before = counts.len();
+            counts.retain(|cp, _| !(from..to_exclusive).contains(cp));
+            Ok(before - counts.len())
+        }
+    }
+
+    /// Test concurrent pipeline that counts transactions like [MemTxCounts], but fails to process
+    /// the checkpoints in `failing`.
+    struct FlakyTxCounts {
+        failing: BTreeSet<u64>,
+    }
+
+    impl Processor for FlakyTxCounts {
+        const NAME: &'static str = "flaky_tx_counts";
+        type Value = TxCount;
+
+        fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+            ensure!(
+                !self.failing.contains(&cp_sequence_number),
+                "Checkpoint {cp_sequence_number} is flaky",
+            );
+
+            MemTxCounts.process(checkpoint)
+        }
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for FlakyTxCounts {
+        type Store = MemStore<BTreeMap<u64, u64>>;
+
+        async fn commit(
+            values: &[Self::Value],
+            conn: &mut MemConnection<'_, BTreeMap<u64, u64>>,
+        ) -> anyhow::Result<usize> {
+            MemTxCounts::commit(values, conn).await
+        }
+    }
+
+    /// Test file sink pipeline that writes the transaction counts from [MemTxCounts] to files, and
+    /// its watermark to an in-memory store.
+    struct TxCountFiles;
+
+    impl Processor for TxCountFiles {
+        const NAME: &'static str = "tx_count_files";
+        type Value = TxCount;
+
+        fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            MemTxCounts.process(checkpoint)
+        }
+    }
+
+    impl file_sink::Handler for TxCountFiles {
+        type Store = MemStore<BTreeMap<u64, u64>>;
+
+        fn record_batch(values: Vec<Self::Value>) -> anyhow::Result<RecordBatch> {
+            let (cps, counts): (Vec<_>, Vec<_>) = values
+                .into_iter()
+                .map(|v| (v.cp_sequence_number, v.count))
+                .unzip();
+
+            Ok(RecordBatch::try_from_iter([
+                (
+                    "cp_sequence_number",
+                    Arc::new(UInt64Array::from(cps)) as ArrayRef,
+                ),
+                ("count", Arc::new(UInt64Array::from(counts)) as ArrayRef),
+            ])?)
+        }
+    }
+
+    /// Run the [MemTxCounts] pipeline over the checkpoints in `checkpoint_dir`, writing to `store`,
+    /// until the indexer finishes.
+    async fn run_mem_tx_counts(
+        store: &MemStore<BTreeMap<u64, u64>>,
+        checkpoint_dir: &Path,
+        indexer_args: IndexerArgs,
+    ) -> anyhow::Result<()> {
+        let mut indexer = Indexer::with_store(
+            store.clone(),
+            indexer_args,
+            ClientArgs {
+                remote_store_url: None,
+                local_ingestion_path: Some(checkpoint_dir.to_owned()),
+                rpc_api_url: None,
+            },
+            IngestionConfig::default(),
+            &Registry::new(),
+            CancellationToken::new(),
+        )?;
+
+        indexer
+            .concurrent_pipeline(MemTxCounts, ConcurrentConfig::default())
+            .await?;
+
+        indexer.run().await?.await?;
+        Ok(())
+    }
+
+    /// Generate 10 checkpoints with 2 transactions each, and index them into a new store.
+    async fn indexed_mem_tx_counts(checkpoint_dir: &Path) -> MemStore<BTreeMap<u64, u64>> {
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: checkpoint_dir.to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 10,
+            checkpoint_size: 2,
+        })
+        .await;
+
+        let store = MemStore::default();
+        run_mem_tx_counts(
+            &store,
+            checkpoint_dir,
+            IndexerArgs {
+                first_checkpoint: Some(0),
+                last_checkpoint: Some(9),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        store
+    }
+
     define_test_concurrent_pipeline!(ConcurrentPipeline1);
     define_test_concurrent_pipeline!(ConcurrentPipeline2);
     define_test_concurrent_pipeline!(ConcurrentPipeline3, true);
@@ -578,4 +1233,415 @@ mod tests {
             .unwrap();
         assert_eq!(indexer.first_checkpoint_from_watermark, 5);
     }
+
+    #[tokio::test]
+    async fn test_add_shadow_pipeline() {
+        let (mut indexer, _temp_db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let watermark1 = CommitterWatermark::new_for_testing(ConcurrentPipeline1::NAME, 10);
+        watermark1
+            .update(&mut indexer.db().connect().await.unwrap())
+            .await
+            .unwrap();
+
+        // The shadow pipeline starts from scratch, so ingestion does too.
+        indexer
+            .shadow_pipeline(
+                ConcurrentPipeline1,
+                ConcurrentConfig::default(),
+                ConcurrentPipeline2,
+                ConcurrentConfig::default(),
+                &[],
+                ShadowConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(indexer.first_checkpoint_from_watermark, 0);
+        assert_eq!(
+            indexer.pipelines().collect::<Vec<_>>(),
+            vec![ConcurrentPipeline1::NAME, ConcurrentPipeline2::NAME],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_retired_pipeline() {
+        let (mut indexer, _temp_db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+
+        let watermark1 = CommitterWatermark::new_for_testing(ConcurrentPipeline1::NAME, 10);
+        watermark1.update(&mut conn).await.unwrap();
+        let watermark2 = CommitterWatermark::new_for_testing(ConcurrentPipeline2::NAME, 20);
+        watermark2.update(&mut conn).await.unwrap();
+        StoredPipelineSwap::swap(
+            &mut conn,
+            ConcurrentPipeline1::NAME,
+            ConcurrentPipeline2::NAME,
+            &[],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        // The retired pipeline does not run, and does not hold back ingestion.
+        indexer
+            .shadow_pipeline(
+                ConcurrentPipeline1,
+                ConcurrentConfig::default(),
+                ConcurrentPipeline2,
+                ConcurrentConfig::default(),
+                &[],
+                ShadowConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(indexer.first_checkpoint_from_watermark, 21);
+        assert_eq!(
+            indexer.pipelines().collect::<Vec<_>>(),
+            vec![ConcurrentPipeline2::NAME],
+        );
+
+        // The retired pipeline cannot be replaced by a different shadow pipeline.
+        let err = indexer
+            .shadow_pipeline(
+                ConcurrentPipeline1,
+                ConcurrentConfig::default(),
+                ConcurrentPipeline3,
+                ConcurrentConfig::default(),
+                &[],
+                ShadowConfig::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already replaced"));
+    }
+
+    #[tokio::test]
+    async fn test_mem_store_pipeline() {
+        let checkpoint_dir = tempdir().unwrap();
+        let store = indexed_mem_tx_counts(checkpoint_dir.path()).await;
+
+        let mut conn = store.connect().await.unwrap();
+        let watermark = conn
+            .committer_watermark(MemTxCounts::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 9);
+        assert_eq!(watermark.tx_hi, 20);
+
+        let expect: BTreeMap<_, _> = (0..10).map(|cp| (cp, 2)).collect();
+        assert_eq!(conn.data(), &expect);
+    }
+
+    #[tokio::test]
+    async fn test_mem_store_backfill() {
+        let checkpoint_dir = tempdir().unwrap();
+        let store = indexed_mem_tx_counts(checkpoint_dir.path()).await;
+
+        // Corrupt some of the indexed data, so that it needs to be backfilled.
+        {
+            let mut conn = store.connect().await.unwrap();
+            for cp in 3..=5 {
+                conn.data().insert(cp, 42);
+            }
+        }
+
+        run_mem_tx_counts(
+            &store,
+            checkpoint_dir.path(),
+            IndexerArgs {
+                first_checkpoint: Some(3),
+                last_checkpoint: Some(5),
+                mode: Mode::Backfill,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        let expect: BTreeMap<_, _> = (0..10).map(|cp| (cp, 2)).collect();
+        assert_eq!(conn.data(), &expect);
+
+        let watermark = conn
+            .committer_watermark(MemTxCounts::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 9);
+        drop(conn);
+
+        // Backfills cannot extend beyond what the pipeline has already indexed.
+        let err = run_mem_tx_counts(
+            &store,
+            checkpoint_dir.path(),
+            IndexerArgs {
+                first_checkpoint: Some(8),
+                last_checkpoint: Some(12),
+                mode: Mode::Backfill,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("committer watermark"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_mem_store_rewind() {
+        let checkpoint_dir = tempdir().unwrap();
+        let store = indexed_mem_tx_counts(checkpoint_dir.path()).await;
+
+        let rewind_to = |to_checkpoint| IndexerArgs {
+            mode: Mode::Rewind { to_checkpoint },
+            ..Default::default()
+        };
+
+        // Cannot rewind forwards.
+        run_mem_tx_counts(&store, checkpoint_dir.path(), rewind_to(12))
+            .await
+            .unwrap_err();
+
+        run_mem_tx_counts(&store, checkpoint_dir.path(), rewind_to(5))
+            .await
+            .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        let expect: BTreeMap<_, _> = (0..5).map(|cp| (cp, 2)).collect();
+        assert_eq!(conn.data(), &expect);
+
+        let watermark = conn
+            .committer_watermark(MemTxCounts::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 4);
+        assert_eq!(watermark.tx_hi, 10);
+        drop(conn);
+
+        // Rewinding to genesis removes the watermark altogether.
+        run_mem_tx_counts(&store, checkpoint_dir.path(), rewind_to(0))
+            .await
+            .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.data().is_empty());
+        assert!(conn
+            .committer_watermark(MemTxCounts::NAME)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mem_store_dead_letters() {
+        let checkpoint_dir = tempdir().unwrap();
+        let store = indexed_mem_tx_counts(checkpoint_dir.path()).await;
+
+        let run_flaky = |failing: &[u64], indexer_args: IndexerArgs| {
+            let mut indexer = Indexer::with_store(
+                store.clone(),
+                indexer_args,
+                ClientArgs {
+                    remote_store_url: None,
+                    local_ingestion_path: Some(checkpoint_dir.path().to_owned()),
+                    rpc_api_url: None,
+                },
+                IngestionConfig::default(),
+                &Registry::new(),
+                CancellationToken::new(),
+            )
+            .unwrap();
+
+            let handler = FlakyTxCounts {
+                failing: failing.iter().copied().collect(),
+            };
+
+            let config = ConcurrentConfig {
+                failure: FailureConfig {
+                    policy: FailurePolicy::RetryThenDeadLetter,
+                    max_retries: 1,
+                },
+                ..Default::default()
+            };
+
+            async move {
+                indexer.concurrent_pipeline(handler, config).await?;
+                indexer.run().await?.await?;
+                anyhow::Ok(())
+            }
+        };
+
+        // Checkpoints that fail to process are skipped, and recorded as dead letters.
+        run_flaky(
+            &[3, 7],
+            IndexerArgs {
+                first_checkpoint: Some(0),
+                last_checkpoint: Some(9),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        let watermark = conn
+            .committer_watermark(FlakyTxCounts::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 9);
+
+        let dead_letters: Vec<_> = conn
+            .dead_letters(Some(FlakyTxCounts::NAME))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.cp_sequence_number, d.stage))
+            .collect();
+        assert_eq!(
+            dead_letters,
+            vec![(3, "process".to_owned()), (7, "process".to_owned())],
+        );
+        drop(conn);
+
+        let replay = || IndexerArgs {
+            mode: Mode::Replay,
+            ..Default::default()
+        };
+
+        // Replays that fail again keep the dead letter around.
+        run_flaky(&[7], replay()).await.unwrap_err();
+
+        let mut conn = store.connect().await.unwrap();
+        let dead_letters = conn.dead_letters(None).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].cp_sequence_number, 7);
+        assert_eq!(dead_letters[0].stage, "replay");
+        drop(conn);
+
+        // Once the checkpoint can be processed, replaying it clears the dead letter.
+        run_flaky(&[], replay()).await.unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.dead_letters(None).await.unwrap().is_empty());
+        drop(conn);
+
+        // The pipeline does not prune, so it cannot clear a range to backfill it.
+        let err = run_flaky(
+            &[],
+            IndexerArgs {
+                first_checkpoint: Some(3),
+                last_checkpoint: Some(5),
+                mode: Mode::Backfill,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("does not clear checkpoints"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_pipeline() {
+        let checkpoint_dir = tempdir().unwrap();
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: checkpoint_dir.path().to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 10,
+            checkpoint_size: 2,
+        })
+        .await;
+
+        let store = MemStore::<BTreeMap<u64, u64>>::default();
+        let object_store = Arc::new(InMemory::new());
+
+        let mut indexer = Indexer::with_store(
+            store.clone(),
+            IndexerArgs {
+                first_checkpoint: Some(0),
+                last_checkpoint: Some(9),
+                ..Default::default()
+            },
+            ClientArgs {
+                remote_store_url: None,
+                local_ingestion_path: Some(checkpoint_dir.path().to_owned()),
+                rpc_api_url: None,
+            },
+            IngestionConfig::default(),
+            &Registry::new(),
+            CancellationToken::new(),
+        )
+        .unwrap();
+
+        let config = FileSinkConfig {
+            max_file_checkpoints: 4,
+            ..Default::default()
+        };
+
+        indexer
+            .file_sink_pipeline(TxCountFiles, object_store.clone(), config)
+            .await
+            .unwrap();
+
+        indexer.run().await.unwrap().await.unwrap();
+
+        // Files are cut every four checkpoints, and at the end of the range being indexed.
+        let paths: Vec<_> = object_store
+            .list(None)
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                "tx_count_files/epoch_0/0_4.parquet",
+                "tx_count_files/epoch_0/4_8.parquet",
+                "tx_count_files/epoch_0/8_10.parquet",
+            ],
+        );
+
+        let bytes = object_store
+            .get(&ObjectPath::from("tx_count_files/epoch_0/4_8.parquet"))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+
+        let batches: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(bytes)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let column = |name| -> Vec<u64> {
+            batches
+                .iter()
+                .flat_map(|batch| {
+                    let array = batch.column_by_name(name).unwrap();
+                    let array = array.as_any().downcast_ref::<UInt64Array>().unwrap();
+                    array.values().to_vec()
+                })
+                .collect()
+        };
+
+        assert_eq!(column("cp_sequence_number"), vec![4, 5, 6, 7]);
+        assert_eq!(column("count"), vec![2, 2, 2, 2]);
+
+        // The watermark is only written once the files covering it have been uploaded.
+        let mut conn = store.connect().await.unwrap();
+        let watermark = conn
+            .committer_watermark(TxCountFiles::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 9);
+        assert_eq!(watermark.tx_hi, 20);
+    }
 }
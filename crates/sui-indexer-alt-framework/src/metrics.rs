@@ -85,6 +85,7 @@ pub struct IndexerMetrics {
     pub total_pruner_chunks_attempted: IntCounterVec,
     pub total_pruner_chunks_deleted: IntCounterVec,
     pub total_pruner_rows_deleted: IntCounterVec,
+    pub total_dead_letters: IntCounterVec,
 
     // Checkpoint lag metrics for the collector.
     pub latest_collected_checkpoint: IntGaugeVec,
@@ -363,6 +364,13 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            total_dead_letters: register_int_counter_vec_with_registry!(
+                "indexer_total_dead_letters",
+                "Total number of checkpoints this pipeline skipped and recorded as dead letters",
+                &["pipeline", "stage"],
+                registry,
+            )
+            .unwrap(),
             latest_collected_checkpoint: register_int_gauge_vec_with_registry!(
                 "indexer_latest_collected_checkpoint",
                 "Latest checkpoint sequence number collected by this collector",
@@ -16,6 +16,8 @@ pub struct StoredCpSequenceNumbers {
     pub cp_sequence_number: i64,
     pub tx_lo: i64,
     pub epoch: i64,
+    /// `None` for checkpoints indexed before timestamps were recorded.
+    pub timestamp_ms: Option<i64>,
 }
 
 /// Inclusive start and exclusive end range of prunable txs.
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use chrono::naive::NaiveDateTime;
+use diesel::{prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+
+use crate::db::Connection;
+use crate::schema::dead_letters;
+use crate::FieldCount;
+
+/// A checkpoint that a pipeline failed to index, and skipped, so that it can be replayed later.
+#[derive(Insertable, Selectable, Queryable, Debug, Clone, FieldCount, PartialEq, Eq)]
+#[diesel(table_name = dead_letters)]
+pub struct StoredDeadLetter {
+    pub pipeline: String,
+    pub cp_sequence_number: i64,
+    pub stage: String,
+    pub error: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl StoredDeadLetter {
+    /// Dead letters for `pipeline`, or for all pipelines if it is not provided, ordered by
+    /// pipeline and then checkpoint.
+    pub(crate) async fn get_all(
+        conn: &mut Connection<'_>,
+        pipeline: Option<&str>,
+    ) -> QueryResult<Vec<Self>> {
+        let mut query = dead_letters::table
+            .select(StoredDeadLetter::as_select())
+            .order_by((dead_letters::pipeline, dead_letters::cp_sequence_number))
+            .into_boxed();
+
+        if let Some(pipeline) = pipeline {
+            query = query.filter(dead_letters::pipeline.eq(pipeline));
+        }
+
+        query.load(conn).await
+    }
+
+    /// Record the dead letter, replacing any existing record for the same pipeline and checkpoint.
+    pub(crate) async fn upsert(&self, conn: &mut Connection<'_>) -> QueryResult<usize> {
+        diesel::insert_into(dead_letters::table)
+            .values(self)
+            .on_conflict((dead_letters::pipeline, dead_letters::cp_sequence_number))
+            .do_update()
+            .set((
+                dead_letters::stage.eq(excluded(dead_letters::stage)),
+                dead_letters::error.eq(excluded(dead_letters::error)),
+                dead_letters::created_at.eq(excluded(dead_letters::created_at)),
+            ))
+            .execute(conn)
+            .await
+    }
+
+    /// Remove the dead letter for `checkpoint` in `pipeline`. Returns a boolean indicating whether
+    /// there was a record to delete.
+    pub(crate) async fn delete(
+        conn: &mut Connection<'_>,
+        pipeline: &str,
+        checkpoint: u64,
+    ) -> QueryResult<bool> {
+        let deleted = diesel::delete(
+            dead_letters::table
+                .filter(dead_letters::pipeline.eq(pipeline))
+                .filter(dead_letters::cp_sequence_number.eq(checkpoint as i64)),
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+}
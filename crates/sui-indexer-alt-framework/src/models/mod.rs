@@ -2,4 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod cp_sequence_numbers;
-pub(crate) mod watermarks;
+pub mod dead_letters;
+pub mod pipeline_swaps;
+pub mod watermarks;
@@ -0,0 +1,142 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use chrono::{naive::NaiveDateTime, Utc};
+use diesel::{prelude::*, sql_query, sql_types::Text};
+use diesel_async::RunQueryDsl;
+
+use crate::db::Connection;
+use crate::schema::pipeline_swaps;
+use crate::FieldCount;
+
+/// A live pipeline that was retired when the shadow pipeline that replaced it was swapped in.
+#[derive(Insertable, Selectable, Queryable, Debug, Clone, FieldCount, PartialEq, Eq)]
+#[diesel(table_name = pipeline_swaps)]
+pub struct StoredPipelineSwap {
+    pub pipeline: String,
+    pub replaced_by: String,
+    pub swapped_at: NaiveDateTime,
+}
+
+/// Copy the shadow pipeline's (`$2`) watermark over the live pipeline's (`$1`), creating it if it
+/// does not exist.
+const MIRROR_WATERMARK: &str = "
+    INSERT INTO watermarks (
+        pipeline,
+        epoch_hi_inclusive,
+        checkpoint_hi_inclusive,
+        tx_hi,
+        timestamp_ms_hi_inclusive,
+        reader_lo,
+        pruner_timestamp,
+        pruner_hi
+    )
+    SELECT
+        $1,
+        epoch_hi_inclusive,
+        checkpoint_hi_inclusive,
+        tx_hi,
+        timestamp_ms_hi_inclusive,
+        reader_lo,
+        pruner_timestamp,
+        pruner_hi
+    FROM
+        watermarks
+    WHERE
+        pipeline = $2
+    ON CONFLICT (pipeline) DO UPDATE SET
+        epoch_hi_inclusive = EXCLUDED.epoch_hi_inclusive,
+        checkpoint_hi_inclusive = EXCLUDED.checkpoint_hi_inclusive,
+        tx_hi = EXCLUDED.tx_hi,
+        timestamp_ms_hi_inclusive = EXCLUDED.timestamp_ms_hi_inclusive,
+        reader_lo = EXCLUDED.reader_lo,
+        pruner_timestamp = EXCLUDED.pruner_timestamp,
+        pruner_hi = EXCLUDED.pruner_hi
+";
+
+impl StoredPipelineSwap {
+    /// The swap that retired `pipeline`, if it has been retired.
+    pub(crate) async fn get(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+    ) -> QueryResult<Option<Self>> {
+        pipeline_swaps::table
+            .select(StoredPipelineSwap::as_select())
+            .filter(pipeline_swaps::pipeline.eq(pipeline))
+            .first(conn)
+            .await
+            .optional()
+    }
+
+    /// Retire `pipeline` in favour of the shadow pipeline `replaced_by`. `tables` pairs each of
+    /// the shadow pipeline's tables with the live pipeline table it replaces. Each live table is
+    /// renamed out of the way (see [retired_table]), and its shadow table is renamed to take its
+    /// place, the live pipeline's watermark is overwritten with the shadow pipeline's, and the swap
+    /// is recorded.
+    ///
+    /// Readers query the renamed shadow tables directly. The shadow tables' old names are left as
+    /// views over them, only so that the shadow pipeline, which still refers to its tables by
+    /// those names, can keep writing to them.
+    ///
+    /// The retired tables and the views are not dropped: That is left to the operator, once they
+    /// are satisfied that the shadow tables can be relied on, and the shadow pipeline has been
+    /// updated to write to the live tables' names.
+    ///
+    /// This should be run inside a transaction, so that readers observe all the live tables and
+    /// the watermark switching over at once.
+    pub(crate) async fn swap(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+        replaced_by: &'static str,
+        tables: &[(&'static str, &'static str)],
+    ) -> QueryResult<Self> {
+        for (shadow, live) in tables {
+            let retired = retired_table(live);
+            sql_query(format!("ALTER TABLE {live} RENAME TO {retired}"))
+                .execute(conn)
+                .await?;
+
+            sql_query(format!("ALTER TABLE {shadow} RENAME TO {live}"))
+                .execute(conn)
+                .await?;
+
+            sql_query(format!("CREATE VIEW {shadow} AS SELECT * FROM {live}"))
+                .execute(conn)
+                .await?;
+        }
+
+        let swap = StoredPipelineSwap {
+            pipeline: pipeline.to_owned(),
+            replaced_by: replaced_by.to_owned(),
+            swapped_at: Utc::now().naive_utc(),
+        };
+
+        swap.mirror_watermark(conn).await?;
+
+        diesel::insert_into(pipeline_swaps::table)
+            .values(&swap)
+            .execute(conn)
+            .await?;
+
+        Ok(swap)
+    }
+
+    /// Overwrite the retired pipeline's watermark with the watermark of the pipeline that replaced
+    /// it, so that readers that check the retired pipeline's watermark see the progress of the
+    /// tables that have replaced its own. Returns a boolean indicating whether there was a
+    /// watermark to copy.
+    pub(crate) async fn mirror_watermark(&self, conn: &mut Connection<'_>) -> QueryResult<bool> {
+        Ok(sql_query(MIRROR_WATERMARK)
+            .bind::<Text, _>(&self.pipeline)
+            .bind::<Text, _>(&self.replaced_by)
+            .execute(conn)
+            .await?
+            > 0)
+    }
+}
+
+/// The name that the live table `live` is renamed to when the pipeline that writes to it is
+/// retired.
+pub(crate) fn retired_table(live: &str) -> String {
+    format!("{live}_retired")
+}
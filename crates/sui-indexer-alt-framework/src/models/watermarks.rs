@@ -4,17 +4,21 @@
 use std::{borrow::Cow, time::Duration};
 
 use chrono::{naive::NaiveDateTime, DateTime, Utc};
-use diesel::{prelude::*, sql_types::BigInt};
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Timestamp},
+    upsert::excluded,
+};
 use diesel_async::RunQueryDsl;
 
 use crate::db::Connection;
-use crate::schema::watermarks;
+use crate::schema::{watermark_shards, watermarks};
 use crate::sql;
 use crate::FieldCount;
 
 #[derive(Insertable, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct StoredWatermark {
+pub struct StoredWatermark {
     pub pipeline: String,
     pub epoch_hi_inclusive: i64,
     pub checkpoint_hi_inclusive: i64,
@@ -28,7 +32,7 @@ pub(crate) struct StoredWatermark {
 /// Fields that the committer is responsible for setting.
 #[derive(AsChangeset, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct CommitterWatermark<'p> {
+pub struct CommitterWatermark<'p> {
     pub pipeline: Cow<'p, str>,
     pub epoch_hi_inclusive: i64,
     pub checkpoint_hi_inclusive: i64,
@@ -38,14 +42,14 @@ pub(crate) struct CommitterWatermark<'p> {
 
 #[derive(AsChangeset, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct ReaderWatermark<'p> {
+pub struct ReaderWatermark<'p> {
     pub pipeline: Cow<'p, str>,
     pub reader_lo: i64,
 }
 
 #[derive(Queryable, Debug, Clone, FieldCount, PartialEq, Eq)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct PrunerWatermark<'p> {
+pub struct PrunerWatermark<'p> {
     /// The pipeline in question
     pub pipeline: Cow<'p, str>,
 
@@ -61,6 +65,32 @@ pub(crate) struct PrunerWatermark<'p> {
     pub pruner_hi: i64,
 }
 
+/// A shard of a concurrent pipeline that has been split across multiple indexer instances: Its
+/// committer watermark, and the lease held by the instance responsible for it.
+#[derive(Insertable, Selectable, Queryable, Debug, Clone, FieldCount)]
+#[diesel(table_name = watermark_shards)]
+pub struct StoredShardWatermark {
+    pub pipeline: String,
+    pub shard: i64,
+    pub shards: i64,
+    pub epoch_hi_inclusive: i64,
+    pub checkpoint_hi_inclusive: i64,
+    pub tx_hi: i64,
+    pub timestamp_ms_hi_inclusive: i64,
+    pub lease_owner: String,
+    pub lease_expires_at: NaiveDateTime,
+}
+
+/// Identifies the lease that `owner` holds (or is trying to acquire) on shard `shard` of
+/// `pipeline`, which is split into `shards` shards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardLease {
+    pub pipeline: &'static str,
+    pub shard: u64,
+    pub shards: u64,
+    pub owner: String,
+}
+
 impl StoredWatermark {
     pub(crate) async fn get(
         conn: &mut Connection<'_>,
@@ -73,6 +103,20 @@ impl StoredWatermark {
             .await
             .optional()
     }
+
+    /// Delete the pipeline's watermark record. Returns a boolean indicating whether there was a
+    /// record to delete.
+    pub(crate) async fn delete(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+    ) -> QueryResult<bool> {
+        Ok(
+            diesel::delete(watermarks::table.filter(watermarks::pipeline.eq(pipeline)))
+                .execute(conn)
+                .await?
+                > 0,
+        )
+    }
 }
 
 impl CommitterWatermark<'static> {
@@ -134,6 +178,160 @@ impl<'p> CommitterWatermark<'p> {
             .await?
             > 0)
     }
+
+    /// Overwrite the high watermark of an existing watermark row, even if this lowers the
+    /// watermark. Used to rewind a pipeline so that it re-indexes checkpoints it has already
+    /// committed.
+    ///
+    /// Returns a boolean indicating whether the watermark was actually updated or not.
+    pub(crate) async fn rewind(&self, conn: &mut Connection<'_>) -> QueryResult<bool> {
+        Ok(diesel::update(watermarks::table)
+            .set(self)
+            .filter(watermarks::pipeline.eq(&self.pipeline))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+}
+
+impl StoredShardWatermark {
+    /// All the shards that `pipeline` has been split into so far.
+    pub(crate) async fn get_all(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+    ) -> QueryResult<Vec<Self>> {
+        watermark_shards::table
+            .select(StoredShardWatermark::as_select())
+            .filter(watermark_shards::pipeline.eq(pipeline))
+            .order_by(watermark_shards::shard)
+            .load(conn)
+            .await
+    }
+
+    /// The shard's committer watermark, or `None` if it has not committed anything yet.
+    pub(crate) fn committer_watermark(&self) -> Option<CommitterWatermark<'static>> {
+        (self.checkpoint_hi_inclusive >= 0).then(|| CommitterWatermark {
+            pipeline: Cow::Owned(self.pipeline.clone()),
+            epoch_hi_inclusive: self.epoch_hi_inclusive,
+            checkpoint_hi_inclusive: self.checkpoint_hi_inclusive,
+            tx_hi: self.tx_hi,
+            timestamp_ms_hi_inclusive: self.timestamp_ms_hi_inclusive,
+        })
+    }
+}
+
+impl ShardLease {
+    /// Take the lease on this shard for `ttl`, as long as no other owner holds a lease on it that
+    /// has not expired yet. If the shard does not exist yet, it is created, starting from the
+    /// pipeline's committer watermark. Returns the shard if the lease was acquired.
+    ///
+    /// Lease expiry is measured using the database's clock, so that instances do not need to agree
+    /// on the time.
+    pub(crate) async fn acquire(
+        &self,
+        conn: &mut Connection<'_>,
+        ttl: Duration,
+    ) -> QueryResult<Option<StoredShardWatermark>> {
+        use diesel::query_dsl::methods::FilterDsl;
+
+        let start = CommitterWatermark::get(conn, self.pipeline).await?;
+        let start = start.unwrap_or_else(|| CommitterWatermark {
+            checkpoint_hi_inclusive: -1,
+            ..CommitterWatermark::initial(self.pipeline.into())
+        });
+
+        let expires_at = sql!(as Timestamp,
+            "NOW() + {BigInt} * INTERVAL '1 millisecond'",
+            ttl.as_millis() as i64,
+        );
+
+        diesel::insert_into(watermark_shards::table)
+            .values((
+                watermark_shards::pipeline.eq(self.pipeline),
+                watermark_shards::shard.eq(self.shard as i64),
+                watermark_shards::shards.eq(self.shards as i64),
+                watermark_shards::epoch_hi_inclusive.eq(start.epoch_hi_inclusive),
+                watermark_shards::checkpoint_hi_inclusive.eq(start.checkpoint_hi_inclusive),
+                watermark_shards::tx_hi.eq(start.tx_hi),
+                watermark_shards::timestamp_ms_hi_inclusive.eq(start.timestamp_ms_hi_inclusive),
+                watermark_shards::lease_owner.eq(&self.owner),
+                watermark_shards::lease_expires_at.eq(expires_at),
+            ))
+            .on_conflict((watermark_shards::pipeline, watermark_shards::shard))
+            .do_update()
+            .set((
+                watermark_shards::lease_owner.eq(excluded(watermark_shards::lease_owner)),
+                watermark_shards::lease_expires_at.eq(excluded(watermark_shards::lease_expires_at)),
+            ))
+            .filter(
+                watermark_shards::lease_owner
+                    .eq(&self.owner)
+                    .or(watermark_shards::lease_expires_at.lt(diesel::dsl::now)),
+            )
+            .returning(StoredShardWatermark::as_returning())
+            .get_result(conn)
+            .await
+            .optional()
+    }
+
+    /// Extend the lease by `ttl` from now, as long as it is still held by its owner. Returns a
+    /// boolean indicating whether the lease was renewed.
+    pub(crate) async fn renew(
+        &self,
+        conn: &mut Connection<'_>,
+        ttl: Duration,
+    ) -> QueryResult<bool> {
+        let expires_at = sql!(as Timestamp,
+            "NOW() + {BigInt} * INTERVAL '1 millisecond'",
+            ttl.as_millis() as i64,
+        );
+
+        Ok(diesel::update(watermark_shards::table)
+            .set(watermark_shards::lease_expires_at.eq(expires_at))
+            .filter(watermark_shards::pipeline.eq(self.pipeline))
+            .filter(watermark_shards::shard.eq(self.shard as i64))
+            .filter(watermark_shards::lease_owner.eq(&self.owner))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+
+    /// Give up the lease, if it is still held by its owner, so that another instance can take over
+    /// the shard straight away. Returns a boolean indicating whether the lease was released.
+    pub(crate) async fn release(&self, conn: &mut Connection<'_>) -> QueryResult<bool> {
+        Ok(diesel::update(watermark_shards::table)
+            .set(watermark_shards::lease_expires_at.eq(diesel::dsl::now))
+            .filter(watermark_shards::pipeline.eq(self.pipeline))
+            .filter(watermark_shards::shard.eq(self.shard as i64))
+            .filter(watermark_shards::lease_owner.eq(&self.owner))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+
+    /// Update the shard's committer watermark, as long as the lease is still held by its owner, and
+    /// this raises the watermark. Returns a boolean indicating whether the watermark was actually
+    /// updated or not.
+    pub(crate) async fn update_watermark(
+        &self,
+        conn: &mut Connection<'_>,
+        watermark: &CommitterWatermark<'_>,
+    ) -> QueryResult<bool> {
+        Ok(diesel::update(watermark_shards::table)
+            .set((
+                watermark_shards::epoch_hi_inclusive.eq(watermark.epoch_hi_inclusive),
+                watermark_shards::checkpoint_hi_inclusive.eq(watermark.checkpoint_hi_inclusive),
+                watermark_shards::tx_hi.eq(watermark.tx_hi),
+                watermark_shards::timestamp_ms_hi_inclusive.eq(watermark.timestamp_ms_hi_inclusive),
+            ))
+            .filter(watermark_shards::pipeline.eq(self.pipeline))
+            .filter(watermark_shards::shard.eq(self.shard as i64))
+            .filter(watermark_shards::lease_owner.eq(&self.owner))
+            .filter(watermark_shards::checkpoint_hi_inclusive.lt(watermark.checkpoint_hi_inclusive))
+            .execute(conn)
+            .await?
+            > 0)
+    }
 }
 
 impl<'p> ReaderWatermark<'p> {
@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use futures::{stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{
+    ingestion::client::IngestionClient, pipeline::PIPELINE_BUFFER,
+    types::full_checkpoint_content::CheckpointData,
+};
+
+/// Feed a pipeline from its own cursor, starting at checkpoint `from`, rather than from wherever
+/// the ingestion stream, `checkpoint_rx`, happens to be.
+///
+/// The task waits for the first checkpoint from `checkpoint_rx` to find out where the stream is.
+/// If it is at or ahead of `from`, the checkpoints up to it are fetched using `client`
+/// (`concurrency` at a time), and sent to the pipeline in its stead. The stream keeps being
+/// drained while this happens (and any checkpoints it delivers are fetched as well), so that a
+/// pipeline that is catching up never holds back ingestion for the other pipelines in the indexer.
+/// Once it has caught up, the pipeline is fed checkpoints from the stream that it has not already
+/// been sent.
+///
+/// Returns the channel that the pipeline should receive checkpoints from. It is closed once the
+/// stream is closed and the pipeline has been sent all the checkpoints up to it, or if `cancel` is
+/// cancelled. If a checkpoint cannot be fetched, the whole indexer is shutdown via `cancel`.
+pub(crate) fn catch_up(
+    pipeline: &'static str,
+    client: IngestionClient,
+    concurrency: usize,
+    from: u64,
+    mut checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    cancel: CancellationToken,
+) -> mpsc::Receiver<Arc<CheckpointData>> {
+    let (tx, rx) = mpsc::channel(PIPELINE_BUFFER);
+
+    tokio::spawn(async move {
+        let first = tokio::select! {
+            _ = cancel.cancelled() => return,
+            checkpoint = checkpoint_rx.recv() => match checkpoint {
+                Some(checkpoint) => checkpoint.checkpoint_summary.sequence_number,
+                None => return,
+            },
+        };
+
+        if first < from {
+            return forward(from, checkpoint_rx, tx, cancel).await;
+        }
+
+        // Checkpoints up to `hi` have been taken from the stream, so the pipeline is sent them by
+        // fetching them (including the first checkpoint from the stream, for simplicity). Only
+        // checkpoints after it are sent from the stream.
+        let mut hi = first;
+
+        let mut next = from;
+        let mut closed = false;
+        while next <= hi {
+            info!(pipeline, from = next, to = hi, "Catching up with ingestion");
+
+            let mut fetches = stream::iter(next..=hi)
+                .map(|cp| {
+                    let client = client.clone();
+                    let cancel = cancel.clone();
+                    async move { (cp, client.fetch(cp, &cancel).await) }
+                })
+                .buffered(concurrency.max(1));
+
+            let round_hi = hi;
+            let mut fetched: Option<Arc<CheckpointData>> = None;
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+
+                    checkpoint = checkpoint_rx.recv(), if !closed => match checkpoint {
+                        Some(c) => hi = hi.max(c.checkpoint_summary.sequence_number),
+                        None => closed = true,
+                    },
+
+                    permit = tx.reserve(), if fetched.is_some() => {
+                        let (Ok(permit), Some(checkpoint)) = (permit, fetched.take()) else {
+                            return;
+                        };
+
+                        permit.send(checkpoint);
+                    }
+
+                    f = fetches.next(), if fetched.is_none() => match f {
+                        Some((_, Ok(checkpoint))) => fetched = Some(checkpoint),
+
+                        Some((cp, Err(e))) => {
+                            error!(pipeline, checkpoint = cp, "Failed to catch up: {e}");
+                            cancel.cancel();
+                            return;
+                        }
+
+                        None => break,
+                    },
+                }
+            }
+
+            next = round_hi + 1;
+        }
+
+        info!(pipeline, hi, "Caught up with ingestion");
+        if !closed {
+            forward(hi + 1, checkpoint_rx, tx, cancel).await;
+        }
+    });
+
+    rx
+}
+
+/// Forward checkpoints from `checkpoint_rx` to `tx`, skipping any before `from`, until either
+/// channel is closed, or `cancel` is cancelled.
+async fn forward(
+    from: u64,
+    mut checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    tx: mpsc::Sender<Arc<CheckpointData>>,
+    cancel: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+
+            checkpoint = checkpoint_rx.recv() => {
+                let Some(checkpoint) = checkpoint else {
+                    break;
+                };
+
+                if checkpoint.checkpoint_summary.sequence_number >= from
+                    && tx.send(checkpoint).await.is_err()
+                {
+                    break;
+                }
+            }
+        }
+    }
+}
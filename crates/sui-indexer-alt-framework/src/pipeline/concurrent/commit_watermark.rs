@@ -7,6 +7,7 @@ use std::{
     sync::Arc,
 };
 
+use async_trait::async_trait;
 use tokio::{
     sync::mpsc,
     task::JoinHandle,
@@ -16,16 +17,41 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    db::Db,
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
     models::watermarks::CommitterWatermark,
     pipeline::{logging::WatermarkLogger, CommitterConfig, WatermarkPart, WARN_PENDING_WATERMARKS},
+    store::{Connection, Store},
 };
 
 use super::Handler;
 
+/// Where the commit watermark task writes the pipeline's watermark to.
+#[async_trait]
+pub(crate) trait WatermarkTarget<S: Store>: Send + Sync + 'static {
+    /// Write `watermark` using `conn`. Returns whether the watermark was updated.
+    async fn write_watermark(
+        &self,
+        conn: &mut S::Connection<'_>,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+}
+
+/// The target for a pipeline that is run by a single instance: It writes its watermark directly.
+pub(crate) struct Unsharded;
+
+#[async_trait]
+impl<S: Store> WatermarkTarget<S> for Unsharded {
+    async fn write_watermark(
+        &self,
+        conn: &mut S::Connection<'_>,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        conn.set_committer_watermark(watermark).await
+    }
+}
+
 /// The watermark task is responsible for keeping track of a pipeline's out-of-order commits and
-/// updating its row in the `watermarks` table when a continuous run of checkpoints have landed
+/// updating its watermark in the store when a continuous run of checkpoints have landed
 /// since the last watermark update.
 ///
 /// It receives watermark "parts" that detail the proportion of each checkpoint's data that has
@@ -41,15 +67,20 @@ use super::Handler;
 /// The task regularly traces its progress, outputting at a higher log level every
 /// [LOUD_WATERMARK_UPDATE_INTERVAL]-many checkpoints.
 ///
+/// The watermark is written to `target`: If the pipeline is one shard of a pipeline that has been
+/// split across multiple instances, this is the shard's watermark, and the pipeline's watermark is
+/// raised to the lowest watermark across all its shards (see [super::sharding]).
+///
 /// The task will shutdown if the `cancel` token is signalled, or if the `rx` channel closes and
 /// the watermark cannot be progressed. If `skip_watermark` is set, the task will shutdown
 /// immediately.
-pub(super) fn commit_watermark<H: Handler + 'static>(
+pub(super) fn commit_watermark<H: Handler + 'static, W: WatermarkTarget<H::Store>>(
     initial_watermark: Option<CommitterWatermark<'static>>,
     config: CommitterConfig,
     skip_watermark: bool,
+    target: W,
     mut rx: mpsc::Receiver<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -103,8 +134,8 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                         );
                     }
 
-                    let Ok(mut conn) = db.connect().await else {
-                        warn!(pipeline = H::NAME, "Commit watermark task failed to get connection for DB");
+                    let Ok(mut conn) = store.connect().await else {
+                        warn!(pipeline = H::NAME, "Commit watermark task failed to get connection for store");
                         continue;
                     };
 
@@ -190,7 +221,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
 
                         // TODO: If initial_watermark is empty, when we update watermark
                         // for the first time, we should also update the low watermark.
-                        match watermark.update(&mut conn).await {
+                        match target.write_watermark(&mut conn, &watermark).await {
                             // If there's an issue updating the watermark, log it but keep going,
                             // it's OK for the watermark to lag from a correctness perspective.
                             Err(e) => {
@@ -1,7 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use backoff::ExponentialBackoff;
 use tokio::{sync::mpsc, task::JoinHandle};
@@ -10,9 +16,12 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    db::Db,
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
-    pipeline::{Break, CommitterConfig, WatermarkPart},
+    pipeline::{
+        is_permanent, record_dead_letters, Break, CommitterConfig, FailureConfig, FailurePolicy,
+        WatermarkPart,
+    },
+    store::{DeadLetterConnection, Store},
     task::TrySpawnStreamExt,
 };
 
@@ -24,25 +33,35 @@ const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
 /// If the committer needs to retry a commit, it will wait at most this long between retries.
 const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
-/// The committer task is responsible for writing batches of rows to the database. It receives
-/// batches on `rx` and writes them out to the `db` concurrently (`config.write_concurrency`
+/// The committer task is responsible for writing batches of rows to the store. It receives
+/// batches on `rx` and writes them out to the `store` concurrently (`config.write_concurrency`
 /// controls the degree of fan-out).
 ///
 /// The writing of each batch will be repeatedly retried on an exponential back-off until it
 /// succeeds. Once the write succeeds, the [WatermarkPart]s for that batch are sent on `tx` to the
 /// watermark task, as long as `skip_watermark` is not true.
 ///
+/// Only failures where the store rejected the batch's rows (see [is_permanent]) are subject to the
+/// `failure` policy: The committer gives up on the batch straight away, or once it has been retried
+/// `failure.max_retries` times, and either shuts down, or records the checkpoints that the batch
+/// has rows for as dead letters and carries on as if the write had succeeded. Other failures, like
+/// failing to get a connection, are always retried.
+///
 /// This task will shutdown via its `cancel`lation token, or if its receiver or sender channels are
 /// closed.
 pub(super) fn committer<H: Handler + 'static>(
     config: CommitterConfig,
+    failure: FailureConfig,
     skip_watermark: bool,
     rx: mpsc::Receiver<BatchedRows<H>>,
     tx: mpsc::Sender<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
-) -> JoinHandle<()> {
+) -> JoinHandle<()>
+where
+    for<'c> <H::Store as Store>::Connection<'c>: DeadLetterConnection,
+{
     tokio::spawn(async move {
         info!(pipeline = H::NAME, "Starting committer");
         let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H>(
@@ -57,14 +76,19 @@ pub(super) fn committer<H: Handler + 'static>(
                 |BatchedRows { values, watermark }| {
                     let values = Arc::new(values);
                     let tx = tx.clone();
-                    let db = db.clone();
+                    let store = store.clone();
                     let metrics = metrics.clone();
+                    let policy = failure.policy;
+                    let max_retries = match policy {
+                        FailurePolicy::Stop => 0,
+                        _ => failure.max_retries,
+                    };
                     let cancel = cancel.clone();
                     let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
 
-                    // Repeatedly try to get a connection to the DB and write the batch. Use an
-                    // exponential backoff in case the failure is due to contention over the DB
-                    // connection pool.
+                    // Repeatedly try to get a connection to the store and write the batch. Use an
+                    // exponential backoff in case the failure is due to contention over the
+                    // store's connection pool.
                     let backoff = ExponentialBackoff {
                         initial_interval: INITIAL_RETRY_INTERVAL,
                         current_interval: INITIAL_RETRY_INTERVAL,
@@ -77,10 +101,17 @@ pub(super) fn committer<H: Handler + 'static>(
                     let highest_checkpoint_timestamp =
                         watermark.iter().map(|w| w.timestamp_ms()).max();
 
+                    // Batches are retried indefinitely, unless the store rejects their rows, in
+                    // which case the error becomes permanent once the failure policy's retries are
+                    // exhausted.
                     use backoff::Error as BE;
+                    let rejections = Arc::new(AtomicU64::new(0));
+                    let dead_letter_store = store.clone();
+                    let dead_letter_metrics = metrics.clone();
                     let commit = move || {
+                        let rejections = rejections.clone();
                         let values = values.clone();
-                        let db = db.clone();
+                        let store = store.clone();
                         let metrics = metrics.clone();
                         let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                         async move {
@@ -98,10 +129,10 @@ pub(super) fn committer<H: Handler + 'static>(
                                 .with_label_values(&[H::NAME])
                                 .start_timer();
 
-                            let mut conn = db.connect().await.map_err(|e| {
+                            let mut conn = store.connect().await.map_err(|e| {
                                 warn!(
                                     pipeline = H::NAME,
-                                    "Committed failed to get connection for DB"
+                                    "Committed failed to get connection for store"
                                 );
 
                                 metrics
@@ -167,26 +198,59 @@ pub(super) fn committer<H: Handler + 'static>(
                                         .with_label_values(&[H::NAME])
                                         .inc();
 
-                                    Err(BE::transient(Break::Err(e)))
+                                    if !is_permanent(&e) {
+                                        Err(BE::transient(Break::Err(e)))
+                                    } else if rejections.fetch_add(1, Ordering::Relaxed)
+                                        >= max_retries
+                                    {
+                                        Err(BE::permanent(Break::Err(e)))
+                                    } else {
+                                        Err(BE::transient(Break::Err(e)))
+                                    }
                                 }
                             }
                         }
                     };
 
                     async move {
-                        tokio::select! {
+                        let committed = tokio::select! {
                             _ = cancel.cancelled() => {
                                 return Err(Break::Cancel);
                             }
 
-                            // Double check that the commit actually went through, (this backoff should
-                            // not produce any permanent errors, but if it does, we need to shutdown
-                            // the pipeline).
-                            commit = backoff::future::retry(backoff, commit) => {
-                                let () = commit?;
-                            }
+                            // The backoff only produces permanent errors for rejected batches, once
+                            // the failure policy's retries are exhausted.
+                            commit = backoff::future::retry(backoff, commit) => commit,
                         };
 
+                        // Double check that the commit actually went through. If it did not, the
+                        // batch is either dead-lettered, or the pipeline needs to shutdown.
+                        match committed {
+                            Ok(()) => {}
+
+                            Err(Break::Err(e)) if policy == FailurePolicy::RetryThenDeadLetter => {
+                                let checkpoints = watermark
+                                    .iter()
+                                    .filter(|part| part.batch_rows > 0)
+                                    .map(|part| part.checkpoint());
+
+                                record_dead_letters(
+                                    &dead_letter_store,
+                                    H::NAME,
+                                    "commit",
+                                    checkpoints,
+                                    &e,
+                                    &dead_letter_metrics,
+                                )
+                                .await
+                                .map_err(|e| {
+                                    Break::Err(e.context("Failed to record dead letters"))
+                                })?;
+                            }
+
+                            Err(e) => return Err(e),
+                        }
+
                         if !skip_watermark && tx.send(watermark).await.is_err() {
                             info!(pipeline = H::NAME, "Watermark closed channel");
                             return Err(Break::Cancel);
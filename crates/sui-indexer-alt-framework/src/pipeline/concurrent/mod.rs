@@ -9,25 +9,40 @@ use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 use crate::{
-    db::{self, Db},
     metrics::IndexerMetrics,
     models::watermarks::CommitterWatermark,
+    store::{DeadLetterConnection, Store},
     types::full_checkpoint_content::CheckpointData,
     FieldCount,
 };
 
-use super::{processor::processor, CommitterConfig, Processor, WatermarkPart, PIPELINE_BUFFER};
+use super::{
+    processor::processor, CommitterConfig, FailureConfig, Processor, WatermarkPart, PIPELINE_BUFFER,
+};
 
 use self::{
-    collector::collector, commit_watermark::commit_watermark, committer::committer, pruner::pruner,
+    collector::collector,
+    commit_watermark::{commit_watermark, WatermarkTarget},
+    committer::committer,
+    pruner::pruner,
     reader_watermark::reader_watermark,
 };
 
+pub(crate) use self::catch_up::catch_up;
+pub(crate) use self::commit_watermark::Unsharded;
+pub(crate) use self::reprocess::{backfill, replay, rewind};
+pub(crate) use self::shadow::{mirror as mirror_swap, retirable, swap as swap_shadow};
+pub(crate) use self::sharding::pipeline as sharded_pipeline;
+
+mod catch_up;
 mod collector;
 mod commit_watermark;
 mod committer;
 mod pruner;
 mod reader_watermark;
+mod reprocess;
+mod shadow;
+mod sharding;
 
 /// The maximum number of watermarks that can show up in a single batch. This limit exists to deal
 /// with pipelines that produce no data for a majority of checkpoints -- the size of these
@@ -35,7 +50,8 @@ mod reader_watermark;
 const MAX_WATERMARK_UPDATES: usize = 10_000;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
-/// implementing [Processor]) into rows for their table, and how to write those rows to the database.
+/// implementing [Processor]) into rows for their table, and how to write those rows to their
+/// [Store].
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values). Reasonable defaults have been chosen to balance concurrency with memory
@@ -55,6 +71,9 @@ const MAX_WATERMARK_UPDATES: usize = 10_000;
 /// back to the ingestion service.
 #[async_trait::async_trait]
 pub trait Handler: Processor<Value: FieldCount> {
+    /// The store that this pipeline writes its data and watermarks to.
+    type Store: Store;
+
     /// If at least this many rows are pending, the committer will commit them eagerly.
     const MIN_EAGER_ROWS: usize = 50;
 
@@ -73,18 +92,27 @@ pub trait Handler: Processor<Value: FieldCount> {
     // 2. The name is a bit abstract.
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = false;
 
-    /// Take a chunk of values and commit them to the database, returning the number of rows
+    /// Whether [Self::prune] deletes all the data this pipeline has committed for the checkpoints
+    /// in its range, so that the range can be indexed again from scratch. Backfills and rewinds
+    /// rely on this to clear the data they replace, and are only supported for pipelines that set
+    /// it. Pipelines that keep the default `prune` (which does nothing), or that only prune data
+    /// that has since been superseded, must leave this unset.
+    const PRUNE_CLEARS_CHECKPOINTS: bool = false;
+
+    /// Take a chunk of values and commit them to the store, returning the number of rows
     /// affected.
-    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>)
-        -> anyhow::Result<usize>;
+    async fn commit(
+        values: &[Self::Value],
+        conn: &mut <Self::Store as Store>::Connection<'_>,
+    ) -> anyhow::Result<usize>;
 
-    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the database, returning
+    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the store, returning
     /// the number of rows affected. This function is optional, and defaults to not pruning at all.
     async fn prune(
         &self,
         _from: u64,
         _to_exclusive: u64,
-        _conn: &mut db::Connection<'_>,
+        _conn: &mut <Self::Store as Store>::Connection<'_>,
     ) -> anyhow::Result<usize> {
         Ok(0)
     }
@@ -98,6 +126,13 @@ pub struct ConcurrentConfig {
 
     /// Configuration for the pruner, that deletes old data.
     pub pruner: Option<PrunerConfig>,
+
+    /// Configuration for splitting the pipeline across multiple indexer instances. If this is not
+    /// set, the pipeline expects to be the only instance writing to its watermark.
+    pub sharding: Option<ShardingConfig>,
+
+    /// How to handle checkpoints that fail to be processed or committed.
+    pub failure: FailureConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -119,6 +154,32 @@ pub struct PrunerConfig {
     pub prune_concurrency: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShardingConfig {
+    /// The number of shards to split the pipeline into. Each instance takes responsibility for one
+    /// shard, which covers the checkpoints whose sequence number modulo `shards` is the shard's
+    /// index. This must be the same for all instances.
+    pub shards: u64,
+
+    /// How long an instance's lease on its shard lasts before it must be renewed, in milliseconds.
+    /// If an instance stops renewing its lease, another instance can take over its shard after
+    /// this long.
+    pub lease_ttl_ms: u64,
+}
+
+/// Configuration for swapping a shadow pipeline in for the live pipeline it replaces.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShadowConfig {
+    /// How close the shadow pipeline's watermark needs to get to the live pipeline's (in
+    /// checkpoints) before the live pipeline is retired. The swap itself waits for the shadow
+    /// pipeline to fully catch up with the retired pipeline.
+    pub max_lag: u64,
+
+    /// How often to check the pipelines' progress, in milliseconds. Once the shadow pipeline has
+    /// been swapped in, this is also how often the retired pipeline's watermark is updated.
+    pub interval_ms: u64,
+}
+
 /// Values ready to be written to the database. This is an internal type used to communicate
 /// between the collector and the committer parts of the pipeline.
 ///
@@ -141,6 +202,18 @@ impl PrunerConfig {
     }
 }
 
+impl ShadowConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+}
+
+impl ShardingConfig {
+    pub fn lease_ttl(&self) -> Duration {
+        Duration::from_millis(self.lease_ttl_ms)
+    }
+}
+
 impl<H: Handler> BatchedRows<H> {
     fn new() -> Self {
         Self {
@@ -173,6 +246,24 @@ impl Default for PrunerConfig {
     }
 }
 
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            max_lag: 100,
+            interval_ms: 1_000,
+        }
+    }
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self {
+            shards: 1,
+            lease_ttl_ms: 30_000,
+        }
+    }
+}
+
 /// Start a new concurrent (out-of-order) indexing pipeline served by the handler, `H`. Starting
 /// strictly after the `watermark` (or from the beginning if no watermark was provided).
 ///
@@ -185,24 +276,30 @@ impl Default for PrunerConfig {
 /// either because it received the checkpoints out-of-order or because of variance in processing
 /// time.
 ///
-/// The pipeline also maintains a row in the `watermarks` table for the pipeline which tracks the
-/// watermark below which all data has been committed (modulo pruning), as long as `skip_watermark`
-/// is not true.
+/// The pipeline also maintains a watermark record for the pipeline in its `store`, which tracks
+/// the watermark below which all data has been committed (modulo pruning), as long as
+/// `skip_watermark` is not true. The watermark is written to `target`, which is [Unsharded],
+/// unless the pipeline is one shard of a pipeline that has been split across multiple instances,
+/// in which case its lease on the shard is used to write the shard's watermark instead.
 ///
 /// Checkpoint data is fed into the pipeline through the `checkpoint_rx` channel, and internal
 /// channels are created to communicate between its various components. The pipeline can be
 /// shutdown using its `cancel` token, and will also shutdown if any of its independent tasks
 /// reports an issue.
-pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
+pub(crate) fn pipeline<H: Handler + Send + Sync + 'static, W: WatermarkTarget<H::Store>>(
     handler: H,
     initial_commit_watermark: Option<CommitterWatermark<'static>>,
     config: ConcurrentConfig,
     skip_watermark: bool,
-    db: Db,
+    target: W,
+    store: H::Store,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
-) -> JoinHandle<()> {
+) -> JoinHandle<()>
+where
+    for<'c> <H::Store as Store>::Connection<'c>: DeadLetterConnection,
+{
     info!(
         pipeline = H::NAME,
         "Starting pipeline with config: {:?}", config
@@ -210,6 +307,8 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     let ConcurrentConfig {
         committer: committer_config,
         pruner: pruner_config,
+        sharding: _,
+        failure,
     } = config;
 
     let (processor_tx, collector_rx) = mpsc::channel(H::FANOUT + PIPELINE_BUFFER);
@@ -227,6 +326,8 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 
     let processor = processor(
         handler.clone(),
+        failure.clone(),
+        store.clone(),
         checkpoint_rx,
         processor_tx,
         metrics.clone(),
@@ -243,10 +344,11 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 
     let committer = committer::<H>(
         committer_config.clone(),
+        failure,
         skip_watermark,
         committer_rx,
         committer_tx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel.clone(),
     );
@@ -255,20 +357,27 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         initial_commit_watermark,
         committer_config,
         skip_watermark,
+        target,
         watermark_rx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel,
     );
 
     let reader_watermark = reader_watermark::<H>(
         pruner_config.clone(),
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         pruner_cancel.clone(),
     );
 
-    let pruner = pruner(handler, pruner_config, db, metrics, pruner_cancel.clone());
+    let pruner = pruner(
+        handler,
+        pruner_config,
+        store,
+        metrics,
+        pruner_cancel.clone(),
+    );
 
     tokio::spawn(async move {
         let (_, _, _, _) = futures::join!(processor, collector, committer, commit_watermark);
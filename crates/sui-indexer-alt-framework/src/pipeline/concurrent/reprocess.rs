@@ -0,0 +1,421 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use anyhow::{bail, ensure, Context};
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use futures::{stream, StreamExt, TryStreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::{
+    ingestion::client::IngestionClient,
+    models::{dead_letters::StoredDeadLetter, watermarks::CommitterWatermark},
+    store::{Connection, DeadLetterConnection, ShardedConnection, Store, TransactionalStore},
+};
+
+use super::{max_chunk_rows, Handler};
+
+/// Re-index checkpoints `first_checkpoint..=last_checkpoint` for the pipeline served by `handler`,
+/// while the pipeline keeps indexing the tip of the network elsewhere.
+///
+/// The range must be one that the pipeline has already committed, and that readers can still see:
+/// Checkpoints beyond its committer watermark will be indexed by the pipeline itself, and
+/// checkpoints below its reader watermark are being pruned.
+///
+/// The range is re-indexed `chunk_size` checkpoints at a time: The checkpoints in a chunk are
+/// fetched (using `client`) and processed concurrently, and only then is the chunk's existing data
+/// deleted (using the handler's `prune` implementation) and replaced with the new data, in a single
+/// transaction, so readers never see a chunk with its data missing. If a backfill is interrupted,
+/// it is safe to run it again.
+pub(crate) async fn backfill<H: Handler>(
+    handler: &H,
+    store: &H::Store,
+    client: &IngestionClient,
+    first_checkpoint: u64,
+    last_checkpoint: u64,
+    chunk_size: usize,
+    cancel: &CancellationToken,
+) -> anyhow::Result<()>
+where
+    H::Store: TransactionalStore,
+{
+    ensure_reprocessable::<H>()?;
+
+    let mut conn = store
+        .connect()
+        .await
+        .context("Failed to connect to store")?;
+    let Some(watermark) = conn
+        .watermark(H::NAME)
+        .await
+        .with_context(|| format!("Failed to get watermark for {}", H::NAME))?
+    else {
+        bail!(
+            "Pipeline {} has not indexed any checkpoints yet, there is nothing to backfill",
+            H::NAME,
+        );
+    };
+
+    ensure!(
+        first_checkpoint as i64 >= watermark.reader_lo,
+        "Cannot backfill pipeline {} from checkpoint {first_checkpoint}, which is below its \
+         reader watermark {}: this data has been, or is about to be, pruned.",
+        H::NAME,
+        watermark.reader_lo,
+    );
+
+    ensure!(
+        last_checkpoint as i64 <= watermark.checkpoint_hi_inclusive,
+        "Cannot backfill pipeline {} up to checkpoint {last_checkpoint}, which is beyond its \
+         committer watermark {}: these checkpoints will be indexed by the pipeline itself.",
+        H::NAME,
+        watermark.checkpoint_hi_inclusive,
+    );
+
+    // Each chunk is replaced in its own transaction, on its own connection.
+    drop(conn);
+
+    let chunk_size = chunk_size.max(1);
+    let mut affected = 0;
+    for lo in (first_checkpoint..=last_checkpoint).step_by(chunk_size) {
+        let hi = lo
+            .saturating_add(chunk_size as u64)
+            .min(last_checkpoint + 1);
+
+        let values: Vec<_> = stream::iter(lo..hi)
+            .map(|checkpoint| process(handler, client, checkpoint, cancel))
+            .buffered(chunk_size)
+            .try_concat()
+            .await?;
+
+        let (deleted, committed) = store
+            .transaction(|conn| {
+                async {
+                    let deleted = handler
+                        .prune(lo, hi, conn)
+                        .await
+                        .with_context(|| format!("Failed to clear [{lo}, {hi}) for {}", H::NAME))?;
+
+                    let committed = commit::<H>(&values, conn).await?;
+                    Ok((deleted, committed))
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        debug!(
+            pipeline = H::NAME,
+            from = lo,
+            to_exclusive = hi,
+            deleted,
+            committed,
+            "Backfilled chunk"
+        );
+        affected += committed;
+    }
+
+    info!(
+        pipeline = H::NAME,
+        first_checkpoint, last_checkpoint, affected, "Backfilled"
+    );
+    Ok(())
+}
+
+/// Rewind the pipeline served by `handler` so that the next time it runs, it starts indexing from
+/// `to_checkpoint`. Its committer watermark is moved back to just before `to_checkpoint` (which is
+/// fetched using `client`, to fill in the rest of the watermark), and the data it has committed
+/// from that checkpoint onwards is deleted (using the handler's `prune` implementation,
+/// `chunk_size` checkpoints at a time).
+///
+/// The pipeline must not be running while it is rewound, otherwise it will continue to move its
+/// watermark forward. The checkpoint is fetched before anything is changed, and the watermark is
+/// moved back and the data deleted in a single transaction, so readers never see a watermark that
+/// covers deleted data, and if a rewind is interrupted, it is safe to run it again.
+pub(crate) async fn rewind<H: Handler>(
+    handler: &H,
+    store: &H::Store,
+    client: &IngestionClient,
+    to_checkpoint: u64,
+    chunk_size: u64,
+    cancel: &CancellationToken,
+) -> anyhow::Result<()>
+where
+    H::Store: TransactionalStore,
+    for<'c> <H::Store as Store>::Connection<'c>: ShardedConnection,
+{
+    ensure_reprocessable::<H>()?;
+
+    let mut conn = store
+        .connect()
+        .await
+        .context("Failed to connect to store")?;
+    let Some(watermark) = conn
+        .watermark(H::NAME)
+        .await
+        .with_context(|| format!("Failed to get watermark for {}", H::NAME))?
+    else {
+        info!(pipeline = H::NAME, "No watermark, nothing to rewind");
+        return Ok(());
+    };
+
+    let shards = conn
+        .shard_watermarks(H::NAME)
+        .await
+        .with_context(|| format!("Failed to get shards for {}", H::NAME))?;
+
+    ensure!(
+        shards.is_empty(),
+        "Cannot rewind pipeline {}: it has been split into shards, which track their own \
+         watermarks.",
+        H::NAME,
+    );
+
+    let next_checkpoint = watermark.checkpoint_hi_inclusive as u64 + 1;
+    if to_checkpoint == next_checkpoint {
+        info!(pipeline = H::NAME, to_checkpoint, "Already at checkpoint");
+        return Ok(());
+    }
+
+    ensure!(
+        to_checkpoint < next_checkpoint,
+        "Cannot rewind pipeline {} to checkpoint {to_checkpoint}, which is ahead of its next \
+         checkpoint {next_checkpoint}: this would create gaps in the data.",
+        H::NAME,
+    );
+
+    ensure!(
+        to_checkpoint as i64 >= watermark.reader_lo,
+        "Cannot rewind pipeline {} to checkpoint {to_checkpoint}, which is below its reader \
+         watermark {}: this data has been, or is about to be, pruned.",
+        H::NAME,
+        watermark.reader_lo,
+    );
+
+    // Release the connection before opening the transaction that rewinds the pipeline (some
+    // stores only hand out one connection at a time).
+    drop(conn);
+
+    // Rewinding to genesis removes the watermark altogether, otherwise the checkpoint before
+    // `to_checkpoint` is fetched to fill in the rest of the watermark.
+    let watermark = match to_checkpoint.checked_sub(1) {
+        None => None,
+        Some(checkpoint_hi_inclusive) => {
+            let checkpoint = client
+                .fetch(checkpoint_hi_inclusive, cancel)
+                .await
+                .with_context(|| format!("Failed to fetch checkpoint {checkpoint_hi_inclusive}"))?;
+
+            let summary = &checkpoint.checkpoint_summary;
+            Some(CommitterWatermark {
+                pipeline: Cow::Borrowed(H::NAME),
+                epoch_hi_inclusive: summary.epoch as i64,
+                checkpoint_hi_inclusive: summary.sequence_number as i64,
+                tx_hi: summary.network_total_transactions as i64,
+                timestamp_ms_hi_inclusive: summary.timestamp_ms as i64,
+            })
+        }
+    };
+
+    let deleted = store
+        .transaction(|conn| {
+            async {
+                if let Some(watermark) = &watermark {
+                    conn.rewind_committer_watermark(watermark)
+                        .await
+                        .with_context(|| format!("Failed to rewind watermark for {}", H::NAME))?;
+                } else {
+                    conn.delete_watermark(H::NAME)
+                        .await
+                        .with_context(|| format!("Failed to delete watermark for {}", H::NAME))?;
+                }
+
+                clear(handler, conn, to_checkpoint, next_checkpoint, chunk_size).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    info!(pipeline = H::NAME, to_checkpoint, deleted, "Rewound");
+    Ok(())
+}
+
+/// Re-index the checkpoints that the pipeline served by `handler` recorded as dead letters,
+/// fetching them using `client`. Each checkpoint is processed and committed on its own, and its
+/// dead letter is removed once it has been committed. Checkpoints that fail again have their dead
+/// letter updated with the new error, and checkpoints that readers can no longer see (because they
+/// are below the pipeline's reader watermark) are dropped without being re-indexed.
+///
+/// Watermarks are left untouched: Checkpoints are only dead-lettered after the pipeline has
+/// skipped them, so they are already covered by its committer watermark, or will be soon.
+pub(crate) async fn replay<H: Handler>(
+    handler: &H,
+    store: &H::Store,
+    client: &IngestionClient,
+    cancel: &CancellationToken,
+) -> anyhow::Result<()>
+where
+    for<'c> <H::Store as Store>::Connection<'c>: DeadLetterConnection,
+{
+    let mut conn = store
+        .connect()
+        .await
+        .context("Failed to connect to store")?;
+
+    let dead_letters = conn
+        .dead_letters(Some(H::NAME))
+        .await
+        .with_context(|| format!("Failed to get dead letters for {}", H::NAME))?;
+
+    if dead_letters.is_empty() {
+        info!(pipeline = H::NAME, "No dead letters, nothing to replay");
+        return Ok(());
+    }
+
+    let reader_lo = conn
+        .watermark(H::NAME)
+        .await
+        .with_context(|| format!("Failed to get watermark for {}", H::NAME))?
+        .map_or(0, |w| w.reader_lo);
+
+    let mut failed = 0;
+    for dead_letter in dead_letters {
+        let checkpoint = dead_letter.cp_sequence_number as u64;
+        if (checkpoint as i64) < reader_lo {
+            warn!(
+                pipeline = H::NAME,
+                checkpoint, reader_lo, "Dropping dead letter below reader watermark"
+            );
+        } else if let Err(e) = reindex(handler, &mut conn, client, checkpoint, cancel).await {
+            warn!(
+                pipeline = H::NAME,
+                checkpoint, "Failed to replay checkpoint: {e:#}"
+            );
+
+            conn.add_dead_letter(&StoredDeadLetter {
+                stage: "replay".to_owned(),
+                error: format!("{e:#}"),
+                created_at: Utc::now().naive_utc(),
+                ..dead_letter
+            })
+            .await
+            .with_context(|| format!("Failed to update dead letter for {checkpoint}"))?;
+
+            failed += 1;
+            continue;
+        }
+
+        conn.delete_dead_letter(H::NAME, checkpoint)
+            .await
+            .with_context(|| format!("Failed to delete dead letter for {checkpoint}"))?;
+    }
+
+    ensure!(
+        failed == 0,
+        "Failed to replay {failed} checkpoint(s) for pipeline {}",
+        H::NAME,
+    );
+
+    info!(pipeline = H::NAME, "Replayed dead letters");
+    Ok(())
+}
+
+/// Backfills and rewinds rely on the handler's `prune` implementation to delete all the data for a
+/// range of checkpoints, which is only possible for pipelines that declare that their pruner does
+/// so (see [Handler::PRUNE_CLEARS_CHECKPOINTS]).
+fn ensure_reprocessable<H: Handler>() -> anyhow::Result<()> {
+    ensure!(
+        H::PRUNE_CLEARS_CHECKPOINTS && !H::PRUNING_REQUIRES_PROCESSED_VALUES,
+        "Pipeline {} cannot be backfilled or rewound: its pruner does not clear checkpoints",
+        H::NAME,
+    );
+
+    Ok(())
+}
+
+/// Delete the data that the pipeline served by `handler` has committed for checkpoints in
+/// `from..to_exclusive`, `chunk_size` checkpoints at a time. Returns the number of rows affected.
+async fn clear<H: Handler>(
+    handler: &H,
+    conn: &mut <H::Store as Store>::Connection<'_>,
+    from: u64,
+    to_exclusive: u64,
+    chunk_size: u64,
+) -> anyhow::Result<usize> {
+    let chunk_size = chunk_size.max(1);
+    let mut affected = 0;
+    for lo in (from..to_exclusive).step_by(chunk_size as usize) {
+        let hi = lo.saturating_add(chunk_size).min(to_exclusive);
+        let rows = handler
+            .prune(lo, hi, conn)
+            .await
+            .with_context(|| format!("Failed to clear [{lo}, {hi}) for {}", H::NAME))?;
+
+        debug!(
+            pipeline = H::NAME,
+            from = lo,
+            to_exclusive = hi,
+            rows,
+            "Cleared chunk"
+        );
+        affected += rows;
+    }
+
+    info!(
+        pipeline = H::NAME,
+        from, to_exclusive, affected, "Cleared data"
+    );
+    Ok(affected)
+}
+
+/// Fetch `checkpoint` using `client`, process it with `handler`, and commit the resulting rows
+/// using `conn`. Returns the number of rows affected.
+async fn reindex<H: Handler>(
+    handler: &H,
+    conn: &mut <H::Store as Store>::Connection<'_>,
+    client: &IngestionClient,
+    checkpoint: u64,
+    cancel: &CancellationToken,
+) -> anyhow::Result<usize> {
+    let values = process(handler, client, checkpoint, cancel).await?;
+    let affected = commit::<H>(&values, conn).await?;
+
+    debug!(
+        pipeline = H::NAME,
+        checkpoint, affected, "Replayed checkpoint"
+    );
+    Ok(affected)
+}
+
+/// Fetch `checkpoint` using `client`, and process it with `handler`.
+async fn process<H: Handler>(
+    handler: &H,
+    client: &IngestionClient,
+    checkpoint: u64,
+    cancel: &CancellationToken,
+) -> anyhow::Result<Vec<H::Value>> {
+    let data = client
+        .fetch(checkpoint, cancel)
+        .await
+        .with_context(|| format!("Failed to fetch checkpoint {checkpoint}"))?;
+
+    handler
+        .process(&data)
+        .with_context(|| format!("Failed to process checkpoint {checkpoint}"))
+}
+
+/// Commit `values` using `conn`, in chunks that fit in a single write. Returns the number of rows
+/// affected.
+async fn commit<H: Handler>(
+    values: &[H::Value],
+    conn: &mut <H::Store as Store>::Connection<'_>,
+) -> anyhow::Result<usize> {
+    let mut affected = 0;
+    for chunk in values.chunks(max_chunk_rows::<H>()) {
+        affected += H::commit(chunk, conn).await?;
+    }
+
+    Ok(affected)
+}
@@ -0,0 +1,383 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use diesel_async::scoped_futures::ScopedFutureExt;
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{
+    db::Db,
+    models::pipeline_swaps::StoredPipelineSwap,
+    pipeline::PIPELINE_BUFFER,
+    store::{Connection, TransactionalStore},
+    types::full_checkpoint_content::CheckpointData,
+};
+
+use super::{Handler, ShadowConfig};
+
+/// Forward checkpoints from `checkpoint_rx` to the live pipeline that is being shadowed, until it
+/// is retired (signalled by `retire`). Once retired, the live pipeline's channel is closed, so that
+/// it winds down the same way it would after ingesting its last checkpoint, while checkpoints keep
+/// being drained from `checkpoint_rx`, so that the ingestion service does not interpret the live
+/// pipeline stopping as a signal to shut the whole indexer down.
+pub(crate) fn retirable(
+    mut checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    retire: CancellationToken,
+    cancel: CancellationToken,
+) -> (mpsc::Receiver<Arc<CheckpointData>>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(PIPELINE_BUFFER);
+
+    let handle = tokio::spawn(async move {
+        let mut tx = Some(tx);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+
+                _ = retire.cancelled(), if tx.is_some() => {
+                    tx = None;
+                }
+
+                checkpoint = checkpoint_rx.recv() => {
+                    let Some(checkpoint) = checkpoint else {
+                        break;
+                    };
+
+                    if let Some(tx) = &tx {
+                        if tx.send(checkpoint).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    (rx, handle)
+}
+
+/// Swap the shadow pipeline, `H`, in for the live pipeline, `L`, once it has caught up.
+///
+/// The task waits until the shadow pipeline's watermark is within `config.max_lag` checkpoints of
+/// the live pipeline's, and then retires the live pipeline (via `retire`) and waits for it to wind
+/// down (it is running as the task behind `live`). Once the shadow pipeline's watermark has reached
+/// the live pipeline's final watermark, the live pipeline's tables are renamed out of the way and
+/// the shadow pipeline's `tables` are renamed to take their places (each is paired with the live
+/// table it replaces), in the same transaction as the swap is recorded, so readers see all the new
+/// tables at once (see [StoredPipelineSwap::swap]).
+///
+/// From then on, until the indexer shuts down, the task keeps the retired pipeline's watermark up
+/// to date with the shadow pipeline's, for the readers that are still checking it (see [mirror]).
+pub(crate) fn swap<L, H>(
+    mut live: JoinHandle<()>,
+    retire: CancellationToken,
+    tables: &'static [(&'static str, &'static str)],
+    config: ShadowConfig,
+    store: Db,
+    cancel: CancellationToken,
+) -> JoinHandle<()>
+where
+    L: Handler<Store = Db> + 'static,
+    H: Handler<Store = Db> + 'static,
+{
+    tokio::spawn(async move {
+        let mut poll = interval(config.interval());
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Wait for the shadow pipeline to catch up with the live pipeline, while the live pipeline
+        // is still running.
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    let _ = live.await;
+                    info!(pipeline = H::NAME, "Shutdown received before shadow caught up");
+                    return;
+                }
+
+                _ = &mut live => {
+                    info!(pipeline = H::NAME, "Live pipeline stopped before shadow caught up");
+                    return;
+                }
+
+                _ = poll.tick() => {
+                    match checkpoints_hi::<L, H>(&store).await {
+                        Ok((live_hi, Some(shadow_hi)))
+                            if live_hi.is_none_or(|l| shadow_hi + config.max_lag >= l) => break,
+
+                        Ok(_) => {}
+
+                        Err(e) => {
+                            warn!(pipeline = H::NAME, "Failed to check shadow progress: {e:#}");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(
+            pipeline = H::NAME,
+            retiring = L::NAME,
+            "Shadow pipeline caught up, retiring live pipeline",
+        );
+
+        retire.cancel();
+        if let Err(e) = live.await {
+            error!(pipeline = L::NAME, "Live pipeline failed to wind down: {e}");
+            cancel.cancel();
+            return;
+        }
+
+        // The live pipeline's watermark will not move any more, so wait for the shadow pipeline to
+        // reach it, so that readers do not see the watermark go backwards when the tables swap.
+        let swap = loop {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    info!(pipeline = H::NAME, "Shutdown received before swap");
+                    return;
+                }
+
+                _ = poll.tick() => {
+                    match checkpoints_hi::<L, H>(&store).await {
+                        Ok((live_hi, Some(shadow_hi)))
+                            if live_hi.is_none_or(|l| shadow_hi >= l) => {}
+
+                        Ok(_) => continue,
+
+                        Err(e) => {
+                            warn!(pipeline = H::NAME, "Failed to check shadow progress: {e:#}");
+                            continue;
+                        }
+                    }
+
+                    let swapped = store
+                        .transaction(|conn| {
+                            async move {
+                                Ok(StoredPipelineSwap::swap(conn, L::NAME, H::NAME, tables).await?)
+                            }
+                            .scope_boxed()
+                        })
+                        .await;
+
+                    match swapped {
+                        Ok(swap) => break swap,
+                        Err(e) => {
+                            warn!(pipeline = H::NAME, "Failed to swap in shadow pipeline: {e:#}");
+                        }
+                    }
+                }
+            }
+        };
+
+        info!(
+            pipeline = H::NAME,
+            retired = L::NAME,
+            "Shadow pipeline swapped in"
+        );
+
+        let _ = mirror(swap, config, store, cancel).await;
+    })
+}
+
+/// Keep the watermark of the pipeline retired by `swap` up-to-date with the watermark of the
+/// pipeline that replaced it, checking at the interval set in `config`, until the indexer shuts
+/// down.
+pub(crate) fn mirror(
+    swap: StoredPipelineSwap,
+    config: ShadowConfig,
+    store: Db,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut poll = interval(config.interval());
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+
+                _ = poll.tick() => {
+                    let mirrored = async {
+                        let mut conn = store.connect().await?;
+                        Ok::<_, anyhow::Error>(swap.mirror_watermark(&mut conn).await?)
+                    };
+
+                    if let Err(e) = mirrored.await {
+                        warn!(
+                            pipeline = %swap.replaced_by,
+                            retired = %swap.pipeline,
+                            "Failed to mirror watermark: {e:#}",
+                        );
+                    }
+                }
+            }
+        }
+
+        info!(
+            pipeline = %swap.replaced_by,
+            retired = %swap.pipeline,
+            "Stopped mirroring watermark"
+        );
+    })
+}
+
+/// The checkpoint high watermarks of the live pipeline, `L`, and the shadow pipeline, `H`, if they
+/// have written one.
+async fn checkpoints_hi<L: Handler<Store = Db>, H: Handler<Store = Db>>(
+    store: &Db,
+) -> anyhow::Result<(Option<u64>, Option<u64>)> {
+    let mut conn = store.connect().await?;
+
+    let live = conn.committer_watermark(L::NAME).await?;
+    let shadow = conn.committer_watermark(H::NAME).await?;
+
+    Ok((
+        live.map(|w| w.checkpoint_hi_inclusive as u64),
+        shadow.map(|w| w.checkpoint_hi_inclusive as u64),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{prelude::*, sql_query};
+    use diesel_async::RunQueryDsl;
+
+    use crate::{models::watermarks::CommitterWatermark, Indexer, MIGRATIONS};
+
+    use super::*;
+
+    diesel::table! {
+        live_rows (x) {
+            x -> Int8,
+        }
+    }
+
+    diesel::table! {
+        live_rows_retired (x) {
+            x -> Int8,
+        }
+    }
+
+    diesel::table! {
+        shadow_rows (x) {
+            x -> Int8,
+        }
+    }
+
+    #[derive(QueryableByName)]
+    struct TableType {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        table_type: String,
+    }
+
+    async fn table_type(conn: &mut crate::db::Connection<'_>, table: &str) -> String {
+        let info: TableType = sql_query(
+            "SELECT table_type::TEXT FROM information_schema.tables WHERE table_name = $1",
+        )
+        .bind::<diesel::sql_types::Text, _>(table)
+        .get_result(conn)
+        .await
+        .unwrap();
+
+        info.table_type
+    }
+
+    #[tokio::test]
+    async fn test_swap_and_mirror() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+
+        for stmt in [
+            "CREATE TABLE live_rows (x BIGINT PRIMARY KEY)",
+            "CREATE TABLE shadow_rows (x BIGINT PRIMARY KEY, y BIGINT)",
+            "INSERT INTO live_rows VALUES (1)",
+            "INSERT INTO shadow_rows VALUES (1, 10), (2, 20)",
+        ] {
+            sql_query(stmt).execute(&mut conn).await.unwrap();
+        }
+
+        CommitterWatermark::new_for_testing("live", 10)
+            .update(&mut conn)
+            .await
+            .unwrap();
+        CommitterWatermark::new_for_testing("shadow", 12)
+            .update(&mut conn)
+            .await
+            .unwrap();
+
+        let swap = indexer
+            .db()
+            .transaction(|conn| {
+                async move {
+                    Ok(StoredPipelineSwap::swap(
+                        conn,
+                        "live",
+                        "shadow",
+                        &[("shadow_rows", "live_rows")],
+                    )
+                    .await?)
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            StoredPipelineSwap::get(&mut conn, "live").await.unwrap(),
+            Some(swap.clone()),
+        );
+
+        // The live table is now the shadow table, and rows written to the shadow table's old name
+        // after the swap end up in it.
+        let count: i64 = live_rows::table
+            .count()
+            .get_result(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        diesel::insert_into(shadow_rows::table)
+            .values(shadow_rows::x.eq(3))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let count: i64 = live_rows::table
+            .count()
+            .get_result(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+
+        // Readers query a real table, and the view under the shadow table's old name is only there
+        // for the shadow pipeline's writes.
+        assert_eq!(table_type(&mut conn, "live_rows").await, "BASE TABLE");
+        assert_eq!(table_type(&mut conn, "shadow_rows").await, "VIEW");
+
+        // The live table's original rows are kept in the retired table.
+        let count: i64 = live_rows_retired::table
+            .count()
+            .get_result(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // The live pipeline's watermark follows the shadow pipeline's.
+        let live = CommitterWatermark::get(&mut conn, "live").await.unwrap();
+        assert_eq!(live.unwrap().checkpoint_hi_inclusive, 12);
+
+        CommitterWatermark::new_for_testing("shadow", 15)
+            .update(&mut conn)
+            .await
+            .unwrap();
+        assert!(swap.mirror_watermark(&mut conn).await.unwrap());
+
+        let live = CommitterWatermark::get(&mut conn, "live").await.unwrap();
+        assert_eq!(live.unwrap().checkpoint_hi_inclusive, 15);
+    }
+}
@@ -0,0 +1,632 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{ensure, Context};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{interval, interval_at, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{
+    ingestion::client::IngestionClient,
+    metrics::IndexerMetrics,
+    models::watermarks::{CommitterWatermark, ShardLease, StoredShardWatermark},
+    pipeline::Processor,
+    store::{DeadLetterConnection, ShardedConnection, Store},
+    types::full_checkpoint_content::CheckpointData,
+};
+
+use super::{
+    catch_up::catch_up, commit_watermark::WatermarkTarget, ConcurrentConfig, Handler,
+    ShardingConfig,
+};
+
+/// A lease on a shard, acquired by [acquire].
+struct Acquired {
+    lease: ShardLease,
+    /// The shard's watermark when the lease was acquired.
+    watermark: Option<CommitterWatermark<'static>>,
+    /// The range of checkpoints dropped while waiting for the lease, if any.
+    dropped: Option<RangeInclusive<u64>>,
+}
+
+/// A handler that only processes the checkpoints that its shard is responsible for: Those whose
+/// sequence number modulo the number of shards is the shard's index. Every other checkpoint is
+/// processed into no values, so that the shard's watermark still advances through them.
+struct Sharded<H> {
+    handler: H,
+    shard: u64,
+    shards: u64,
+}
+
+impl<H: Handler> Processor for Sharded<H> {
+    const NAME: &'static str = H::NAME;
+    const FANOUT: usize = H::FANOUT;
+    type Value = H::Value;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+        if checkpoint.checkpoint_summary.sequence_number % self.shards != self.shard {
+            return Ok(vec![]);
+        }
+
+        self.handler.process(checkpoint)
+    }
+}
+
+#[async_trait::async_trait]
+impl<H: Handler + Send + Sync> Handler for Sharded<H> {
+    type Store = H::Store;
+
+    const MIN_EAGER_ROWS: usize = H::MIN_EAGER_ROWS;
+    const MAX_PENDING_ROWS: usize = H::MAX_PENDING_ROWS;
+    const PRUNING_REQUIRES_PROCESSED_VALUES: bool = H::PRUNING_REQUIRES_PROCESSED_VALUES;
+    const PRUNE_CLEARS_CHECKPOINTS: bool = H::PRUNE_CLEARS_CHECKPOINTS;
+
+    async fn commit(
+        values: &[Self::Value],
+        conn: &mut <Self::Store as Store>::Connection<'_>,
+    ) -> anyhow::Result<usize> {
+        H::commit(values, conn).await
+    }
+
+    async fn prune(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut <Self::Store as Store>::Connection<'_>,
+    ) -> anyhow::Result<usize> {
+        self.handler.prune(from, to_exclusive, conn).await
+    }
+}
+
+/// Start one shard of a concurrent pipeline that has been split across multiple indexer
+/// instances, served by the handler, `H`.
+///
+/// The task first waits until it can acquire a lease on one of the pipeline's shards that no other
+/// instance holds (or whose holder has stopped renewing it), and then runs a concurrent pipeline
+/// for just that shard: Every instance ingests every checkpoint, but only processes and commits
+/// the checkpoints its shard is responsible for. The shard's watermark is tracked separately, and
+/// the pipeline's watermark is the lowest watermark across all its shards. Only the instance
+/// responsible for shard 0 prunes the pipeline.
+///
+/// While it waits for a lease, the task drains and drops checkpoints from `checkpoint_rx`, so that
+/// a standby instance does not hold back ingestion for the other pipelines in the indexer. If it
+/// dropped checkpoints that the shard it eventually acquires has not committed yet, the pipeline
+/// is started from the shard's watermark instead: The checkpoints it missed are fetched again
+/// (using `client`, `concurrency` at a time) before it resumes from the ingestion stream (see
+/// [catch_up]).
+///
+/// The lease is renewed regularly while the pipeline runs, and released when it shuts down, so
+/// that another instance can take over. If the lease is lost (because it could not be renewed in
+/// time, and another instance took over), the whole indexer is shutdown via `cancel`, because the
+/// instance can no longer safely write to the shard.
+pub(super) fn pipeline<H: Handler + Send + Sync + 'static>(
+    handler: H,
+    mut config: ConcurrentConfig,
+    sharding: ShardingConfig,
+    store: H::Store,
+    client: IngestionClient,
+    concurrency: usize,
+    mut checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    metrics: Arc<IndexerMetrics>,
+    cancel: CancellationToken,
+) -> JoinHandle<()>
+where
+    for<'c> <H::Store as Store>::Connection<'c>: ShardedConnection + DeadLetterConnection,
+{
+    tokio::spawn(async move {
+        let owner = lease_owner();
+        let Some(Acquired {
+            lease,
+            watermark: initial_watermark,
+            dropped,
+        }) = (match acquire::<H>(&store, &sharding, owner, &mut checkpoint_rx, &cancel).await {
+            Ok(acquired) => acquired,
+            Err(e) => {
+                error!(pipeline = H::NAME, "Failed to acquire shard: {e:#}");
+                cancel.cancel();
+                return;
+            }
+        })
+        else {
+            info!(
+                pipeline = H::NAME,
+                "Shutdown received before acquiring shard"
+            );
+            return;
+        };
+
+        let committed_hi = initial_watermark
+            .as_ref()
+            .map(|w| w.checkpoint_hi_inclusive as u64);
+
+        let checkpoint_rx = match dropped {
+            Some(dropped) if committed_hi.is_none_or(|c| *dropped.end() > c) => {
+                let from = committed_hi.map_or(*dropped.start(), |c| c + 1);
+                warn!(
+                    pipeline = H::NAME,
+                    shard = lease.shard,
+                    from,
+                    to = dropped.end(),
+                    "Checkpoints needed by acquired shard were dropped while waiting for it, \
+                     fetching them again",
+                );
+
+                catch_up(
+                    H::NAME,
+                    client,
+                    concurrency,
+                    from,
+                    checkpoint_rx,
+                    cancel.clone(),
+                )
+            }
+
+            _ => checkpoint_rx,
+        };
+
+        if lease.shard != 0 {
+            config.pruner = None;
+        }
+
+        let handler = Sharded {
+            handler,
+            shard: lease.shard,
+            shards: lease.shards,
+        };
+
+        let mut pipeline = super::pipeline(
+            handler,
+            initial_watermark,
+            config,
+            false,
+            lease.clone(),
+            store.clone(),
+            checkpoint_rx,
+            metrics,
+            cancel.clone(),
+        );
+
+        let mut renew = interval(sharding.lease_ttl() / 3);
+        renew.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut renewed_at = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = &mut pipeline => break,
+
+                _ = renew.tick() => {
+                    match renew_lease(&store, &lease, &sharding).await {
+                        Ok(true) => renewed_at = Instant::now(),
+
+                        Ok(false) => {
+                            error!(
+                                pipeline = H::NAME,
+                                shard = lease.shard,
+                                "Lost lease on shard",
+                            );
+                            cancel.cancel();
+                        }
+
+                        Err(e) if renewed_at.elapsed() >= sharding.lease_ttl() => {
+                            error!(
+                                pipeline = H::NAME,
+                                shard = lease.shard,
+                                "Failed to renew lease before it expired: {e:#}",
+                            );
+                            cancel.cancel();
+                        }
+
+                        Err(e) => {
+                            warn!(
+                                pipeline = H::NAME,
+                                shard = lease.shard,
+                                "Failed to renew lease: {e:#}",
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        match release_lease(&store, &lease).await {
+            Ok(_) => info!(
+                pipeline = H::NAME,
+                shard = lease.shard,
+                "Released lease on shard"
+            ),
+            Err(e) => warn!(
+                pipeline = H::NAME,
+                shard = lease.shard,
+                "Failed to release lease on shard: {e:#}",
+            ),
+        }
+    })
+}
+
+/// Update the shard's watermark, and then raise the pipeline's watermark to the lowest watermark
+/// across all its shards, if every shard has committed something. Returns whether the shard's
+/// watermark was updated.
+async fn set_watermark<C: ShardedConnection>(
+    conn: &mut C,
+    lease: &ShardLease,
+    watermark: &CommitterWatermark<'_>,
+) -> anyhow::Result<bool> {
+    if !conn.set_shard_watermark(lease, watermark).await? {
+        return Ok(false);
+    }
+
+    let shards: Vec<_> = conn
+        .shard_watermarks(lease.pipeline)
+        .await?
+        .into_iter()
+        .filter(|s| s.shards as u64 == lease.shards)
+        .collect();
+
+    if shards.len() as u64 != lease.shards {
+        return Ok(true);
+    }
+
+    let lowest = shards
+        .iter()
+        .map(StoredShardWatermark::committer_watermark)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|ws| ws.into_iter().min_by_key(|w| w.checkpoint_hi_inclusive));
+
+    if let Some(lowest) = lowest {
+        conn.set_committer_watermark(&lowest).await?;
+    }
+
+    Ok(true)
+}
+
+/// One shard of a sharded pipeline writes its watermark through its lease (see [set_watermark]).
+#[async_trait::async_trait]
+impl<S: Store> WatermarkTarget<S> for ShardLease
+where
+    for<'c> S::Connection<'c>: ShardedConnection,
+{
+    async fn write_watermark(
+        &self,
+        conn: &mut S::Connection<'_>,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        set_watermark(conn, self, watermark).await
+    }
+}
+
+/// Try to acquire a lease on each of the pipeline's shards in turn, until one succeeds, waiting
+/// for leases to expire if they are all held by other instances. A lease is tried for before any
+/// checkpoints are received, and checkpoints received from `checkpoint_rx` while waiting to try
+/// again are dropped.
+///
+/// Returns the lease, the shard's watermark, and the range of checkpoints that were dropped (if
+/// any), or `None` if the indexer was shutdown (or ingestion finished) before a shard could be
+/// acquired.
+async fn acquire<H: Handler>(
+    store: &H::Store,
+    sharding: &ShardingConfig,
+    owner: String,
+    checkpoint_rx: &mut mpsc::Receiver<Arc<CheckpointData>>,
+    cancel: &CancellationToken,
+) -> anyhow::Result<Option<Acquired>>
+where
+    for<'c> <H::Store as Store>::Connection<'c>: ShardedConnection,
+{
+    let period = sharding.lease_ttl() / 2;
+    let mut retry = interval_at(tokio::time::Instant::now() + period, period);
+    retry.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut dropped: Option<RangeInclusive<u64>> = None;
+
+    loop {
+        let mut conn = store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
+
+        for shard in 0..sharding.shards {
+            let lease = ShardLease {
+                pipeline: H::NAME,
+                shard,
+                shards: sharding.shards,
+                owner: owner.clone(),
+            };
+
+            let Some(stored) = conn
+                .acquire_shard_lease(&lease, sharding.lease_ttl())
+                .await
+                .with_context(|| format!("Failed to acquire shard {shard} of {}", H::NAME))?
+            else {
+                continue;
+            };
+
+            ensure!(
+                stored.shards as u64 == sharding.shards,
+                "Pipeline {} is split into {} shards, but is configured with {}",
+                H::NAME,
+                stored.shards,
+                sharding.shards,
+            );
+
+            info!(
+                pipeline = H::NAME,
+                shard,
+                shards = sharding.shards,
+                owner = lease.owner,
+                "Acquired lease on shard",
+            );
+
+            return Ok(Some(Acquired {
+                lease,
+                watermark: stored.committer_watermark(),
+                dropped,
+            }));
+        }
+
+        info!(
+            pipeline = H::NAME,
+            shards = sharding.shards,
+            "All shards are leased, waiting for one to become available",
+        );
+
+        // Release the connection while waiting, so that the store can be used by others.
+        drop(conn);
+
+        loop {
+            tokio::select! {
+                biased;
+
+                _ = cancel.cancelled() => return Ok(None),
+                _ = retry.tick() => break,
+                checkpoint = checkpoint_rx.recv() => {
+                    let Some(checkpoint) = checkpoint else {
+                        return Ok(None);
+                    };
+
+                    let cp = checkpoint.checkpoint_summary.sequence_number;
+                    dropped = Some(match dropped {
+                        Some(d) => (*d.start()).min(cp)..=(*d.end()).max(cp),
+                        None => cp..=cp,
+                    });
+                }
+            }
+        }
+    }
+}
+
+async fn renew_lease<S: Store>(
+    store: &S,
+    lease: &ShardLease,
+    sharding: &ShardingConfig,
+) -> anyhow::Result<bool>
+where
+    for<'c> S::Connection<'c>: ShardedConnection,
+{
+    let mut conn = store.connect().await?;
+    conn.renew_shard_lease(lease, sharding.lease_ttl()).await
+}
+
+async fn release_lease<S: Store>(store: &S, lease: &ShardLease) -> anyhow::Result<bool>
+where
+    for<'c> S::Connection<'c>: ShardedConnection,
+{
+    let mut conn = store.connect().await?;
+    conn.release_shard_lease(lease).await
+}
+
+/// A name for this instance of the pipeline, to identify which instance holds which lease. It is
+/// unique to the process, and mentions the host it is running on, where possible, to help operators
+/// find it.
+fn lease_owner() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_owned());
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    format!("{host}:{}:{nanos:x}", std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sui_storage::blob::Blob;
+
+    use crate::{
+        ingestion::test_utils::test_checkpoint_data,
+        store::{Connection, MemConnection, MemStore},
+    };
+
+    use super::*;
+
+    struct TestHandler;
+
+    impl Processor for TestHandler {
+        const NAME: &'static str = "p";
+        type Value = ();
+
+        fn process(&self, _: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Handler for TestHandler {
+        type Store = MemStore;
+
+        async fn commit(_: &[Self::Value], _: &mut MemConnection<'_, ()>) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    fn checkpoint(cp: u64) -> Arc<CheckpointData> {
+        Arc::new(Blob::from_bytes(&test_checkpoint_data(cp)).unwrap())
+    }
+
+    fn lease(shard: u64, owner: &str) -> ShardLease {
+        ShardLease {
+            pipeline: "p",
+            shard,
+            shards: 2,
+            owner: owner.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lease_exclusive_until_expiry() {
+        let store = MemStore::<()>::default();
+        let mut conn = store.connect().await.unwrap();
+        let ttl = Duration::from_millis(100);
+
+        let a = lease(0, "a");
+        let b = lease(0, "b");
+        assert!(conn.acquire_shard_lease(&a, ttl).await.unwrap().is_some());
+        assert!(conn.acquire_shard_lease(&b, ttl).await.unwrap().is_none());
+
+        // The owner can re-acquire, or renew its own lease, but nobody else can.
+        assert!(conn.acquire_shard_lease(&a, ttl).await.unwrap().is_some());
+        assert!(conn.renew_shard_lease(&a, ttl).await.unwrap());
+        assert!(!conn.renew_shard_lease(&b, ttl).await.unwrap());
+
+        // Once the lease expires, another owner can take over, and the previous owner can no longer
+        // write to the shard.
+        tokio::time::sleep(2 * ttl).await;
+        assert!(conn.acquire_shard_lease(&b, ttl).await.unwrap().is_some());
+        let watermark = CommitterWatermark::new_for_testing("p", 10);
+        assert!(!conn.set_shard_watermark(&a, &watermark).await.unwrap());
+        assert!(conn.set_shard_watermark(&b, &watermark).await.unwrap());
+
+        // Releasing the lease makes it available immediately.
+        assert!(conn.release_shard_lease(&b).await.unwrap());
+        assert!(conn.acquire_shard_lease(&a, ttl).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_watermark_is_lowest_shard() {
+        let store = MemStore::<()>::default();
+        let mut conn = store.connect().await.unwrap();
+        let ttl = Duration::from_secs(60);
+
+        // The pipeline already has a watermark, which new shards start from.
+        let initial = CommitterWatermark::new_for_testing("p", 5);
+        conn.set_committer_watermark(&initial).await.unwrap();
+
+        let (a, b) = (lease(0, "a"), lease(1, "b"));
+        let shard = conn.acquire_shard_lease(&a, ttl).await.unwrap().unwrap();
+        assert_eq!(
+            shard.committer_watermark().unwrap().checkpoint_hi_inclusive,
+            5
+        );
+
+        // Until every shard exists, the pipeline watermark does not move.
+        let w20 = CommitterWatermark::new_for_testing("p", 20);
+        assert!(set_watermark(&mut conn, &a, &w20).await.unwrap());
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 5);
+
+        conn.acquire_shard_lease(&b, ttl).await.unwrap().unwrap();
+        let w10 = CommitterWatermark::new_for_testing("p", 10);
+        assert!(set_watermark(&mut conn, &b, &w10).await.unwrap());
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 10);
+
+        let w30 = CommitterWatermark::new_for_testing("p", 30);
+        assert!(set_watermark(&mut conn, &b, &w30).await.unwrap());
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 20);
+    }
+
+    /// A standby instance keeps draining checkpoints while it waits for a lease, so that it does
+    /// not hold back ingestion, and reports the range of checkpoints it dropped.
+    #[tokio::test]
+    async fn test_acquire_drains_checkpoints_while_waiting() {
+        let store = MemStore::<()>::default();
+        let sharding = ShardingConfig {
+            shards: 2,
+            lease_ttl_ms: 200,
+        };
+
+        // Both shards are held by other instances, until their leases expire.
+        {
+            let mut conn = store.connect().await.unwrap();
+            for shard in 0..2 {
+                let held = lease(shard, "other");
+                let stored = conn
+                    .acquire_shard_lease(&held, sharding.lease_ttl())
+                    .await
+                    .unwrap();
+                assert!(stored.is_some());
+            }
+        }
+
+        // More checkpoints than the channel can buffer, so sending them all only succeeds if the
+        // waiting instance is draining them.
+        let (checkpoint_tx, mut checkpoint_rx) = mpsc::channel(1);
+        let cancel = CancellationToken::new();
+        let acquire = tokio::spawn({
+            let store = store.clone();
+            let cancel = cancel.clone();
+            async move {
+                acquire::<TestHandler>(
+                    &store,
+                    &sharding,
+                    "me".to_owned(),
+                    &mut checkpoint_rx,
+                    &cancel,
+                )
+                .await
+            }
+        });
+
+        for cp in 0..5 {
+            checkpoint_tx.send(checkpoint(cp)).await.unwrap();
+        }
+
+        let acquired = acquire.await.unwrap().unwrap().unwrap();
+        assert_eq!(acquired.lease.owner, "me");
+        assert_eq!(acquired.dropped, Some(0..=4));
+        assert!(acquired.watermark.is_none());
+    }
+
+    /// If a shard is available, it is acquired before any checkpoints are drained, so none are
+    /// dropped.
+    #[tokio::test]
+    async fn test_acquire_available_shard_drops_nothing() {
+        let store = MemStore::<()>::default();
+        let sharding = ShardingConfig {
+            shards: 2,
+            lease_ttl_ms: 200,
+        };
+
+        let (checkpoint_tx, mut checkpoint_rx) = mpsc::channel(1);
+        checkpoint_tx.send(checkpoint(0)).await.unwrap();
+
+        let cancel = CancellationToken::new();
+        let acquired = acquire::<TestHandler>(
+            &store,
+            &sharding,
+            "me".to_owned(),
+            &mut checkpoint_rx,
+            &cancel,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(acquired.lease.shard, 0);
+        assert_eq!(acquired.dropped, None);
+
+        let received = checkpoint_rx.try_recv().unwrap();
+        assert_eq!(received.checkpoint_summary.sequence_number, 0);
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use arrow_array::RecordBatch;
+use object_store::ObjectStore;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+use super::{processor::processor, FailureConfig, NoDeadLetters, Processor, PIPELINE_BUFFER};
+
+use crate::{
+    metrics::IndexerMetrics, models::watermarks::CommitterWatermark, store::Store,
+    types::full_checkpoint_content::CheckpointData,
+};
+
+use self::writer::writer;
+
+mod writer;
+
+/// Handlers implement the logic for a file sink pipeline: How to process checkpoint data (by
+/// implementing [Processor]) into rows, and how to convert the rows from a range of checkpoints
+/// into an Arrow [RecordBatch], to be written out as a Parquet file.
+///
+/// Unlike other pipelines, the pipeline's data is not written to its [Store]: It is written to an
+/// object store, one file per range of checkpoints, and only the pipeline's watermark is written
+/// to the [Store], once the file covering the checkpoints up to the watermark has been uploaded.
+pub trait Handler: Processor {
+    /// The store that this pipeline writes its watermark to.
+    type Store: Store;
+
+    /// Convert `values` into a record batch, to be written to a single file. The values are from
+    /// a contiguous range of checkpoints, in checkpoint order, and there is always at least one
+    /// value.
+    fn record_batch(values: Vec<Self::Value>) -> anyhow::Result<RecordBatch>;
+}
+
+/// Configuration for a file sink pipeline.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileSinkConfig {
+    /// The writer will check for pending data at least this often, in milliseconds.
+    pub collect_interval_ms: u64,
+
+    /// The maximum number of checkpoints to write to a single file.
+    pub max_file_checkpoints: u64,
+
+    /// A file is closed as soon as it contains at least this many rows.
+    pub max_file_rows: usize,
+
+    /// A file is closed once this much time has passed since its first checkpoint was added to
+    /// it, in milliseconds, even if it has not reached its maximum size.
+    pub max_file_age_ms: u64,
+}
+
+impl FileSinkConfig {
+    pub fn collect_interval(&self) -> Duration {
+        Duration::from_millis(self.collect_interval_ms)
+    }
+
+    pub fn max_file_age(&self) -> Duration {
+        Duration::from_millis(self.max_file_age_ms)
+    }
+}
+
+impl Default for FileSinkConfig {
+    fn default() -> Self {
+        Self {
+            collect_interval_ms: 500,
+            max_file_checkpoints: 10_000,
+            max_file_rows: 1_000_000,
+            max_file_age_ms: 10 * 60 * 1000,
+        }
+    }
+}
+
+/// Start a new file sink pipeline, served by the handler, `H`, that writes Parquet files to
+/// `object_store`. Starting strictly after the `watermark` (or from the beginning if no watermark
+/// was provided).
+///
+/// Each pipeline consists of a processor which takes checkpoint data and breaks it down into rows,
+/// and a writer which orders the rows and gathers them into files covering contiguous ranges of
+/// checkpoints from the same epoch. Each file is written to
+/// `{H::NAME}/epoch_{epoch}/{first_checkpoint}_{last_checkpoint + 1}.parquet`, and the pipeline's
+/// watermark is only updated (unless `skip_watermark` is set) after the file has been uploaded.
+/// Ranges of checkpoints that produced no rows advance the watermark without writing a file.
+///
+/// If the pipeline stops after uploading a file, but before updating its watermark, it will write
+/// that range of checkpoints again the next time it runs, potentially to a file covering a
+/// different range (files can be closed early, once they reach `config.max_file_age()`). To avoid
+/// leaving overlapping files behind, the writer deletes any file beyond the watermark before it
+/// starts writing again (unless `skip_watermark` is set, in which case the watermark does not
+/// reflect the files this pipeline has written).
+///
+/// Checkpoint data is fed into the pipeline through the `checkpoint_rx` channel, progress is
+/// communicated to the ingestion service through the `watermark_tx` channel (as soon as checkpoints
+/// are added to a file, rather than once it has been uploaded) and internal
+/// channels are created to communicate between its various components. The pipeline can be
+/// shutdown using its `cancel` token, and will also shutdown if any of its input or output
+/// channels close, or any of its independent tasks fail.
+pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
+    handler: H,
+    initial_watermark: Option<CommitterWatermark<'static>>,
+    config: FileSinkConfig,
+    skip_watermark: bool,
+    store: H::Store,
+    object_store: Arc<dyn ObjectStore>,
+    checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    watermark_tx: mpsc::UnboundedSender<(&'static str, u64)>,
+    metrics: Arc<IndexerMetrics>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    let (processor_tx, writer_rx) = mpsc::channel(H::FANOUT + PIPELINE_BUFFER);
+
+    // Files must cover contiguous ranges of checkpoints, so processing failures always stop the
+    // pipeline.
+    let processor = processor(
+        Arc::new(handler),
+        FailureConfig::default(),
+        NoDeadLetters,
+        checkpoint_rx,
+        processor_tx,
+        metrics.clone(),
+        cancel.clone(),
+    );
+
+    let writer = writer::<H>(
+        config,
+        initial_watermark,
+        skip_watermark,
+        writer_rx,
+        watermark_tx,
+        store,
+        object_store,
+        metrics.clone(),
+        cancel.clone(),
+    );
+
+    tokio::spawn(async move {
+        let (_, _) = futures::join!(processor, writer);
+    })
+}
@@ -0,0 +1,679 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Ordering, collections::BTreeMap, sync::Arc};
+
+use anyhow::Context;
+use arrow_array::RecordBatch;
+use futures::TryStreamExt;
+use object_store::{path::Path, ObjectStore, PutPayload};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{interval, Instant, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    metrics::IndexerMetrics,
+    models::watermarks::CommitterWatermark,
+    pipeline::{logging::WatermarkLogger, IndexedCheckpoint, WARN_PENDING_WATERMARKS},
+    store::{Connection, Store},
+};
+
+use super::{FileSinkConfig, Handler};
+
+/// Rows gathered from a contiguous range of checkpoints in the same epoch, to be written to a
+/// single file.
+struct Batch<H: Handler> {
+    /// The first checkpoint in the batch.
+    first_checkpoint: u64,
+
+    /// The number of checkpoints in the batch.
+    checkpoints: u64,
+
+    /// When the first checkpoint was added to the batch.
+    opened: Instant,
+
+    /// The rows from all the checkpoints in the batch, in checkpoint order.
+    values: Vec<H::Value>,
+
+    /// The watermark associated with the last checkpoint in the batch.
+    watermark: CommitterWatermark<'static>,
+}
+
+/// A batch that has been encoded, and is waiting to be uploaded, before its watermark can be
+/// written.
+struct File {
+    /// Where to upload the file, and its contents. This is `None` if the batch did not contain
+    /// any rows, or once the file has been uploaded.
+    upload: Option<(Path, PutPayload)>,
+
+    /// The number of rows in the file.
+    rows: usize,
+
+    /// The watermark to write once the file has been uploaded.
+    watermark: CommitterWatermark<'static>,
+}
+
+/// The writer task gathers rows into files and uploads them to the object store.
+///
+/// Data arrives out of order, grouped by checkpoint, on `rx`. The task orders them and adds them
+/// to the next file, until the file contains `config.max_file_checkpoints` checkpoints or
+/// `config.max_file_rows` rows, the next checkpoint is from a different epoch, the file has been
+/// open for longer than `config.max_file_age()`, or there is no more data to come.
+///
+/// Once a file is closed, it is encoded as Parquet and uploaded to `object_store`, and only then
+/// is the pipeline's watermark written to `store` (unless `skip_watermark` is set). Failed writes
+/// are retried every `config.collect_interval()` until they succeed, and no more data is gathered
+/// in the meantime.
+///
+/// Before gathering any data, the task deletes any file that was uploaded beyond `watermark` (see
+/// [clear_uncommitted]), because the range of checkpoints it covers is about to be written again,
+/// and the new file may cover a different range.
+///
+/// As checkpoints are added to the next file, the task reports its progress back to the ingestion
+/// service, to unblock its regulator. This is separate from the watermark, which only moves once
+/// the file has been uploaded: Files can cover more checkpoints than the ingestion service buffers,
+/// so waiting for the upload would stop ingestion before the file is full. Memory is still bounded,
+/// because the open file is bounded, and no more checkpoints are added to it while the previous
+/// file is waiting to be written.
+///
+/// The task can be shutdown using its `cancel` token or if either of its channels are closed. It
+/// stops the whole pipeline (by cancelling `cancel`) if the handler fails to convert a batch of
+/// rows into a record batch.
+pub(super) fn writer<H: Handler + 'static>(
+    config: FileSinkConfig,
+    watermark: Option<CommitterWatermark<'static>>,
+    skip_watermark: bool,
+    mut rx: mpsc::Receiver<IndexedCheckpoint<H>>,
+    tx: mpsc::UnboundedSender<(&'static str, u64)>,
+    store: H::Store,
+    object_store: Arc<dyn ObjectStore>,
+    metrics: Arc<IndexerMetrics>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // The `poll` interval controls the maximum time to wait between attempts to gather and
+        // write files.
+        let mut poll = interval(config.collect_interval());
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // The batch being gathered for the next file, and the file waiting to be written, if
+        // there is one. New data is only gathered once the previous file has been written.
+        // Attempt is incremented every time a file fails to be written, and is reset when it
+        // succeeds.
+        let mut attempt = 0;
+        let mut batch: Option<Batch<H>> = None;
+        let mut file: Option<File> = None;
+
+        if !skip_watermark {
+            loop {
+                let cleared = clear_uncommitted::<H>(object_store.as_ref(), watermark.as_ref());
+                let Err(e) = cleared.await else {
+                    break;
+                };
+
+                warn!(
+                    pipeline = H::NAME,
+                    attempt, "Failed to clear uncommitted files: {e:#}",
+                );
+
+                attempt += 1;
+                tokio::select! {
+                    _ = cancel.cancelled() => {
+                        info!(pipeline = H::NAME, "Shutdown received");
+                        return;
+                    }
+
+                    _ = poll.tick() => {}
+                }
+            }
+
+            attempt = 0;
+        }
+
+        let (watermark, mut next_checkpoint) = if let Some(watermark) = watermark {
+            let next = watermark.checkpoint_hi_inclusive as u64 + 1;
+            (watermark, next)
+        } else {
+            (CommitterWatermark::initial(H::NAME.into()), 0)
+        };
+
+        // The writer task will periodically output a log message at a higher log level to
+        // demonstrate that the pipeline is making progress.
+        let mut logger = WatermarkLogger::new("file_sink_writer", &watermark);
+
+        // Data for checkpoints that haven't been added to a batch yet.
+        let mut pending: BTreeMap<u64, IndexedCheckpoint<H>> = BTreeMap::new();
+
+        info!(pipeline = H::NAME, ?watermark, "Starting writer");
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    info!(pipeline = H::NAME, "Shutdown received");
+                    break;
+                }
+
+                _ = poll.tick() => {
+                    // No more data will be added to the open batch if the processor is done and
+                    // the next checkpoint will never arrive.
+                    let exhausted = rx.is_closed()
+                        && rx.is_empty()
+                        && !can_process_pending(next_checkpoint, &pending);
+
+                    if exhausted && batch.is_none() && file.is_none() {
+                        info!(
+                            pipeline = H::NAME,
+                            "Process closed channel and no more data to write",
+                        );
+                        break;
+                    }
+
+                    if pending.len() > WARN_PENDING_WATERMARKS {
+                        warn!(
+                            pipeline = H::NAME,
+                            pending = pending.len(),
+                            "Pipeline has a large number of pending watermarks",
+                        );
+                    }
+
+                    if file.is_none() {
+                        let guard = metrics
+                            .collector_gather_latency
+                            .with_label_values(&[H::NAME])
+                            .start_timer();
+
+                        // Add data to the batch as long as it's from contiguous checkpoints, and
+                        // the batch is not full yet.
+                        let gathered_from = next_checkpoint;
+                        let mut full = false;
+                        while let Some(entry) = pending.first_entry() {
+                            match next_checkpoint.cmp(entry.key()) {
+                                // Next pending checkpoint is from the future.
+                                Ordering::Less => break,
+
+                                // This is the next checkpoint -- include it, unless it starts a
+                                // new epoch, because files do not span epochs.
+                                Ordering::Equal => {
+                                    let epoch = entry.get().watermark.epoch_hi_inclusive;
+                                    if batch
+                                        .as_ref()
+                                        .is_some_and(|b| b.watermark.epoch_hi_inclusive != epoch)
+                                    {
+                                        full = true;
+                                        break;
+                                    }
+
+                                    let indexed = entry.remove();
+                                    let b = batch.get_or_insert_with(|| Batch::new(&indexed));
+                                    b.add(indexed);
+                                    next_checkpoint += 1;
+
+                                    if b.checkpoints >= config.max_file_checkpoints
+                                        || b.values.len() >= config.max_file_rows
+                                    {
+                                        full = true;
+                                        break;
+                                    }
+                                }
+
+                                // Next pending checkpoint is in the past, ignore it to avoid
+                                // double writes.
+                                Ordering::Greater => {
+                                    metrics
+                                        .total_watermarks_out_of_order
+                                        .with_label_values(&[H::NAME])
+                                        .inc();
+
+                                    entry.remove();
+                                }
+                            }
+                        }
+
+                        let elapsed = guard.stop_and_record();
+                        debug!(
+                            pipeline = H::NAME,
+                            elapsed_ms = elapsed * 1000.0,
+                            rows = batch.as_ref().map_or(0, |b| b.values.len()),
+                            pending = pending.len(),
+                            "Gathered batch",
+                        );
+
+                        // Ignore the result -- the ingestion service will close this channel
+                        // once it is done, but there may still be checkpoints buffered that need
+                        // processing.
+                        if next_checkpoint > gathered_from {
+                            let _ = tx.send((H::NAME, next_checkpoint - 1));
+                        }
+
+                        let close = full
+                            || exhausted
+                            || batch
+                                .as_ref()
+                                .is_some_and(|b| b.opened.elapsed() >= config.max_file_age());
+
+                        if let Some(b) = batch.take_if(|_| close) {
+                            metrics
+                                .collector_batch_size
+                                .with_label_values(&[H::NAME])
+                                .observe(b.values.len() as f64);
+
+                            match b.encode() {
+                                Ok(encoded) => file = Some(encoded),
+                                Err(e) => {
+                                    error!(pipeline = H::NAME, "Failed to encode file: {e:#}");
+                                    cancel.cancel();
+                                    break;
+                                }
+                            }
+                        }
+                    }
+
+                    let Some(f) = &mut file else {
+                        continue;
+                    };
+
+                    metrics
+                        .total_committer_batches_attempted
+                        .with_label_values(&[H::NAME])
+                        .inc();
+
+                    metrics
+                        .watermark_epoch
+                        .with_label_values(&[H::NAME])
+                        .set(f.watermark.epoch_hi_inclusive);
+
+                    metrics
+                        .watermark_checkpoint
+                        .with_label_values(&[H::NAME])
+                        .set(f.watermark.checkpoint_hi_inclusive);
+
+                    metrics
+                        .watermark_transaction
+                        .with_label_values(&[H::NAME])
+                        .set(f.watermark.tx_hi);
+
+                    metrics
+                        .watermark_timestamp_ms
+                        .with_label_values(&[H::NAME])
+                        .set(f.watermark.timestamp_ms_hi_inclusive);
+
+                    let guard = metrics
+                        .committer_commit_latency
+                        .with_label_values(&[H::NAME])
+                        .start_timer();
+
+                    let written = f.write(skip_watermark, &store, object_store.as_ref()).await;
+                    let elapsed = guard.stop_and_record();
+
+                    if let Err(e) = written {
+                        warn!(
+                            pipeline = H::NAME,
+                            elapsed_ms = elapsed * 1000.0,
+                            attempt,
+                            rows = f.rows,
+                            "Error writing file: {e:#}",
+                        );
+
+                        metrics
+                            .total_committer_batches_failed
+                            .with_label_values(&[H::NAME])
+                            .inc();
+
+                        attempt += 1;
+                        continue;
+                    }
+
+                    debug!(pipeline = H::NAME, attempt, rows = f.rows, "Wrote file");
+                    logger.log::<H>(&f.watermark, elapsed);
+
+                    metrics
+                        .total_committer_batches_succeeded
+                        .with_label_values(&[H::NAME])
+                        .inc();
+
+                    metrics
+                        .total_committer_rows_committed
+                        .with_label_values(&[H::NAME])
+                        .inc_by(f.rows as u64);
+
+                    if !skip_watermark {
+                        metrics
+                            .watermark_epoch_in_db
+                            .with_label_values(&[H::NAME])
+                            .set(f.watermark.epoch_hi_inclusive);
+
+                        metrics
+                            .watermark_checkpoint_in_db
+                            .with_label_values(&[H::NAME])
+                            .set(f.watermark.checkpoint_hi_inclusive);
+
+                        metrics
+                            .watermark_transaction_in_db
+                            .with_label_values(&[H::NAME])
+                            .set(f.watermark.tx_hi);
+
+                        metrics
+                            .watermark_timestamp_in_db_ms
+                            .with_label_values(&[H::NAME])
+                            .set(f.watermark.timestamp_ms_hi_inclusive);
+                    }
+
+                    file = None;
+                    attempt = 0;
+
+                    // If we could make more progress immediately, then schedule more work without
+                    // waiting.
+                    if can_process_pending(next_checkpoint, &pending) {
+                        poll.reset_immediately();
+                    }
+                }
+
+                Some(indexed) = rx.recv() => {
+                    metrics
+                        .total_collector_rows_received
+                        .with_label_values(&[H::NAME])
+                        .inc_by(indexed.len() as u64);
+
+                    pending.insert(indexed.checkpoint(), indexed);
+                }
+            }
+        }
+
+        info!(pipeline = H::NAME, "Stopping writer");
+    })
+}
+
+impl<H: Handler> Batch<H> {
+    /// An empty batch, that will start at the checkpoint that `indexed` is from.
+    fn new(indexed: &IndexedCheckpoint<H>) -> Self {
+        Self {
+            first_checkpoint: indexed.checkpoint(),
+            checkpoints: 0,
+            opened: Instant::now(),
+            values: vec![],
+            watermark: indexed.watermark.clone(),
+        }
+    }
+
+    /// Add the data from the next checkpoint to the batch.
+    fn add(&mut self, indexed: IndexedCheckpoint<H>) {
+        self.checkpoints += 1;
+        self.values.extend(indexed.values);
+        self.watermark = indexed.watermark;
+    }
+
+    /// Encode the batch as a Parquet file (if it contains any rows), ready to be uploaded to a path
+    /// that identifies the pipeline, epoch, and range of checkpoints it covers.
+    fn encode(self) -> anyhow::Result<File> {
+        let rows = self.values.len();
+        let upload = if self.values.is_empty() {
+            None
+        } else {
+            let epoch = self.watermark.epoch_hi_inclusive;
+            let lo = self.first_checkpoint;
+            let hi = self.watermark.checkpoint_hi_inclusive + 1;
+            let path = epoch_path::<H>(epoch).child(format!("{lo}_{hi}.parquet"));
+
+            let batch = H::record_batch(self.values)
+                .with_context(|| format!("Failed to convert rows for {path}"))?;
+
+            let bytes = to_parquet(&batch)
+                .with_context(|| format!("Failed to write Parquet for {path}"))?;
+
+            Some((path, bytes.into()))
+        };
+
+        Ok(File {
+            upload,
+            rows,
+            watermark: self.watermark,
+        })
+    }
+}
+
+impl File {
+    /// Upload the file, if it hasn't been uploaded already, and then write its watermark (unless
+    /// `skip_watermark` is set).
+    async fn write<S: Store>(
+        &mut self,
+        skip_watermark: bool,
+        store: &S,
+        object_store: &dyn ObjectStore,
+    ) -> anyhow::Result<()> {
+        if let Some((path, payload)) = &self.upload {
+            object_store
+                .put(path, payload.clone())
+                .await
+                .with_context(|| format!("Failed to upload {path}"))?;
+
+            self.upload = None;
+        }
+
+        if !skip_watermark {
+            let mut conn = store.connect().await?;
+            conn.set_committer_watermark(&self.watermark)
+                .await
+                .context("Failed to update watermark")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Delete the files in `object_store` that start after `watermark` (or all the pipeline's files, if
+/// it has no watermark): They were uploaded, but the pipeline stopped before it could write their
+/// watermark, so their checkpoints will be written again. Files are uploaded one at a time, and
+/// the watermark is written after each upload, so such a file can only be in the watermark's epoch,
+/// or the next one.
+async fn clear_uncommitted<H: Handler>(
+    object_store: &dyn ObjectStore,
+    watermark: Option<&CommitterWatermark<'_>>,
+) -> anyhow::Result<()> {
+    let (prefixes, next_checkpoint) = match watermark {
+        Some(w) => (
+            vec![
+                epoch_path::<H>(w.epoch_hi_inclusive),
+                epoch_path::<H>(w.epoch_hi_inclusive + 1),
+            ],
+            w.checkpoint_hi_inclusive as u64 + 1,
+        ),
+        None => (vec![Path::from(H::NAME)], 0),
+    };
+
+    for prefix in prefixes {
+        let files: Vec<_> = object_store
+            .list(Some(&prefix))
+            .try_collect()
+            .await
+            .with_context(|| format!("Failed to list {prefix}"))?;
+
+        for file in files {
+            let path = file.location;
+            let Some(lo) = path
+                .filename()
+                .and_then(|f| f.strip_suffix(".parquet"))
+                .and_then(|f| f.split_once('_'))
+                .and_then(|(lo, _)| lo.parse::<u64>().ok())
+            else {
+                continue;
+            };
+
+            if lo < next_checkpoint {
+                continue;
+            }
+
+            warn!(pipeline = H::NAME, %path, "Deleting file beyond watermark");
+            object_store
+                .delete(&path)
+                .await
+                .with_context(|| format!("Failed to delete {path}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The directory that the pipeline's files for `epoch` are written to.
+fn epoch_path<H: Handler>(epoch: i64) -> Path {
+    Path::from(H::NAME).child(format!("epoch_{epoch}"))
+}
+
+/// Serialize `batch` as a Snappy-compressed Parquet file.
+fn to_parquet(batch: &RecordBatch) -> anyhow::Result<Vec<u8>> {
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut bytes = vec![];
+    let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), Some(properties))?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(bytes)
+}
+
+/// Tests whether the first checkpoint in the `pending` buffer is at or before the
+/// `next_checkpoint` expected by the writer, meaning it can be processed immediately.
+fn can_process_pending<T>(next_checkpoint: u64, pending: &BTreeMap<u64, T>) -> bool {
+    pending
+        .first_key_value()
+        .is_some_and(|(&first, _)| first <= next_checkpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use arrow_array::{ArrayRef, UInt64Array};
+    use object_store::memory::InMemory;
+
+    use crate::{
+        ingestion::IngestionConfig, metrics::tests::test_metrics, pipeline::Processor,
+        store::MemStore, types::full_checkpoint_content::CheckpointData,
+    };
+
+    use super::*;
+
+    struct Checkpoint(u64);
+
+    struct TestHandler;
+
+    impl Processor for TestHandler {
+        const NAME: &'static str = "test";
+        type Value = Checkpoint;
+
+        fn process(&self, _: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            Ok(vec![])
+        }
+    }
+
+    impl Handler for TestHandler {
+        type Store = MemStore;
+
+        fn record_batch(values: Vec<Self::Value>) -> anyhow::Result<RecordBatch> {
+            let cps: Vec<_> = values.into_iter().map(|Checkpoint(cp)| cp).collect();
+            Ok(RecordBatch::try_from_iter([(
+                "cp_sequence_number",
+                Arc::new(UInt64Array::from(cps)) as ArrayRef,
+            )])?)
+        }
+    }
+
+    /// With the default configs, a file can cover more checkpoints than the ingestion service
+    /// buffers ahead of the pipeline, so the writer needs to report its progress before the file
+    /// is uploaded, to avoid stalling ingestion.
+    #[tokio::test]
+    async fn test_progress_reported_before_upload() {
+        let buffer_size = IngestionConfig::default().checkpoint_buffer_size as u64;
+        let config = FileSinkConfig::default();
+        assert!(config.max_file_checkpoints > buffer_size);
+
+        let (processor_tx, processor_rx) = mpsc::channel(10);
+        let (watermark_tx, mut watermark_rx) = mpsc::unbounded_channel();
+        let object_store = Arc::new(InMemory::new());
+        let cancel = CancellationToken::new();
+
+        let _writer = writer::<TestHandler>(
+            config,
+            None,
+            false,
+            processor_rx,
+            watermark_tx,
+            MemStore::default(),
+            object_store.clone(),
+            test_metrics(),
+            cancel.clone(),
+        );
+
+        let checkpoints = buffer_size * 2;
+        let sender = tokio::spawn(async move {
+            for cp in 0..checkpoints {
+                let indexed = IndexedCheckpoint::new(0, cp, cp, cp, vec![Checkpoint(cp)]);
+                processor_tx.send(indexed).await.unwrap();
+            }
+
+            // Keep the channel open, so that the writer does not close the file early.
+            processor_tx
+        });
+
+        let progress = tokio::time::timeout(Duration::from_secs(10), async {
+            while let Some((_, hi)) = watermark_rx.recv().await {
+                if hi + 1 == checkpoints {
+                    return;
+                }
+            }
+        });
+
+        progress.await.expect("Writer did not report its progress");
+
+        // The file is still open, so nothing has been uploaded yet.
+        let paths: Vec<_> = object_store.list(None).try_collect().await.unwrap();
+        assert!(paths.is_empty());
+
+        cancel.cancel();
+        drop(sender.await.unwrap());
+    }
+
+    /// A file that was uploaded, but whose watermark was not written, is deleted when the writer
+    /// starts, so that writing its checkpoints again does not produce overlapping files.
+    #[tokio::test]
+    async fn test_uncommitted_files_cleared() {
+        let object_store = Arc::new(InMemory::new());
+        let committed = Path::from("test/epoch_0/0_10.parquet");
+        let uncommitted = Path::from("test/epoch_0/10_15.parquet");
+        for path in [&committed, &uncommitted] {
+            object_store.put(path, vec![0u8].into()).await.unwrap();
+        }
+
+        let (_processor_tx, processor_rx) = mpsc::channel(10);
+        let (watermark_tx, _watermark_rx) = mpsc::unbounded_channel();
+        let cancel = CancellationToken::new();
+
+        let watermark = CommitterWatermark::new_for_testing("test", 9);
+        let _writer = writer::<TestHandler>(
+            FileSinkConfig::default(),
+            Some(watermark),
+            false,
+            processor_rx,
+            watermark_tx,
+            MemStore::default(),
+            object_store.clone(),
+            test_metrics(),
+            cancel.clone(),
+        );
+
+        let cleared = tokio::time::timeout(Duration::from_secs(10), async {
+            while object_store.head(&uncommitted).await.is_ok() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        cleared.await.expect("Uncommitted file was not deleted");
+        assert!(object_store.head(&committed).await.is_ok());
+        cancel.cancel();
+    }
+}
@@ -3,12 +3,20 @@
 
 use std::time::Duration;
 
+use anyhow::bail;
+use chrono::Utc;
 pub use processor::Processor;
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
-use crate::models::watermarks::CommitterWatermark;
+use crate::{
+    metrics::IndexerMetrics,
+    models::{dead_letters::StoredDeadLetter, watermarks::CommitterWatermark},
+    store::{DeadLetterConnection, Store},
+};
 
 pub mod concurrent;
+pub mod file_sink;
 mod logging;
 mod processor;
 pub mod sequential;
@@ -38,6 +46,37 @@ pub struct CommitterConfig {
     pub watermark_interval_ms: u64,
 }
 
+/// What a pipeline does when it cannot index a checkpoint: When the checkpoint fails to process,
+/// or the store rejects the rows it produced (see [is_permanent]). Failures that retrying can fix,
+/// like losing the connection to the store, are retried until they succeed, under every policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Stop the pipeline (and the indexer) on the first failure.
+    #[default]
+    Stop,
+
+    /// Retry with an exponential back-off, and stop the pipeline (and the indexer) if it is still
+    /// failing after the maximum number of retries.
+    RetryThenStop,
+
+    /// Retry like [FailurePolicy::RetryThenStop], but if it is still failing after the maximum
+    /// number of retries, record the checkpoints involved in the dead-letter table and skip them,
+    /// so that they can be replayed later.
+    RetryThenDeadLetter,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FailureConfig {
+    /// What to do when a checkpoint fails to be processed or committed.
+    pub policy: FailurePolicy,
+
+    /// How many times to retry a checkpoint that failed to process, or a batch that the store
+    /// rejected, before giving up on it. Only applies to the `retry_then_stop` and
+    /// `retry_then_dead_letter` policies.
+    pub max_retries: u64,
+}
+
 /// Processed values associated with a single checkpoint. This is an internal type used to
 /// communicate between the processor and the collector parts of the pipeline.
 struct IndexedCheckpoint<P: Processor> {
@@ -146,6 +185,15 @@ impl WatermarkPart {
     }
 }
 
+impl Default for FailureConfig {
+    fn default() -> Self {
+        Self {
+            policy: FailurePolicy::Stop,
+            max_retries: 10,
+        }
+    }
+}
+
 impl Default for CommitterConfig {
     fn default() -> Self {
         Self {
@@ -155,3 +203,121 @@ impl Default for CommitterConfig {
         }
     }
 }
+
+/// Whether `error`, from committing a batch, will happen again however many times the commit is
+/// retried, because the store rejected the rows themselves (e.g. they violate a constraint). Any
+/// other error, including losing the connection to the store, or failing to get a connection from
+/// its pool, could be transient, so it is never treated as permanent.
+fn is_permanent(error: &anyhow::Error) -> bool {
+    use diesel::result::{DatabaseErrorKind as K, Error as E};
+
+    error.chain().any(|e| {
+        matches!(
+            e.downcast_ref::<E>(),
+            Some(
+                E::DatabaseError(
+                    K::UniqueViolation
+                        | K::ForeignKeyViolation
+                        | K::NotNullViolation
+                        | K::CheckViolation,
+                    _,
+                ) | E::SerializationError(_)
+                    | E::QueryBuilderError(_)
+            )
+        )
+    })
+}
+
+/// Where the processor records the checkpoints that it skips because it could not process them
+/// (see [FailurePolicy::RetryThenDeadLetter]).
+#[async_trait::async_trait]
+trait DeadLetterTarget: Clone + Send + Sync + 'static {
+    /// Record `checkpoint` as a dead letter for `pipeline` (see [record_dead_letters]).
+    async fn record(
+        &self,
+        pipeline: &'static str,
+        stage: &'static str,
+        checkpoint: u64,
+        error: &anyhow::Error,
+        metrics: &IndexerMetrics,
+    ) -> anyhow::Result<usize>;
+}
+
+/// The target for pipelines that cannot skip checkpoints, and so never record dead letters (their
+/// failure policy never dead-letters).
+#[derive(Clone)]
+struct NoDeadLetters;
+
+#[async_trait::async_trait]
+impl DeadLetterTarget for NoDeadLetters {
+    async fn record(
+        &self,
+        pipeline: &'static str,
+        _stage: &'static str,
+        checkpoint: u64,
+        error: &anyhow::Error,
+        _metrics: &IndexerMetrics,
+    ) -> anyhow::Result<usize> {
+        bail!("Pipeline {pipeline} cannot skip checkpoint {checkpoint}: {error:#}");
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: Store> DeadLetterTarget for S
+where
+    for<'c> S::Connection<'c>: DeadLetterConnection,
+{
+    async fn record(
+        &self,
+        pipeline: &'static str,
+        stage: &'static str,
+        checkpoint: u64,
+        error: &anyhow::Error,
+        metrics: &IndexerMetrics,
+    ) -> anyhow::Result<usize> {
+        record_dead_letters(self, pipeline, stage, [checkpoint], error, metrics).await
+    }
+}
+
+/// Record `checkpoints` as dead letters for `pipeline` in `store`: They could not be indexed
+/// because of `error` (encountered at `stage` of the pipeline), and are being skipped.
+async fn record_dead_letters<S: Store>(
+    store: &S,
+    pipeline: &'static str,
+    stage: &'static str,
+    checkpoints: impl IntoIterator<Item = u64>,
+    error: &anyhow::Error,
+    metrics: &IndexerMetrics,
+) -> anyhow::Result<usize>
+where
+    for<'c> S::Connection<'c>: DeadLetterConnection,
+{
+    let mut conn = store.connect().await?;
+    let created_at = Utc::now().naive_utc();
+
+    let mut recorded = 0;
+    for checkpoint in checkpoints {
+        error!(
+            pipeline,
+            stage, checkpoint, "Skipping checkpoint, recording dead letter: {error:#}",
+        );
+
+        conn.add_dead_letter(&StoredDeadLetter {
+            pipeline: pipeline.to_owned(),
+            cp_sequence_number: checkpoint as i64,
+            stage: stage.to_owned(),
+            error: format!("{error:#}"),
+            created_at,
+        })
+        .await?;
+
+        metrics
+            .total_dead_letters
+            .with_label_values(&[pipeline, stage])
+            .inc();
+
+        recorded += 1;
+    }
+
+    Ok(recorded)
+}
@@ -1,13 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
@@ -15,7 +15,14 @@ use crate::{
     task::TrySpawnStreamExt,
 };
 
-use super::IndexedCheckpoint;
+use super::{DeadLetterTarget, FailureConfig, FailurePolicy, IndexedCheckpoint};
+
+/// If the processor needs to retry processing a checkpoint, it will wait this long initially.
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// If the processor needs to retry processing a checkpoint, it will wait at most this long between
+/// retries.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Implementors of this trait are responsible for transforming checkpoint into rows for their
 /// table. The `FANOUT` associated value controls how many concurrent workers will be used to
@@ -41,10 +48,16 @@ pub trait Processor {
 /// Each worker processes a checkpoint into rows and sends them on to the committer using the `tx`
 /// channel.
 ///
-/// The task will shutdown if the `cancel` token is cancelled, or if any of the workers encounters
-/// an error -- there is no retry logic at this level.
-pub(super) fn processor<P: Processor + Send + Sync + 'static>(
+/// If a checkpoint fails to process, the worker follows the `failure` policy: It either gives up
+/// straight away, or retries processing the checkpoint. If retries are exhausted, it either gives
+/// up, or records the checkpoint as a dead letter in `dead_letters` and sends on no rows for it.
+///
+/// The task will shutdown if the `cancel` token is cancelled, or if any of the workers gives up on
+/// a checkpoint.
+pub(super) fn processor<P: Processor + Send + Sync + 'static, D: DeadLetterTarget>(
     processor: Arc<P>,
+    failure: FailureConfig,
+    dead_letters: D,
     rx: mpsc::Receiver<Arc<CheckpointData>>,
     tx: mpsc::Sender<IndexedCheckpoint<P>>,
     metrics: Arc<IndexerMetrics>,
@@ -65,6 +78,8 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                 let cancel = cancel.clone();
                 let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                 let processor = processor.clone();
+                let failure = failure.clone();
+                let dead_letters = dead_letters.clone();
 
                 async move {
                     if cancel.is_cancelled() {
@@ -81,7 +96,22 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                         .with_label_values(&[P::NAME])
                         .start_timer();
 
-                    let values = processor.process(&checkpoint)?;
+                    let values = match processor.process(&checkpoint) {
+                        Ok(values) => values,
+                        Err(e) => {
+                            recover(
+                                &*processor,
+                                &checkpoint,
+                                e,
+                                &failure,
+                                &dead_letters,
+                                &metrics,
+                                &cancel,
+                            )
+                            .await?
+                        }
+                    };
+
                     let elapsed = guard.stop_and_record();
 
                     let epoch = checkpoint.checkpoint_summary.epoch;
@@ -138,3 +168,58 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
         };
     })
 }
+
+/// Handle `error` from processing `checkpoint`, according to the `failure` policy. Either gives up
+/// straight away, or retries processing the checkpoint with an exponential back-off. If the
+/// retries are exhausted, it either gives up, or records the checkpoint as a dead letter in
+/// `dead_letters`, and returns no values for it, so that the pipeline can carry on.
+async fn recover<P: Processor, D: DeadLetterTarget>(
+    processor: &P,
+    checkpoint: &Arc<CheckpointData>,
+    mut error: anyhow::Error,
+    failure: &FailureConfig,
+    dead_letters: &D,
+    metrics: &IndexerMetrics,
+    cancel: &CancellationToken,
+) -> Result<Vec<P::Value>, Break> {
+    let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+    if failure.policy == FailurePolicy::Stop {
+        return Err(Break::Err(error));
+    }
+
+    let mut interval = INITIAL_RETRY_INTERVAL;
+    for attempt in 1..=failure.max_retries {
+        warn!(
+            pipeline = P::NAME,
+            checkpoint = cp_sequence_number,
+            attempt,
+            "Failed to process checkpoint, retrying in {interval:?}: {error:#}",
+        );
+
+        tokio::select! {
+            _ = cancel.cancelled() => return Err(Break::Cancel),
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        match processor.process(checkpoint) {
+            Ok(values) => return Ok(values),
+            Err(e) => error = e,
+        }
+
+        interval = (interval * 2).min(MAX_RETRY_INTERVAL);
+    }
+
+    if failure.policy == FailurePolicy::RetryThenStop {
+        return Err(Break::Err(error.context(format!(
+            "Failed to process checkpoint {cp_sequence_number} after {} retries",
+            failure.max_retries,
+        ))));
+    }
+
+    dead_letters
+        .record(P::NAME, "process", cp_sequence_number, &error, metrics)
+        .await
+        .map_err(|e| Break::Err(e.context("Failed to record dead letter")))?;
+
+    Ok(vec![])
+}
@@ -7,12 +7,14 @@ use serde::{Deserialize, Serialize};
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 
-use super::{processor::processor, CommitterConfig, Processor, PIPELINE_BUFFER};
+use super::{
+    processor::processor, CommitterConfig, FailureConfig, NoDeadLetters, Processor, PIPELINE_BUFFER,
+};
 
 use crate::{
-    db::{self, Db},
     metrics::IndexerMetrics,
     models::watermarks::CommitterWatermark,
+    store::{Store, TransactionalStore},
     types::full_checkpoint_content::CheckpointData,
 };
 
@@ -22,7 +24,7 @@ mod committer;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
 /// implementing [Processor]) into rows for their table, how to combine multiple rows into a single
-/// DB operation, and then how to write those rows atomically to the database.
+/// DB operation, and then how to write those rows atomically to their [TransactionalStore].
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values).
@@ -39,6 +41,10 @@ mod committer;
 /// checkpoints that can be received before the next checkpoint.
 #[async_trait::async_trait]
 pub trait Handler: Processor {
+    /// The store that this pipeline writes its data and watermarks to. Sequential pipelines write
+    /// both in the same transaction.
+    type Store: TransactionalStore;
+
     /// If at least this many rows are pending, the committer will commit them eagerly.
     const MIN_EAGER_ROWS: usize = 50;
 
@@ -56,9 +62,12 @@ pub trait Handler: Processor {
     /// guaranteed to be presented to the batch in checkpoint order.
     fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>);
 
-    /// Take a batch of values and commit them to the database, returning the number of rows
+    /// Take a batch of values and commit them to the store, returning the number of rows
     /// affected.
-    async fn commit(batch: &Self::Batch, conn: &mut db::Connection<'_>) -> anyhow::Result<usize>;
+    async fn commit(
+        batch: &Self::Batch,
+        conn: &mut <Self::Store as Store>::Connection<'_>,
+    ) -> anyhow::Result<usize>;
 }
 
 /// Configuration for a sequential pipeline
@@ -100,7 +109,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     handler: H,
     initial_watermark: Option<CommitterWatermark<'static>>,
     config: SequentialConfig,
-    db: Db,
+    store: H::Store,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     watermark_tx: mpsc::UnboundedSender<(&'static str, u64)>,
     metrics: Arc<IndexerMetrics>,
@@ -108,8 +117,12 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 ) -> JoinHandle<()> {
     let (processor_tx, committer_rx) = mpsc::channel(H::FANOUT + PIPELINE_BUFFER);
 
+    // Sequential pipelines cannot skip checkpoints, so processing failures always stop the
+    // pipeline.
     let processor = processor(
         Arc::new(handler),
+        FailureConfig::default(),
+        NoDeadLetters,
         checkpoint_rx,
         processor_tx,
         metrics.clone(),
@@ -121,7 +134,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         initial_watermark,
         committer_rx,
         watermark_tx,
-        db.clone(),
+        store,
         metrics.clone(),
         cancel.clone(),
     );
@@ -7,6 +7,39 @@ diesel::table! {
         cp_sequence_number -> Int8,
         tx_lo -> Int8,
         epoch -> Int8,
+        timestamp_ms -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    dead_letters (pipeline, cp_sequence_number) {
+        pipeline -> Text,
+        cp_sequence_number -> Int8,
+        stage -> Text,
+        error -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    pipeline_swaps (pipeline) {
+        pipeline -> Text,
+        replaced_by -> Text,
+        swapped_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    watermark_shards (pipeline, shard) {
+        pipeline -> Text,
+        shard -> Int8,
+        shards -> Int8,
+        epoch_hi_inclusive -> Int8,
+        checkpoint_hi_inclusive -> Int8,
+        tx_hi -> Int8,
+        timestamp_ms_hi_inclusive -> Int8,
+        lease_owner -> Text,
+        lease_expires_at -> Timestamp,
     }
 }
 
@@ -23,4 +56,10 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(cp_sequence_numbers, watermarks,);
+diesel::allow_tables_to_appear_in_same_query!(
+    cp_sequence_numbers,
+    dead_letters,
+    pipeline_swaps,
+    watermark_shards,
+    watermarks,
+);
@@ -0,0 +1,464 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::models::dead_letters::StoredDeadLetter;
+use crate::models::watermarks::{
+    CommitterWatermark, PrunerWatermark, ReaderWatermark, ShardLease, StoredShardWatermark,
+    StoredWatermark,
+};
+
+use super::{Connection, DeadLetterConnection, ShardedConnection, Store, TransactionalStore};
+
+/// An embedded store that keeps watermarks and pipeline data in memory. Handlers that write to
+/// this store access their data (of type `T`) through [MemConnection::data].
+///
+/// Connections hold an exclusive lock over the whole store, so writes are serialized, and
+/// transactions are implemented by restoring a snapshot of the store if they fail. This makes it
+/// unsuitable for production workloads, but useful for testing pipelines without a database.
+#[derive(Default)]
+pub struct MemStore<T = ()> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+pub struct MemConnection<'c, T> {
+    state: MutexGuard<'c, State<T>>,
+}
+
+#[derive(Clone, Default)]
+struct State<T> {
+    watermarks: BTreeMap<String, StoredWatermark>,
+    shards: BTreeMap<(String, u64), StoredShardWatermark>,
+    dead_letters: BTreeMap<(String, u64), StoredDeadLetter>,
+    data: T,
+}
+
+impl<T> MemStore<T> {
+    /// A new store, with no watermarks, wrapping `data`.
+    pub fn new(data: T) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                watermarks: BTreeMap::new(),
+                shards: BTreeMap::new(),
+                dead_letters: BTreeMap::new(),
+                data,
+            })),
+        }
+    }
+}
+
+impl<T> Clone for MemStore<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> MemConnection<'_, T> {
+    /// Access to the data that pipelines have written to the store.
+    pub fn data(&mut self) -> &mut T {
+        &mut self.state.data
+    }
+
+    /// The shard that `lease` is for, if `lease` is still held by its owner.
+    fn leased_shard(&mut self, lease: &ShardLease) -> Option<&mut StoredShardWatermark> {
+        self.state
+            .shards
+            .get_mut(&(lease.pipeline.to_owned(), lease.shard))
+            .filter(|s| s.lease_owner == lease.owner)
+    }
+}
+
+#[async_trait]
+impl<T: Send> Connection for MemConnection<'_, T> {
+    async fn watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<StoredWatermark>> {
+        Ok(self.state.watermarks.get(pipeline).cloned())
+    }
+
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>> {
+        Ok(self
+            .state
+            .watermarks
+            .get(pipeline)
+            .map(|w| CommitterWatermark {
+                pipeline: pipeline.into(),
+                epoch_hi_inclusive: w.epoch_hi_inclusive,
+                checkpoint_hi_inclusive: w.checkpoint_hi_inclusive,
+                tx_hi: w.tx_hi,
+                timestamp_ms_hi_inclusive: w.timestamp_ms_hi_inclusive,
+            }))
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>> {
+        let now = Utc::now().naive_utc();
+        Ok(self
+            .state
+            .watermarks
+            .get(pipeline)
+            .map(|w| PrunerWatermark {
+                pipeline: pipeline.into(),
+                wait_for: delay.as_millis() as i64 + (w.pruner_timestamp - now).num_milliseconds(),
+                reader_lo: w.reader_lo,
+                pruner_hi: w.pruner_hi,
+            }))
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.state.watermarks.get_mut(watermark.pipeline.as_ref()) else {
+            self.state.watermarks.insert(
+                watermark.pipeline.to_string(),
+                StoredWatermark::from(watermark.clone()),
+            );
+            return Ok(true);
+        };
+
+        if stored.checkpoint_hi_inclusive >= watermark.checkpoint_hi_inclusive {
+            return Ok(false);
+        }
+
+        stored.epoch_hi_inclusive = watermark.epoch_hi_inclusive;
+        stored.checkpoint_hi_inclusive = watermark.checkpoint_hi_inclusive;
+        stored.tx_hi = watermark.tx_hi;
+        stored.timestamp_ms_hi_inclusive = watermark.timestamp_ms_hi_inclusive;
+        Ok(true)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        watermark: &ReaderWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.state.watermarks.get_mut(watermark.pipeline.as_ref()) else {
+            return Ok(false);
+        };
+
+        if stored.reader_lo >= watermark.reader_lo {
+            return Ok(false);
+        }
+
+        stored.reader_lo = watermark.reader_lo;
+        stored.pruner_timestamp = Utc::now().naive_utc();
+        Ok(true)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        watermark: &PrunerWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.state.watermarks.get_mut(watermark.pipeline.as_ref()) else {
+            return Ok(false);
+        };
+
+        stored.pruner_hi = watermark.pruner_hi;
+        Ok(true)
+    }
+
+    async fn rewind_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.state.watermarks.get_mut(watermark.pipeline.as_ref()) else {
+            return Ok(false);
+        };
+
+        stored.epoch_hi_inclusive = watermark.epoch_hi_inclusive;
+        stored.checkpoint_hi_inclusive = watermark.checkpoint_hi_inclusive;
+        stored.tx_hi = watermark.tx_hi;
+        stored.timestamp_ms_hi_inclusive = watermark.timestamp_ms_hi_inclusive;
+        Ok(true)
+    }
+
+    async fn delete_watermark(&mut self, pipeline: &'static str) -> anyhow::Result<bool> {
+        Ok(self.state.watermarks.remove(pipeline).is_some())
+    }
+}
+
+#[async_trait]
+impl<T: Send> ShardedConnection for MemConnection<'_, T> {
+    async fn shard_watermarks(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Vec<StoredShardWatermark>> {
+        Ok(self
+            .state
+            .shards
+            .values()
+            .filter(|s| s.pipeline == pipeline)
+            .cloned()
+            .collect())
+    }
+
+    async fn acquire_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<Option<StoredShardWatermark>> {
+        let now = Utc::now().naive_utc();
+        let expires_at = now + ttl;
+
+        let key = (lease.pipeline.to_owned(), lease.shard);
+        if let Some(stored) = self.state.shards.get_mut(&key) {
+            if stored.lease_owner != lease.owner && stored.lease_expires_at >= now {
+                return Ok(None);
+            }
+
+            stored.lease_owner = lease.owner.clone();
+            stored.lease_expires_at = expires_at;
+            return Ok(Some(stored.clone()));
+        }
+
+        let start = self.state.watermarks.get(lease.pipeline);
+        let stored = StoredShardWatermark {
+            pipeline: lease.pipeline.to_owned(),
+            shard: lease.shard as i64,
+            shards: lease.shards as i64,
+            epoch_hi_inclusive: start.map_or(0, |w| w.epoch_hi_inclusive),
+            checkpoint_hi_inclusive: start.map_or(-1, |w| w.checkpoint_hi_inclusive),
+            tx_hi: start.map_or(0, |w| w.tx_hi),
+            timestamp_ms_hi_inclusive: start.map_or(0, |w| w.timestamp_ms_hi_inclusive),
+            lease_owner: lease.owner.clone(),
+            lease_expires_at: expires_at,
+        };
+
+        self.state.shards.insert(key, stored.clone());
+        Ok(Some(stored))
+    }
+
+    async fn renew_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.leased_shard(lease) else {
+            return Ok(false);
+        };
+
+        stored.lease_expires_at = Utc::now().naive_utc() + ttl;
+        Ok(true)
+    }
+
+    async fn release_shard_lease(&mut self, lease: &ShardLease) -> anyhow::Result<bool> {
+        let Some(stored) = self.leased_shard(lease) else {
+            return Ok(false);
+        };
+
+        stored.lease_expires_at = Utc::now().naive_utc();
+        Ok(true)
+    }
+
+    async fn set_shard_watermark(
+        &mut self,
+        lease: &ShardLease,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let Some(stored) = self.leased_shard(lease) else {
+            return Ok(false);
+        };
+
+        if stored.checkpoint_hi_inclusive >= watermark.checkpoint_hi_inclusive {
+            return Ok(false);
+        }
+
+        stored.epoch_hi_inclusive = watermark.epoch_hi_inclusive;
+        stored.checkpoint_hi_inclusive = watermark.checkpoint_hi_inclusive;
+        stored.tx_hi = watermark.tx_hi;
+        stored.timestamp_ms_hi_inclusive = watermark.timestamp_ms_hi_inclusive;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl<T: Send> DeadLetterConnection for MemConnection<'_, T> {
+    async fn dead_letters(
+        &mut self,
+        pipeline: Option<&str>,
+    ) -> anyhow::Result<Vec<StoredDeadLetter>> {
+        Ok(self
+            .state
+            .dead_letters
+            .values()
+            .filter(|d| pipeline.is_none_or(|p| d.pipeline == p))
+            .cloned()
+            .collect())
+    }
+
+    async fn add_dead_letter(&mut self, dead_letter: &StoredDeadLetter) -> anyhow::Result<()> {
+        let key = (
+            dead_letter.pipeline.clone(),
+            dead_letter.cp_sequence_number as u64,
+        );
+
+        self.state.dead_letters.insert(key, dead_letter.clone());
+        Ok(())
+    }
+
+    async fn delete_dead_letter(
+        &mut self,
+        pipeline: &str,
+        checkpoint: u64,
+    ) -> anyhow::Result<bool> {
+        let key = (pipeline.to_owned(), checkpoint);
+        Ok(self.state.dead_letters.remove(&key).is_some())
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Store for MemStore<T> {
+    type Connection<'c> = MemConnection<'c, T>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Ok(MemConnection {
+            state: self.state.lock().await,
+        })
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + 'static> TransactionalStore for MemStore<T> {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = self.connect().await?;
+        let snapshot = conn.state.clone();
+
+        let result = f(&mut conn).await;
+        if result.is_err() {
+            *conn.state = snapshot;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::scoped_futures::ScopedFutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_moves_forward() {
+        let store = MemStore::<()>::default();
+        let mut conn = store.connect().await.unwrap();
+
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+
+        let hi = CommitterWatermark::new_for_testing("p", 10);
+        assert!(conn.set_committer_watermark(&hi).await.unwrap());
+
+        let lo = CommitterWatermark::new_for_testing("p", 5);
+        assert!(!conn.set_committer_watermark(&lo).await.unwrap());
+
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 10);
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_pruner_watermarks() {
+        let store = MemStore::<()>::default();
+        let mut conn = store.connect().await.unwrap();
+
+        // The reader and pruner watermarks can only be set on an existing record.
+        let reader = ReaderWatermark::new("p", 5);
+        assert!(!conn.set_reader_watermark(&reader).await.unwrap());
+
+        let committer = CommitterWatermark::new_for_testing("p", 10);
+        conn.set_committer_watermark(&committer).await.unwrap();
+        assert!(conn.set_reader_watermark(&reader).await.unwrap());
+        assert!(!conn.set_reader_watermark(&reader).await.unwrap());
+
+        let pruner = PrunerWatermark::new_for_testing("p", 3);
+        assert!(conn.set_pruner_watermark(&pruner).await.unwrap());
+
+        // The reader watermark was just updated, so the pruner has to wait out (most of) the
+        // delay.
+        let delay = Duration::from_secs(60);
+        let watermark = conn.pruner_watermark("p", delay).await.unwrap().unwrap();
+        assert_eq!(watermark.reader_lo, 5);
+        assert_eq!(watermark.pruner_hi, 3);
+        assert!(watermark.wait_for().unwrap() <= delay);
+        assert!(watermark.wait_for().unwrap() > Duration::from_secs(50));
+    }
+
+    #[tokio::test]
+    async fn test_rewind_and_delete_watermark() {
+        let store = MemStore::<()>::default();
+        let mut conn = store.connect().await.unwrap();
+
+        // Rewinding requires an existing record.
+        let lo = CommitterWatermark::new_for_testing("p", 5);
+        assert!(!conn.rewind_committer_watermark(&lo).await.unwrap());
+
+        let hi = CommitterWatermark::new_for_testing("p", 10);
+        conn.set_committer_watermark(&hi).await.unwrap();
+        assert!(conn.rewind_committer_watermark(&lo).await.unwrap());
+
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 5);
+
+        assert!(conn.delete_watermark("p").await.unwrap());
+        assert!(!conn.delete_watermark("p").await.unwrap());
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_transaction_is_rolled_back() {
+        let store = MemStore::new(vec![1u64]);
+
+        let result: anyhow::Result<()> = store
+            .transaction(|conn| {
+                async move {
+                    let watermark = CommitterWatermark::new_for_testing("p", 10);
+                    conn.set_committer_watermark(&watermark).await?;
+                    conn.data().push(2);
+                    anyhow::bail!("Failed");
+                }
+                .scope_boxed()
+            })
+            .await;
+        assert!(result.is_err());
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+        assert_eq!(conn.data(), &vec![1]);
+        drop(conn);
+
+        store
+            .transaction(|conn| {
+                async move {
+                    conn.data().push(2);
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        assert_eq!(conn.data(), &vec![1, 2]);
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+
+use crate::models::dead_letters::StoredDeadLetter;
+use crate::models::watermarks::{
+    CommitterWatermark, PrunerWatermark, ReaderWatermark, ShardLease, StoredShardWatermark,
+    StoredWatermark,
+};
+
+pub use mem::{MemConnection, MemStore};
+
+mod mem;
+mod postgres;
+
+/// A connection to a [Store]. Pipelines use it to write their data (through their handler), and to
+/// read and update their watermarks.
+///
+/// Watermark updates are expected to be monotonic: each `set_*` method only moves its watermark
+/// forward (or leaves it unchanged), and reports whether it actually changed anything.
+#[async_trait]
+pub trait Connection: Send {
+    /// The pipeline's full watermark record, if it has one.
+    async fn watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<StoredWatermark>>;
+
+    /// The high watermark that the pipeline's committer last wrote, if it has one.
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>>;
+
+    /// The bounds of the region that the pipeline's pruner still has to prune, along with how long
+    /// the pruner needs to wait (given it must wait at least `delay` after the reader watermark
+    /// was last updated) before it can act on them.
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>>;
+
+    /// Upsert the pipeline's high watermark, as long as it raises the watermark that is currently
+    /// stored. Returns whether the watermark was updated.
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Raise the pipeline's reader low watermark, recording the time at which it changed. Returns
+    /// whether the watermark was updated.
+    async fn set_reader_watermark(
+        &mut self,
+        watermark: &ReaderWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Update the pipeline's pruner high watermark. Returns whether the watermark was updated.
+    async fn set_pruner_watermark(
+        &mut self,
+        watermark: &PrunerWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Move the pipeline's high watermark to `watermark`, even if that lowers it. Unlike the other
+    /// `set_*` methods, this is not monotonic: it is used to rewind a pipeline so that it
+    /// re-indexes checkpoints it has already committed. Returns whether the watermark was updated
+    /// (it is only updated if the pipeline already has a watermark record).
+    async fn rewind_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Remove the pipeline's watermark record altogether, so that it behaves as if it has never
+    /// run. Returns whether there was a record to remove.
+    async fn delete_watermark(&mut self, pipeline: &'static str) -> anyhow::Result<bool>;
+}
+
+/// A [Connection] to a store that can coordinate a pipeline that has been split into shards, so
+/// that it can be run by multiple indexer instances at once, each leasing one shard at a time.
+#[async_trait]
+pub trait ShardedConnection: Connection {
+    /// All the shards that the pipeline has been split into, if it is sharded, ordered by shard
+    /// index.
+    async fn shard_watermarks(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Vec<StoredShardWatermark>>;
+
+    /// Try to acquire `lease` for `ttl`. This succeeds if nobody holds a lease on the shard, its
+    /// lease has expired, or it is already held by the same owner. Shards that do not exist yet are
+    /// created, starting from the pipeline's committer watermark. Returns the shard if the lease
+    /// was acquired.
+    async fn acquire_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<Option<StoredShardWatermark>>;
+
+    /// Extend `lease` by `ttl` from now. Returns whether the lease was renewed (it is not renewed
+    /// if it is no longer held by its owner).
+    async fn renew_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<bool>;
+
+    /// Give up `lease`, so that another owner can acquire it immediately. Returns whether the
+    /// lease was released.
+    async fn release_shard_lease(&mut self, lease: &ShardLease) -> anyhow::Result<bool>;
+
+    /// Raise the committer watermark of the shard that `lease` is for, as long as `lease` is still
+    /// held by its owner. Returns whether the watermark was updated.
+    async fn set_shard_watermark(
+        &mut self,
+        lease: &ShardLease,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+}
+
+/// A [Connection] to a store that can record the checkpoints that pipelines failed to index and
+/// skipped (dead letters), so that they can be listed and replayed later.
+#[async_trait]
+pub trait DeadLetterConnection: Connection {
+    /// Dead letters recorded for `pipeline`, or for all pipelines if it is not provided, ordered by
+    /// pipeline and then checkpoint.
+    async fn dead_letters(
+        &mut self,
+        pipeline: Option<&str>,
+    ) -> anyhow::Result<Vec<StoredDeadLetter>>;
+
+    /// Record a checkpoint that a pipeline failed to index, replacing any existing record for the
+    /// same pipeline and checkpoint.
+    async fn add_dead_letter(&mut self, dead_letter: &StoredDeadLetter) -> anyhow::Result<()>;
+
+    /// Remove the record of `pipeline` failing to index `checkpoint`. Returns whether there was a
+    /// record to remove.
+    async fn delete_dead_letter(&mut self, pipeline: &str, checkpoint: u64)
+        -> anyhow::Result<bool>;
+}
+
+/// A storage backend that pipelines write their data and watermarks into. Handlers declare which
+/// store they write to, and receive a connection to it when committing and pruning.
+#[async_trait]
+pub trait Store: Clone + Send + Sync + 'static {
+    type Connection<'c>: Connection
+    where
+        Self: 'c;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>>;
+}
+
+/// A [Store] that supports running a sequence of writes atomically. Sequential pipelines require a
+/// transactional store, so that their data and watermark are always written together.
+#[async_trait]
+pub trait TransactionalStore: Store {
+    /// Run `f` against a fresh connection in a transaction: its writes are committed if it
+    /// succeeds, and discarded if it fails.
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>;
+}
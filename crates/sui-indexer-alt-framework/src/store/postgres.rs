@@ -0,0 +1,162 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel_async::{scoped_futures::ScopedBoxFuture, AsyncConnection};
+
+use crate::{
+    db::{self, Db},
+    models::{
+        dead_letters::StoredDeadLetter,
+        watermarks::{
+            CommitterWatermark, PrunerWatermark, ReaderWatermark, ShardLease, StoredShardWatermark,
+            StoredWatermark,
+        },
+    },
+};
+
+use super::{Connection, DeadLetterConnection, ShardedConnection, Store, TransactionalStore};
+
+#[async_trait]
+impl Connection for db::Connection<'_> {
+    async fn watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<StoredWatermark>> {
+        Ok(StoredWatermark::get(self, pipeline).await?)
+    }
+
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>> {
+        Ok(CommitterWatermark::get(self, pipeline).await?)
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>> {
+        Ok(PrunerWatermark::get(self, pipeline, delay).await?)
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(watermark.update(self).await?)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        watermark: &ReaderWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(watermark.update(self).await?)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        watermark: &PrunerWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(watermark.update(self).await?)
+    }
+
+    async fn rewind_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(watermark.rewind(self).await?)
+    }
+
+    async fn delete_watermark(&mut self, pipeline: &'static str) -> anyhow::Result<bool> {
+        Ok(StoredWatermark::delete(self, pipeline).await?)
+    }
+}
+
+#[async_trait]
+impl ShardedConnection for db::Connection<'_> {
+    async fn shard_watermarks(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Vec<StoredShardWatermark>> {
+        Ok(StoredShardWatermark::get_all(self, pipeline).await?)
+    }
+
+    async fn acquire_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<Option<StoredShardWatermark>> {
+        Ok(lease.acquire(self, ttl).await?)
+    }
+
+    async fn renew_shard_lease(
+        &mut self,
+        lease: &ShardLease,
+        ttl: Duration,
+    ) -> anyhow::Result<bool> {
+        Ok(lease.renew(self, ttl).await?)
+    }
+
+    async fn release_shard_lease(&mut self, lease: &ShardLease) -> anyhow::Result<bool> {
+        Ok(lease.release(self).await?)
+    }
+
+    async fn set_shard_watermark(
+        &mut self,
+        lease: &ShardLease,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(lease.update_watermark(self, watermark).await?)
+    }
+}
+
+#[async_trait]
+impl DeadLetterConnection for db::Connection<'_> {
+    async fn dead_letters(
+        &mut self,
+        pipeline: Option<&str>,
+    ) -> anyhow::Result<Vec<StoredDeadLetter>> {
+        Ok(StoredDeadLetter::get_all(self, pipeline).await?)
+    }
+
+    async fn add_dead_letter(&mut self, dead_letter: &StoredDeadLetter) -> anyhow::Result<()> {
+        dead_letter.upsert(self).await?;
+        Ok(())
+    }
+
+    async fn delete_dead_letter(
+        &mut self,
+        pipeline: &str,
+        checkpoint: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(StoredDeadLetter::delete(self, pipeline, checkpoint).await?)
+    }
+}
+
+#[async_trait]
+impl Store for Db {
+    type Connection<'c> = db::Connection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Db::connect(self).await
+    }
+}
+
+#[async_trait]
+impl TransactionalStore for Db {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = Db::connect(self).await?;
+        AsyncConnection::transaction(&mut conn, |conn| f(conn)).await
+    }
+}
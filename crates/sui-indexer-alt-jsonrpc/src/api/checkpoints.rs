@@ -3,8 +3,11 @@
 
 use anyhow::Context as _;
 
+use diesel::{ExpressionMethods, QueryDsl};
+use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_json_rpc_types::Checkpoint;
+use sui_indexer_alt_schema::schema::watermarks;
+use sui_json_rpc_types::{Checkpoint, Page as PageResponse};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::sui_serde::BigInt;
@@ -12,6 +15,7 @@ use sui_types::sui_serde::BigInt;
 use crate::{
     context::Context,
     error::{invalid_params, InternalContext, RpcError},
+    paginate::{Cursor as _, JsonCursor, Page},
 };
 
 use super::rpc_module::RpcModule;
@@ -26,14 +30,57 @@ trait CheckpointsApi {
         /// Checkpoint sequence number.
         seq: BigInt<u64>,
     ) -> RpcResult<Checkpoint>;
+
+    /// Return a paginated list of checkpoints.
+    ///
+    /// If a cursor is provided, the query will start from the checkpoint after the one pointed to
+    /// by this cursor, otherwise pagination starts from the first checkpoint (or the latest
+    /// checkpoint, if `descending_order` is set).
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(
+        &self,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of checkpoints to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence number.
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<Checkpoint, String>>;
+
+    /// Return the sequence number of the latest checkpoint that has been indexed.
+    #[method(name = "getLatestCheckpointSequenceNumber")]
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return the total number of transactions that have been indexed, as of the latest indexed
+    /// checkpoint.
+    #[method(name = "getTotalTransactionBlocks")]
+    async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>>;
 }
 
 pub(crate) struct Checkpoints(pub Context);
 
-#[derive(thiserror::Error, Debug, Clone)]
+#[derive(thiserror::Error, Debug)]
 enum Error {
     #[error("Checkpoint {0} not found")]
     NotFound(u64),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
+
+type Cursor = JsonCursor<u64>;
+
+/// The bounds of the data that the RPC can serve, based on the progress of the indexer.
+pub(super) struct Watermark {
+    /// The earliest checkpoint whose contents have not been pruned.
+    pub checkpoint_lo: u64,
+
+    /// The latest checkpoint that has been indexed, and whose contents can be loaded.
+    pub checkpoint_hi_inclusive: u64,
+
+    /// The number of transactions up to and including `checkpoint_hi_inclusive` (an exclusive
+    /// upper bound on transaction sequence numbers).
+    pub tx_hi: u64,
 }
 
 #[async_trait::async_trait]
@@ -44,6 +91,44 @@ impl CheckpointsApiServer for Checkpoints {
             format!("Failed to fetch checkpoint at sequence number {seq:?}")
         })?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<Checkpoint, String>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().checkpoints;
+
+        let page: Page<Cursor> = Page::from_params::<Error>(
+            config.default_page_size,
+            config.max_page_size,
+            cursor,
+            limit,
+            descending_order,
+        )?;
+
+        Ok(checkpoints(ctx, &page)
+            .await
+            .internal_context("Failed to fetch checkpoints")?)
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>> {
+        let Self(ctx) = self;
+        let Watermark {
+            checkpoint_hi_inclusive,
+            ..
+        } = watermark::<Error>(ctx).await?;
+
+        Ok(checkpoint_hi_inclusive.into())
+    }
+
+    async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>> {
+        let Self(ctx) = self;
+        let Watermark { tx_hi, .. } = watermark::<Error>(ctx).await?;
+        Ok(tx_hi.into())
+    }
 }
 
 impl RpcModule for Checkpoints {
@@ -67,3 +152,106 @@ async fn response(ctx: &Context, seq: u64) -> Result<Checkpoint, RpcError<Error>
 
     Ok(Checkpoint::from((summary, contents, signature.signature)))
 }
+
+/// Load a page of checkpoints. Checkpoint sequence numbers are contiguous, so the sequence numbers
+/// in the page are derived from the cursor and the range of checkpoints that are available (have
+/// been indexed and not yet pruned), and then loaded from the kv store.
+async fn checkpoints(
+    ctx: &Context,
+    page: &Page<Cursor>,
+) -> Result<PageResponse<Checkpoint, String>, RpcError<Error>> {
+    let Watermark {
+        checkpoint_lo: lo,
+        checkpoint_hi_inclusive: hi,
+        ..
+    } = watermark::<Error>(ctx).await?;
+
+    let limit = page.limit as usize;
+    let seqs: Vec<u64> = match (page.cursor.as_ref().map(|c| **c), page.descending) {
+        (None, false) => (lo..=hi).take(limit).collect(),
+        (Some(c), false) => (c.saturating_add(1).max(lo)..=hi).take(limit).collect(),
+        (None, true) => (lo..=hi).rev().take(limit).collect(),
+        (Some(c), true) => (lo..c.min(hi + 1)).rev().take(limit).collect(),
+    };
+
+    let has_next_page = match (seqs.last(), page.descending) {
+        (Some(&last), false) => last < hi,
+        (Some(&last), true) => last > lo,
+        (None, _) => false,
+    };
+
+    let next_cursor = seqs
+        .last()
+        .map(|last| JsonCursor(*last).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let data = future::join_all(seqs.into_iter().map(|seq| response(ctx, seq)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Fetch the bounds of the data that has been indexed. The upper bound is the lower of the
+/// watermarks for the `cp_sequence_numbers` pipeline, which tracks every checkpoint and how many
+/// transactions it contains, and the `kv_checkpoints` pipeline, which checkpoint contents are
+/// loaded from (so that every checkpoint in range can be served, even if one pipeline lags the
+/// other). The lower bound comes from the reader low watermark of the `kv_checkpoints` pipeline
+/// (checkpoint contents may have been pruned below it).
+pub(super) async fn watermark<E: std::error::Error>(
+    ctx: &Context,
+) -> Result<Watermark, RpcError<E>> {
+    use watermarks::dsl as w;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let query = w::watermarks
+        .select((
+            w::pipeline,
+            w::reader_lo,
+            w::checkpoint_hi_inclusive,
+            w::tx_hi,
+        ))
+        .filter(w::pipeline.eq_any(vec!["cp_sequence_numbers", "kv_checkpoints"]));
+
+    let watermarks: Vec<(String, i64, i64, i64)> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch watermarks")?;
+
+    let cp_sequence_numbers = watermarks
+        .iter()
+        .find(|(pipeline, _, _, _)| pipeline == "cp_sequence_numbers")
+        .context("No checkpoints have been indexed yet")?;
+
+    let kv_checkpoints = watermarks
+        .iter()
+        .find(|(pipeline, _, _, _)| pipeline == "kv_checkpoints")
+        .context("No checkpoint contents have been indexed yet")?;
+
+    // The transaction high watermark is taken from the same pipeline as the checkpoint high
+    // watermark, so that the two agree.
+    let (_, _, checkpoint_hi_inclusive, tx_hi) = if kv_checkpoints.2 < cp_sequence_numbers.2 {
+        kv_checkpoints
+    } else {
+        cp_sequence_numbers
+    };
+
+    let (_, checkpoint_lo, _, _) = kv_checkpoints;
+
+    Ok(Watermark {
+        checkpoint_lo: *checkpoint_lo as u64,
+        checkpoint_hi_inclusive: *checkpoint_hi_inclusive as u64,
+        tx_hi: *tx_hi as u64,
+    })
+}
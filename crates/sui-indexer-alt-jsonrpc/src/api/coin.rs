@@ -1,12 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use anyhow::Context as _;
+use diesel::pg::Pg;
 use diesel::prelude::*;
-use diesel::sql_types::Bool;
+use diesel::sql_query;
+use diesel::sql_types::{BigInt, Bool, Bytea, SmallInt, Text};
 use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use move_core_types::language_storage::{StructTag, TypeTag};
@@ -19,16 +21,18 @@ use sui_open_rpc_macros::open_rpc;
 use sui_sql_macro::sql;
 use sui_types::object::Object;
 use sui_types::{
+    balance::Supply,
     base_types::{ObjectID, SuiAddress},
-    gas_coin::GAS,
+    coin::TreasuryCap,
+    gas_coin::{GAS, TOTAL_SUPPLY_MIST},
 };
 
 use crate::data::singleton_object::load_singleton_object_id;
 use crate::{
     context::Context,
-    data::objects::load_latest,
+    data::objects::{load_at_checkpoint, load_latest, load_latest_deserialized},
     error::{invalid_params, InternalContext, RpcError},
-    paginate::{BcsCursor, Cursor as _, Page},
+    paginate::{checkpoint_snapshot, BcsCursor, Cursor as _, Page},
 };
 
 use super::rpc_module::RpcModule;
@@ -38,6 +42,10 @@ use super::rpc_module::RpcModule;
 trait CoinsApi {
     /// Return Coin objects owned by an address with a specified coin type.
     /// If no coin type is specified, SUI coins are returned.
+    ///
+    /// All pages are read as of the checkpoint at which the first page was fetched. If that
+    /// checkpoint is pruned before pagination finishes, the request fails, and pagination must
+    /// start again from the first page.
     #[method(name = "getCoins")]
     async fn get_coins(
         &self,
@@ -51,6 +59,32 @@ trait CoinsApi {
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>>;
 
+    /// Return all Coin objects owned by an address, regardless of their coin type.
+    ///
+    /// All pages are read as of the checkpoint at which the first page was fetched, as for
+    /// `suix_getCoins`.
+    #[method(name = "getAllCoins")]
+    async fn get_all_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>>;
+
+    /// Return the total coin balance for one coin type, owned by the address owner.
+    /// If no coin type is specified, the balance of SUI coins is returned.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
     /// Return the total coin balance for all coin types, owned by the address owner.
     #[method(name = "getAllBalances")]
     async fn get_all_balances(
@@ -68,6 +102,15 @@ trait CoinsApi {
         /// type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC)
         coin_type: String,
     ) -> RpcResult<Option<SuiCoinMetadata>>;
+
+    /// Return the total supply for a coin. Note that this relies on the coin's `TreasuryCap` being
+    /// a live object that is not wrapped in another object, otherwise it will not be found.
+    #[method(name = "getTotalSupply")]
+    async fn get_total_supply(
+        &self,
+        /// type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC)
+        coin_type: String,
+    ) -> RpcResult<Supply>;
 }
 
 pub(crate) struct Coins(pub Context);
@@ -79,6 +122,9 @@ pub(crate) enum Error {
 
     #[error("Failed to parse type {0:?}: {1}")]
     BadType(String, anyhow::Error),
+
+    #[error("Could not find TreasuryCap for coin type {0:?}")]
+    TreasuryCapNotFound(String),
 }
 
 #[derive(Queryable, Debug, Serialize, Deserialize)]
@@ -87,10 +133,23 @@ struct BalanceCursor {
     object_id: Vec<u8>,
     cp_sequence_number: u64,
     coin_balance_bucket: u64,
+
+    /// The checkpoint that pagination is pinned to.
+    checkpoint: u64,
 }
 
 type Cursor = BcsCursor<BalanceCursor>;
 
+/// The pipelines whose tables are read to find the coins owned by an address, and their contents
+/// at the checkpoint that coins are being read as of.
+const PIPELINES: &[&str] = &["coin_balance_buckets", "obj_versions"];
+
+/// The pipeline whose table is read to sum up the balances of the coins owned by an address.
+///
+/// `getBalance` and `getAllBalances` depend on the `coin_balance_history` pipeline: They fail with
+/// an internal error if the indexer writing to this service's database does not run it.
+const BALANCE_PIPELINES: &[&str] = &["coin_balance_history"];
+
 #[async_trait::async_trait]
 impl CoinsApiServer for Coins {
     async fn get_coins(
@@ -100,12 +159,7 @@ impl CoinsApiServer for Coins {
         cursor: Option<String>,
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>> {
-        let coin_type_tag = if let Some(coin_type) = coin_type {
-            sui_types::parse_sui_type_tag(&coin_type)
-                .map_err(|e| invalid_params(Error::BadType(coin_type, e)))?
-        } else {
-            GAS::type_tag()
-        };
+        let coin_type_tag = coin_type_or_gas(coin_type, Error::BadType)?;
 
         let Self(ctx) = self;
         let config = &ctx.config().coins;
@@ -119,59 +173,55 @@ impl CoinsApiServer for Coins {
         )?;
 
         // We get all the qualified coin ids first.
-        let coin_id_page = filter_coins(ctx, owner, Some(coin_type_tag), Some(page)).await?;
+        let (coin_id_page, checkpoint) =
+            filter_coins(ctx, owner, Some(coin_type_tag), Some(page)).await?;
+        Ok(coins_page(ctx, coin_id_page, checkpoint).await?)
+    }
+
+    async fn get_all_coins(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().coins;
 
-        let coin_futures = coin_id_page.data.iter().map(|id| coin_response(ctx, *id));
+        let page: Page<Cursor> = Page::from_params::<Error>(
+            config.default_page_size,
+            config.max_page_size,
+            cursor,
+            limit,
+            None,
+        )?;
 
-        let coins = future::join_all(coin_futures)
-            .await
-            .into_iter()
-            .zip(coin_id_page.data)
-            .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
-            .collect::<Result<Vec<_>, _>>()?;
+        let (coin_id_page, checkpoint) = filter_coins(ctx, owner, None, Some(page)).await?;
+        Ok(coins_page(ctx, coin_id_page, checkpoint).await?)
+    }
 
-        Ok(PageResponse {
-            data: coins,
-            next_cursor: coin_id_page.next_cursor,
-            has_next_page: coin_id_page.has_next_page,
-        })
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type_tag = coin_type_or_gas(coin_type, Error::BadType)?;
+
+        let Self(ctx) = self;
+        let coin_type = coin_type_tag.to_canonical_string(/* with_prefix */ true);
+        let mut balances = balances(ctx, owner, Some(coin_type_tag)).await?;
+
+        // An owner with no coins of the requested type is reported as having a zero balance.
+        Ok(balances.pop().unwrap_or_else(|| Balance {
+            coin_type,
+            coin_object_count: 0,
+            total_balance: 0,
+            locked_balance: HashMap::new(),
+        }))
     }
 
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
         let Self(ctx) = self;
-        let coin_ids = filter_coins(ctx, owner, None, None).await?;
-        let coin_futures = coin_ids
-            .data
-            .iter()
-            .map(|id| object_with_coin_data(ctx, *id));
-        let coins = future::join_all(coin_futures)
-            .await
-            .into_iter()
-            .zip(coin_ids.data)
-            .map(|(r, c)| {
-                let id = c;
-                r.with_internal_context(|| format!("Failed to get object {id}"))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Using a BTreeMap so that the ordering of keys is deterministic.
-        let mut balance_map: BTreeMap<String, (usize, u128)> = BTreeMap::new();
-        for (_, coin_type, balance) in coins {
-            let entry = balance_map.entry(coin_type).or_insert((0, 0));
-            entry.0 += 1;
-            entry.1 += balance as u128;
-        }
-        let balances: Vec<Balance> = balance_map
-            .into_iter()
-            .map(|(coin_type, (coin_object_count, total_balance))| Balance {
-                coin_type,
-                coin_object_count,
-                total_balance,
-                // LockedCoin is deprecated
-                locked_balance: HashMap::new(),
-            })
-            .collect();
-        Ok(balances)
+        Ok(balances(ctx, owner, None).await?)
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
@@ -186,6 +236,11 @@ impl CoinsApiServer for Coins {
 
         Ok(Some(coin_metadata))
     }
+
+    async fn get_total_supply(&self, coin_type: String) -> RpcResult<Supply> {
+        let Self(ctx) = self;
+        Ok(total_supply(ctx, coin_type).await?)
+    }
 }
 
 impl RpcModule for Coins {
@@ -198,14 +253,163 @@ impl RpcModule for Coins {
     }
 }
 
+/// Parse `coin_type` as a type tag, defaulting to SUI if it is not provided. Types that fail to
+/// parse are reported as invalid params, using `bad_type` to construct the error.
+pub(super) fn coin_type_or_gas<E: std::error::Error>(
+    coin_type: Option<String>,
+    bad_type: impl FnOnce(String, anyhow::Error) -> E,
+) -> Result<TypeTag, RpcError<E>> {
+    let Some(coin_type) = coin_type else {
+        return Ok(GAS::type_tag());
+    };
+
+    sui_types::parse_sui_type_tag(&coin_type).map_err(|e| invalid_params(bad_type(coin_type, e)))
+}
+
+/// Load the contents of the coins in `coin_id_page` as of `checkpoint`, to build a page of coin
+/// responses.
+async fn coins_page(
+    ctx: &Context,
+    coin_id_page: PageResponse<ObjectID, String>,
+    checkpoint: u64,
+) -> Result<PageResponse<Coin, String>, RpcError<Error>> {
+    let coin_futures = coin_id_page
+        .data
+        .iter()
+        .map(|id| coin_response(ctx, *id, checkpoint));
+
+    let coins = future::join_all(coin_futures)
+        .await
+        .into_iter()
+        .zip(coin_id_page.data)
+        .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data: coins,
+        next_cursor: coin_id_page.next_cursor,
+        has_next_page: coin_id_page.has_next_page,
+    })
+}
+
+/// Sum up the balances of all the coins owned by `owner`, grouped by coin type, optionally
+/// limited to coins of type `coin_type_tag`. Balances are summed in the database, from the latest
+/// row for each coin in `coin_balance_history`, as of the latest checkpoint with consistent data
+/// (`coin_balance_buckets` only records balances to the nearest bucket). They are returned in coin
+/// type order.
+async fn balances(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type_tag: Option<TypeTag>,
+) -> Result<Vec<Balance>, RpcError<Error>> {
+    #[derive(QueryableByName)]
+    struct StoredBalance {
+        #[diesel(sql_type = Bytea)]
+        coin_type: Vec<u8>,
+        #[diesel(sql_type = BigInt)]
+        coin_object_count: i64,
+        #[diesel(sql_type = Text)]
+        total_balance: String,
+    }
+
+    let checkpoint = checkpoint_snapshot(ctx, BALANCE_PIPELINES, None).await?;
+
+    // Balances are stored as signed integers with the same bit pattern as the unsigned balance, so
+    // negative values need to be shifted back into range before they are summed.
+    let mut query = sql_query(format!(
+        r#"
+        SELECT
+            c.coin_type,
+            COUNT(*) AS coin_object_count,
+            COALESCE(SUM(
+                c.balance::NUMERIC
+                + CASE WHEN c.balance < 0 THEN 18446744073709551616 ELSE 0 END
+            ), 0)::TEXT AS total_balance
+        FROM
+            coin_balance_history c
+        LEFT JOIN
+            coin_balance_history n
+        ON
+            c.object_id = n.object_id
+        AND c.cp_sequence_number < n.cp_sequence_number
+        AND n.cp_sequence_number <= $1
+        WHERE
+            n.object_id IS NULL
+        AND c.cp_sequence_number <= $1
+        AND c.owner_kind = $2
+        AND c.owner_id = $3
+        {}
+        GROUP BY
+            c.coin_type
+        "#,
+        if coin_type_tag.is_some() {
+            "AND c.coin_type = $4"
+        } else {
+            ""
+        },
+    ))
+    .into_boxed::<Pg>()
+    .bind::<BigInt, _>(checkpoint as i64)
+    .bind::<SmallInt, _>(StoredCoinOwnerKind::Fastpath as i16)
+    .bind::<Bytea, _>(owner.to_vec());
+
+    if let Some(coin_type_tag) = coin_type_tag {
+        let serialized_coin_type =
+            bcs::to_bytes(&coin_type_tag).context("Failed to serialize coin type tag")?;
+        query = query.bind::<Bytea, _>(serialized_coin_type);
+    }
+
+    let stored: Vec<StoredBalance> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?
+        .results(query)
+        .await
+        .context("Failed to sum balances")?;
+
+    let mut balances = stored
+        .into_iter()
+        .map(|b| {
+            let coin_type: TypeTag =
+                bcs::from_bytes(&b.coin_type).context("Failed to deserialize coin type")?;
+
+            Ok(Balance {
+                coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+                coin_object_count: b.coin_object_count as usize,
+                total_balance: b
+                    .total_balance
+                    .parse()
+                    .context("Failed to parse total balance")?,
+                // LockedCoin is deprecated
+                locked_balance: HashMap::new(),
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    balances.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+    Ok(balances)
+}
+
+/// Fetch the IDs of the coins owned by `owner`, optionally limited to coins of type
+/// `coin_type_tag`, and paginated according to `page` (if provided). Returns the IDs, along with
+/// the checkpoint that they were read as of: The checkpoint pinned by the page's cursor, or the
+/// latest checkpoint with consistent data, for the first page (or if no page was provided).
 async fn filter_coins(
     ctx: &Context,
     owner: SuiAddress,
     coin_type_tag: Option<TypeTag>,
     page: Option<Page<Cursor>>,
-) -> Result<PageResponse<ObjectID, String>, RpcError<Error>> {
+) -> Result<(PageResponse<ObjectID, String>, u64), RpcError<Error>> {
     use coin_balance_buckets::dsl as cb;
 
+    let pinned = page
+        .as_ref()
+        .and_then(|p| p.cursor.as_ref())
+        .map(|c| c.checkpoint);
+
+    let checkpoint = checkpoint_snapshot(ctx, PIPELINES, pinned).await?;
+
     let mut conn = ctx
         .pg_reader()
         .connect()
@@ -231,7 +435,8 @@ async fn filter_coins(
         };
     }
 
-    // Construct the basic query first to filter by owner, not deleted and newest rows.
+    // Construct the basic query first to filter by owner, not deleted and newest rows, as of the
+    // checkpoint being read.
     let mut query = candidates
         .select((
             candidates!(object_id),
@@ -241,9 +446,11 @@ async fn filter_coins(
         .left_join(
             newer.on(candidates!(object_id)
                 .eq(newer!(object_id))
-                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))),
+                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))
+                .and(newer!(cp_sequence_number).le(checkpoint as i64))),
         )
         .filter(newer!(object_id).is_null())
+        .filter(candidates!(cp_sequence_number).le(checkpoint as i64))
         .filter(candidates!(owner_kind).eq(StoredCoinOwnerKind::Fastpath))
         .filter(candidates!(owner_id).eq(owner.to_vec()))
         .into_boxed();
@@ -296,6 +503,7 @@ async fn filter_coins(
                 object_id: object_id.clone(),
                 cp_sequence_number: *cp_sequence_number as u64,
                 coin_balance_bucket: *coin_balance_bucket as u64,
+                checkpoint,
             })
             .encode()
         })
@@ -308,11 +516,13 @@ async fn filter_coins(
         .collect::<Result<Vec<_>, _>>()
         .context("Failed to parse object id")?;
 
-    Ok(PageResponse {
+    let page = PageResponse {
         data: ids,
         next_cursor,
         has_next_page,
-    })
+    };
+
+    Ok((page, checkpoint))
 }
 
 async fn coin_metadata_object_id(
@@ -329,8 +539,12 @@ async fn coin_metadata_object_id(
         .context("Failed to load singleton object id")?)
 }
 
-async fn coin_response(ctx: &Context, id: ObjectID) -> Result<Coin, RpcError<Error>> {
-    let (object, coin_type, balance) = object_with_coin_data(ctx, id).await?;
+async fn coin_response(
+    ctx: &Context,
+    id: ObjectID,
+    checkpoint: u64,
+) -> Result<Coin, RpcError<Error>> {
+    let (object, coin_type, balance) = object_with_coin_data(ctx, id, checkpoint).await?;
 
     let coin_object_id = object.id();
     let digest = object.digest();
@@ -361,13 +575,40 @@ async fn coin_metadata_response(
     Ok(coin_metadata)
 }
 
+async fn total_supply(ctx: &Context, coin_type: String) -> Result<Supply, RpcError<Error>> {
+    let coin_type_param = StructTag::from_str(&coin_type)
+        .map_err(|e| invalid_params(Error::BadType(coin_type.clone(), e)))?;
+
+    // SUI's supply is fixed, and its TreasuryCap is not a standalone object.
+    if GAS::is_gas(&coin_type_param) {
+        return Ok(Supply {
+            value: TOTAL_SUPPLY_MIST,
+        });
+    }
+
+    let Some(id) = load_singleton_object_id(ctx.pg_reader(), TreasuryCap::type_(coin_type_param))
+        .await
+        .context("Failed to load singleton object id")?
+    else {
+        return Err(invalid_params(Error::TreasuryCapNotFound(coin_type)));
+    };
+
+    let treasury_cap: TreasuryCap = load_latest_deserialized(ctx, id)
+        .await
+        .with_context(|| format!("Failed to load TreasuryCap {id}"))?;
+
+    Ok(treasury_cap.total_supply)
+}
+
+/// Load the contents of coin `id` as of `checkpoint`, along with its coin type and balance.
 async fn object_with_coin_data(
     ctx: &Context,
     id: ObjectID,
+    checkpoint: u64,
 ) -> Result<(Object, String, u64), RpcError<Error>> {
-    let object = load_latest(ctx, id)
+    let object = load_at_checkpoint(ctx, id, checkpoint)
         .await?
-        .ok_or_else(|| anyhow::anyhow!("Failed to load latest object {}", id))?;
+        .ok_or_else(|| anyhow::anyhow!("Failed to load object {id} at checkpoint {checkpoint}"))?;
 
     let coin = object
         .as_coin_maybe()
@@ -0,0 +1,275 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_indexer_alt_schema::{
+    epochs::{StoredEpochEnd, StoredEpochStart},
+    schema::{kv_epoch_ends, kv_epoch_starts},
+};
+use sui_json_rpc_types::{EndOfEpochInfo, EpochInfo, Page as PageResponse};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::sui_system_state::{SuiSystemState, SuiSystemStateTrait};
+
+use crate::{
+    context::Context,
+    error::{InternalContext, RpcError},
+    paginate::{Cursor as _, JsonCursor, Page},
+};
+
+use super::{
+    checkpoints::{watermark, Watermark},
+    rpc_module::RpcModule,
+};
+
+#[open_rpc(namespace = "suix", tag = "Extended API")]
+#[rpc(server, namespace = "suix")]
+trait EpochsApi {
+    /// Return a paginated list of epochs.
+    ///
+    /// If a cursor is provided, the query will start from the epoch after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first epoch (or the latest epoch, if
+    /// `descending_order` is set).
+    #[method(name = "getEpochs")]
+    async fn get_epochs(
+        &self,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of epochs to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by epoch.
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<EpochInfo, String>>;
+
+    /// Return information about the latest epoch that has started.
+    #[method(name = "getCurrentEpoch")]
+    async fn get_current_epoch(&self) -> RpcResult<EpochInfo>;
+}
+
+pub(crate) struct Epochs(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
+
+type Cursor = JsonCursor<u64>;
+
+#[async_trait::async_trait]
+impl EpochsApiServer for Epochs {
+    async fn get_epochs(
+        &self,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<EpochInfo, String>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().epochs;
+
+        let page: Page<Cursor> = Page::from_params::<Error>(
+            config.default_page_size,
+            config.max_page_size,
+            cursor,
+            limit,
+            descending_order,
+        )?;
+
+        Ok(epochs(ctx, &page)
+            .await
+            .internal_context("Failed to fetch epochs")?)
+    }
+
+    async fn get_current_epoch(&self) -> RpcResult<EpochInfo> {
+        let Self(ctx) = self;
+        Ok(current_epoch(ctx)
+            .await
+            .internal_context("Failed to fetch current epoch")?)
+    }
+}
+
+impl RpcModule for Epochs {
+    fn schema(&self) -> Module {
+        EpochsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load a page of epochs, starting from the epoch after the cursor.
+async fn epochs(
+    ctx: &Context,
+    page: &Page<Cursor>,
+) -> Result<PageResponse<EpochInfo, String>, RpcError<Error>> {
+    use kv_epoch_starts::dsl as e;
+
+    let mut query = e::kv_epoch_starts.into_boxed();
+
+    if let Some(JsonCursor(epoch)) = page.cursor {
+        if page.descending {
+            query = query.filter(e::epoch.lt(epoch as i64));
+        } else {
+            query = query.filter(e::epoch.gt(epoch as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(e::epoch.desc());
+    } else {
+        query = query.order(e::epoch.asc());
+    }
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut starts: Vec<StoredEpochStart> = conn
+        .results(query.limit(page.limit + 1))
+        .await
+        .context("Failed to fetch epoch starts")?;
+
+    let has_next_page = starts.len() > page.limit as usize;
+    if has_next_page {
+        starts.truncate(page.limit as usize);
+    }
+
+    let next_cursor = starts
+        .last()
+        .map(|last| JsonCursor(last.epoch as u64).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    Ok(PageResponse {
+        data: epoch_infos(ctx, starts).await?,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Load information about the latest epoch to have started.
+async fn current_epoch(ctx: &Context) -> Result<EpochInfo, RpcError<Error>> {
+    use kv_epoch_starts::dsl as e;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let start: StoredEpochStart = conn
+        .first(e::kv_epoch_starts.order(e::epoch.desc()))
+        .await
+        .context("Failed to fetch latest epoch start")?
+        .context("No epochs found")?;
+
+    Ok(epoch_infos(ctx, vec![start])
+        .await?
+        .pop()
+        .context("Failed to build current epoch")?)
+}
+
+/// Combine the start-of-epoch information in `starts` with the corresponding end-of-epoch
+/// information (if the epoch has ended), and the end of the preceding epoch (used to calculate
+/// the number of transactions in each epoch), preserving order.
+///
+/// Epochs that have not ended yet count their transactions up to the latest indexed checkpoint.
+async fn epoch_infos(
+    ctx: &Context,
+    starts: Vec<StoredEpochStart>,
+) -> Result<Vec<EpochInfo>, RpcError<Error>> {
+    use kv_epoch_ends::dsl as e;
+
+    let (Some(lo), Some(hi)) = (
+        starts.iter().map(|s| s.epoch).min(),
+        starts.iter().map(|s| s.epoch).max(),
+    ) else {
+        return Ok(vec![]);
+    };
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let ends: Vec<StoredEpochEnd> = conn
+        .results(e::kv_epoch_ends.filter(e::epoch.between(lo - 1, hi)))
+        .await
+        .context("Failed to fetch epoch ends")?;
+
+    let ends: HashMap<_, _> = ends.into_iter().map(|e| (e.epoch, e)).collect();
+
+    // Only fetch the watermark if it is needed to count the transactions in an ongoing epoch.
+    let tx_hi = if starts.iter().any(|s| !ends.contains_key(&s.epoch)) {
+        let Watermark { tx_hi, .. } = watermark::<Error>(ctx).await?;
+        Some(tx_hi)
+    } else {
+        None
+    };
+
+    starts
+        .into_iter()
+        .map(|start| {
+            let epoch = start.epoch;
+            epoch_info(start, ends.get(&epoch), ends.get(&(epoch - 1)), tx_hi)
+                .with_context(|| format!("Failed to build info for epoch {epoch}"))
+                .map_err(RpcError::from)
+        })
+        .collect()
+}
+
+/// Convert the start (and optionally, end) of an epoch into an `EpochInfo` response. The end of
+/// the previous epoch is used to calculate how many transactions the epoch contained, up to its
+/// end if it has ended, or up to `tx_hi` (the number of transactions indexed so far) otherwise.
+fn epoch_info(
+    start: StoredEpochStart,
+    end: Option<&StoredEpochEnd>,
+    prev: Option<&StoredEpochEnd>,
+    tx_hi: Option<u64>,
+) -> anyhow::Result<EpochInfo> {
+    let system_state: SuiSystemState =
+        bcs::from_bytes(&start.system_state).context("Failed to deserialize system state")?;
+    let summary = system_state.into_sui_system_state_summary();
+
+    let tx_lo = prev.map_or(0, |prev| prev.tx_hi as u64);
+    let epoch_total_transactions = match (end, tx_hi) {
+        (Some(end), _) => (end.tx_hi as u64).saturating_sub(tx_lo),
+        (None, Some(tx_hi)) => tx_hi.saturating_sub(tx_lo),
+        (None, None) => 0,
+    };
+
+    let end_of_epoch_info = end.map(|end| EndOfEpochInfo {
+        last_checkpoint_id: end.cp_hi as u64 - 1,
+        epoch_end_timestamp: end.end_timestamp_ms as u64,
+        protocol_version: start.protocol_version as u64,
+        reference_gas_price: start.reference_gas_price as u64,
+        total_stake: end.total_stake.unwrap_or_default() as u64,
+        storage_fund_reinvestment: end.storage_fund_reinvestment.unwrap_or_default() as u64,
+        storage_charge: end.storage_charge.unwrap_or_default() as u64,
+        storage_rebate: end.storage_rebate.unwrap_or_default() as u64,
+        storage_fund_balance: end.storage_fund_balance.unwrap_or_default() as u64,
+        stake_subsidy_amount: end.stake_subsidy_amount.unwrap_or_default() as u64,
+        total_gas_fees: end.total_gas_fees.unwrap_or_default() as u64,
+        total_stake_rewards_distributed: end.total_stake_rewards_distributed.unwrap_or_default()
+            as u64,
+        leftover_storage_fund_inflow: end.leftover_storage_fund_inflow.unwrap_or_default() as u64,
+    });
+
+    Ok(EpochInfo {
+        epoch: start.epoch as u64,
+        validators: summary.active_validators,
+        epoch_total_transactions,
+        first_checkpoint_id: start.cp_lo as u64,
+        epoch_start_timestamp: start.start_timestamp_ms as u64,
+        end_of_epoch_info,
+        reference_gas_price: Some(start.reference_gas_price as u64),
+    })
+}
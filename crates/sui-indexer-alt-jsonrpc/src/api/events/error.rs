@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("Invalid time range: start time {start_time} is after end time {end_time}")]
+    BadTimeRange { start_time: u64, end_time: u64 },
+
+    #[error(
+        "Time range starting at {start_time} may include checkpoints indexed without timestamps, \
+         up to checkpoint {checkpoint}"
+    )]
+    UnknownTimestamps { start_time: u64, checkpoint: u64 },
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
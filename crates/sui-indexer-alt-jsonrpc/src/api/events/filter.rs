@@ -0,0 +1,643 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{
+    expression::{
+        is_aggregate::{Never, No},
+        MixedAggregates, ValidGrouping,
+    },
+    pg::Pg,
+    query_builder::{BoxedSelectStatement, FromClause, QueryFragment},
+    sql_query,
+    sql_types::{BigInt as SqlBigInt, Nullable},
+    AppearsOnTable, Column, Expression, ExpressionMethods, QueryDsl, QuerySource, QueryableByName,
+};
+use futures::future;
+use move_core_types::language_storage::StructTag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sui_indexer_alt_schema::schema::{ev_emit_mod, ev_struct_inst, tx_digests};
+use sui_json_rpc_types::Page as PageResponse;
+use sui_sql_macro::sql;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    event::Event,
+    sui_serde::{BigInt, SuiStructTag},
+    Identifier,
+};
+
+use crate::{
+    context::Context,
+    data::tx_digests::TxDigestKey,
+    error::{invalid_params, RpcError},
+    paginate::{Cursor as _, JsonCursor, Page},
+};
+
+use super::error::Error;
+
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub(crate) enum EventFilter {
+    /// Query by sender address.
+    Sender(SuiAddress),
+    /// Return events emitted by the given transaction.
+    Transaction(TransactionDigest),
+    /// Return events emitted in a specified Move module. If the event is defined in Module A but
+    /// emitted in a transaction by Module B, querying `MoveModule` by module B returns the event.
+    MoveModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        module: Identifier,
+    },
+    /// Return events with the given Move event struct name (struct tag). For example, if the
+    /// event is defined in `0xabcd::MyModule`, and named `Foo`, then the struct tag is
+    /// `0xabcd::MyModule::Foo`.
+    MoveEventType(
+        #[serde_as(as = "SuiStructTag")]
+        #[schemars(with = "String")]
+        StructTag,
+    ),
+    /// Return events with the given Move module name where the event struct is defined. If the
+    /// event is defined in Module A but emitted in a transaction by Module B, querying
+    /// `MoveEventModule` by module A returns the event.
+    MoveEventModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        module: Identifier,
+    },
+    /// Return events emitted in the [start_time, end_time) interval.
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        /// Left endpoint of the time interval, milliseconds since epoch, inclusive.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        start_time: u64,
+        /// Right endpoint of the time interval, milliseconds since epoch, exclusive.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        end_time: u64,
+    },
+}
+
+/// An event that was selected by a filter, along with the information about the transaction that
+/// emitted it that is needed to render it.
+pub(super) struct FilteredEvent {
+    pub digest: TransactionDigest,
+    pub event_seq: u64,
+    pub timestamp_ms: u64,
+    pub event: Event,
+}
+
+/// Events are paginated by the transaction that emitted them, and then by their position within
+/// that transaction.
+#[derive(Clone, Serialize, Deserialize)]
+struct EventCursor {
+    tx_sequence_number: u64,
+    event_seq: u64,
+}
+
+type Cursor = JsonCursor<EventCursor>;
+type Events = PageResponse<FilteredEvent, String>;
+
+impl EventFilter {
+    /// Whether `event`, emitted by the transaction with digest `digest` at `timestamp_ms`, is
+    /// selected by this filter. The database tables used to implement event filters are indexed by
+    /// transaction, so this check is used to pick out the individual events within a transaction
+    /// that match the filter, and to filter events from newly indexed transactions for
+    /// subscriptions.
+    pub(crate) fn matches(
+        &self,
+        digest: &TransactionDigest,
+        timestamp_ms: u64,
+        event: &Event,
+    ) -> bool {
+        use EventFilter as F;
+        match self {
+            F::Sender(sender) => &event.sender == sender,
+            F::Transaction(tx) => tx == digest,
+            F::MoveModule { package, module } => {
+                &event.package_id == package && &event.transaction_module == module
+            }
+            F::MoveEventType(tag) => &event.type_ == tag,
+            F::MoveEventModule { package, module } => {
+                ObjectID::from(event.type_.address) == *package && &event.type_.module == module
+            }
+            F::TimeRange {
+                start_time,
+                end_time,
+            } => (*start_time..*end_time).contains(&timestamp_ms),
+        }
+    }
+}
+
+/// Fetch a page of events that satisfy the given `filter` and pagination parameters. Returns the
+/// events and a cursor pointing to the last result (if there are any results).
+///
+/// Events are found by first fetching the sequence numbers of transactions that emitted at least
+/// one matching event, and then loading those transactions' events and picking out the matching
+/// ones.
+pub(super) async fn events(
+    ctx: &Context,
+    filter: &EventFilter,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<Events, RpcError<Error>> {
+    let config = &ctx.config().events;
+    let page: Page<Cursor> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        descending_order,
+    )?;
+
+    use EventFilter as F;
+    let tx_sequence_numbers = match filter {
+        F::Sender(sender) => by_sender(ctx, &page, *sender).await?,
+
+        F::Transaction(digest) => by_transaction(ctx, &page, *digest).await?,
+
+        F::MoveModule { package, module } => by_emit_module(ctx, &page, *package, module).await?,
+
+        F::MoveEventType(tag) => by_struct_inst(ctx, &page, tag).await?,
+
+        F::MoveEventModule { package, module } => {
+            by_struct_module(ctx, &page, *package, module).await?
+        }
+
+        F::TimeRange {
+            start_time,
+            end_time,
+        } => by_time_range(ctx, &page, *start_time, *end_time).await?,
+    };
+
+    from_sequence_numbers(ctx, &page, filter, tx_sequence_numbers).await
+}
+
+/// Fetch the sequence numbers of transactions that emitted events from `sender`. The `DISTINCT`
+/// is served by the `ev_emit_sender` index, which orders a sender's rows by transaction.
+async fn by_sender(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    sender: SuiAddress,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use ev_emit_mod::dsl as e;
+
+    let query = e::ev_emit_mod
+        .select(e::tx_sequence_number)
+        .filter(e::sender.eq(sender.to_inner()))
+        .distinct()
+        .into_boxed();
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(page, "ev_emit_mod", e::tx_sequence_number, query))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// Fetch the sequence number of the transaction with the given `digest`, if it exists, and it
+/// falls within the page.
+async fn by_transaction(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    digest: TransactionDigest,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use tx_digests::dsl as d;
+
+    let query = d::tx_digests
+        .select(d::tx_sequence_number)
+        .filter(d::tx_digest.eq(digest.into_inner()))
+        .into_boxed();
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(page, "tx_digests", d::tx_sequence_number, query))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// Fetch the sequence numbers of transactions that emitted events from the given module (the
+/// module that was called into to emit the event, which is not necessarily the module that
+/// defines the event's type).
+async fn by_emit_module(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    package: ObjectID,
+    module: &Identifier,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use ev_emit_mod::dsl as e;
+
+    let query = e::ev_emit_mod
+        .select(e::tx_sequence_number)
+        .filter(e::package.eq(package.into_bytes()))
+        .filter(e::module.eq(module.as_str()))
+        .into_boxed();
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(page, "ev_emit_mod", e::tx_sequence_number, query))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// Fetch the sequence numbers of transactions that emitted events whose type is exactly `tag`.
+async fn by_struct_inst(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    tag: &StructTag,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use ev_struct_inst::dsl as e;
+
+    let instantiation =
+        bcs::to_bytes(&tag.type_params).context("Failed to serialize type params")?;
+
+    let query = e::ev_struct_inst
+        .select(e::tx_sequence_number)
+        .filter(e::package.eq(tag.address.into_bytes()))
+        .filter(e::module.eq(tag.module.as_str()))
+        .filter(e::name.eq(tag.name.as_str()))
+        .filter(e::instantiation.eq(instantiation))
+        .into_boxed();
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(
+            page,
+            "ev_struct_inst",
+            e::tx_sequence_number,
+            query,
+        ))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// Fetch the sequence numbers of transactions that emitted events whose types are defined in the
+/// given module.
+async fn by_struct_module(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    package: ObjectID,
+    module: &Identifier,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use ev_struct_inst::dsl as e;
+
+    let query = e::ev_struct_inst
+        .select(e::tx_sequence_number)
+        .filter(e::package.eq(package.into_bytes()))
+        .filter(e::module.eq(module.as_str()))
+        .distinct()
+        .into_boxed();
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(
+            page,
+            "ev_struct_inst",
+            e::tx_sequence_number,
+            query,
+        ))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// Fetch the sequence numbers of transactions that emitted events between `start_time`
+/// (inclusive) and `end_time` (exclusive). Timestamps are translated into transaction bounds
+/// using the timestamps recorded in the `cp_sequence_numbers` table.
+async fn by_time_range(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    start_time: u64,
+    end_time: u64,
+) -> Result<Vec<i64>, RpcError<Error>> {
+    use ev_emit_mod::dsl as e;
+
+    if start_time > end_time {
+        return Err(invalid_params(Error::BadTimeRange {
+            start_time,
+            end_time,
+        }));
+    }
+
+    let TxBounds {
+        tx_lo,
+        tx_hi,
+        unknown_cp_hi,
+        known_timestamp_lo,
+    } = tx_bounds(ctx, start_time, end_time).await?;
+
+    // Checkpoints indexed before timestamps were recorded can't be found by time, so reject any
+    // range that could reach them, rather than silently leaving them out.
+    if let Some(checkpoint) = unknown_cp_hi {
+        if known_timestamp_lo.is_none_or(|t| start_time as i64 <= t) {
+            return Err(invalid_params(Error::UnknownTimestamps {
+                start_time,
+                checkpoint: checkpoint as u64,
+            }));
+        }
+    }
+
+    // No checkpoint has been indexed at or after `start_time` yet.
+    let Some(tx_lo) = tx_lo else {
+        return Ok(vec![]);
+    };
+
+    let mut query = e::ev_emit_mod
+        .select(e::tx_sequence_number)
+        .filter(e::tx_sequence_number.ge(tx_lo))
+        .distinct()
+        .into_boxed();
+
+    // If no checkpoint has been indexed at or after `end_time`, the range is open-ended.
+    if let Some(tx_hi) = tx_hi {
+        if tx_lo >= tx_hi {
+            return Ok(vec![]);
+        }
+
+        query = query.filter(e::tx_sequence_number.lt(tx_hi));
+    }
+
+    Ok(ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(page, "ev_emit_mod", e::tx_sequence_number, query))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?)
+}
+
+/// The sequence numbers of the first transactions in the first checkpoints whose timestamps are
+/// at or after the start and end of a time range, if such checkpoints have been indexed.
+///
+/// Also the latest checkpoint whose timestamp is unknown (because it was indexed before timestamps
+/// were recorded), if any remain unpruned, and the timestamp of the checkpoint after it.
+#[derive(QueryableByName)]
+struct TxBounds {
+    #[diesel(sql_type = Nullable<SqlBigInt>)]
+    tx_lo: Option<i64>,
+    #[diesel(sql_type = Nullable<SqlBigInt>)]
+    tx_hi: Option<i64>,
+    #[diesel(sql_type = Nullable<SqlBigInt>)]
+    unknown_cp_hi: Option<i64>,
+    #[diesel(sql_type = Nullable<SqlBigInt>)]
+    known_timestamp_lo: Option<i64>,
+}
+
+/// Translate the time range `[start_time, end_time)` into transaction bounds, in a single query
+/// against the indices on checkpoint timestamps.
+async fn tx_bounds(
+    ctx: &Context,
+    start_time: u64,
+    end_time: u64,
+) -> Result<TxBounds, RpcError<Error>> {
+    let query = sql_query(
+        r#"
+        SELECT
+            (
+                SELECT
+                    tx_lo
+                FROM
+                    cp_sequence_numbers
+                WHERE
+                    timestamp_ms >= $1
+                ORDER BY
+                    timestamp_ms, tx_lo
+                LIMIT 1
+            ) AS tx_lo,
+            (
+                SELECT
+                    tx_lo
+                FROM
+                    cp_sequence_numbers
+                WHERE
+                    timestamp_ms >= $2
+                ORDER BY
+                    timestamp_ms, tx_lo
+                LIMIT 1
+            ) AS tx_hi,
+            u.cp_hi AS unknown_cp_hi,
+            (
+                SELECT
+                    timestamp_ms
+                FROM
+                    cp_sequence_numbers
+                WHERE
+                    cp_sequence_number = u.cp_hi + 1
+            ) AS known_timestamp_lo
+        FROM (
+            SELECT
+                MAX(cp_sequence_number) AS cp_hi
+            FROM
+                cp_sequence_numbers
+            WHERE
+                timestamp_ms IS NULL
+        ) u
+        "#,
+    )
+    .bind::<SqlBigInt, _>(start_time as i64)
+    .bind::<SqlBigInt, _>(end_time as i64);
+
+    let bounds: Vec<TxBounds> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch transaction bounds")?;
+
+    Ok(bounds
+        .into_iter()
+        .next()
+        .context("Failed to fetch transaction bounds")?)
+}
+
+/// Modify `query` to be paginated according to `page`, using `tx_sequence_number` as the column
+/// containing the sequence number. The query is also modified to limit results returned by the
+/// reader low-watermark from the `watermarks` table, similar to transaction queries.
+///
+/// Unlike transaction pagination, the transaction the cursor points to is included in the results,
+/// because it may contain further events that match the filter. The query fetches two more
+/// elements than the limit: one to account for the cursor's transaction (which may not contain any
+/// more matching events), and one to determine if there is a next page.
+fn paginate<'q, TX, ST, QS>(
+    page: &Page<Cursor>,
+    pipeline: &'static str,
+    tx_sequence_number: TX,
+    mut query: BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>,
+) -> BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>
+where
+    QS: QuerySource,
+    TX: Copy + Send + Sync + 'q,
+    TX: ValidGrouping<()> + QueryFragment<Pg>,
+    TX: Column<Table = QS> + AppearsOnTable<QS>,
+    TX: ExpressionMethods + Expression<SqlType = SqlBigInt>,
+    TX::IsAggregate: MixedAggregates<Never, Output = No>,
+{
+    query = query.filter(tx_sequence_number.ge(sql!(as SqlBigInt,
+        "COALESCE(
+            (
+                SELECT
+                    MAX(tx_lo)
+                FROM
+                    watermarks w
+                INNER JOIN
+                    cp_sequence_numbers c
+                ON
+                    w.reader_lo = c.cp_sequence_number
+                WHERE
+                    w.pipeline IN ({Text}, 'tx_digests')
+            ),
+            0
+        )",
+        pipeline,
+    )));
+
+    if let Some(c) = &page.cursor {
+        if page.descending {
+            query = query.filter(tx_sequence_number.le(c.tx_sequence_number as i64));
+        } else {
+            query = query.filter(tx_sequence_number.ge(c.tx_sequence_number as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(tx_sequence_number.desc());
+    } else {
+        query = query.order(tx_sequence_number.asc());
+    }
+
+    query.limit(page.limit + 2)
+}
+
+/// Convert a list of raw transaction sequence numbers from the database into a page of events.
+/// This requires loading the transactions' digests and contents, and then picking out the events
+/// that match `filter` and come after the cursor.
+async fn from_sequence_numbers(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    filter: &EventFilter,
+    rows: Vec<i64>,
+) -> Result<Events, RpcError<Error>> {
+    let digests = ctx
+        .pg_loader()
+        .load_many(rows.iter().map(|&seq| TxDigestKey(seq as u64)))
+        .await
+        .context("Failed to load transaction digests")?;
+
+    let mut keys = Vec::with_capacity(rows.len());
+    for seq in rows {
+        let bytes = digests
+            .get(&TxDigestKey(seq as u64))
+            .with_context(|| format!("Missing transaction digest for transaction {seq}"))?
+            .tx_digest
+            .as_slice();
+
+        let digest = TransactionDigest::try_from(bytes)
+            .context("Failed to deserialize transaction digests")?;
+
+        keys.push((seq as u64, digest));
+    }
+
+    let transactions = future::join_all(
+        keys.iter()
+            .map(|(_, digest)| ctx.kv_loader().load_one_transaction(*digest)),
+    )
+    .await;
+
+    let limit = page.limit as usize;
+    let mut results = vec![];
+    for ((tx_sequence_number, digest), tx) in keys.into_iter().zip(transactions) {
+        let tx = tx
+            .context("Failed to fetch transaction from store")?
+            .with_context(|| format!("Missing transaction contents for transaction {digest}"))?;
+
+        let timestamp_ms = tx.timestamp_ms();
+        let mut events: Vec<_> = tx
+            .events()?
+            .into_iter()
+            .enumerate()
+            .map(|(ix, event)| (ix as u64, event))
+            .collect();
+
+        if page.descending {
+            events.reverse();
+        }
+
+        for (event_seq, event) in events {
+            // Skip events at or before the cursor, within the cursor's transaction.
+            if let Some(c) = &page.cursor {
+                let key = (tx_sequence_number, event_seq);
+                let cursor = (c.tx_sequence_number, c.event_seq);
+                if (page.descending && key >= cursor) || (!page.descending && key <= cursor) {
+                    continue;
+                }
+            }
+
+            if !filter.matches(&digest, timestamp_ms, &event) {
+                continue;
+            }
+
+            results.push((
+                tx_sequence_number,
+                FilteredEvent {
+                    digest,
+                    event_seq,
+                    timestamp_ms,
+                    event,
+                },
+            ));
+        }
+
+        if results.len() > limit {
+            break;
+        }
+    }
+
+    let has_next_page = results.len() > limit;
+    if has_next_page {
+        results.truncate(limit);
+    }
+
+    let next_cursor = results
+        .last()
+        .map(|(tx_sequence_number, e)| {
+            JsonCursor(EventCursor {
+                tx_sequence_number: *tx_sequence_number,
+                event_seq: e.event_seq,
+            })
+            .encode()
+        })
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    Ok(PageResponse {
+        data: results.into_iter().map(|(_, e)| e).collect(),
+        next_cursor,
+        has_next_page,
+    })
+}
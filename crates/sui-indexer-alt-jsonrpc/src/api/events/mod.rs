@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_json_rpc_types::{Page, SuiEvent};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+
+use self::{error::Error, filter::EventFilter};
+
+use crate::{
+    context::Context,
+    error::{InternalContext, RpcError},
+};
+
+use super::rpc_module::RpcModule;
+
+mod error;
+pub(crate) mod filter;
+pub(crate) mod response;
+
+#[open_rpc(namespace = "suix", tag = "Query Events API")]
+#[rpc(server, namespace = "suix")]
+trait QueryEventsApi {
+    /// Query events based on their properties (sender, emitting module, type, etc). Returns a
+    /// paginated list of events.
+    ///
+    /// If a cursor is provided, the query will start from the event after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first event that meets the query
+    /// criteria.
+    ///
+    /// The definition of "first" event is changed by the `descending_order` parameter, which is
+    /// optional, and defaults to false, meaning that the oldest event is shown first.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// The event query criteria.
+        query: EventFilter,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of events to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence on-chain.
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>>;
+}
+
+pub(crate) struct QueryEvents(pub Context);
+
+#[async_trait::async_trait]
+impl QueryEventsApiServer for QueryEvents {
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>> {
+        let Self(ctx) = self;
+
+        let Page {
+            data: events,
+            next_cursor,
+            has_next_page,
+        } = filter::events(ctx, &query, cursor.clone(), limit, descending_order).await?;
+
+        let event_futures = events.into_iter().map(|e| async move {
+            let (digest, event_seq) = (e.digest, e.event_seq);
+            response::event::<Error>(ctx, digest, event_seq, e.timestamp_ms, e.event)
+                .await
+                .with_internal_context(|| {
+                    format!("Failed to get event {event_seq} from transaction {digest}")
+                })
+        });
+
+        let data = future::join_all(event_futures)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, RpcError<Error>>>()?;
+
+        Ok(Page {
+            data,
+            next_cursor: next_cursor.or(cursor),
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for QueryEvents {
+    fn schema(&self) -> Module {
+        QueryEventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
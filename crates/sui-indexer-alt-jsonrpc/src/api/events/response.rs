@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use move_core_types::annotated_value::{MoveDatatypeLayout, MoveTypeLayout};
+use sui_json_rpc_types::SuiEvent;
+use sui_types::{digests::TransactionDigest, event::Event};
+
+use crate::{
+    context::Context,
+    error::{rpc_bail, RpcError},
+};
+
+/// Convert the `ix`-th event emitted by the transaction with digest `digest` (which finished
+/// executing at `timestamp_ms`) into its JSON-RPC representation. This involves resolving the
+/// event's type layout, to convert its BCS contents into JSON.
+pub(crate) async fn event<E: std::error::Error>(
+    ctx: &Context,
+    digest: TransactionDigest,
+    ix: u64,
+    timestamp_ms: u64,
+    event: Event,
+) -> Result<SuiEvent, RpcError<E>> {
+    let layout = match ctx
+        .package_resolver()
+        .type_layout(event.type_.clone().into())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve layout for {}",
+                event.type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })? {
+        MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
+        MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
+        _ => rpc_bail!(
+            "Event {ix} is not a struct or enum: {}",
+            event.type_.to_canonical_string(/* with_prefix */ true)
+        ),
+    };
+
+    Ok(
+        SuiEvent::try_from(event, digest, ix, Some(timestamp_ms), layout)
+            .with_context(|| format!("Failed to convert Event {ix} into response"))?,
+    )
+}
@@ -0,0 +1,25 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("Epoch {0} not found")]
+    EpochNotFound(u64),
+
+    #[error("Object {0} is not a StakedSui object")]
+    NotStakedSui(ObjectID),
+
+    #[error("StakedSui object {0} not found")]
+    StakeNotFound(ObjectID),
+
+    #[error(
+        "Address {owner} owns more than {max} stakes, page through them with \
+         suix_getOwnedObjects and fetch them with suix_getStakesByIds instead"
+    )]
+    TooManyStakes { owner: SuiAddress, max: usize },
+
+    #[error("Requested {requested} keys, exceeding maximum {max}")]
+    TooManyKeys { requested: usize, max: usize },
+}
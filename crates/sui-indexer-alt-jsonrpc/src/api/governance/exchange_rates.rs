@@ -0,0 +1,163 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
+use futures::future;
+use sui_indexer_alt_schema::schema::kv_epoch_ends;
+use sui_json_rpc_types::{ValidatorApy, ValidatorApys};
+use sui_types::{
+    base_types::ObjectID,
+    dynamic_field::{derive_dynamic_field_id, Field},
+    sui_system_state::{
+        sui_system_state_summary::{SuiSystemStateSummary, SuiValidatorSummary},
+        PoolTokenExchangeRate,
+    },
+    TypeTag,
+};
+
+use crate::{context::Context, data::objects::load_latest, error::RpcError};
+
+use super::error::Error;
+
+/// The maximum number of epochs' worth of exchange rate changes that are averaged to calculate a
+/// validator's APY.
+const MAX_APY_EPOCHS: usize = 30;
+
+/// Load the exchange rate that was recorded in the exchange rate table at `exchange_rates_id`, for
+/// `epoch`. Returns `None` if no rate was recorded for that epoch, which can happen if the previous
+/// epoch ended in safe mode, or the staking pool was not active at the time.
+pub(super) async fn exchange_rate(
+    ctx: &Context,
+    exchange_rates_id: ObjectID,
+    epoch: u64,
+) -> anyhow::Result<Option<PoolTokenExchangeRate>> {
+    let field_id = derive_dynamic_field_id(
+        exchange_rates_id,
+        &TypeTag::U64,
+        &bcs::to_bytes(&epoch).context("Failed to serialize epoch")?,
+    )
+    .context("Failed to derive exchange rate field ID")?;
+
+    let Some(object) = load_latest(ctx, field_id).await? else {
+        return Ok(None);
+    };
+
+    let move_object = object.data.try_as_move().context("Not a Move object")?;
+    let field: Field<u64, PoolTokenExchangeRate> =
+        bcs::from_bytes(move_object.contents()).context("Failed to deserialize exchange rate")?;
+
+    Ok(Some(field.value))
+}
+
+/// Calculate the APY of every active validator, by averaging the change in their staking pools'
+/// exchange rates over (up to) the last [MAX_APY_EPOCHS] epochs since stake subsidies started.
+pub(super) async fn validator_apys(
+    ctx: &Context,
+    summary: &SuiSystemStateSummary,
+) -> Result<ValidatorApys, RpcError<Error>> {
+    let epochs = rate_epochs(ctx, summary.stake_subsidy_start_epoch).await?;
+
+    let apy_futures = summary
+        .active_validators
+        .iter()
+        .map(|validator| validator_apy(ctx, validator, &epochs));
+
+    let apys = future::join_all(apy_futures)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(ValidatorApys {
+        apys,
+        epoch: summary.epoch,
+    })
+}
+
+/// The epochs (in descending order) to fetch exchange rates for, to calculate validator APYs.
+///
+/// The exchange rate for epoch `E` is recorded when epoch `E - 1` ends, unless that epoch ended in
+/// safe mode, so the history of epoch ends is used to find the epochs that have exchange rates.
+async fn rate_epochs(
+    ctx: &Context,
+    stake_subsidy_start_epoch: u64,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use kv_epoch_ends::dsl as e;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let query = e::kv_epoch_ends
+        .select(e::epoch)
+        .filter(e::safe_mode.eq(false))
+        .filter(e::epoch.ge(stake_subsidy_start_epoch.saturating_sub(1) as i64))
+        .order(e::epoch.desc())
+        .limit(MAX_APY_EPOCHS as i64 + 1);
+
+    let epochs: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch epoch ends")?;
+
+    Ok(epochs.into_iter().map(|e| e as u64 + 1).collect())
+}
+
+/// Calculate the APY for a single validator from its exchange rates at `epochs`.
+async fn validator_apy(
+    ctx: &Context,
+    validator: &SuiValidatorSummary,
+    epochs: &[u64],
+) -> anyhow::Result<ValidatorApy> {
+    let rate_futures = epochs
+        .iter()
+        .map(|epoch| exchange_rate(ctx, validator.exchange_rates_id, *epoch));
+
+    let rates = future::join_all(rate_futures)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()
+        .with_context(|| {
+            format!(
+                "Failed to load exchange rates for {}",
+                validator.sui_address
+            )
+        })?;
+
+    let rates: Vec<_> = epochs
+        .iter()
+        .zip(rates)
+        .filter_map(|(epoch, rate)| Some((*epoch, rate?)))
+        .collect();
+
+    Ok(ValidatorApy {
+        address: validator.sui_address,
+        apy: average_apy(&rates),
+    })
+}
+
+/// Average the APYs implied by the exchange rates between consecutive epochs in `rates` (which
+/// are in descending order by epoch), filtering out outliers. Returns zero if there are not enough
+/// rates to calculate an APY.
+fn average_apy(rates: &[(u64, PoolTokenExchangeRate)]) -> f64 {
+    let apys: Vec<_> = rates
+        .windows(2)
+        .filter(|w| w[0].0 == w[1].0 + 1)
+        .map(|w| calculate_apy(&w[1].1, &w[0].1))
+        .filter(|apy| *apy > 0.0 && *apy < 0.1)
+        .take(MAX_APY_EPOCHS)
+        .collect();
+
+    if apys.is_empty() {
+        0.0
+    } else {
+        apys.iter().sum::<f64>() / apys.len() as f64
+    }
+}
+
+/// APY_e = (ER_e / ER_e+1) ^ 365 - 1
+fn calculate_apy(rate_e: &PoolTokenExchangeRate, rate_e_1: &PoolTokenExchangeRate) -> f64 {
+    (rate_e.rate() / rate_e_1.rate()).powf(365.0) - 1.0
+}
@@ -0,0 +1,305 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
+
+use fastcrypto::traits::ToFromBytes;
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_indexer_alt_schema::{epochs::StoredEpochStart, schema::kv_epoch_starts};
+use sui_json_rpc_types::{DelegatedStake, SuiCommittee, ValidatorApys};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::{AuthorityName, ObjectID, SuiAddress},
+    dynamic_field::{derive_dynamic_field_id, Field},
+    governance::StakedSui,
+    sui_serde::BigInt,
+    sui_system_state::{
+        sui_system_state_inner_v1::SuiSystemStateInnerV1,
+        sui_system_state_inner_v2::SuiSystemStateInnerV2,
+        sui_system_state_summary::SuiSystemStateSummary, SuiSystemState, SuiSystemStateTrait,
+        SuiSystemStateWrapper,
+    },
+    TypeTag, SUI_SYSTEM_STATE_OBJECT_ID,
+};
+use tokio::sync::Mutex;
+
+use crate::{
+    context::Context,
+    data::objects::load_latest_deserialized,
+    error::{internal_error, invalid_params, rpc_bail, InternalContext, RpcError},
+};
+
+use self::error::Error;
+
+use super::rpc_module::RpcModule;
+
+mod error;
+mod exchange_rates;
+mod stakes;
+
+#[open_rpc(namespace = "suix", tag = "Governance API")]
+#[rpc(server, namespace = "suix")]
+trait GovernanceApi {
+    /// Return the reference gas price for the network as of the latest epoch.
+    #[method(name = "getReferenceGasPrice")]
+    async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return a summary of the latest version of the Sui System State object (0x5), on-chain.
+    #[method(name = "getLatestSuiSystemState")]
+    async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary>;
+
+    /// Return the stakes owned by an address, grouped by staking pool, alongside their status
+    /// (pending or active) and estimated rewards, as of the latest epoch.
+    #[method(name = "getStakes")]
+    async fn get_stakes(
+        &self,
+        /// The address that owns the stakes.
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<DelegatedStake>>;
+
+    /// Return the stakes with the given IDs, grouped by staking pool. If a stake has been
+    /// withdrawn, its status will be Unstaked.
+    #[method(name = "getStakesByIds")]
+    async fn get_stakes_by_ids(
+        &self,
+        /// The IDs of the StakedSui objects to fetch.
+        staked_sui_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<DelegatedStake>>;
+
+    /// Return the committee (validators and their voting power) for the given epoch.
+    #[method(name = "getCommitteeInfo")]
+    async fn get_committee_info(
+        &self,
+        /// The epoch of interest. If None, defaults to the latest epoch.
+        epoch: Option<BigInt<u64>>,
+    ) -> RpcResult<SuiCommittee>;
+
+    /// Return the APY of each active validator, averaged over recent epochs.
+    #[method(name = "getValidatorsApy")]
+    async fn get_validators_apy(&self) -> RpcResult<ValidatorApys>;
+}
+
+pub(crate) struct Governance {
+    ctx: Context,
+
+    /// Validator APYs only change when the epoch changes, so they are calculated at most once per
+    /// epoch, and cached.
+    apys: Mutex<Option<ValidatorApys>>,
+}
+
+impl Governance {
+    pub(crate) fn new(ctx: Context) -> Self {
+        Self {
+            ctx,
+            apys: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl GovernanceApiServer for Governance {
+    async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
+        Ok(rgp_response(&self.ctx).await?)
+    }
+
+    async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
+        Ok(latest_sui_system_state_response(&self.ctx).await?)
+    }
+
+    async fn get_stakes(&self, owner: SuiAddress) -> RpcResult<Vec<DelegatedStake>> {
+        let ctx = &self.ctx;
+        let stakes = stakes::stakes_by_owner(ctx, owner)
+            .await
+            .with_internal_context(|| format!("Failed to fetch stakes for {owner}"))?;
+
+        Ok(delegated_stakes_response(ctx, stakes).await?)
+    }
+
+    async fn get_stakes_by_ids(
+        &self,
+        staked_sui_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<DelegatedStake>> {
+        let ctx = &self.ctx;
+        let config = &ctx.config().objects;
+        if staked_sui_ids.len() > config.max_multi_get_objects {
+            return Err(invalid_params(Error::TooManyKeys {
+                requested: staked_sui_ids.len(),
+                max: config.max_multi_get_objects,
+            })
+            .into());
+        }
+
+        let stake_futures = staked_sui_ids.iter().map(|id| async move {
+            stakes::stake_by_id(ctx, *id)
+                .await
+                .with_internal_context(|| format!("Failed to fetch stake {id}"))
+        });
+
+        let stakes = future::join_all(stake_futures)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(delegated_stakes_response(ctx, stakes).await?)
+    }
+
+    async fn get_committee_info(&self, epoch: Option<BigInt<u64>>) -> RpcResult<SuiCommittee> {
+        Ok(committee_response(&self.ctx, epoch.map(|e| *e)).await?)
+    }
+
+    async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
+        let ctx = &self.ctx;
+        let summary = latest_sui_system_state_response(ctx).await?;
+
+        // Holding the lock while calculating APYs prevents concurrent requests from repeating the
+        // calculation for the same epoch.
+        let mut cached = self.apys.lock().await;
+        if let Some(apys) = cached.as_ref().filter(|apys| apys.epoch == summary.epoch) {
+            return Ok(apys.clone());
+        }
+
+        let apys = exchange_rates::validator_apys(ctx, &summary)
+            .await
+            .internal_context("Failed to calculate validator APYs")?;
+
+        *cached = Some(apys.clone());
+        Ok(apys)
+    }
+}
+
+impl RpcModule for Governance {
+    fn schema(&self) -> Module {
+        GovernanceApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load data and generate response for `getReferenceGasPrice`.
+async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError> {
+    use kv_epoch_starts::dsl as e;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let rgp: i64 = conn
+        .first(
+            e::kv_epoch_starts
+                .select(e::reference_gas_price)
+                .order(e::epoch.desc()),
+        )
+        .await
+        .context("Failed to fetch the reference gas price")?
+        .context("No reference gas price found")?;
+
+    Ok((rgp as u64).into())
+}
+
+/// Load data and generate response for `getLatestSuiSystemState`.
+async fn latest_sui_system_state_response(
+    ctx: &Context,
+) -> Result<SuiSystemStateSummary, RpcError<Error>> {
+    let wrapper: SuiSystemStateWrapper = load_latest_deserialized(ctx, SUI_SYSTEM_STATE_OBJECT_ID)
+        .await
+        .context("Failed to fetch system state wrapper object")?;
+
+    let inner_id = derive_dynamic_field_id(
+        SUI_SYSTEM_STATE_OBJECT_ID,
+        &TypeTag::U64,
+        &bcs::to_bytes(&wrapper.version).context("Failed to serialize system state version")?,
+    )
+    .context("Failed to derive inner system state field ID")?;
+
+    Ok(match wrapper.version {
+        1 => load_latest_deserialized::<Field<u64, SuiSystemStateInnerV1>>(ctx, inner_id)
+            .await
+            .context("Failed to fetch inner system state object")?
+            .value
+            .into_sui_system_state_summary(),
+        2 => load_latest_deserialized::<Field<u64, SuiSystemStateInnerV2>>(ctx, inner_id)
+            .await
+            .context("Failed to fetch inner system state object")?
+            .value
+            .into_sui_system_state_summary(),
+        v => rpc_bail!("Unexpected inner system state version: {v}"),
+    })
+}
+
+/// Load data and generate response for `getStakes` and `getStakesByIds`, given the stakes to
+/// include, and whether each of them is still live.
+async fn delegated_stakes_response(
+    ctx: &Context,
+    stakes: Vec<(StakedSui, bool)>,
+) -> Result<Vec<DelegatedStake>, RpcError<Error>> {
+    if stakes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let summary = latest_sui_system_state_response(ctx).await?;
+    stakes::delegated_stakes(ctx, &summary, stakes)
+        .await
+        .internal_context("Failed to gather delegated stakes")
+}
+
+/// Load data and generate response for `getCommitteeInfo`, based on the system state at the start
+/// of `epoch` (or the latest epoch, if none is provided).
+async fn committee_response(
+    ctx: &Context,
+    epoch: Option<u64>,
+) -> Result<SuiCommittee, RpcError<Error>> {
+    use kv_epoch_starts::dsl as e;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut query = e::kv_epoch_starts.into_boxed();
+    if let Some(epoch) = epoch {
+        query = query.filter(e::epoch.eq(epoch as i64));
+    } else {
+        query = query.order(e::epoch.desc());
+    }
+
+    let Some(start): Option<StoredEpochStart> = conn
+        .first(query)
+        .await
+        .context("Failed to fetch epoch start")?
+    else {
+        return Err(match epoch {
+            Some(epoch) => invalid_params(Error::EpochNotFound(epoch)),
+            None => internal_error!("No epochs found"),
+        });
+    };
+
+    let system_state: SuiSystemState =
+        bcs::from_bytes(&start.system_state).context("Failed to deserialize system state")?;
+    let summary = system_state.into_sui_system_state_summary();
+
+    let mut validators = summary
+        .active_validators
+        .into_iter()
+        .map(|v| {
+            let name = AuthorityName::from_bytes(&v.protocol_pubkey_bytes)
+                .context("Failed to deserialize validator protocol key")?;
+            Ok((name, v.voting_power))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Match the order that validators appear in, in a `Committee`.
+    validators.sort_by_key(|(name, _)| *name);
+
+    Ok(SuiCommittee {
+        epoch: start.epoch as u64,
+        validators,
+    })
+}
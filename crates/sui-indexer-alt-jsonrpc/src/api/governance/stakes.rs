@@ -0,0 +1,266 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use diesel::{BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl};
+use futures::future;
+use move_core_types::language_storage::StructTag;
+use sui_indexer_alt_schema::{objects::StoredOwnerKind, schema::obj_info};
+use sui_json_rpc_types::{DelegatedStake, Stake, StakeStatus};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    dynamic_field::{derive_dynamic_field_id, Field},
+    governance::StakedSui,
+    id::ID,
+    object::Object,
+    sui_system_state::{
+        sui_system_state_inner_v1::ValidatorV1,
+        sui_system_state_summary::{SuiSystemStateSummary, SuiValidatorSummary},
+        ValidatorWrapper,
+    },
+    MoveTypeTagTrait, TypeTag,
+};
+
+use crate::{
+    context::Context,
+    data::objects::{load_latest, load_latest_deserialized, load_live},
+    error::{invalid_params, rpc_bail, RpcError},
+};
+
+use super::{error::Error, exchange_rates::exchange_rate};
+
+/// Load the `StakedSui` objects owned by `owner`. Fails if `owner` owns more than the configured
+/// maximum number of stakes, rather than returning a partial result.
+pub(super) async fn stakes_by_owner(
+    ctx: &Context,
+    owner: SuiAddress,
+) -> Result<Vec<(StakedSui, bool)>, RpcError<Error>> {
+    use obj_info::dsl as o;
+
+    let (candidates, newer) = diesel::alias!(obj_info as candidates, obj_info as newer);
+
+    macro_rules! candidates {
+        ($($field:ident),*) => {
+            candidates.fields(($(o::$field),*))
+        };
+    }
+
+    macro_rules! newer {
+        ($($field:ident),*) => {
+            newer.fields(($(o::$field),*))
+        };
+    }
+
+    let config = &ctx.config().governance;
+    let StructTag {
+        address,
+        module,
+        name,
+        ..
+    } = StakedSui::type_();
+
+    let query = candidates
+        .select(candidates!(object_id))
+        .left_join(
+            newer.on(candidates!(object_id)
+                .eq(newer!(object_id))
+                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))),
+        )
+        .filter(newer!(object_id).is_null())
+        .filter(candidates!(owner_kind).eq(StoredOwnerKind::Address))
+        .filter(candidates!(owner_id).eq(owner.to_inner()))
+        .filter(candidates!(package).eq(address.to_vec()))
+        .filter(candidates!(module).eq(module.as_str()))
+        .filter(candidates!(name).eq(name.as_str()))
+        .order_by(candidates!(cp_sequence_number).desc())
+        .then_order_by(candidates!(object_id).desc())
+        .limit(config.max_stakes as i64 + 1);
+
+    let ids: Vec<Vec<u8>> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch object info")?;
+
+    if ids.len() > config.max_stakes {
+        return Err(invalid_params(Error::TooManyStakes {
+            owner,
+            max: config.max_stakes,
+        }));
+    }
+
+    let stake_futures = ids.into_iter().map(|id| async move {
+        let id = ObjectID::from_bytes(&id).context("Failed to deserialize Object ID")?;
+        let object = load_live(ctx, id)
+            .await?
+            .with_context(|| format!("Failed to load StakedSui object {id}"))?;
+
+        let stake = StakedSui::try_from(&object)
+            .with_context(|| format!("Failed to deserialize StakedSui object {id}"))?;
+
+        Ok::<_, anyhow::Error>((stake, true))
+    });
+
+    Ok(future::join_all(stake_futures)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?)
+}
+
+/// Load the `StakedSui` object with ID `id`, and whether it is still live. If the object has been
+/// deleted (i.e. the stake has been withdrawn), its contents before deletion are returned.
+pub(super) async fn stake_by_id(
+    ctx: &Context,
+    id: ObjectID,
+) -> Result<(StakedSui, bool), RpcError<Error>> {
+    let (object, exists): (Object, bool) = if let Some(object) = load_live(ctx, id).await? {
+        (object, true)
+    } else if let Some(object) = load_latest(ctx, id).await? {
+        (object, false)
+    } else {
+        return Err(invalid_params(Error::StakeNotFound(id)));
+    };
+
+    let stake =
+        StakedSui::try_from(&object).map_err(|_| invalid_params(Error::NotStakedSui(id)))?;
+    Ok((stake, exists))
+}
+
+/// Group `stakes` by the staking pool they belong to, and calculate their status, and their
+/// estimated rewards if they are active, as of the epoch in `summary`.
+pub(super) async fn delegated_stakes(
+    ctx: &Context,
+    summary: &SuiSystemStateSummary,
+    stakes: Vec<(StakedSui, bool)>,
+) -> Result<Vec<DelegatedStake>, RpcError<Error>> {
+    let pools = stakes.into_iter().fold(
+        BTreeMap::<_, Vec<_>>::new(),
+        |mut pools, (stake, exists)| {
+            pools
+                .entry(stake.pool_id())
+                .or_default()
+                .push((stake, exists));
+            pools
+        },
+    );
+
+    let pool_futures = pools
+        .into_iter()
+        .map(|(pool_id, stakes)| delegated_stake(ctx, summary, pool_id, stakes));
+
+    future::join_all(pool_futures).await.into_iter().collect()
+}
+
+/// Gather the `stakes` in the staking pool with ID `pool_id`.
+async fn delegated_stake(
+    ctx: &Context,
+    summary: &SuiSystemStateSummary,
+    pool_id: ObjectID,
+    stakes: Vec<(StakedSui, bool)>,
+) -> Result<DelegatedStake, RpcError<Error>> {
+    let Some(validator) = validator(ctx, summary, pool_id).await? else {
+        rpc_bail!("Cannot find validator for staking pool {pool_id}");
+    };
+
+    // The current exchange rate is the latest one recorded for the pool: for the current epoch if
+    // the pool is active, or for the epoch it was deactivated in, otherwise.
+    let current_epoch = validator
+        .staking_pool_deactivation_epoch
+        .unwrap_or(summary.epoch);
+
+    let current_rate = exchange_rate(ctx, validator.exchange_rates_id, current_epoch)
+        .await
+        .context("Failed to load current exchange rate")?;
+
+    let mut delegations = vec![];
+    for (stake, exists) in stakes {
+        let status = if !exists {
+            StakeStatus::Unstaked
+        } else if summary.epoch >= stake.activation_epoch() {
+            let estimated_reward = if let Some(current_rate) = &current_rate {
+                let stake_rate =
+                    exchange_rate(ctx, validator.exchange_rates_id, stake.activation_epoch())
+                        .await
+                        .context("Failed to load exchange rate at stake activation")?
+                        .unwrap_or_default();
+
+                let estimated_reward =
+                    ((stake_rate.rate() / current_rate.rate()) - 1.0) * stake.principal() as f64;
+                estimated_reward.round().max(0.0) as u64
+            } else {
+                0
+            };
+
+            StakeStatus::Active { estimated_reward }
+        } else {
+            StakeStatus::Pending
+        };
+
+        delegations.push(Stake {
+            staked_sui_id: stake.id(),
+            stake_request_epoch: stake.activation_epoch().saturating_sub(1),
+            stake_active_epoch: stake.activation_epoch(),
+            principal: stake.principal(),
+            status,
+        });
+    }
+
+    Ok(DelegatedStake {
+        validator_address: validator.sui_address,
+        staking_pool: pool_id,
+        stakes: delegations,
+    })
+}
+
+/// Find the validator that operates the staking pool with ID `pool_id`, from among the active
+/// validators in `summary`, or failing that, the pools of validators that are no longer active.
+async fn validator(
+    ctx: &Context,
+    summary: &SuiSystemStateSummary,
+    pool_id: ObjectID,
+) -> Result<Option<SuiValidatorSummary>, RpcError<Error>> {
+    if let Some(validator) = summary
+        .active_validators
+        .iter()
+        .find(|v| v.staking_pool_id == pool_id)
+    {
+        return Ok(Some(validator.clone()));
+    }
+
+    let wrapper_id = derive_dynamic_field_id(
+        summary.inactive_pools_id,
+        &ID::get_type_tag(),
+        &bcs::to_bytes(&pool_id).context("Failed to serialize pool ID")?,
+    )
+    .context("Failed to derive inactive validator field ID")?;
+
+    let Some(object) = load_latest(ctx, wrapper_id).await? else {
+        return Ok(None);
+    };
+
+    let move_object = object.data.try_as_move().context("Not a Move object")?;
+    let wrapper: Field<ID, ValidatorWrapper> = bcs::from_bytes(move_object.contents())
+        .context("Failed to deserialize validator wrapper")?;
+
+    let versioned = wrapper.value.inner;
+    let inner_id = derive_dynamic_field_id(
+        versioned.id.id.bytes,
+        &TypeTag::U64,
+        &bcs::to_bytes(&versioned.version).context("Failed to serialize validator version")?,
+    )
+    .context("Failed to derive inner validator field ID")?;
+
+    Ok(Some(match versioned.version {
+        1 => load_latest_deserialized::<Field<u64, ValidatorV1>>(ctx, inner_id)
+            .await
+            .context("Failed to fetch inner validator object")?
+            .value
+            .into_sui_validator_summary(),
+        v => rpc_bail!("Unexpected inner validator version: {v}"),
+    }))
+}
@@ -0,0 +1,287 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context as _};
+use diesel::{prelude::*, sql_query, sql_types};
+use futures::{future, stream, StreamExt, TryStreamExt};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use move_core_types::language_storage::TypeTag;
+use sui_indexer_alt_schema::objects::StoredCoinOwnerKind;
+use sui_json_rpc_types::{Balance, Page as PageResponse, SuiObjectDataOptions, SuiObjectResponse};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{base_types::SuiAddress, sui_serde::BigInt};
+
+use crate::{
+    context::Context,
+    data::watermarks::{consistent_range, CheckpointRange},
+    error::{invalid_params, InternalContext, RpcError},
+};
+
+use super::{
+    coin::coin_type_or_gas,
+    objects::{filter::owned_objects_at_checkpoint, response::object_at_checkpoint},
+    rpc_module::RpcModule,
+};
+
+#[open_rpc(namespace = "suix", tag = "History API")]
+#[rpc(server, namespace = "suix")]
+trait HistoryApi {
+    /// Return the objects owned by an address as of a past checkpoint, with their contents at that
+    /// checkpoint.
+    ///
+    /// The checkpoint must be within the range that ownership history is retained for, and the
+    /// contents of the objects must not have been pruned, otherwise the request fails. Objects
+    /// are returned in a fixed order, so the same cursor can be used to continue paginating from
+    /// where the last page left off.
+    #[method(name = "getOwnedObjectsAtCheckpoint")]
+    async fn get_owned_objects_at_checkpoint(
+        &self,
+        /// the owner's Sui address
+        address: SuiAddress,
+        /// the checkpoint to query ownership as of
+        checkpoint: BigInt<u64>,
+        /// Options for specifying the content to be returned
+        options: Option<SuiObjectDataOptions>,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<SuiObjectResponse, String>>;
+
+    /// Return the total balance of one coin type, owned by an address, as of each of the given
+    /// checkpoints, in the same order as the checkpoints. If no coin type is specified, the
+    /// balance of SUI coins is returned.
+    ///
+    /// All the checkpoints must be within the range that balance history is retained for,
+    /// otherwise the request fails.
+    #[method(name = "getBalanceHistory")]
+    async fn get_balance_history(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// the checkpoints to query the balance as of
+        checkpoints: Vec<BigInt<u64>>,
+    ) -> RpcResult<Vec<Balance>>;
+}
+
+pub(crate) struct History(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+
+    #[error("Failed to parse type {0:?}: {1}")]
+    BadType(String, anyhow::Error),
+
+    #[error(
+        "Checkpoint {checkpoint} is outside the available range [{}, {}]",
+        range.lo,
+        range.hi
+    )]
+    CheckpointOutOfRange {
+        checkpoint: u64,
+        range: CheckpointRange,
+    },
+
+    #[error("No history available")]
+    NoHistory,
+
+    #[error("Requested {requested} checkpoints, exceeding maximum {max}")]
+    TooManyCheckpoints { requested: usize, max: usize },
+}
+
+/// The pipelines whose tables are read to find the objects owned by an address at a past
+/// checkpoint, and their contents as of that checkpoint.
+const OWNERSHIP_PIPELINES: &[&str] = &["obj_info_history", "obj_versions"];
+
+/// The pipeline whose table is read to calculate balances at past checkpoints.
+const BALANCE_PIPELINES: &[&str] = &["coin_balance_history"];
+
+#[async_trait::async_trait]
+impl HistoryApiServer for History {
+    async fn get_owned_objects_at_checkpoint(
+        &self,
+        address: SuiAddress,
+        checkpoint: BigInt<u64>,
+        options: Option<SuiObjectDataOptions>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<SuiObjectResponse, String>> {
+        let Self(ctx) = self;
+        let checkpoint = *checkpoint;
+        check_checkpoints(ctx, OWNERSHIP_PIPELINES, &[checkpoint]).await?;
+
+        let PageResponse {
+            data: object_ids,
+            next_cursor,
+            has_next_page,
+        } = owned_objects_at_checkpoint(ctx, address, checkpoint, cursor, limit).await?;
+
+        let options = options.unwrap_or_default();
+        let obj_futures = object_ids
+            .iter()
+            .map(|id| object_at_checkpoint(ctx, *id, checkpoint, &options));
+
+        let data = future::join_all(obj_futures)
+            .await
+            .into_iter()
+            .zip(object_ids)
+            .map(|(r, id)| {
+                r.with_internal_context(|| {
+                    format!("Failed to get object {id} at checkpoint {checkpoint}")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PageResponse {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoints: Vec<BigInt<u64>>,
+    ) -> RpcResult<Vec<Balance>> {
+        let Self(ctx) = self;
+
+        let max = ctx.config().history.max_balance_checkpoints;
+        if checkpoints.len() > max {
+            return Err(invalid_params(Error::TooManyCheckpoints {
+                requested: checkpoints.len(),
+                max,
+            })
+            .into());
+        }
+
+        let coin_type_tag = coin_type_or_gas(coin_type, Error::BadType)?;
+        let checkpoints: Vec<u64> = checkpoints.into_iter().map(|c| *c).collect();
+        check_checkpoints(ctx, BALANCE_PIPELINES, &checkpoints).await?;
+
+        // Each checkpoint's balance is a separate query, so limit how many run at once, to avoid
+        // one request monopolizing the database connection pool.
+        let concurrency = ctx.config().history.balance_query_concurrency;
+        let balances: Vec<Balance> = stream::iter(&checkpoints)
+            .map(|cp| balance_at_checkpoint(ctx, owner, &coin_type_tag, *cp))
+            .buffered(concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(balances)
+    }
+}
+
+impl RpcModule for History {
+    fn schema(&self) -> Module {
+        HistoryApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Check that the history retained by the tables written by `pipelines` covers all of
+/// `checkpoints`.
+async fn check_checkpoints(
+    ctx: &Context,
+    pipelines: &[&'static str],
+    checkpoints: &[u64],
+) -> Result<(), RpcError<Error>> {
+    let range = consistent_range(ctx.pg_reader(), pipelines)
+        .await
+        .context("Failed to fetch watermarks")?
+        .ok_or_else(|| invalid_params(Error::NoHistory))?;
+
+    if let Some(&checkpoint) = checkpoints.iter().find(|cp| !range.contains(**cp)) {
+        return Err(invalid_params(Error::CheckpointOutOfRange {
+            checkpoint,
+            range,
+        }));
+    }
+
+    Ok(())
+}
+
+/// Sum up the balances of the coins of type `coin_type` that `owner` owned as of `checkpoint`,
+/// from the balance history table. Balances are summed in the database, from the latest row for
+/// each coin as of `checkpoint`.
+async fn balance_at_checkpoint(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type: &TypeTag,
+    checkpoint: u64,
+) -> Result<Balance, RpcError<Error>> {
+    #[derive(QueryableByName)]
+    struct StoredBalance {
+        #[diesel(sql_type = sql_types::BigInt)]
+        coin_object_count: i64,
+        #[diesel(sql_type = sql_types::Text)]
+        total_balance: String,
+    }
+
+    let serialized_coin_type = bcs::to_bytes(coin_type).context("Failed to serialize coin type")?;
+
+    // Balances are stored as signed integers with the same bit pattern as the unsigned balance, so
+    // negative values need to be shifted back into range before they are summed.
+    let query = sql_query(
+        r#"
+        SELECT
+            COUNT(*) AS coin_object_count,
+            COALESCE(SUM(
+                c.balance::NUMERIC
+                + CASE WHEN c.balance < 0 THEN 18446744073709551616 ELSE 0 END
+            ), 0)::TEXT AS total_balance
+        FROM
+            coin_balance_history c
+        LEFT JOIN
+            coin_balance_history n
+        ON
+            c.object_id = n.object_id
+        AND c.cp_sequence_number < n.cp_sequence_number
+        AND n.cp_sequence_number <= $1
+        WHERE
+            n.object_id IS NULL
+        AND c.cp_sequence_number <= $1
+        AND c.owner_kind = $2
+        AND c.owner_id = $3
+        AND c.coin_type = $4
+        "#,
+    )
+    .bind::<sql_types::BigInt, _>(checkpoint as i64)
+    .bind::<sql_types::SmallInt, _>(StoredCoinOwnerKind::Fastpath as i16)
+    .bind::<sql_types::Bytea, _>(owner.to_vec())
+    .bind::<sql_types::Bytea, _>(serialized_coin_type);
+
+    let stored: Vec<StoredBalance> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to sum balance history")?;
+
+    let Some(stored) = stored.into_iter().next() else {
+        return Err(anyhow!("No balance returned for checkpoint {checkpoint}").into());
+    };
+
+    Ok(Balance {
+        coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+        coin_object_count: stored.coin_object_count as usize,
+        total_balance: stored
+            .total_balance
+            .parse()
+            .context("Failed to parse total balance")?,
+        locked_balance: HashMap::new(),
+    })
+}
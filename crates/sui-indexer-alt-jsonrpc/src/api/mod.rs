@@ -4,10 +4,14 @@
 pub(crate) mod checkpoints;
 pub(crate) mod coin;
 pub(crate) mod dynamic_fields;
+pub(crate) mod epochs;
+pub(crate) mod events;
 pub(crate) mod governance;
+pub(crate) mod history;
 pub(crate) mod move_utils;
 pub(crate) mod name_service;
 pub(crate) mod objects;
 pub(crate) mod rpc_module;
+pub(crate) mod subscriptions;
 pub(crate) mod transactions;
 pub mod write;
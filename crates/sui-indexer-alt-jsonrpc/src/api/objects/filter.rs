@@ -7,7 +7,10 @@ use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_indexer_alt_schema::{objects::StoredOwnerKind, schema::obj_info};
+use sui_indexer_alt_schema::{
+    objects::StoredOwnerKind,
+    schema::{obj_info, obj_info_history},
+};
 use sui_json_rpc_types::{Page as PageResponse, SuiObjectDataOptions};
 use sui_sql_macro::sql;
 use sui_types::{
@@ -19,7 +22,7 @@ use sui_types::{
 use crate::{
     context::Context,
     error::RpcError,
-    paginate::{BcsCursor, Cursor as _, Page},
+    paginate::{checkpoint_snapshot, BcsCursor, Cursor as _, Page},
 };
 
 use super::error::Error;
@@ -58,11 +61,18 @@ pub(crate) enum SuiObjectDataFilter {
 struct ObjectCursor {
     object_id: Vec<u8>,
     cp_sequence_number: u64,
+
+    /// The checkpoint that pagination is pinned to.
+    checkpoint: u64,
 }
 
 type Cursor = BcsCursor<ObjectCursor>;
 type ObjectIDs = PageResponse<ObjectID, String>;
 
+/// The pipelines whose tables are read to find the objects owned by an address, and their contents
+/// at the checkpoint that pagination is pinned to.
+const PIPELINES: &[&str] = &["obj_info", "obj_versions"];
+
 impl SuiObjectDataFilter {
     fn package(&self) -> ObjectID {
         match self {
@@ -99,32 +109,88 @@ impl SuiObjectDataFilter {
     }
 }
 
+/// Build a query for the IDs of the objects that `$owner` owned as of `$checkpoint` (and the
+/// checkpoints they were last modified at) that satisfy `$filter`, from `$table`, which is either
+/// `obj_info` or a table with the same schema. The query fetches the page after `$page`'s cursor,
+/// and one more row than the page's limit, to detect whether there is a next page.
+macro_rules! owned_objects_query {
+    ($table:ident, $owner:expr, $checkpoint:expr, $filter:expr, $page:expr) => {{
+        use $table::dsl as o;
+
+        let owner: SuiAddress = $owner;
+        let checkpoint: u64 = $checkpoint;
+        let filter: Option<&SuiObjectDataFilter> = $filter;
+        let page: &Page<Cursor> = $page;
+
+        let (candidates, newer) = diesel::alias!($table as candidates, $table as newer);
+
+        let mut query = candidates
+            .select(candidates.fields((o::object_id, o::cp_sequence_number)))
+            .left_join(
+                newer.on(
+                    candidates
+                        .field(o::object_id)
+                        .eq(newer.field(o::object_id))
+                        .and(
+                            candidates
+                                .field(o::cp_sequence_number)
+                                .lt(newer.field(o::cp_sequence_number)),
+                        )
+                        .and(newer.field(o::cp_sequence_number).le(checkpoint as i64)),
+                ),
+            )
+            .filter(newer.field(o::object_id).is_null())
+            .filter(candidates.field(o::cp_sequence_number).le(checkpoint as i64))
+            .filter(candidates.field(o::owner_kind).eq(StoredOwnerKind::Address))
+            .filter(candidates.field(o::owner_id).eq(owner.to_inner()))
+            .order_by(candidates.field(o::cp_sequence_number).desc())
+            .then_order_by(candidates.field(o::object_id).desc())
+            .limit(page.limit + 1)
+            .into_boxed();
+
+        if let Some(c) = &page.cursor {
+            query = query.filter(sql!(as Bool,
+                "(candidates.cp_sequence_number, candidates.object_id) < ({BigInt}, {Bytea})",
+                c.cp_sequence_number as i64,
+                c.object_id.clone(),
+            ));
+        }
+
+        if let Some(package) = filter.map(|f| f.package()) {
+            query = query.filter(candidates.field(o::package).eq(package.into_bytes()));
+        }
+
+        if let Some(module) = filter.and_then(|f| f.module()) {
+            query = query.filter(candidates.field(o::module).eq(module));
+        }
+
+        if let Some(name) = filter.and_then(|f| f.name()) {
+            query = query.filter(candidates.field(o::name).eq(name));
+        }
+
+        if let Some(type_params) = filter.and_then(|f| f.type_params()) {
+            let bytes = bcs::to_bytes(type_params).context("Failed to serialize type params")?;
+            query = query.filter(candidates.field(o::instantiation).eq(bytes));
+        }
+
+        query
+    }};
+}
+
 /// Fetch ObjectIDs for a page of objects owned by `owner` that satisfy the given `filter` and
 /// pagination parameters. Returns the digests and a cursor point to the last result (if there are
-/// any results).
+/// any results), along with the checkpoint that the page was read as of.
+///
+/// All the pages in a paginated query are read as of the same checkpoint (chosen when the first
+/// page is fetched, and carried in the cursor), so that they describe the objects the address
+/// owned at a single point in time.
 pub(super) async fn owned_objects(
     ctx: &Context,
     owner: SuiAddress,
     filter: &Option<SuiObjectDataFilter>,
     cursor: Option<String>,
     limit: Option<usize>,
-) -> Result<ObjectIDs, RpcError<Error>> {
-    use obj_info::dsl as o;
-
-    let (candidates, newer) = diesel::alias!(obj_info as candidates, obj_info as newer);
-
-    macro_rules! candidates {
-        ($($field:ident),*) => {
-            candidates.fields(($(o::$field),*))
-        };
-    }
-
-    macro_rules! newer {
-        ($($field:ident),*) => {
-            newer.fields(($(o::$field),*))
-        };
-    }
-
+) -> Result<(ObjectIDs, u64), RpcError<Error>> {
     let config = &ctx.config().objects;
     let page: Page<Cursor> = Page::from_params(
         config.default_page_size,
@@ -134,56 +200,65 @@ pub(super) async fn owned_objects(
         None,
     )?;
 
-    let mut query = candidates
-        .select(candidates!(object_id, cp_sequence_number))
-        .left_join(
-            newer.on(candidates!(object_id)
-                .eq(newer!(object_id))
-                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))),
-        )
-        .filter(newer!(object_id).is_null())
-        .filter(candidates!(owner_kind).eq(StoredOwnerKind::Address))
-        .filter(candidates!(owner_id).eq(owner.to_inner()))
-        .order_by(candidates!(cp_sequence_number).desc())
-        .then_order_by(candidates!(object_id).desc())
-        .limit(page.limit + 1)
-        .into_boxed();
-
-    if let Some(c) = page.cursor {
-        query = query.filter(sql!(as Bool,
-            "(candidates.cp_sequence_number, candidates.object_id) < ({BigInt}, {Bytea})",
-            c.cp_sequence_number as i64,
-            c.object_id.clone(),
-        ));
-    }
+    let pinned = page.cursor.as_ref().map(|c| c.checkpoint);
+    let checkpoint = checkpoint_snapshot(ctx, PIPELINES, pinned).await?;
 
-    let filter = filter.as_ref();
-    if let Some(package) = filter.map(|f| f.package()) {
-        query = query.filter(candidates!(package).eq(package.into_bytes()));
-    }
-
-    if let Some(module) = filter.and_then(|f| f.module()) {
-        query = query.filter(candidates!(module).eq(module));
-    }
+    let query = owned_objects_query!(obj_info, owner, checkpoint, filter.as_ref(), &page);
+    let results: Vec<(Vec<u8>, i64)> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch object info")?;
 
-    if let Some(name) = filter.and_then(|f| f.name()) {
-        query = query.filter(candidates!(name).eq(name));
-    }
+    Ok((object_ids_page(results, &page, checkpoint)?, checkpoint))
+}
 
-    if let Some(type_params) = filter.and_then(|f| f.type_params()) {
-        let bytes = bcs::to_bytes(type_params).context("Failed to serialize type params")?;
-        query = query.filter(candidates!(instantiation).eq(bytes));
-    }
+/// Like [owned_objects], but fetches the objects that `owner` owned as of a given `checkpoint` in
+/// the past, from the ownership history table (which retains ownership for longer than
+/// `obj_info`), and without filtering them. Callers are responsible for checking that `checkpoint`
+/// is within the range that the ownership history covers.
+pub(crate) async fn owned_objects_at_checkpoint<E>(
+    ctx: &Context,
+    owner: SuiAddress,
+    checkpoint: u64,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<ObjectIDs, RpcError<E>>
+where
+    E: From<crate::paginate::Error> + std::error::Error,
+{
+    let config = &ctx.config().history;
+    let page: Page<Cursor> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
 
-    let mut results: Vec<(Vec<u8>, i64)> = ctx
+    let query = owned_objects_query!(obj_info_history, owner, checkpoint, None, &page);
+    let results: Vec<(Vec<u8>, i64)> = ctx
         .pg_reader()
         .connect()
         .await
         .context("Failed to connect to the database")?
         .results(query)
         .await
-        .context("Failed to fetch object info")?;
+        .context("Failed to fetch object info history")?;
+
+    Ok(object_ids_page(results, &page, checkpoint)?)
+}
 
+/// Turn the `(object_id, cp_sequence_number)` pairs fetched by `owned_objects_query!` into a page
+/// of object IDs, with a cursor pointing at the last result, pinned to `checkpoint`.
+fn object_ids_page(
+    mut results: Vec<(Vec<u8>, i64)>,
+    page: &Page<Cursor>,
+    checkpoint: u64,
+) -> anyhow::Result<ObjectIDs> {
     let has_next_page = results.len() > page.limit as usize;
     if has_next_page {
         results.truncate(page.limit as usize);
@@ -195,6 +270,7 @@ pub(super) async fn owned_objects(
             BcsCursor(ObjectCursor {
                 object_id: o.clone(),
                 cp_sequence_number: *c as u64,
+                checkpoint,
             })
             .encode()
         })
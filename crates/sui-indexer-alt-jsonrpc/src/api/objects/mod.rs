@@ -21,7 +21,7 @@ use super::rpc_module::RpcModule;
 use self::error::Error;
 
 mod error;
-mod filter;
+pub(crate) mod filter;
 pub(crate) mod response;
 
 #[open_rpc(namespace = "sui", tag = "Objects API")]
@@ -89,9 +89,11 @@ trait QueryObjectsApi {
     ///
     /// The definition of "first" page is somewhat arbitrary. It is a page such that continuing to
     /// paginate an address's objects from this page will eventually reach all objects owned by
-    /// that address assuming that the owned object set does not change. If the owned object set
-    /// does change, pagination may not be consistent (may not reflect a set of objects that the
-    /// address owned at a single point in time).
+    /// that address, as of the checkpoint at which the first page was fetched: All pages reflect
+    /// the set of objects that the address owned at that checkpoint (and the contents of those
+    /// objects at that checkpoint), even if the owned object set changes while paginating. If
+    /// that checkpoint is pruned before pagination finishes, the request fails, and pagination
+    /// must start again from the first page.
     ///
     /// The size of each page is controlled by the `limit` parameter.
     #[method(name = "getOwnedObjects")]
@@ -224,24 +226,29 @@ impl QueryObjectsApiServer for QueryObjects {
 
         let query = query.unwrap_or_default();
 
-        let Page {
-            data: object_ids,
-            next_cursor,
-            has_next_page,
-        } = filter::owned_objects(ctx, address, &query.filter, cursor, limit).await?;
+        let (
+            Page {
+                data: object_ids,
+                next_cursor,
+                has_next_page,
+            },
+            checkpoint,
+        ) = filter::owned_objects(ctx, address, &query.filter, cursor, limit).await?;
 
         let options = query.options.unwrap_or_default();
 
         let obj_futures = object_ids
             .iter()
-            .map(|id| response::latest_object(ctx, *id, &options));
+            .map(|id| response::object_at_checkpoint(ctx, *id, checkpoint, &options));
 
         let data = future::join_all(obj_futures)
             .await
             .into_iter()
             .zip(object_ids)
             .map(|(r, id)| {
-                r.with_internal_context(|| format!("Failed to get object {id} at latest version"))
+                r.with_internal_context(|| {
+                    format!("Failed to get object {id} at checkpoint {checkpoint}")
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
 
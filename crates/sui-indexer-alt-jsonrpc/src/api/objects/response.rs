@@ -22,7 +22,11 @@ use tokio::join;
 
 use crate::{
     context::Context,
-    data::{displays::DisplayKey, object_info::LatestObjectInfoKey, objects::load_latest},
+    data::{
+        displays::DisplayKey,
+        object_info::LatestObjectInfoKey,
+        objects::{load_at_checkpoint, load_latest},
+    },
     error::{rpc_bail, InternalContext, RpcError},
 };
 
@@ -79,6 +83,27 @@ pub(super) async fn latest_object(
     ))
 }
 
+/// Fetch the version of an object that was current as of `checkpoint`, and convert it into a
+/// response. This is intended to be used after checking with `obj_info` that the object was live
+/// at that checkpoint.
+pub(crate) async fn object_at_checkpoint(
+    ctx: &Context,
+    object_id: ObjectID,
+    checkpoint: u64,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiObjectResponse, RpcError> {
+    // Similar to `latest_object`, the object is known to be live at this checkpoint, so failing to
+    // find its contents is an internal error.
+    let object = load_at_checkpoint(ctx, object_id, checkpoint)
+        .await
+        .context("Failed to load object at checkpoint")?
+        .context("Could not find content for live object at checkpoint")?;
+
+    Ok(SuiObjectResponse::new_with_data(
+        object_data_with_options(ctx, object, options).await?,
+    ))
+}
+
 /// Fetch the necessary data from the stores in `ctx` and transform it to build a response for a
 /// past object identified by its ID and version, according to the response `options`.
 pub(super) async fn past_object(
@@ -0,0 +1,378 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, future::Future, sync::Arc};
+
+use anyhow::{anyhow, Context as _};
+use jsonrpsee::{
+    core::SubscriptionResult, proc_macros::rpc, server::SubscriptionMessage,
+    PendingSubscriptionSink,
+};
+use serde::Serialize;
+use sui_json_rpc_types::{SuiEvent, SuiTransactionBlockEffects};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    OwnedSemaphorePermit, Semaphore,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    api::{events::filter::EventFilter, transactions::filter::TransactionFilter},
+    context::Context,
+    data::checkpoint_tail_task::{CheckpointTailTask, IndexedTransaction},
+};
+
+use super::{events::response::event, rpc_module::RpcModule};
+
+#[open_rpc(namespace = "suix", tag = "Subscriptions API")]
+#[rpc(server, namespace = "suix")]
+trait SubscriptionsApi {
+    /// Subscribe to a stream of events from newly indexed transactions, that match the given
+    /// filter. The stream starts from the first checkpoint to be indexed after the subscription
+    /// is created.
+    ///
+    /// Subscriptions that fall too far behind the indexer are closed with an error.
+    #[subscription(name = "subscribeEvent", item = SuiEvent)]
+    async fn subscribe_event(
+        &self,
+        /// The filter criteria of the event stream.
+        filter: EventFilter,
+    ) -> SubscriptionResult;
+
+    /// Subscribe to a stream of effects from newly indexed transactions, that match the given
+    /// filter. The stream starts from the first checkpoint to be indexed after the subscription
+    /// is created.
+    ///
+    /// Subscriptions that fall too far behind the indexer are closed with an error.
+    #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
+    async fn subscribe_transaction(
+        &self,
+        /// The filter criteria of the transaction stream.
+        filter: TransactionFilter,
+    ) -> SubscriptionResult;
+}
+
+pub(crate) struct Subscriptions {
+    /// Access to the package resolver, to render events.
+    context: Context,
+
+    /// Source of newly indexed transactions.
+    tx: broadcast::Sender<Arc<IndexedTransaction>>,
+
+    /// Limits the number of subscriptions that can be open at once, across all connections.
+    permits: Arc<Semaphore>,
+}
+
+impl Subscriptions {
+    /// Create a new subscriptions module, that is fed by the checkpoint tail task, `tail`.
+    pub(crate) fn new(context: Context, tail: &CheckpointTailTask) -> Self {
+        let max_subscriptions = context.config().subscriptions.max_subscriptions;
+        Self {
+            context,
+            tx: tail.sender(),
+            permits: Arc::new(Semaphore::new(max_subscriptions)),
+        }
+    }
+
+    fn acquire_permit(&self) -> anyhow::Result<OwnedSemaphorePermit> {
+        self.permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| anyhow!("Too many subscriptions, try again later"))
+    }
+}
+
+#[async_trait::async_trait]
+impl SubscriptionsApiServer for Subscriptions {
+    async fn subscribe_event(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: EventFilter,
+    ) -> SubscriptionResult {
+        let permit = self.acquire_permit()?;
+        let ctx = self.context.clone();
+
+        run_subscription(pending, self.tx.subscribe(), permit, move |tx| {
+            let ctx = ctx.clone();
+            let filter = filter.clone();
+            async move {
+                let mut events = vec![];
+                for (ix, ev) in tx.events.iter().enumerate() {
+                    if !filter.matches(&tx.digest, tx.timestamp_ms, ev) {
+                        continue;
+                    }
+
+                    let ix = ix as u64;
+                    let ev = ev.clone();
+                    events
+                        .push(event::<Infallible>(&ctx, tx.digest, ix, tx.timestamp_ms, ev).await?);
+                }
+
+                Ok(events)
+            }
+        })
+        .await
+    }
+
+    async fn subscribe_transaction(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: TransactionFilter,
+    ) -> SubscriptionResult {
+        let permit = self.acquire_permit()?;
+
+        run_subscription(pending, self.tx.subscribe(), permit, move |tx| {
+            let filter = filter.clone();
+            async move {
+                if !filter.matches(tx.checkpoint, &tx.data, &tx.effects) {
+                    return Ok(vec![]);
+                }
+
+                let effects: SuiTransactionBlockEffects = tx
+                    .effects
+                    .clone()
+                    .try_into()
+                    .context("Failed to convert Effects into response")?;
+
+                Ok(vec![effects])
+            }
+        })
+        .await
+    }
+}
+
+impl RpcModule for Subscriptions {
+    fn schema(&self) -> Module {
+        SubscriptionsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Accept the `pending` subscription and feed it until it is closed: Each value received from
+/// `rx` is converted into zero or more items using `render`, which are sent to the subscriber.
+///
+/// Sending applies back-pressure from the connection's bounded message buffer, and if the
+/// subscriber falls behind far enough that `rx` lags, the subscription is closed with an error,
+/// rather than silently dropping items. The subscription holds its `permit` until it is closed.
+///
+/// Returning an error closes the subscription and sends the error to the subscriber.
+async fn run_subscription<I, T, F, Fut>(
+    pending: PendingSubscriptionSink,
+    mut rx: broadcast::Receiver<Arc<I>>,
+    permit: OwnedSemaphorePermit,
+    render: F,
+) -> SubscriptionResult
+where
+    I: Send + Sync,
+    T: Serialize + Send,
+    F: Fn(Arc<I>) -> Fut + Send,
+    Fut: Future<Output = anyhow::Result<Vec<T>>> + Send,
+{
+    let _permit = permit;
+    let sink = pending.accept().await?;
+
+    loop {
+        let value = match rx.recv().await {
+            Ok(value) => value,
+
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    subscription = ?sink.subscription_id(),
+                    skipped, "Subscription fell behind, closing"
+                );
+                return Err(format!(
+                    "Subscription fell behind by {skipped} transactions and was closed, \
+                     re-subscribe to continue"
+                )
+                .into());
+            }
+
+            Err(RecvError::Closed) => return Err("Subscription source shut down".into()),
+        };
+
+        let items = match render(value).await {
+            Ok(items) => items,
+            Err(e) => {
+                warn!(subscription = ?sink.subscription_id(), "Failed to render item: {e:#}");
+                return Err("Failed to render subscription item".into());
+            }
+        };
+
+        for item in items {
+            let message = SubscriptionMessage::from_json(&item)
+                .map_err(|_| "Failed to serialize subscription item")?;
+
+            if sink.send(message).await.is_err() {
+                debug!(subscription = ?sink.subscription_id(), "Subscriber disconnected");
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        time::Duration,
+    };
+
+    use jsonrpsee::{
+        core::client::{Subscription, SubscriptionClientT},
+        rpc_params,
+        ws_client::WsClientBuilder,
+    };
+    use prometheus::Registry;
+    use sui_pg_db::temp::get_available_port;
+    use tokio::task::JoinHandle;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{RpcArgs, RpcService};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscription() {
+        let (tx, _) = broadcast::channel(10);
+        let (url, cancel, handle) = test_service(true, tx.clone()).await;
+
+        let client = WsClientBuilder::default().build(&url).await.unwrap();
+        let mut sub = subscribe_numbers(&client).await;
+
+        for n in [1, 2, 3] {
+            tx.send(Arc::new(n)).unwrap();
+        }
+
+        for n in [1, 2, 3] {
+            let next = tokio::time::timeout(Duration::from_millis(500), sub.next())
+                .await
+                .expect("Subscription should not timeout");
+            assert_eq!(next.unwrap().unwrap(), n);
+        }
+
+        shutdown(cancel, handle).await;
+    }
+
+    #[tokio::test]
+    async fn test_subscription_lagged() {
+        let (tx, _) = broadcast::channel(2);
+        let (url, cancel, handle) = test_service(true, tx.clone()).await;
+
+        let client = WsClientBuilder::default().build(&url).await.unwrap();
+        let mut sub = subscribe_numbers(&client).await;
+
+        // Overflow the subscription's buffer before it gets a chance to read from it.
+        for n in 0..10 {
+            tx.send(Arc::new(n)).unwrap();
+        }
+
+        // The subscription is closed, rather than skipping over the values it missed.
+        let next = tokio::time::timeout(Duration::from_millis(500), sub.next())
+            .await
+            .expect("Subscription should not timeout");
+        assert!(!matches!(next, Some(Ok(_))), "Unexpected item: {next:?}");
+
+        // ...but the connection stays open.
+        assert!(client.is_connected());
+
+        shutdown(cancel, handle).await;
+    }
+
+    #[tokio::test]
+    async fn test_subscription_http_only() {
+        let (tx, _) = broadcast::channel(10);
+        let (url, cancel, handle) = test_service(false, tx).await;
+
+        assert!(WsClientBuilder::default().build(&url).await.is_err());
+
+        shutdown(cancel, handle).await;
+    }
+
+    // Test Helpers
+
+    #[open_rpc(namespace = "test", tag = "Test API")]
+    #[rpc(server, namespace = "test")]
+    trait NumbersApi {
+        #[subscription(name = "subscribeNumbers", item = u64)]
+        async fn subscribe_numbers(&self) -> SubscriptionResult;
+    }
+
+    struct Numbers {
+        tx: broadcast::Sender<Arc<u64>>,
+        permits: Arc<Semaphore>,
+    }
+
+    #[async_trait::async_trait]
+    impl NumbersApiServer for Numbers {
+        async fn subscribe_numbers(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+            let permit = self.permits.clone().try_acquire_owned()?;
+            run_subscription(pending, self.tx.subscribe(), permit, |n| async move {
+                Ok(vec![*n])
+            })
+            .await
+        }
+    }
+
+    impl RpcModule for Numbers {
+        fn schema(&self) -> Module {
+            NumbersApiOpenRpc::module_doc()
+        }
+
+        fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+            self.into_rpc()
+        }
+    }
+
+    async fn test_service(
+        websocket: bool,
+        tx: broadcast::Sender<Arc<u64>>,
+    ) -> (String, CancellationToken, JoinHandle<()>) {
+        let cancel = CancellationToken::new();
+        let rpc_listen_address =
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), get_available_port());
+
+        let mut rpc = RpcService::new(
+            RpcArgs {
+                rpc_listen_address,
+                websocket,
+                ..Default::default()
+            },
+            &Registry::new(),
+            cancel.clone(),
+        )
+        .unwrap();
+
+        rpc.add_module(Numbers {
+            tx,
+            permits: Arc::new(Semaphore::new(1)),
+        })
+        .unwrap();
+
+        let handle = rpc.run().await.unwrap();
+        (format!("ws://{rpc_listen_address}"), cancel, handle)
+    }
+
+    async fn subscribe_numbers(client: &impl SubscriptionClientT) -> Subscription<u64> {
+        client
+            .subscribe(
+                "test_subscribeNumbers",
+                rpc_params![],
+                "test_unsubscribeNumbers",
+            )
+            .await
+            .expect("Subscription should succeed")
+    }
+
+    async fn shutdown(cancel: CancellationToken, handle: JoinHandle<()>) {
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_millis(500), handle)
+            .await
+            .expect("Shutdown should not timeout")
+            .expect("Shutdown should succeed");
+    }
+}
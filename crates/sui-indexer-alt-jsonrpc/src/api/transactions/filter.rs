@@ -15,16 +15,19 @@ use diesel::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_indexer_alt_schema::schema::{
-    tx_affected_addresses, tx_affected_objects, tx_calls, tx_digests,
+use sui_indexer_alt_schema::{
+    schema::{tx_affected_addresses, tx_affected_objects, tx_calls, tx_digests},
+    transactions::affected_addresses,
 };
 use sui_json_rpc_types::{Page as PageResponse, SuiTransactionBlockResponseOptions};
 use sui_sql_macro::sql;
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
     digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI},
     messages_checkpoint::CheckpointSequenceNumber,
     sui_serde::{BigInt, Readable},
+    transaction::{TransactionData, TransactionDataAPI},
 };
 
 use crate::{
@@ -77,6 +80,45 @@ pub(crate) enum TransactionFilter {
 type Cursor = JsonCursor<u64>;
 type Digests = PageResponse<TransactionDigest, String>;
 
+impl TransactionFilter {
+    /// Whether the transaction with the given `data` and `effects`, included in checkpoint
+    /// `checkpoint`, is selected by this filter. This mirrors the criteria the indexer uses to
+    /// populate the tables that back transaction queries, and is used to filter newly indexed
+    /// transactions for subscriptions.
+    pub(crate) fn matches(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        data: &TransactionData,
+        effects: &TransactionEffects,
+    ) -> bool {
+        // Shares its definition of affected addresses with the `tx_affected_addresses` pipeline.
+        let affects = |address: &SuiAddress| affected_addresses(data, effects).contains(address);
+
+        use TransactionFilter as F;
+        match self {
+            F::Checkpoint(seq) => *seq == checkpoint,
+
+            F::MoveFunction {
+                package,
+                module,
+                function,
+            } => data.move_calls().into_iter().any(|(p, m, f)| {
+                p == package
+                    && module.as_ref().is_none_or(|module| module == m)
+                    && function.as_ref().is_none_or(|function| function == f)
+            }),
+
+            F::AffectedObject(object) => effects.object_changes().iter().any(|c| c.id == *object),
+
+            F::FromAddress(from) => data.sender() == *from,
+
+            F::FromAndToAddress { from, to } => data.sender() == *from && affects(to),
+
+            F::FromOrToAddress { addr } => affects(addr),
+        }
+    }
+}
+
 /// Fetch the digests for a page of transactions that satisfy the given `filter` and pagination
 /// parameters. Returns the digests and a cursor pointing to the last result (if there are any
 /// results).
@@ -18,7 +18,7 @@ use crate::{
 use super::rpc_module::RpcModule;
 
 mod error;
-mod filter;
+pub(crate) mod filter;
 mod response;
 
 #[open_rpc(namespace = "sui", tag = "Transactions API")]
@@ -5,12 +5,11 @@ use std::str::FromStr;
 
 use anyhow::Context as _;
 use futures::future::OptionFuture;
-use move_core_types::annotated_value::{MoveDatatypeLayout, MoveTypeLayout};
 use sui_indexer_alt_schema::transactions::{BalanceChange, StoredTxBalanceChange};
 use sui_json_rpc_types::{
-    BalanceChange as SuiBalanceChange, ObjectChange as SuiObjectChange, SuiEvent,
-    SuiTransactionBlock, SuiTransactionBlockData, SuiTransactionBlockEffects,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    BalanceChange as SuiBalanceChange, ObjectChange as SuiObjectChange, SuiTransactionBlock,
+    SuiTransactionBlockData, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_types::{
     base_types::{ObjectID, SequenceNumber},
@@ -25,6 +24,7 @@ use sui_types::{
 use tokio::join;
 
 use crate::{
+    api::events::response::event,
     context::Context,
     data::{
         kv_loader::TransactionContents, objects::VersionedObjectKey,
@@ -133,30 +133,8 @@ async fn events(
     let events: Vec<Event> = tx.events()?;
     let mut sui_events = Vec::with_capacity(events.len());
 
-    for (ix, event) in events.into_iter().enumerate() {
-        let layout = match ctx
-            .package_resolver()
-            .type_layout(event.type_.clone().into())
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to resolve layout for {}",
-                    event.type_.to_canonical_display(/* with_prefix */ true)
-                )
-            })? {
-            MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
-            MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
-            _ => rpc_bail!(
-                "Event {ix} is not a struct or enum: {}",
-                event.type_.to_canonical_string(/* with_prefix */ true)
-            ),
-        };
-
-        let sui_event =
-            SuiEvent::try_from(event, digest, ix as u64, Some(tx.timestamp_ms()), layout)
-                .with_context(|| format!("Failed to convert Event {ix} into response"))?;
-
-        sui_events.push(sui_event)
+    for (ix, ev) in events.into_iter().enumerate() {
+        sui_events.push(event(ctx, digest, ix as u64, tx.timestamp_ms(), ev).await?);
     }
 
     Ok(SuiTransactionBlockEvents { data: sui_events })
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::mem;
+use std::{mem, time::Duration};
 
 use sui_default_config::DefaultConfig;
 use sui_protocol_config::ProtocolConfig;
@@ -18,15 +18,33 @@ pub struct RpcConfig {
     /// Configuration for transaction-related RPC methods.
     pub transactions: TransactionsConfig,
 
+    /// Configuration for event-related RPC methods.
+    pub events: EventsConfig,
+
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsConfig,
+
+    /// Configuration for epoch-related RPC methods.
+    pub epochs: EpochsConfig,
+
+    /// Configuration for governance and staking RPC methods.
+    pub governance: GovernanceConfig,
+
     /// Configuration for SuiNS related RPC methods.
     pub name_service: NameServiceConfig,
 
     /// Configuration for coin-related RPC methods.
     pub coins: CoinsConfig,
 
+    /// Configuration for RPC methods that query ownership and balances at past checkpoints.
+    pub history: HistoryConfig,
+
     /// Configuration for transaction execution RPC methods.
     pub write: WriteConfig,
 
+    /// Configuration for subscriptions to newly indexed transactions and events.
+    pub subscriptions: SubscriptionsConfig,
+
     /// Configuration for bigtable kv store, if it is used.
     pub bigtable: Option<BigtableConfig>,
 
@@ -43,15 +61,33 @@ pub struct RpcLayer {
     /// Configuration for transaction-related RPC methods.
     pub transactions: TransactionsLayer,
 
+    /// Configuration for event-related RPC methods.
+    pub events: EventsLayer,
+
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsLayer,
+
+    /// Configuration for epoch-related RPC methods.
+    pub epochs: EpochsLayer,
+
+    /// Configuration for governance and staking RPC methods.
+    pub governance: GovernanceLayer,
+
     /// Configuration for SuiNS related RPC methods.
     pub name_service: NameServiceLayer,
 
     /// Configuration for coin-related RPC methods.
     pub coins: CoinsLayer,
 
+    /// Configuration for RPC methods that query ownership and balances at past checkpoints.
+    pub history: HistoryLayer,
+
     /// Configuration for transaction execution RPC methods.
     pub write: WriteLayer,
 
+    /// Configuration for subscriptions to newly indexed transactions and events.
+    pub subscriptions: SubscriptionsLayer,
+
     /// Configuration for bigtable kv store, if it is used.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bigtable: Option<BigtableConfig>,
@@ -115,6 +151,81 @@ pub struct TransactionsLayer {
     pub extra: toml::Table,
 }
 
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    /// The default page size limit when querying events, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying events. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EventsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckpointsConfig {
+    /// The default page size limit when querying checkpoints, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying checkpoints. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct CheckpointsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct EpochsConfig {
+    /// The default page size limit when querying epochs, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying epochs. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EpochsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct GovernanceConfig {
+    /// The maximum number of stakes that will be returned for a single owner.
+    pub max_stakes: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct GovernanceLayer {
+    pub max_stakes: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 pub struct NameServiceLayer {
@@ -146,6 +257,37 @@ pub struct CoinsLayer {
     pub extra: toml::Table,
 }
 
+#[derive(Debug, Clone)]
+pub struct HistoryConfig {
+    /// The default page size limit when querying the objects owned by an address at a past
+    /// checkpoint, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying the objects owned by an address at a past
+    /// checkpoint. Requesting a page larger than this is a user error.
+    pub max_page_size: usize,
+
+    /// The maximum number of checkpoints that an address's balance can be requested at in a
+    /// single request.
+    pub max_balance_checkpoints: usize,
+
+    /// The maximum number of checkpoints that a single request calculates an address's balance at
+    /// concurrently (each calculation is a separate database query).
+    pub balance_query_concurrency: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct HistoryLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+    pub max_balance_checkpoints: Option<usize>,
+    pub balance_query_concurrency: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[derive(Clone, Debug)]
 pub struct WriteConfig {
     /// The value of the header to be sent to the fullnode RPC, used to distinguish between different instances.
@@ -164,6 +306,30 @@ pub struct WriteLayer {
     pub extra: toml::Table,
 }
 
+#[derive(Clone, Debug)]
+pub struct SubscriptionsConfig {
+    /// The maximum number of subscriptions that can be open across all connections at once.
+    pub max_subscriptions: usize,
+
+    /// The number of newly indexed transactions that are buffered for each subscription. A
+    /// subscription that falls further behind than this is closed.
+    pub buffer_size: usize,
+
+    /// How long to wait between checks for newly indexed checkpoints, in milliseconds.
+    pub polling_interval_ms: u64,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct SubscriptionsLayer {
+    pub max_subscriptions: Option<usize>,
+    pub buffer_size: Option<usize>,
+    pub polling_interval_ms: Option<u64>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 pub struct BigtableConfig {
@@ -190,11 +356,17 @@ impl RpcLayer {
         Self {
             objects: ObjectsConfig::default().into(),
             transactions: TransactionsConfig::default().into(),
+            events: EventsConfig::default().into(),
+            checkpoints: CheckpointsConfig::default().into(),
+            epochs: EpochsConfig::default().into(),
+            governance: GovernanceConfig::default().into(),
             name_service: NameServiceConfig::default().into(),
             coins: CoinsConfig::default().into(),
+            history: HistoryConfig::default().into(),
             bigtable: None,
             package_resolver: PackageResolverLayer::default(),
             write: WriteConfig::default().into(),
+            subscriptions: SubscriptionsConfig::default().into(),
             extra: Default::default(),
         }
     }
@@ -204,9 +376,15 @@ impl RpcLayer {
         RpcConfig {
             objects: self.objects.finish(ObjectsConfig::default()),
             transactions: self.transactions.finish(TransactionsConfig::default()),
+            events: self.events.finish(EventsConfig::default()),
+            checkpoints: self.checkpoints.finish(CheckpointsConfig::default()),
+            epochs: self.epochs.finish(EpochsConfig::default()),
+            governance: self.governance.finish(GovernanceConfig::default()),
             name_service: self.name_service.finish(NameServiceConfig::default()),
             coins: self.coins.finish(CoinsConfig::default()),
+            history: self.history.finish(HistoryConfig::default()),
             write: self.write.finish(WriteConfig::default()),
+            subscriptions: self.subscriptions.finish(SubscriptionsConfig::default()),
             bigtable: self.bigtable,
             package_resolver: self.package_resolver.finish(),
         }
@@ -242,6 +420,45 @@ impl TransactionsLayer {
     }
 }
 
+impl EventsLayer {
+    pub fn finish(self, base: EventsConfig) -> EventsConfig {
+        check_extra("events", self.extra);
+        EventsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl CheckpointsLayer {
+    pub fn finish(self, base: CheckpointsConfig) -> CheckpointsConfig {
+        check_extra("checkpoints", self.extra);
+        CheckpointsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl EpochsLayer {
+    pub fn finish(self, base: EpochsConfig) -> EpochsConfig {
+        check_extra("epochs", self.extra);
+        EpochsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl GovernanceLayer {
+    pub fn finish(self, base: GovernanceConfig) -> GovernanceConfig {
+        check_extra("governance", self.extra);
+        GovernanceConfig {
+            max_stakes: self.max_stakes.unwrap_or(base.max_stakes),
+        }
+    }
+}
+
 impl NameServiceLayer {
     pub fn finish(self, base: NameServiceConfig) -> NameServiceConfig {
         check_extra("name service", self.extra);
@@ -263,6 +480,22 @@ impl CoinsLayer {
     }
 }
 
+impl HistoryLayer {
+    pub fn finish(self, base: HistoryConfig) -> HistoryConfig {
+        check_extra("history", self.extra);
+        HistoryConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+            max_balance_checkpoints: self
+                .max_balance_checkpoints
+                .unwrap_or(base.max_balance_checkpoints),
+            balance_query_concurrency: self
+                .balance_query_concurrency
+                .unwrap_or(base.balance_query_concurrency),
+        }
+    }
+}
+
 impl WriteLayer {
     pub fn finish(self, base: WriteConfig) -> WriteConfig {
         check_extra("write", self.extra);
@@ -273,6 +506,17 @@ impl WriteLayer {
     }
 }
 
+impl SubscriptionsLayer {
+    pub fn finish(self, base: SubscriptionsConfig) -> SubscriptionsConfig {
+        check_extra("subscriptions", self.extra);
+        SubscriptionsConfig {
+            max_subscriptions: self.max_subscriptions.unwrap_or(base.max_subscriptions),
+            buffer_size: self.buffer_size.unwrap_or(base.buffer_size),
+            polling_interval_ms: self.polling_interval_ms.unwrap_or(base.polling_interval_ms),
+        }
+    }
+}
+
 impl PackageResolverLayer {
     pub fn finish(self) -> sui_package_resolver::Limits {
         check_extra("package-resolver", self.extra);
@@ -285,14 +529,26 @@ impl PackageResolverLayer {
     }
 }
 
+impl SubscriptionsConfig {
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_millis(self.polling_interval_ms)
+    }
+}
+
 impl Default for RpcConfig {
     fn default() -> Self {
         Self {
             objects: ObjectsConfig::default(),
             transactions: TransactionsConfig::default(),
+            events: EventsConfig::default(),
+            checkpoints: CheckpointsConfig::default(),
+            epochs: EpochsConfig::default(),
+            governance: GovernanceConfig::default(),
             name_service: NameServiceConfig::default(),
             coins: CoinsConfig::default(),
+            history: HistoryConfig::default(),
             write: WriteConfig::default(),
+            subscriptions: SubscriptionsConfig::default(),
             bigtable: None,
             package_resolver: PackageResolverLayer::default().finish(),
         }
@@ -320,6 +576,39 @@ impl Default for TransactionsConfig {
     }
 }
 
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for EpochsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        Self { max_stakes: 1000 }
+    }
+}
+
 impl Default for CoinsConfig {
     fn default() -> Self {
         Self {
@@ -329,6 +618,17 @@ impl Default for CoinsConfig {
     }
 }
 
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+            max_balance_checkpoints: 100,
+            balance_query_concurrency: 10,
+        }
+    }
+}
+
 impl Default for WriteConfig {
     fn default() -> Self {
         Self {
@@ -338,6 +638,16 @@ impl Default for WriteConfig {
     }
 }
 
+impl Default for SubscriptionsConfig {
+    fn default() -> Self {
+        Self {
+            max_subscriptions: 100,
+            buffer_size: 1000,
+            polling_interval_ms: 500,
+        }
+    }
+}
+
 impl Default for PackageResolverLayer {
     fn default() -> Self {
         // SAFETY: Accessing the max supported config by the binary (and disregarding specific
@@ -379,6 +689,45 @@ impl From<TransactionsConfig> for TransactionsLayer {
     }
 }
 
+impl From<EventsConfig> for EventsLayer {
+    fn from(config: EventsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<CheckpointsConfig> for CheckpointsLayer {
+    fn from(config: CheckpointsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<EpochsConfig> for EpochsLayer {
+    fn from(config: EpochsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<GovernanceConfig> for GovernanceLayer {
+    fn from(config: GovernanceConfig) -> Self {
+        Self {
+            max_stakes: Some(config.max_stakes),
+            extra: Default::default(),
+        }
+    }
+}
+
 impl From<NameServiceConfig> for NameServiceLayer {
     fn from(config: NameServiceConfig) -> Self {
         Self {
@@ -400,6 +749,18 @@ impl From<CoinsConfig> for CoinsLayer {
     }
 }
 
+impl From<HistoryConfig> for HistoryLayer {
+    fn from(config: HistoryConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            max_balance_checkpoints: Some(config.max_balance_checkpoints),
+            balance_query_concurrency: Some(config.balance_query_concurrency),
+            extra: Default::default(),
+        }
+    }
+}
+
 impl From<WriteConfig> for WriteLayer {
     fn from(config: WriteConfig) -> Self {
         Self {
@@ -410,6 +771,17 @@ impl From<WriteConfig> for WriteLayer {
     }
 }
 
+impl From<SubscriptionsConfig> for SubscriptionsLayer {
+    fn from(config: SubscriptionsConfig) -> Self {
+        Self {
+            max_subscriptions: Some(config.max_subscriptions),
+            buffer_size: Some(config.buffer_size),
+            polling_interval_ms: Some(config.polling_interval_ms),
+            extra: Default::default(),
+        }
+    }
+}
+
 /// Check whether there are any unrecognized extra fields and if so, warn about them.
 fn check_extra(pos: &str, extra: toml::Table) {
     if !extra.is_empty() {
@@ -0,0 +1,217 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use diesel::{
+    sql_query,
+    sql_types::{BigInt, Nullable},
+    QueryableByName,
+};
+use futures::future;
+use sui_types::{
+    digests::TransactionDigest, effects::TransactionEffects, event::Event,
+    transaction::TransactionData,
+};
+use tokio::{sync::broadcast, task::JoinHandle, time};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{config::SubscriptionsConfig, context::Context};
+
+/// A transaction from a newly indexed checkpoint, as broadcast to subscriptions.
+pub(crate) struct IndexedTransaction {
+    pub checkpoint: u64,
+    pub digest: TransactionDigest,
+    pub timestamp_ms: u64,
+    pub data: TransactionData,
+    pub effects: TransactionEffects,
+    pub events: Vec<Event>,
+}
+
+/// Background task responsible for tailing checkpoints as they are indexed, and broadcasting their
+/// transactions to subscriptions.
+pub(crate) struct CheckpointTailTask {
+    /// Access to the database and kv store.
+    context: Context,
+    /// Channel to broadcast newly indexed transactions on.
+    tx: broadcast::Sender<Arc<IndexedTransaction>>,
+    /// How long to wait between checks.
+    interval: Duration,
+    /// Signal to cancel the task.
+    cancel: CancellationToken,
+}
+
+impl CheckpointTailTask {
+    pub(crate) fn new(
+        context: Context,
+        config: &SubscriptionsConfig,
+        cancel: CancellationToken,
+    ) -> Self {
+        let (tx, _) = broadcast::channel(config.buffer_size);
+        Self {
+            context,
+            tx,
+            interval: config.polling_interval(),
+            cancel,
+        }
+    }
+
+    /// A handle for subscribing to the transactions broadcast by this task. Each receiver buffers
+    /// up to the configured `buffer_size` transactions, beyond which it will start to lag.
+    pub(crate) fn sender(&self) -> broadcast::Sender<Arc<IndexedTransaction>> {
+        self.tx.clone()
+    }
+
+    /// Start a new task that regularly polls the database for the latest checkpoint that has been
+    /// written to the kv store, and broadcasts the transactions from any new checkpoints it finds.
+    /// The task only tails checkpoints while there are subscribers: when there are none, it
+    /// resumes from the latest checkpoint the next time a subscriber appears.
+    ///
+    /// This operation consumes the `self` and returns a handle to the spawned tokio task. The task
+    /// will continue to run until its cancellation token is triggered.
+    pub(crate) fn run(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let Self {
+                context,
+                tx,
+                interval,
+                cancel,
+            } = self;
+
+            let mut next_checkpoint: Option<u64> = None;
+            let mut interval = time::interval(interval);
+
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => {
+                        info!("Shutdown signal received, terminating checkpoint tail task");
+                        break;
+                    }
+
+                    _ = interval.tick() => {
+                        if tx.receiver_count() == 0 {
+                            next_checkpoint = None;
+                            continue;
+                        }
+
+                        let checkpoint_hi = match latest_checkpoint(&context).await {
+                            Ok(Some(hi)) => hi,
+
+                            Ok(None) => {
+                                info!("KV store isn't populated yet, no checkpoints to tail");
+                                continue;
+                            }
+
+                            Err(e) => {
+                                error!("Failed to fetch latest checkpoint: {e:#}");
+                                continue;
+                            }
+                        };
+
+                        // Start tailing from the first checkpoint after the latest one at the time
+                        // of the first subscription.
+                        let next = next_checkpoint.get_or_insert(checkpoint_hi + 1);
+
+                        while *next <= checkpoint_hi {
+                            let transactions = match checkpoint_transactions(&context, *next).await {
+                                Ok(transactions) => transactions,
+                                Err(e) => {
+                                    error!(checkpoint = *next, "Failed to load checkpoint: {e:#}");
+                                    break;
+                                }
+                            };
+
+                            for transaction in transactions {
+                                // An error means there are no receivers left, which will be
+                                // detected on the next tick.
+                                let _ = tx.send(Arc::new(transaction));
+                            }
+
+                            *next += 1;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// The latest checkpoint whose checkpoint and transaction data has been written to the kv store,
+/// or `None` if the relevant pipelines have not written anything yet.
+async fn latest_checkpoint(ctx: &Context) -> anyhow::Result<Option<u64>> {
+    #[derive(QueryableByName, Copy, Clone)]
+    struct Watermark {
+        #[diesel(sql_type = Nullable<BigInt>)]
+        checkpoint_hi_inclusive: Option<i64>,
+    }
+
+    let query = sql_query(
+        r#"
+        SELECT
+            MIN(checkpoint_hi_inclusive) AS checkpoint_hi_inclusive
+        FROM
+            watermarks
+        WHERE
+            pipeline IN ('kv_checkpoints', 'kv_transactions')
+        HAVING
+            COUNT(*) = 2
+        "#,
+    );
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let watermarks: Vec<Watermark> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch watermarks")?;
+
+    Ok(watermarks
+        .first()
+        .and_then(|w| w.checkpoint_hi_inclusive)
+        .map(|hi| hi as u64))
+}
+
+/// Load the contents of all the transactions in `checkpoint`, in execution order.
+async fn checkpoint_transactions(
+    ctx: &Context,
+    checkpoint: u64,
+) -> anyhow::Result<Vec<IndexedTransaction>> {
+    let (_, contents, _) = ctx
+        .kv_loader()
+        .load_one_checkpoint(checkpoint)
+        .await
+        .context("Failed to load checkpoint")?
+        .context("Checkpoint not found")?;
+
+    let tx_futures = contents
+        .iter()
+        .map(|digests| ctx.kv_loader().load_one_transaction(digests.transaction));
+
+    let mut transactions = Vec::with_capacity(contents.size());
+    for (tx, digests) in future::join_all(tx_futures)
+        .await
+        .into_iter()
+        .zip(contents.iter())
+    {
+        let tx = tx
+            .context("Failed to load transaction")?
+            .with_context(|| format!("Transaction {} not found", digests.transaction))?;
+
+        transactions.push(IndexedTransaction {
+            checkpoint,
+            digest: tx.digest()?,
+            timestamp_ms: tx.timestamp_ms(),
+            data: tx.data()?,
+            effects: tx.effects()?,
+            events: tx.events()?,
+        });
+    }
+
+    Ok(transactions)
+}
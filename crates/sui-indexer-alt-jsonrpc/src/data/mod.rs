@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod bigtable_reader;
+pub(crate) mod checkpoint_tail_task;
 pub(crate) mod checkpoints;
 pub(crate) mod displays;
 pub(crate) mod error;
@@ -16,3 +17,4 @@ pub mod system_package_task;
 pub(crate) mod transactions;
 pub(crate) mod tx_balance_changes;
 pub(crate) mod tx_digests;
+pub(crate) mod watermarks;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::Arc,
 };
 
@@ -20,6 +20,11 @@ use super::pg_reader::PgReader;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct LatestObjectVersionKey(pub ObjectID);
 
+/// Key for fetching the latest version of an object as of a given checkpoint (inclusive), not
+/// accounting for deletions or wraps, like [LatestObjectVersionKey].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct CheckpointBoundedObjectVersionKey(pub ObjectID, pub u64);
+
 #[async_trait::async_trait]
 impl Loader<LatestObjectVersionKey> for PgReader {
     type Value = StoredObjVersion;
@@ -62,3 +67,54 @@ impl Loader<LatestObjectVersionKey> for PgReader {
             .collect())
     }
 }
+
+#[async_trait::async_trait]
+impl Loader<CheckpointBoundedObjectVersionKey> for PgReader {
+    type Value = StoredObjVersion;
+    type Error = Arc<Error>;
+
+    async fn load(
+        &self,
+        keys: &[CheckpointBoundedObjectVersionKey],
+    ) -> Result<HashMap<CheckpointBoundedObjectVersionKey, StoredObjVersion>, Self::Error> {
+        use obj_versions::dsl as v;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        // Keys are typically loaded together because they were fetched as part of the same
+        // snapshot, so group them by checkpoint, to issue one query per checkpoint.
+        let mut checkpoints: BTreeMap<u64, BTreeSet<_>> = BTreeMap::new();
+        for CheckpointBoundedObjectVersionKey(id, checkpoint) in keys {
+            checkpoints
+                .entry(*checkpoint)
+                .or_default()
+                .insert(id.into_bytes());
+        }
+
+        let mut key_to_stored = HashMap::new();
+        for (checkpoint, ids) in checkpoints {
+            let obj_versions: Vec<StoredObjVersion> = conn
+                .results(
+                    v::obj_versions
+                        .filter(v::object_id.eq_any(ids))
+                        .filter(v::cp_sequence_number.le(checkpoint as i64))
+                        .distinct_on(v::object_id)
+                        .order((v::object_id, v::object_version.desc())),
+                )
+                .await
+                .map_err(Arc::new)?;
+
+            for stored in obj_versions {
+                let id = ObjectID::from_bytes(&stored.object_id)
+                    .map_err(|e| Arc::new(Error::Serde(e.into())))?;
+                key_to_stored.insert(CheckpointBoundedObjectVersionKey(id, checkpoint), stored);
+            }
+        }
+
+        Ok(key_to_stored)
+    }
+}
@@ -14,8 +14,11 @@ use sui_types::{base_types::ObjectID, object::Object, storage::ObjectKey};
 use crate::context::Context;
 
 use super::{
-    bigtable_reader::BigtableReader, error::Error, object_info::LatestObjectInfoKey,
-    object_versions::LatestObjectVersionKey, pg_reader::PgReader,
+    bigtable_reader::BigtableReader,
+    error::Error,
+    object_info::LatestObjectInfoKey,
+    object_versions::{CheckpointBoundedObjectVersionKey, LatestObjectVersionKey},
+    pg_reader::PgReader,
 };
 
 /// Key for fetching the contents a particular version of an object.
@@ -129,6 +132,33 @@ pub(crate) async fn load_latest(
     Ok(object)
 }
 
+/// Load the contents of an object as of checkpoint `checkpoint` (inclusive) from the store and
+/// deserialize it as an `Object`. Like [load_latest], this function does not respect deletion and
+/// wrapping. It returns `None` if the object did not exist as of that checkpoint, or its versions
+/// from that checkpoint have been pruned.
+pub(crate) async fn load_at_checkpoint(
+    ctx: &Context,
+    object_id: ObjectID,
+    checkpoint: u64,
+) -> Result<Option<Object>, anyhow::Error> {
+    let Some(version) = ctx
+        .pg_loader()
+        .load_one(CheckpointBoundedObjectVersionKey(object_id, checkpoint))
+        .await
+        .context("Failed to load version at checkpoint")?
+    else {
+        return Ok(None);
+    };
+
+    let object = ctx
+        .kv_loader()
+        .load_one_object(object_id, version.object_version as u64)
+        .await
+        .context("Failed to load object at checkpoint")?;
+
+    Ok(object)
+}
+
 /// Fetch the latest version of the object at ID `object_id`, and deserialize its contents as a
 /// Rust type `T`, assuming that it is a Move object (not a package). This function does not
 /// respect deletion and wrapping, see [load_latest] for more information.
@@ -0,0 +1,53 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::{ExpressionMethods, QueryDsl};
+use sui_indexer_alt_schema::schema::watermarks;
+
+use super::{error::Error, pg_reader::PgReader};
+
+/// A range of checkpoints (inclusive at both ends) whose data is available to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CheckpointRange {
+    pub lo: u64,
+    pub hi: u64,
+}
+
+impl CheckpointRange {
+    pub(crate) fn contains(&self, checkpoint: u64) -> bool {
+        (self.lo..=self.hi).contains(&checkpoint)
+    }
+}
+
+/// The range of checkpoints that can be read consistently across the tables written by all of
+/// `pipelines`: From the highest reader low watermark (the data below it may have been pruned from
+/// at least one of the tables), to the lowest committer high watermark (the data above it may not
+/// have been written to at least one of the tables yet).
+///
+/// Returns `None` if any of the pipelines has not written a watermark yet, or if the pipelines'
+/// ranges do not overlap.
+pub(crate) async fn consistent_range(
+    pg_reader: &PgReader,
+    pipelines: &[&'static str],
+) -> Result<Option<CheckpointRange>, Error> {
+    use watermarks::dsl as w;
+
+    let mut conn = pg_reader.connect().await?;
+
+    let bounds: Vec<(i64, i64)> = conn
+        .results(
+            w::watermarks
+                .select((w::reader_lo, w::checkpoint_hi_inclusive))
+                .filter(w::pipeline.eq_any(pipelines.to_vec())),
+        )
+        .await?;
+
+    if bounds.len() < pipelines.len() {
+        return Ok(None);
+    }
+
+    let lo = bounds.iter().map(|(lo, _)| *lo as u64).max().unwrap_or(0);
+    let hi = bounds.iter().map(|(_, hi)| *hi as u64).min().unwrap_or(0);
+
+    Ok((lo <= hi).then_some(CheckpointRange { lo, hi }))
+}
@@ -8,13 +8,18 @@ use anyhow::Context as _;
 use api::checkpoints::Checkpoints;
 use api::coin::Coins;
 use api::dynamic_fields::DynamicFields;
+use api::epochs::Epochs;
+use api::events::QueryEvents;
+use api::history::History;
 use api::move_utils::MoveUtils;
 use api::name_service::NameService;
 use api::objects::{Objects, QueryObjects};
 use api::rpc_module::RpcModule;
+use api::subscriptions::Subscriptions;
 use api::transactions::{QueryTransactions, Transactions};
 use api::write::{Write, WriteArgs};
 use config::RpcConfig;
+use data::checkpoint_tail_task::CheckpointTailTask;
 use data::system_package_task::{SystemPackageTask, SystemPackageTaskArgs};
 use jsonrpsee::server::{BatchRequestConfig, RpcServiceBuilder, ServerBuilder};
 use metrics::middleware::MetricsLayer;
@@ -51,6 +56,12 @@ pub struct RpcArgs {
     /// many requests, it will start responding with 429.
     #[clap(long, default_value_t = Self::default().max_in_flight_requests)]
     pub max_in_flight_requests: u32,
+
+    /// Accept WebSocket connections in addition to HTTP, and serve subscriptions to newly indexed
+    /// transactions and events over them. Off by default, in which case the service only accepts
+    /// HTTP connections.
+    #[clap(long)]
+    pub websocket: bool,
 }
 
 pub struct RpcService {
@@ -84,12 +95,20 @@ impl RpcService {
         let RpcArgs {
             rpc_listen_address,
             max_in_flight_requests,
+            websocket,
         } = rpc_args;
 
         let metrics = RpcMetrics::new(registry);
 
-        let server = ServerBuilder::new()
-            .http_only()
+        // WebSocket connections are only accepted if they have been opted into, to support
+        // subscriptions.
+        let server = if websocket {
+            ServerBuilder::new()
+        } else {
+            ServerBuilder::new().http_only()
+        };
+
+        let server = server
             // `jsonrpsee` calls this a limit on connections, but it is implemented as a limit on
             // requests.
             .max_connections(max_in_flight_requests)
@@ -205,6 +224,7 @@ impl Default for RpcArgs {
         Self {
             rpc_listen_address: "0.0.0.0:6000".parse().unwrap(),
             max_in_flight_requests: 2000,
+            websocket: false,
         }
     }
 }
@@ -213,7 +233,8 @@ impl Default for RpcArgs {
 /// command-line). The service will continue to run until the cancellation token is triggered, and
 /// will signal cancellation on the token when it is shutting down.
 ///
-/// The service may spin up auxiliary services (such as the system package task) to support itself,
+/// The service may spin up auxiliary services (such as the system package task, or the checkpoint
+/// tail task that feeds subscriptions, if WebSocket connections are enabled) to support itself,
 /// and will clean these up on shutdown as well.
 pub async fn start_rpc(
     database_url: Url,
@@ -225,6 +246,7 @@ pub async fn start_rpc(
     registry: &Registry,
     cancel: CancellationToken,
 ) -> anyhow::Result<JoinHandle<()>> {
+    let websocket = rpc_args.websocket;
     let mut rpc = RpcService::new(rpc_args, registry, cancel.child_token())
         .context("Failed to create RPC service")?;
 
@@ -236,17 +258,33 @@ pub async fn start_rpc(
         cancel.child_token(),
     );
 
+    // Subscriptions are only served over WebSocket connections.
+    let checkpoint_tail_task = websocket.then(|| {
+        CheckpointTailTask::new(
+            context.clone(),
+            &context.config().subscriptions,
+            cancel.child_token(),
+        )
+    });
+
     rpc.add_module(Checkpoints(context.clone()))?;
     rpc.add_module(Coins(context.clone()))?;
     rpc.add_module(DynamicFields(context.clone()))?;
-    rpc.add_module(Governance(context.clone()))?;
+    rpc.add_module(Epochs(context.clone()))?;
+    rpc.add_module(Governance::new(context.clone()))?;
+    rpc.add_module(History(context.clone()))?;
     rpc.add_module(MoveUtils(context.clone()))?;
     rpc.add_module(NameService(context.clone()))?;
     rpc.add_module(Objects(context.clone()))?;
+    rpc.add_module(QueryEvents(context.clone()))?;
     rpc.add_module(QueryObjects(context.clone()))?;
     rpc.add_module(QueryTransactions(context.clone()))?;
     rpc.add_module(Transactions(context.clone()))?;
 
+    if let Some(checkpoint_tail_task) = &checkpoint_tail_task {
+        rpc.add_module(Subscriptions::new(context.clone(), checkpoint_tail_task))?;
+    }
+
     // Add the write module if a fullnode rpc url is provided.
     if let Some(write_args) = write_args {
         rpc.add_module(Write::new(write_args, context.config().write.clone())?)?;
@@ -254,11 +292,15 @@ pub async fn start_rpc(
 
     let h_rpc = rpc.run().await.context("Failed to start RPC service")?;
     let h_system_package_task = system_package_task.run();
+    let h_checkpoint_tail_task = checkpoint_tail_task.map(|t| t.run());
 
     Ok(tokio::spawn(async move {
         let _ = h_rpc.await;
         cancel.cancel();
         let _ = h_system_package_task.await;
+        if let Some(h_checkpoint_tail_task) = h_checkpoint_tail_task {
+            let _ = h_checkpoint_tail_task.await;
+        }
     }))
 }
 
@@ -3,13 +3,18 @@
 
 use std::ops::Deref;
 
+use anyhow::Context as _;
 use fastcrypto::{
     encoding::{Base64, Encoding},
     error::FastCryptoError,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::error::{invalid_params, RpcError};
+use crate::{
+    context::Context,
+    data::watermarks::{consistent_range, CheckpointRange},
+    error::{invalid_params, RpcError},
+};
 
 pub(crate) trait Cursor: Sized {
     /// Interpret the string as a cursor, Base64-decode it, and then deserialize it from JSON. A
@@ -55,6 +60,17 @@ pub(crate) enum Error {
 
     #[error("Requested page size {requested} exceeds maximum {max}")]
     ExceededMaxPageSize { requested: usize, max: usize },
+
+    #[error(
+        "Cursor is pinned to checkpoint {checkpoint}, which is outside the available range \
+         [{}, {}]",
+        range.lo,
+        range.hi
+    )]
+    PinnedCheckpointOutOfRange {
+        checkpoint: u64,
+        range: CheckpointRange,
+    },
 }
 
 impl<T: Serialize + DeserializeOwned> Cursor for BcsCursor<T> {
@@ -116,6 +132,38 @@ impl<C: Cursor> Page<C> {
     }
 }
 
+/// Choose the checkpoint that a paginated query should read its data as of, so that all its pages
+/// come from the same snapshot, even if the underlying tables are updated between requests.
+///
+/// The first page (which has no `pinned` checkpoint) reads as of the latest checkpoint whose data
+/// is available in all of the tables written by `pipelines`. Cursors for subsequent pages carry
+/// the checkpoint that was chosen for the first page, and those pages are read as of the same
+/// checkpoint, as long as it is still available. If it has since been pruned from one of the
+/// tables, the request fails, and pagination needs to start again.
+pub(crate) async fn checkpoint_snapshot<E: From<Error> + std::error::Error>(
+    ctx: &Context,
+    pipelines: &[&'static str],
+    pinned: Option<u64>,
+) -> Result<u64, RpcError<E>> {
+    let range = consistent_range(ctx.pg_reader(), pipelines)
+        .await
+        .context("Failed to fetch watermarks")?
+        .with_context(|| format!("No consistent data available for {pipelines:?}"))?;
+
+    let Some(checkpoint) = pinned else {
+        return Ok(range.hi);
+    };
+
+    if !range.contains(checkpoint) {
+        return Err(invalid_params(E::from(Error::PinnedCheckpointOutOfRange {
+            checkpoint,
+            range,
+        })));
+    }
+
+    Ok(checkpoint)
+}
+
 impl<T> Deref for BcsCursor<T> {
     type Target = T;
 
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS obj_info_history;
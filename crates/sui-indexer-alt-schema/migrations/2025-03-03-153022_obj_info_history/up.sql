@@ -0,0 +1,34 @@
+-- A table that keeps track of updates to object type and owner information, like `obj_info`, but
+-- with its own (typically much longer) retention, to answer questions about which objects an
+-- address owned at some checkpoint in the past.
+--
+-- Rows are only pruned once they have been superseded by a newer row that is itself outside the
+-- retention window, so for any checkpoint in the retention window, the latest row for each object
+-- at or before that checkpoint is still available. Unlike `obj_info`, this table is pruned purely
+-- in SQL (without relying on information held in memory by the indexer), so that its retention
+-- can span many restarts of the indexer.
+CREATE TABLE IF NOT EXISTS obj_info_history
+(
+    object_id                   BYTEA         NOT NULL,
+    cp_sequence_number          BIGINT        NOT NULL,
+    -- The object's ownership model, as in `obj_info`. NULL if the object was deleted or wrapped
+    -- at this checkpoint.
+    owner_kind                  SMALLINT,
+    -- The address for address-owned objects, and the parent object for
+    -- object-owned objects.
+    owner_id                    BYTEA,
+    -- The type's package ID, module name, name and BCS-encoded type parameters, as in
+    -- `obj_info`. These are NULL for Move Packages.
+    package                     BYTEA,
+    module                      TEXT,
+    name                        TEXT,
+    instantiation               BYTEA,
+    PRIMARY KEY (object_id, cp_sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS obj_info_history_owner
+ON obj_info_history (owner_kind, owner_id, cp_sequence_number DESC, object_id DESC);
+
+-- Used by the pruner to find the rows written in the range of checkpoints it is pruning.
+CREATE INDEX IF NOT EXISTS obj_info_history_cp_sequence_number
+ON obj_info_history (cp_sequence_number);
@@ -0,0 +1,31 @@
+-- A table of the exact balances and owners of address-owned coins, keyed on object ID and
+-- checkpoint sequence number, used to calculate an address's balance at a checkpoint in the past.
+-- A row is inserted whenever a coin's presence, owner or balance changes, and a record is kept
+-- when a coin is deleted, wrapped, or stops being address-owned, in which case all fields except
+-- the primary key will be `NULL`.
+--
+-- This table is pruned in the same way as `obj_info_history`.
+CREATE TABLE IF NOT EXISTS coin_balance_history
+(
+    object_id                   BYTEA         NOT NULL,
+    cp_sequence_number          BIGINT        NOT NULL,
+    -- The kind of owner of this coin, represented by `StoredCoinOwnerKind`, as in
+    -- `coin_balance_buckets`.
+    owner_kind                  SMALLINT,
+    -- The address that owns this version of the coin.
+    owner_id                    BYTEA,
+    -- The type of the coin, as a BCS-serialized `TypeTag` (only the marker type, e.g.
+    -- `0x0...02::sui::SUI`).
+    coin_type                   BYTEA,
+    -- The coin's balance. Balances are unsigned 64-bit integers, stored with the same bit pattern
+    -- in a signed column, so they must be converted back before being summed.
+    balance                     BIGINT,
+    PRIMARY KEY (object_id, cp_sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS coin_balance_history_owner_type
+ON coin_balance_history (owner_kind, owner_id, coin_type, cp_sequence_number DESC, object_id);
+
+-- Used by the pruner to find the rows written in the range of checkpoints it is pruning.
+CREATE INDEX IF NOT EXISTS coin_balance_history_cp_sequence_number
+ON coin_balance_history (cp_sequence_number);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS ev_emit_sender;
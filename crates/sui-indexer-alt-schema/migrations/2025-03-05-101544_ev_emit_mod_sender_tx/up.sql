@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS ev_emit_sender
+ON ev_emit_mod (sender, tx_sequence_number);
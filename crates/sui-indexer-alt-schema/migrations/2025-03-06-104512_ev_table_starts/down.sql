@@ -0,0 +1 @@
+DROP TABLE IF EXISTS ev_table_starts;
@@ -0,0 +1,8 @@
+-- The first checkpoint that each table written to by the `ev_tables` pipeline has all events from.
+-- Tables that are added after the pipeline has started indexing only have the events from the
+-- checkpoints it indexes after that, until the checkpoints before it are backfilled.
+CREATE TABLE IF NOT EXISTS ev_table_starts
+(
+    table_name                  TEXT          PRIMARY KEY,
+    cp_lo                       BIGINT        NOT NULL
+);
@@ -10,7 +10,10 @@ use diesel::{
 use sui_field_count::FieldCount;
 use sui_types::object::{Object, Owner};
 
-use crate::schema::{coin_balance_buckets, kv_objects, obj_info, obj_versions};
+use crate::schema::{
+    coin_balance_buckets, coin_balance_history, kv_objects, obj_info, obj_info_history,
+    obj_versions,
+};
 
 #[derive(Insertable, Debug, Clone, FieldCount, Queryable)]
 #[diesel(table_name = kv_objects, primary_key(object_id, object_version))]
@@ -74,6 +77,32 @@ pub struct StoredCoinBalanceBucket {
     pub coin_balance_bucket: Option<i16>,
 }
 
+#[derive(Insertable, Queryable, Debug, Clone, FieldCount, Eq, PartialEq)]
+#[diesel(table_name = obj_info_history, primary_key(object_id, cp_sequence_number))]
+#[diesel(treat_none_as_default_value = false)]
+pub struct StoredObjInfoHistory {
+    pub object_id: Vec<u8>,
+    pub cp_sequence_number: i64,
+    pub owner_kind: Option<StoredOwnerKind>,
+    pub owner_id: Option<Vec<u8>>,
+    pub package: Option<Vec<u8>>,
+    pub module: Option<String>,
+    pub name: Option<String>,
+    pub instantiation: Option<Vec<u8>>,
+}
+
+#[derive(Insertable, Queryable, Debug, Clone, FieldCount, Eq, PartialEq)]
+#[diesel(table_name = coin_balance_history, primary_key(object_id, cp_sequence_number))]
+#[diesel(treat_none_as_default_value = false)]
+pub struct StoredCoinBalanceHistory {
+    pub object_id: Vec<u8>,
+    pub cp_sequence_number: i64,
+    pub owner_kind: Option<StoredCoinOwnerKind>,
+    pub owner_id: Option<Vec<u8>>,
+    pub coin_type: Option<Vec<u8>>,
+    pub balance: Option<i64>,
+}
+
 impl StoredObjInfo {
     pub fn from_object(object: &Object, cp_sequence_number: i64) -> anyhow::Result<Self> {
         let type_ = object.type_();
@@ -117,6 +146,21 @@ impl StoredObjInfo {
     }
 }
 
+impl From<StoredObjInfo> for StoredObjInfoHistory {
+    fn from(info: StoredObjInfo) -> Self {
+        Self {
+            object_id: info.object_id,
+            cp_sequence_number: info.cp_sequence_number,
+            owner_kind: info.owner_kind,
+            owner_id: info.owner_id,
+            package: info.package,
+            module: info.module,
+            name: info.name,
+            instantiation: info.instantiation,
+        }
+    }
+}
+
 impl<DB: Backend> serialize::ToSql<SmallInt, DB> for StoredOwnerKind
 where
     i16: serialize::ToSql<SmallInt, DB>,
@@ -13,11 +13,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    coin_balance_history (object_id, cp_sequence_number) {
+        object_id -> Bytea,
+        cp_sequence_number -> Int8,
+        owner_kind -> Nullable<Int2>,
+        owner_id -> Nullable<Bytea>,
+        coin_type -> Nullable<Bytea>,
+        balance -> Nullable<Int8>,
+    }
+}
+
 diesel::table! {
     cp_sequence_numbers (cp_sequence_number) {
         cp_sequence_number -> Int8,
         tx_lo -> Int8,
         epoch -> Int8,
+        timestamp_ms -> Nullable<Int8>,
     }
 }
 
@@ -41,6 +53,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    ev_table_starts (table_name) {
+        table_name -> Text,
+        cp_lo -> Int8,
+    }
+}
+
 diesel::table! {
     kv_checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -137,6 +156,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    obj_info_history (object_id, cp_sequence_number) {
+        object_id -> Bytea,
+        cp_sequence_number -> Int8,
+        owner_kind -> Nullable<Int2>,
+        owner_id -> Nullable<Bytea>,
+        package -> Nullable<Bytea>,
+        module -> Nullable<Text>,
+        name -> Nullable<Text>,
+        instantiation -> Nullable<Bytea>,
+    }
+}
+
 diesel::table! {
     obj_versions (object_id, object_version) {
         object_id -> Bytea,
@@ -227,9 +259,11 @@ diesel::table! {
 
 diesel::allow_tables_to_appear_in_same_query!(
     coin_balance_buckets,
+    coin_balance_history,
     cp_sequence_numbers,
     ev_emit_mod,
     ev_struct_inst,
+    ev_table_starts,
     kv_checkpoints,
     kv_epoch_ends,
     kv_epoch_starts,
@@ -239,6 +273,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     kv_protocol_configs,
     kv_transactions,
     obj_info,
+    obj_info_history,
     obj_versions,
     sum_displays,
     sum_packages,
@@ -14,8 +14,14 @@ use diesel::{
     sql_types::SmallInt,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use sui_field_count::FieldCount;
-use sui_types::object::Owner;
+use sui_types::{
+    base_types::SuiAddress,
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    object::Owner,
+    transaction::{TransactionData, TransactionDataAPI},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BalanceChange {
@@ -127,3 +133,22 @@ where
         })
     }
 }
+
+/// The addresses affected by a transaction: its sender, its gas owner, and the recipients of any
+/// objects it changed. This is the set of addresses that `tx_affected_addresses` is populated
+/// with, and is shared with any code that needs to select transactions by the same criteria.
+pub fn affected_addresses(
+    data: &TransactionData,
+    effects: &TransactionEffects,
+) -> BTreeSet<SuiAddress> {
+    let recipients = effects.all_changed_objects().into_iter().filter_map(
+        |(_object_ref, owner, _write_kind)| match owner {
+            Owner::AddressOwner(address) => Some(address),
+            _ => None,
+        },
+    );
+
+    recipients
+        .chain([data.sender(), data.gas_owner()])
+        .collect()
+}
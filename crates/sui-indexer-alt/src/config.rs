@@ -1,15 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::mem;
+use std::{collections::BTreeMap, mem};
 
 use sui_default_config::DefaultConfig;
 use sui_indexer_alt_framework::{
     ingestion::IngestionConfig,
     pipeline::{
-        concurrent::{ConcurrentConfig, PrunerConfig},
+        concurrent::{ConcurrentConfig, PrunerConfig, ShardingConfig},
         sequential::SequentialConfig,
-        CommitterConfig,
+        CommitterConfig, FailureConfig, FailurePolicy,
     },
 };
 use tracing::warn;
@@ -28,6 +28,11 @@ pub struct IndexerConfig {
     /// Configuration for the retention on consistent pipelines.
     pub consistency: PrunerLayer,
 
+    /// Configuration for the retention on history pipelines, which record how ownership and coin
+    /// balances change over time, to answer queries about past checkpoints. This is typically much
+    /// longer than the retention on consistent pipelines.
+    pub history: PrunerLayer,
+
     /// Default configuration for committers that is shared by all pipelines. Pipelines can
     /// override individual settings in their own configuration sections.
     pub committer: CommitterLayer,
@@ -42,6 +47,10 @@ pub struct IndexerConfig {
     /// Per-pipeline configurations.
     pub pipeline: PipelineLayer,
 
+    /// Tables to derive from Move event types, for the `ev_tables` pipeline to write events to,
+    /// mapping each table's name to the type of event it stores (e.g. `0xabc::pool::SwapEvent`).
+    pub event_tables: BTreeMap<String, String>,
+
     #[serde(flatten)]
     pub extra: toml::Table,
 }
@@ -80,6 +89,8 @@ pub struct SequentialLayer {
 pub struct ConcurrentLayer {
     pub committer: Option<CommitterLayer>,
     pub pruner: Option<PrunerLayer>,
+    pub sharding: Option<ShardingLayer>,
+    pub failure: Option<FailureLayer>,
 
     #[serde(flatten)]
     pub extra: toml::Table,
@@ -109,6 +120,26 @@ pub struct PrunerLayer {
     pub extra: toml::Table,
 }
 
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct ShardingLayer {
+    pub shards: Option<u64>,
+    pub lease_ttl_ms: Option<u64>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct FailureLayer {
+    pub policy: Option<FailurePolicy>,
+    pub max_retries: Option<u64>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -117,6 +148,10 @@ pub struct PipelineLayer {
     pub coin_balance_buckets: Option<CommitterLayer>,
     pub obj_info: Option<CommitterLayer>,
 
+    // History pipelines
+    pub coin_balance_history: Option<CommitterLayer>,
+    pub obj_info_history: Option<CommitterLayer>,
+
     // Sequential pipelines
     pub sum_displays: Option<SequentialLayer>,
     pub sum_packages: Option<SequentialLayer>,
@@ -125,6 +160,7 @@ pub struct PipelineLayer {
     pub cp_sequence_numbers: Option<ConcurrentLayer>,
     pub ev_emit_mod: Option<ConcurrentLayer>,
     pub ev_struct_inst: Option<ConcurrentLayer>,
+    pub ev_tables: Option<ConcurrentLayer>,
     pub kv_checkpoints: Option<ConcurrentLayer>,
     pub kv_epoch_ends: Option<ConcurrentLayer>,
     pub kv_epoch_starts: Option<ConcurrentLayer>,
@@ -156,6 +192,11 @@ impl IndexerConfig {
             retention: Some(4 * 60 * 60),
             ..Default::default()
         };
+        example.history = PrunerLayer {
+            interval_ms: Some(60_000),
+            retention: Some(90 * 24 * 60 * 60),
+            ..Default::default()
+        };
         example.committer = CommitterConfig::default().into();
         example.pruner = PrunerConfig::default().into();
         example.pipeline = PipelineLayer::example();
@@ -184,6 +225,11 @@ impl IndexerConfig {
                 delay_ms: Some(0),
                 ..Default::default()
             },
+            history: PrunerLayer {
+                interval_ms: Some(50),
+                delay_ms: Some(0),
+                ..Default::default()
+            },
             pruner: PrunerLayer {
                 interval_ms: Some(50),
                 delay_ms: Some(0),
@@ -228,7 +274,8 @@ impl SequentialLayer {
 
 impl ConcurrentLayer {
     /// Unlike other parameters, `pruner` will appear in the finished configuration only if they
-    /// appear in the layer *and* in the base.
+    /// appear in the layer *and* in the base. `sharding` is enabled if it appears in either, with
+    /// missing values taken from the defaults.
     pub fn finish(self, base: ConcurrentConfig) -> ConcurrentConfig {
         check_extra("concurrent pipeline", self.extra);
         ConcurrentConfig {
@@ -241,6 +288,15 @@ impl ConcurrentLayer {
                 (None, _) | (_, None) => None,
                 (Some(pruner), Some(base)) => Some(pruner.finish(base)),
             },
+            sharding: match (self.sharding, base.sharding) {
+                (None, base) => base,
+                (Some(sharding), base) => Some(sharding.finish(base.unwrap_or_default())),
+            },
+            failure: if let Some(failure) = self.failure {
+                failure.finish(base.failure)
+            } else {
+                base.failure
+            },
         }
     }
 }
@@ -271,6 +327,26 @@ impl PrunerLayer {
     }
 }
 
+impl ShardingLayer {
+    pub fn finish(self, base: ShardingConfig) -> ShardingConfig {
+        check_extra("sharding", self.extra);
+        ShardingConfig {
+            shards: self.shards.unwrap_or(base.shards),
+            lease_ttl_ms: self.lease_ttl_ms.unwrap_or(base.lease_ttl_ms),
+        }
+    }
+}
+
+impl FailureLayer {
+    pub fn finish(self, base: FailureConfig) -> FailureConfig {
+        check_extra("failure", self.extra);
+        FailureConfig {
+            policy: self.policy.unwrap_or(base.policy),
+            max_retries: self.max_retries.unwrap_or(base.max_retries),
+        }
+    }
+}
+
 impl PipelineLayer {
     /// Generate an example configuration, suitable for demonstrating the fields available to
     /// configure.
@@ -278,11 +354,14 @@ impl PipelineLayer {
         PipelineLayer {
             coin_balance_buckets: Some(Default::default()),
             obj_info: Some(Default::default()),
+            coin_balance_history: Some(Default::default()),
+            obj_info_history: Some(Default::default()),
             sum_displays: Some(Default::default()),
             sum_packages: Some(Default::default()),
             cp_sequence_numbers: Some(Default::default()),
             ev_emit_mod: Some(Default::default()),
             ev_struct_inst: Some(Default::default()),
+            ev_tables: Some(Default::default()),
             kv_checkpoints: Some(Default::default()),
             kv_epoch_ends: Some(Default::default()),
             kv_epoch_starts: Some(Default::default()),
@@ -314,9 +393,15 @@ impl Merge for IndexerConfig {
         IndexerConfig {
             ingestion: self.ingestion.merge(other.ingestion),
             consistency: self.consistency.merge(other.consistency),
+            history: self.history.merge(other.history),
             committer: self.committer.merge(other.committer),
             pruner: self.pruner.merge(other.pruner),
             pipeline: self.pipeline.merge(other.pipeline),
+            event_tables: {
+                let mut event_tables = self.event_tables;
+                event_tables.extend(other.event_tables);
+                event_tables
+            },
             extra: Default::default(),
         }
     }
@@ -354,6 +439,8 @@ impl Merge for ConcurrentLayer {
         ConcurrentLayer {
             committer: self.committer.merge(other.committer),
             pruner: self.pruner.merge(other.pruner),
+            sharding: self.sharding.merge(other.sharding),
+            failure: self.failure.merge(other.failure),
             extra: Default::default(),
         }
     }
@@ -393,6 +480,30 @@ impl Merge for PrunerLayer {
     }
 }
 
+impl Merge for ShardingLayer {
+    fn merge(self, other: ShardingLayer) -> ShardingLayer {
+        check_extra("sharding", self.extra);
+        check_extra("sharding", other.extra);
+        ShardingLayer {
+            shards: other.shards.or(self.shards),
+            lease_ttl_ms: other.lease_ttl_ms.or(self.lease_ttl_ms),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl Merge for FailureLayer {
+    fn merge(self, other: FailureLayer) -> FailureLayer {
+        check_extra("failure", self.extra);
+        check_extra("failure", other.extra);
+        FailureLayer {
+            policy: other.policy.or(self.policy),
+            max_retries: other.max_retries.or(self.max_retries),
+            extra: Default::default(),
+        }
+    }
+}
+
 impl Merge for PipelineLayer {
     fn merge(self, other: PipelineLayer) -> PipelineLayer {
         check_extra("pipeline", self.extra);
@@ -400,11 +511,14 @@ impl Merge for PipelineLayer {
         PipelineLayer {
             coin_balance_buckets: self.coin_balance_buckets.merge(other.coin_balance_buckets),
             obj_info: self.obj_info.merge(other.obj_info),
+            coin_balance_history: self.coin_balance_history.merge(other.coin_balance_history),
+            obj_info_history: self.obj_info_history.merge(other.obj_info_history),
             sum_displays: self.sum_displays.merge(other.sum_displays),
             sum_packages: self.sum_packages.merge(other.sum_packages),
             cp_sequence_numbers: self.cp_sequence_numbers.merge(other.cp_sequence_numbers),
             ev_emit_mod: self.ev_emit_mod.merge(other.ev_emit_mod),
             ev_struct_inst: self.ev_struct_inst.merge(other.ev_struct_inst),
+            ev_tables: self.ev_tables.merge(other.ev_tables),
             kv_checkpoints: self.kv_checkpoints.merge(other.kv_checkpoints),
             kv_epoch_ends: self.kv_epoch_ends.merge(other.kv_epoch_ends),
             kv_epoch_starts: self.kv_epoch_starts.merge(other.kv_epoch_starts),
@@ -462,6 +576,8 @@ impl From<ConcurrentConfig> for ConcurrentLayer {
         Self {
             committer: Some(config.committer.into()),
             pruner: config.pruner.map(Into::into),
+            sharding: config.sharding.map(Into::into),
+            failure: Some(config.failure.into()),
             extra: Default::default(),
         }
     }
@@ -491,6 +607,26 @@ impl From<PrunerConfig> for PrunerLayer {
     }
 }
 
+impl From<ShardingConfig> for ShardingLayer {
+    fn from(config: ShardingConfig) -> Self {
+        Self {
+            shards: Some(config.shards),
+            lease_ttl_ms: Some(config.lease_ttl_ms),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<FailureConfig> for FailureLayer {
+    fn from(config: FailureConfig) -> Self {
+        Self {
+            policy: Some(config.policy),
+            max_retries: Some(config.max_retries),
+            extra: Default::default(),
+        }
+    }
+}
+
 /// Check whether there are any unrecognized extra fields and if so, warn about them.
 fn check_extra(pos: &str, extra: toml::Table) {
     if !extra.is_empty() {
@@ -602,6 +738,8 @@ mod tests {
                         extra: _,
                     }),
                     pruner: None,
+                    sharding: None,
+                    failure: None,
                     extra: _,
                 }),
                 ..
@@ -639,6 +777,8 @@ mod tests {
                         extra: _,
                     }),
                     pruner: None,
+                    sharding: None,
+                    failure: None,
                     extra: _,
                 }),
                 ..
@@ -699,6 +839,8 @@ mod tests {
         let layer = ConcurrentLayer {
             committer: None,
             pruner: None,
+            sharding: None,
+            failure: None,
             extra: Default::default(),
         };
 
@@ -709,6 +851,8 @@ mod tests {
                 watermark_interval_ms: 500,
             },
             pruner: Some(PrunerConfig::default()),
+            sharding: None,
+            failure: FailureConfig::default(),
         };
 
         assert_matches!(
@@ -720,6 +864,8 @@ mod tests {
                     watermark_interval_ms: 500,
                 },
                 pruner: None,
+                sharding: None,
+                failure: _,
             },
         );
     }
@@ -729,6 +875,8 @@ mod tests {
         let layer = ConcurrentLayer {
             committer: None,
             pruner: None,
+            sharding: None,
+            failure: None,
             extra: Default::default(),
         };
 
@@ -739,6 +887,8 @@ mod tests {
                 watermark_interval_ms: 500,
             },
             pruner: None,
+            sharding: None,
+            failure: FailureConfig::default(),
         };
 
         assert_matches!(
@@ -750,6 +900,8 @@ mod tests {
                     watermark_interval_ms: 500,
                 },
                 pruner: None,
+                sharding: None,
+                failure: _,
             },
         );
     }
@@ -762,6 +914,8 @@ mod tests {
                 interval_ms: Some(1000),
                 ..Default::default()
             }),
+            sharding: None,
+            failure: None,
             extra: Default::default(),
         };
 
@@ -778,6 +932,8 @@ mod tests {
                 max_chunk_size: 400,
                 prune_concurrency: 1,
             }),
+            sharding: None,
+            failure: FailureConfig::default(),
         };
 
         assert_matches!(
@@ -795,6 +951,95 @@ mod tests {
                     max_chunk_size: 400,
                     prune_concurrency: 1,
                 }),
+                sharding: None,
+                failure: _,
+            },
+        );
+    }
+
+    #[test]
+    fn finish_concurrent_sharding() {
+        let layer = ConcurrentLayer {
+            committer: None,
+            pruner: None,
+            sharding: Some(ShardingLayer {
+                shards: Some(4),
+                ..Default::default()
+            }),
+            failure: None,
+            extra: Default::default(),
+        };
+
+        let base = ConcurrentConfig {
+            committer: CommitterConfig {
+                write_concurrency: 5,
+                collect_interval_ms: 50,
+                watermark_interval_ms: 500,
+            },
+            pruner: None,
+            sharding: None,
+            failure: FailureConfig::default(),
+        };
+
+        assert_matches!(
+            layer.finish(base),
+            ConcurrentConfig {
+                committer: CommitterConfig {
+                    write_concurrency: 5,
+                    collect_interval_ms: 50,
+                    watermark_interval_ms: 500,
+                },
+                pruner: None,
+                sharding: Some(ShardingConfig {
+                    shards: 4,
+                    lease_ttl_ms: 30_000,
+                }),
+                failure: _,
+            },
+        );
+    }
+
+    #[test]
+    fn finish_concurrent_failure() {
+        let layer = ConcurrentLayer {
+            committer: None,
+            pruner: None,
+            sharding: None,
+            failure: Some(FailureLayer {
+                policy: Some(FailurePolicy::RetryThenDeadLetter),
+                ..Default::default()
+            }),
+            extra: Default::default(),
+        };
+
+        let base = ConcurrentConfig {
+            committer: CommitterConfig {
+                write_concurrency: 5,
+                collect_interval_ms: 50,
+                watermark_interval_ms: 500,
+            },
+            pruner: None,
+            sharding: None,
+            failure: FailureConfig {
+                policy: FailurePolicy::RetryThenStop,
+                max_retries: 3,
+            },
+        };
+
+        assert_matches!(
+            layer.finish(base),
+            ConcurrentConfig {
+                committer: CommitterConfig {
+                    write_concurrency: 5,
+                    collect_interval_ms: 50,
+                    watermark_interval_ms: 500,
+                },
+                pruner: None,
+                sharding: None,
+                failure: FailureConfig {
+                    policy: FailurePolicy::RetryThenDeadLetter,
+                    max_retries: 3,
+                },
             },
         );
     }
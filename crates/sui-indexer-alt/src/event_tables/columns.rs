@@ -0,0 +1,550 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeSet, fmt};
+
+use anyhow::{bail, ensure, Context, Result};
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
+    identifier::{IdentStr, Identifier},
+    language_storage::StructTag,
+};
+use serde_json::{json, Map, Value};
+use sui_indexer_alt_framework::types::{
+    base_types::{RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR},
+    id::{OBJECT_MODULE_NAME, RESOLVED_SUI_ID, UID_STRUCT_NAME},
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+/// Postgres limits identifiers to this many bytes, and silently truncates longer ones.
+const MAX_IDENTIFIER_LEN: usize = 63;
+
+/// A column that a field (or a nested field) of an event is stored in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Column {
+    pub name: String,
+    pub type_: ColumnType,
+}
+
+/// The Postgres types that Move values are stored as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnType {
+    /// `bool`
+    Boolean,
+
+    /// `u8` and `u16`.
+    Integer,
+
+    /// `u32`.
+    BigInt,
+
+    /// `u64`, `u128` and `u256`.
+    Numeric,
+
+    /// `address`, `vector<u8>`, `sui::object::ID` and `sui::object::UID`.
+    Bytea,
+
+    /// `std::string::String` and `std::ascii::String`.
+    Text,
+
+    /// All other vectors, enums, and optional values that cannot be stored in a nullable column
+    /// of one of the other types.
+    Jsonb,
+}
+
+/// How a value with a given layout is mapped onto columns.
+enum Shape<'l> {
+    /// Stored in a single column.
+    Column(ColumnType),
+
+    /// A `std::option::Option` of a value that would be stored in a single column, stored in that
+    /// column, which is `NULL` when the option is empty.
+    Optional(ColumnType),
+
+    /// A struct whose fields are flattened into the table, in columns prefixed by the field's name.
+    Nested(&'l MoveStructLayout),
+}
+
+/// Derive the columns that an event with the given `layout` is stored in: Each field gets its own
+/// column, named after the field, except for fields that are themselves structs, which are
+/// flattened into columns named `<field>_<nested field>`.
+///
+/// Fails if two fields end up with the same column name (e.g. a field `a_b`, and a field `a` whose
+/// struct has a field `b`), rather than storing both in the same column.
+pub(crate) fn columns(layout: &MoveStructLayout) -> Result<Vec<Column>> {
+    let mut columns = vec![];
+    flatten_layout("", layout, &mut columns)?;
+
+    let mut names = BTreeSet::new();
+    for Column { name, .. } in &columns {
+        ensure!(
+            names.insert(name.as_str()),
+            "Multiple fields are stored in column {name}",
+        );
+    }
+
+    Ok(columns)
+}
+
+/// Convert an event's value into a row for its table, mapping the name of each of its columns (as
+/// derived by [columns]) to its value, as JSON that Postgres can convert into the column's type.
+pub(crate) fn row(layout: &MoveStructLayout, value: MoveStruct) -> Result<Map<String, Value>> {
+    let mut row = Map::new();
+    flatten_value("", layout, value, &mut row)?;
+    Ok(row)
+}
+
+impl ColumnType {
+    /// Encode `value` as JSON that Postgres will accept as input for a column of this type.
+    fn encode(self, value: MoveValue) -> Result<Value> {
+        use ColumnType as C;
+        use MoveValue as V;
+
+        Ok(match (self, value) {
+            (C::Boolean, V::Bool(b)) => json!(b),
+
+            (C::Integer, V::U8(n)) => json!(n),
+            (C::Integer, V::U16(n)) => json!(n),
+            (C::BigInt, V::U32(n)) => json!(n),
+
+            // Large integers are passed as strings, to avoid losing precision.
+            (C::Numeric, V::U64(n)) => json!(n.to_string()),
+            (C::Numeric, V::U128(n)) => json!(n.to_string()),
+            (C::Numeric, V::U256(n)) => json!(n.to_string()),
+
+            (C::Bytea, value) => {
+                let bytes = bytes(value).context("Expected bytes")?;
+                json!(format!("\\x{}", hex::encode(bytes)))
+            }
+
+            (C::Text, value @ V::Struct(_)) => {
+                let bytes = bytes(value).context("Expected a string")?;
+                json!(String::from_utf8(bytes).context("Invalid UTF-8 in string")?)
+            }
+
+            (C::Jsonb, value) => to_json(value),
+
+            (type_, value) => bail!("Cannot store {value:?} in a {type_} column"),
+        })
+    }
+}
+
+impl<'l> Shape<'l> {
+    fn of(layout: &'l MoveTypeLayout) -> Self {
+        use ColumnType as C;
+        use MoveTypeLayout as L;
+
+        match layout {
+            L::Bool => Shape::Column(C::Boolean),
+            L::U8 | L::U16 => Shape::Column(C::Integer),
+            L::U32 => Shape::Column(C::BigInt),
+            L::U64 | L::U128 | L::U256 => Shape::Column(C::Numeric),
+            L::Address | L::Signer => Shape::Column(C::Bytea),
+
+            L::Vector(element) if matches!(element.as_ref(), L::U8) => Shape::Column(C::Bytea),
+            L::Vector(_) | L::Enum(_) => Shape::Column(C::Jsonb),
+
+            L::Struct(s) if is_string(&s.type_) => Shape::Column(C::Text),
+            L::Struct(s) if is_id(&s.type_) => Shape::Column(C::Bytea),
+
+            L::Struct(s) if is_option(&s.type_) => {
+                let [field] = &s.fields[..] else {
+                    return Shape::Column(C::Jsonb);
+                };
+
+                let L::Vector(element) = &field.layout else {
+                    return Shape::Column(C::Jsonb);
+                };
+
+                match Shape::of(element) {
+                    Shape::Column(type_) => Shape::Optional(type_),
+                    Shape::Optional(_) | Shape::Nested(_) => Shape::Column(C::Jsonb),
+                }
+            }
+
+            L::Struct(s) => Shape::Nested(s),
+        }
+    }
+}
+
+fn flatten_layout(prefix: &str, layout: &MoveStructLayout, out: &mut Vec<Column>) -> Result<()> {
+    for field in &layout.fields {
+        let name = column_name(prefix, &field.name)?;
+        match Shape::of(&field.layout) {
+            Shape::Column(type_) | Shape::Optional(type_) => out.push(Column { name, type_ }),
+            Shape::Nested(nested) => flatten_layout(&name, nested, out)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn flatten_value(
+    prefix: &str,
+    layout: &MoveStructLayout,
+    value: MoveStruct,
+    out: &mut Map<String, Value>,
+) -> Result<()> {
+    ensure!(
+        layout.fields.len() == value.fields.len(),
+        "Expected {} fields for {}, got {}",
+        layout.fields.len(),
+        layout.type_,
+        value.fields.len(),
+    );
+
+    for (field, (_, value)) in layout.fields.iter().zip(value.fields) {
+        let name = column_name(prefix, &field.name)?;
+        match Shape::of(&field.layout) {
+            Shape::Column(type_) => {
+                let value = type_
+                    .encode(value)
+                    .with_context(|| format!("Failed to encode {name}"))?;
+                out.insert(name, value);
+            }
+
+            Shape::Optional(type_) => {
+                let value = match option(value) {
+                    Some(value) => type_
+                        .encode(value)
+                        .with_context(|| format!("Failed to encode {name}"))?,
+                    None => Value::Null,
+                };
+                out.insert(name, value);
+            }
+
+            Shape::Nested(nested) => {
+                let MoveValue::Struct(value) = value else {
+                    bail!("Expected a struct for {name}");
+                };
+
+                flatten_value(&name, nested, value, out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a Move value into JSON: Small integers become numbers, while large integers, addresses
+/// and IDs become strings. Strings and options are unwrapped, other structs become objects, and
+/// enum variants become objects with their variant's name stored under `@variant`.
+fn to_json(value: MoveValue) -> Value {
+    use MoveValue as V;
+
+    match value {
+        V::Bool(b) => json!(b),
+        V::U8(n) => json!(n),
+        V::U16(n) => json!(n),
+        V::U32(n) => json!(n),
+        V::U64(n) => json!(n.to_string()),
+        V::U128(n) => json!(n.to_string()),
+        V::U256(n) => json!(n.to_string()),
+        V::Address(a) | V::Signer(a) => json!(a.to_canonical_string(/* with_prefix */ true)),
+        V::Vector(values) => Value::Array(values.into_iter().map(to_json).collect()),
+
+        V::Struct(s) if is_string(&s.type_) => bytes(V::Struct(s))
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .map_or(Value::Null, Value::from),
+
+        V::Struct(s) if is_id(&s.type_) => bytes(V::Struct(s))
+            .and_then(|bytes| AccountAddress::from_bytes(bytes).ok())
+            .map_or(Value::Null, |id| {
+                json!(id.to_canonical_string(/* with_prefix */ true))
+            }),
+
+        V::Struct(s) if is_option(&s.type_) => match option(V::Struct(s)) {
+            Some(value) => to_json(value),
+            None => Value::Null,
+        },
+
+        V::Struct(s) => fields(s.fields),
+
+        V::Variant(v) => {
+            let mut object = fields(v.fields);
+            if let Value::Object(map) = &mut object {
+                map.insert("@variant".to_owned(), json!(v.variant_name.as_str()));
+            }
+            object
+        }
+    }
+}
+
+fn fields(fields: Vec<(Identifier, MoveValue)>) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.into_string(), to_json(value)))
+            .collect(),
+    )
+}
+
+/// The raw bytes of an address, a `vector<u8>`, or a struct that wraps one (like `ID` or `UID`).
+fn bytes(value: MoveValue) -> Option<Vec<u8>> {
+    match value {
+        MoveValue::Address(a) | MoveValue::Signer(a) => Some(a.to_vec()),
+
+        MoveValue::Vector(values) => values
+            .into_iter()
+            .map(|v| match v {
+                MoveValue::U8(b) => Some(b),
+                _ => None,
+            })
+            .collect(),
+
+        MoveValue::Struct(s) => {
+            let [(_, value)]: [_; 1] = s.fields.try_into().ok()?;
+            bytes(value)
+        }
+
+        _ => None,
+    }
+}
+
+/// The value inside a `std::option::Option`, if it has one.
+fn option(value: MoveValue) -> Option<MoveValue> {
+    let MoveValue::Struct(s) = value else {
+        return None;
+    };
+
+    let [(_, MoveValue::Vector(mut values))]: [_; 1] = s.fields.try_into().ok()? else {
+        return None;
+    };
+
+    values.pop()
+}
+
+fn column_name(prefix: &str, field: &IdentStr) -> Result<String> {
+    let name = if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}_{field}")
+    };
+
+    ensure!(
+        name.len() <= MAX_IDENTIFIER_LEN,
+        "Column name {name} is longer than {MAX_IDENTIFIER_LEN} bytes",
+    );
+
+    Ok(name)
+}
+
+fn is_string(tag: &StructTag) -> bool {
+    let resolved = (
+        &tag.address,
+        tag.module.as_ident_str(),
+        tag.name.as_ident_str(),
+    );
+    resolved == RESOLVED_UTF8_STR || resolved == RESOLVED_ASCII_STR
+}
+
+fn is_id(tag: &StructTag) -> bool {
+    let resolved = (
+        &tag.address,
+        tag.module.as_ident_str(),
+        tag.name.as_ident_str(),
+    );
+    let resolved_uid = (&SUI_FRAMEWORK_ADDRESS, OBJECT_MODULE_NAME, UID_STRUCT_NAME);
+    resolved == RESOLVED_SUI_ID || resolved == resolved_uid
+}
+
+fn is_option(tag: &StructTag) -> bool {
+    let resolved = (
+        &tag.address,
+        tag.module.as_ident_str(),
+        tag.name.as_ident_str(),
+    );
+    resolved == RESOLVED_STD_OPTION
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ColumnType as C;
+        write!(
+            f,
+            "{}",
+            match self {
+                C::Boolean => "boolean",
+                C::Integer => "integer",
+                C::BigInt => "bigint",
+                C::Numeric => "numeric",
+                C::Bytea => "bytea",
+                C::Text => "text",
+                C::Jsonb => "jsonb",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::annotated_value::MoveFieldLayout;
+    use sui_indexer_alt_framework::types::parse_sui_struct_tag;
+
+    use super::*;
+
+    fn tag(s: &str) -> StructTag {
+        parse_sui_struct_tag(s).unwrap()
+    }
+
+    fn ident(s: &str) -> Identifier {
+        Identifier::new(s).unwrap()
+    }
+
+    fn struct_layout(type_: &str, fields: Vec<(&str, MoveTypeLayout)>) -> MoveStructLayout {
+        MoveStructLayout {
+            type_: tag(type_),
+            fields: fields
+                .into_iter()
+                .map(|(name, layout)| MoveFieldLayout::new(ident(name), layout))
+                .collect(),
+        }
+    }
+
+    fn struct_value(type_: &str, fields: Vec<(&str, MoveValue)>) -> MoveStruct {
+        MoveStruct::new(
+            tag(type_),
+            fields
+                .into_iter()
+                .map(|(name, value)| (ident(name), value))
+                .collect(),
+        )
+    }
+
+    /// Layout for `0xabc::pool::SwapEvent`, which has a field of each shape.
+    fn swap_event_layout() -> MoveStructLayout {
+        use MoveTypeLayout as L;
+
+        let id = struct_layout("0x2::object::ID", vec![("bytes", L::Address)]);
+        let string = struct_layout(
+            "0x1::string::String",
+            vec![("bytes", L::Vector(Box::new(L::U8)))],
+        );
+        let balance = struct_layout(
+            "0x2::balance::Balance<0x2::sui::SUI>",
+            vec![("value", L::U64)],
+        );
+        let referrer = struct_layout(
+            "0x1::option::Option<address>",
+            vec![("vec", L::Vector(Box::new(L::Address)))],
+        );
+
+        struct_layout(
+            "0xabc::pool::SwapEvent",
+            vec![
+                ("pool", L::Struct(Box::new(id))),
+                ("amount_in", L::U64),
+                ("a2b", L::Bool),
+                ("memo", L::Struct(Box::new(string))),
+                ("fee", L::Struct(Box::new(balance))),
+                ("referrer", L::Struct(Box::new(referrer))),
+                ("path", L::Vector(Box::new(L::U16))),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_columns() {
+        use ColumnType as C;
+
+        let columns: Vec<_> = columns(&swap_event_layout())
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.name, c.type_))
+            .collect();
+
+        assert_eq!(
+            columns,
+            vec![
+                ("pool".to_owned(), C::Bytea),
+                ("amount_in".to_owned(), C::Numeric),
+                ("a2b".to_owned(), C::Boolean),
+                ("memo".to_owned(), C::Text),
+                ("fee_value".to_owned(), C::Numeric),
+                ("referrer".to_owned(), C::Bytea),
+                ("path".to_owned(), C::Jsonb),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_row() {
+        use MoveValue as V;
+
+        let pool = AccountAddress::from_hex_literal("0x42").unwrap();
+        let value = struct_value(
+            "0xabc::pool::SwapEvent",
+            vec![
+                (
+                    "pool",
+                    V::Struct(struct_value(
+                        "0x2::object::ID",
+                        vec![("bytes", V::Address(pool))],
+                    )),
+                ),
+                ("amount_in", V::U64(u64::MAX)),
+                ("a2b", V::Bool(true)),
+                (
+                    "memo",
+                    V::Struct(struct_value(
+                        "0x1::string::String",
+                        vec![(
+                            "bytes",
+                            V::Vector(b"hi".iter().copied().map(V::U8).collect()),
+                        )],
+                    )),
+                ),
+                (
+                    "fee",
+                    V::Struct(struct_value(
+                        "0x2::balance::Balance<0x2::sui::SUI>",
+                        vec![("value", V::U64(7))],
+                    )),
+                ),
+                (
+                    "referrer",
+                    V::Struct(struct_value(
+                        "0x1::option::Option<address>",
+                        vec![("vec", V::Vector(vec![]))],
+                    )),
+                ),
+                ("path", V::Vector(vec![V::U16(1), V::U16(2)])),
+            ],
+        );
+
+        let row = row(&swap_event_layout(), value).unwrap();
+        assert_eq!(
+            Value::Object(row),
+            json!({
+                "pool": format!("\\x{}", hex::encode(pool.to_vec())),
+                "amount_in": u64::MAX.to_string(),
+                "a2b": true,
+                "memo": "hi",
+                "fee_value": "7",
+                "referrer": null,
+                "path": [1, 2],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_columns_collision() {
+        use MoveTypeLayout as L;
+
+        let nested = struct_layout("0xabc::pool::Fee", vec![("value", L::U64)]);
+        let layout = struct_layout(
+            "0xabc::pool::FeeEvent",
+            vec![("fee", L::Struct(Box::new(nested))), ("fee_value", L::U32)],
+        );
+
+        let err = columns(&layout).unwrap_err();
+        assert!(err.to_string().contains("fee_value"), "{err}");
+    }
+
+    #[test]
+    fn test_row_wrong_shape() {
+        let value = struct_value("0xabc::pool::SwapEvent", vec![("pool", MoveValue::U8(1))]);
+        row(&swap_event_layout(), value).unwrap_err();
+    }
+}
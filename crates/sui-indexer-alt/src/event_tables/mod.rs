@@ -0,0 +1,412 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables derived from the layouts of Move event types, for the `ev_tables` pipeline. Each table
+//! is configured with the event type whose instances it stores, and gets one row per event of that
+//! type, with a column for each of the event's fields (see [columns::columns]) alongside the
+//! position of the event in the chain's history.
+//!
+//! Event layouts are resolved from the packages in the indexer's own `sum_packages` table when the
+//! indexer starts, so the packages that define the event types must already have been indexed by
+//! the `sum_packages` pipeline by then. On a fresh database, the indexer needs to run without
+//! `ev_tables` until that is the case.
+//!
+//! All tables share the `ev_tables` pipeline and its watermark, so a table that is added after the
+//! pipeline has started indexing only gets the events from the checkpoints the pipeline indexes
+//! from then on. The first checkpoint that each table has all events from is tracked in the
+//! `ev_table_starts` table, and the pipeline refuses to run until any gap before that checkpoint has
+//! been backfilled (see [EventTable::ensure_complete] and [EventTable::backfilled]).
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{bail, ensure, Context, Result};
+use diesel::{
+    sql_query,
+    sql_types::{Array, BigInt, Nullable, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use move_core_types::{
+    annotated_value::{MoveStruct, MoveStructLayout, MoveTypeLayout},
+    language_storage::{StructTag, TypeTag},
+};
+use serde_json::{json, Map, Value};
+use sui_indexer_alt_framework::{
+    db::{self, Db},
+    pipeline::Processor,
+    store::Connection,
+    types::parse_sui_struct_tag,
+    FieldCount,
+};
+use sui_package_resolver::{error::Error, Resolver};
+
+use crate::handlers::ev_tables::EvTables;
+
+use self::{columns::Column, package_store::DbPackageStore};
+
+mod columns;
+mod package_store;
+
+/// Postgres limits identifiers to this many bytes, and silently truncates longer ones.
+const MAX_TABLE_NAME_LEN: usize = 63;
+
+/// Columns that every event table has, identifying the event that each row came from.
+const METADATA_COLUMNS: [&str; 3] = [
+    "cp_sequence_number",
+    "tx_sequence_number",
+    "event_sequence_number",
+];
+
+/// A table that stores all events of a given type.
+pub(crate) struct EventTable {
+    /// The name of the table.
+    pub name: Arc<str>,
+
+    /// The type of events stored in the table.
+    pub type_: StructTag,
+
+    /// The layout of the event type, used to decode events.
+    layout: MoveStructLayout,
+
+    /// The columns that the event's fields are stored in.
+    columns: Vec<Column>,
+
+    /// The first checkpoint that the table has all events from.
+    cp_lo: u64,
+}
+
+/// A row to be written to an event table.
+#[derive(FieldCount)]
+pub(crate) struct EventRow {
+    /// The table the row belongs to.
+    pub table: Arc<str>,
+
+    /// The row's value for each of the table's columns, as JSON.
+    pub values: Map<String, Value>,
+}
+
+#[derive(QueryableByName)]
+struct TableInfo {
+    #[diesel(sql_type = Nullable<Text>)]
+    description: Option<String>,
+}
+
+#[derive(QueryableByName)]
+struct TableStart {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+
+    #[diesel(sql_type = BigInt)]
+    cp_lo: i64,
+}
+
+#[derive(QueryableByName)]
+struct ColumnInfo {
+    #[diesel(sql_type = Text)]
+    column_name: String,
+
+    #[diesel(sql_type = Text)]
+    data_type: String,
+}
+
+impl EventTable {
+    /// Set up the tables described by `tables` (mapping table names to the event types they
+    /// store) in `db`: Each event type's layout is resolved, and its table is created, or
+    /// extended with any columns it is missing.
+    ///
+    /// Tables that are created while the `ev_tables` pipeline already has a watermark are recorded
+    /// as starting from the checkpoint after it, because the pipeline will not revisit the
+    /// checkpoints before it. Tables created before the pipeline started, or before their starts
+    /// were tracked, are assumed to have all events.
+    pub(crate) async fn setup(db: &Db, tables: BTreeMap<String, String>) -> Result<Vec<Self>> {
+        let resolver = Resolver::new(DbPackageStore(db.clone()));
+        let mut conn = db.connect().await?;
+
+        let cp_next = conn
+            .watermark(EvTables::NAME)
+            .await
+            .context("Failed to get watermark for ev_tables")?
+            .map_or(0, |w| w.checkpoint_hi_inclusive + 1);
+
+        let mut event_tables: Vec<EventTable> = vec![];
+        for (name, type_) in tables {
+            let type_ = parse_sui_struct_tag(&type_)
+                .with_context(|| format!("Invalid event type for table {name}: {type_}"))?;
+
+            if let Some(other) = event_tables.iter().find(|t| t.type_ == type_) {
+                bail!(
+                    "Event type {type_} is stored in both {} and {name}",
+                    other.name
+                );
+            }
+
+            let table = Self::resolve(&resolver, name, type_).await?;
+            if table.migrate(&mut conn).await? {
+                sql_query(
+                    "INSERT INTO ev_table_starts (table_name, cp_lo) VALUES ($1, $2) \
+                     ON CONFLICT (table_name) DO UPDATE SET cp_lo = EXCLUDED.cp_lo",
+                )
+                .bind::<Text, _>(table.name.as_ref())
+                .bind::<BigInt, _>(cp_next)
+                .execute(&mut conn)
+                .await
+                .with_context(|| format!("Failed to record start of table {}", table.name))?;
+            }
+
+            event_tables.push(table);
+        }
+
+        let starts: Vec<TableStart> =
+            sql_query("SELECT table_name, cp_lo FROM ev_table_starts WHERE table_name = ANY($1)")
+                .bind::<Array<Text>, _>(Self::names(&event_tables))
+                .load(&mut conn)
+                .await
+                .context("Failed to get starts of event tables")?;
+
+        for TableStart { table_name, cp_lo } in starts {
+            if let Some(table) = event_tables.iter_mut().find(|t| *t.name == table_name) {
+                table.cp_lo = cp_lo as u64;
+            }
+        }
+
+        Ok(event_tables)
+    }
+
+    /// Check that `tables` have all the events that readers of the `ev_tables` pipeline can expect
+    /// to find in them: Every table must have events from the pipeline's reader watermark onwards.
+    /// Tables that were added after the pipeline started need to be backfilled before the pipeline
+    /// can run.
+    pub(crate) async fn ensure_complete(db: &Db, tables: &[Self]) -> Result<()> {
+        let mut conn = db.connect().await?;
+        let Some(watermark) = conn
+            .watermark(EvTables::NAME)
+            .await
+            .context("Failed to get watermark for ev_tables")?
+        else {
+            return Ok(());
+        };
+
+        let reader_lo = watermark.reader_lo as u64;
+        let incomplete: Vec<_> = tables.iter().filter(|t| t.cp_lo > reader_lo).collect();
+        let Some(cp_lo) = incomplete.iter().map(|t| t.cp_lo).max() else {
+            return Ok(());
+        };
+
+        let incomplete: Vec<_> = incomplete
+            .iter()
+            .map(|t| format!("{} (from checkpoint {})", t.name, t.cp_lo))
+            .collect();
+
+        bail!(
+            "Event tables were added after ev_tables started indexing, and only have events from \
+             later checkpoints: {}. Backfill ev_tables from checkpoint {reader_lo} to {} before \
+             running it.",
+            incomplete.join(", "),
+            cp_lo - 1,
+        );
+    }
+
+    /// Record that the tables named `tables` have all events from `first_checkpoint` onwards, now
+    /// that the `ev_tables` pipeline has been backfilled from `first_checkpoint` to
+    /// `last_checkpoint`. Only tables whose start is within the backfilled range (or just after it)
+    /// are moved back.
+    pub(crate) async fn backfilled(
+        db: &Db,
+        tables: Vec<String>,
+        first_checkpoint: u64,
+        last_checkpoint: u64,
+    ) -> Result<()> {
+        let mut conn = db.connect().await?;
+        sql_query(
+            "UPDATE ev_table_starts SET cp_lo = $1 \
+             WHERE table_name = ANY($3) AND cp_lo BETWEEN $1 AND $2 + 1",
+        )
+        .bind::<BigInt, _>(first_checkpoint as i64)
+        .bind::<BigInt, _>(last_checkpoint as i64)
+        .bind::<Array<Text>, _>(tables)
+        .execute(&mut conn)
+        .await
+        .context("Failed to record backfilled event tables")?;
+
+        Ok(())
+    }
+
+    /// Decode the BCS-encoded `contents` of an event and convert it into a row for this table.
+    pub(crate) fn row(
+        &self,
+        cp_sequence_number: u64,
+        tx_sequence_number: u64,
+        event_sequence_number: u64,
+        contents: &[u8],
+    ) -> Result<EventRow> {
+        let value = MoveStruct::simple_deserialize(contents, &self.layout)
+            .with_context(|| format!("Failed to deserialize {}", self.type_))?;
+
+        let mut values = Map::new();
+        values.insert("cp_sequence_number".to_owned(), json!(cp_sequence_number));
+        values.insert("tx_sequence_number".to_owned(), json!(tx_sequence_number));
+        values.insert(
+            "event_sequence_number".to_owned(),
+            json!(event_sequence_number),
+        );
+
+        values.extend(columns::row(&self.layout, value)?);
+
+        Ok(EventRow {
+            table: self.name.clone(),
+            values,
+        })
+    }
+
+    /// Resolve the layout of `type_`, and derive the columns for table `name` from it.
+    async fn resolve(
+        resolver: &Resolver<DbPackageStore>,
+        name: String,
+        type_: StructTag,
+    ) -> Result<Self> {
+        ensure!(
+            !name.is_empty()
+                && name.len() <= MAX_TABLE_NAME_LEN
+                && name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            "Invalid table name {name:?}: Must be at most {MAX_TABLE_NAME_LEN} lowercase \
+             letters, digits, or underscores, and not start with a digit",
+        );
+
+        let layout = match resolver
+            .type_layout(TypeTag::Struct(Box::new(type_.clone())))
+            .await
+        {
+            Ok(layout) => layout,
+
+            Err(Error::PackageNotFound(id)) => bail!(
+                "Failed to resolve layout for {type_}: Package {id} has not been indexed yet. \
+                 Run the indexer with sum_packages (and without ev_tables) until it has indexed \
+                 the package, before setting up table {name}",
+            ),
+
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to resolve layout for {type_}"))
+            }
+        };
+
+        let MoveTypeLayout::Struct(layout) = layout else {
+            bail!("Event type {type_} is not a struct");
+        };
+
+        let columns = columns::columns(&layout)
+            .with_context(|| format!("Failed to derive columns for {type_}"))?;
+
+        for column in &columns {
+            ensure!(
+                !METADATA_COLUMNS.contains(&column.name.as_str()),
+                "Column {} for {type_} clashes with a column that all event tables have",
+                column.name,
+            );
+        }
+
+        Ok(Self {
+            name: name.into(),
+            type_,
+            layout: *layout,
+            columns,
+            cp_lo: 0,
+        })
+    }
+
+    /// The names of `tables`.
+    pub(crate) fn names(tables: &[Self]) -> Vec<String> {
+        tables.iter().map(|t| t.name.to_string()).collect()
+    }
+
+    /// Create this table if it does not exist, or add any columns it is missing if it does. Fails
+    /// if the table exists but was not created for this event type, or if it has a column of the
+    /// wrong type. Returns whether the table was created.
+    async fn migrate(&self, conn: &mut db::Connection<'_>) -> Result<bool> {
+        let name = &self.name;
+        let description = format!("Events of type {}", self.type_.to_canonical_string(true));
+
+        let info: TableInfo =
+            sql_query("SELECT obj_description(to_regclass($1), 'pg_class')::TEXT AS description")
+                .bind::<Text, _>(name.as_ref())
+                .get_result(conn)
+                .await
+                .with_context(|| format!("Failed to look up table {name}"))?;
+
+        let existing: Vec<ColumnInfo> = sql_query(
+            "SELECT column_name::TEXT, data_type::TEXT \
+             FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = $1",
+        )
+        .bind::<Text, _>(name.as_ref())
+        .load(conn)
+        .await
+        .with_context(|| format!("Failed to look up columns for {name}"))?;
+
+        let created = existing.is_empty();
+        if created {
+            sql_query(format!(
+                r#"
+                CREATE TABLE "{name}"
+                (
+                    cp_sequence_number          BIGINT        NOT NULL,
+                    tx_sequence_number          BIGINT        NOT NULL,
+                    event_sequence_number       BIGINT        NOT NULL,
+                    PRIMARY KEY (tx_sequence_number, event_sequence_number)
+                )
+                "#
+            ))
+            .execute(conn)
+            .await
+            .with_context(|| format!("Failed to create table {name}"))?;
+
+            sql_query(format!(
+                r#"CREATE INDEX "{name}_cp_sequence_number" ON "{name}" (cp_sequence_number)"#
+            ))
+            .execute(conn)
+            .await
+            .with_context(|| format!("Failed to create index for {name}"))?;
+
+            sql_query(format!(r#"COMMENT ON TABLE "{name}" IS '{description}'"#))
+                .execute(conn)
+                .await
+                .with_context(|| format!("Failed to describe table {name}"))?;
+        } else {
+            ensure!(
+                info.description.as_deref() == Some(description.as_str()),
+                "Table {name} already exists, but does not store events of type {}",
+                self.type_,
+            );
+        }
+
+        let existing: BTreeMap<_, _> = existing
+            .into_iter()
+            .map(|c| (c.column_name, c.data_type))
+            .collect();
+
+        for Column {
+            name: column,
+            type_,
+        } in &self.columns
+        {
+            if let Some(data_type) = existing.get(column) {
+                ensure!(
+                    *data_type == type_.to_string(),
+                    "Column {column} of table {name} has type {data_type}, expected {type_}",
+                );
+                continue;
+            }
+
+            sql_query(format!(
+                r#"ALTER TABLE "{name}" ADD COLUMN "{column}" {type_}"#
+            ))
+            .execute(conn)
+            .await
+            .with_context(|| format!("Failed to add column {column} to {name}"))?;
+        }
+
+        Ok(created)
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+use move_core_types::account_address::AccountAddress;
+use sui_indexer_alt_framework::{db::Db, types::move_package::MovePackage};
+use sui_indexer_alt_schema::schema::sum_packages;
+use sui_package_resolver::{error::Error, Package, PackageStore, Result};
+
+const STORE: &str = "PostgreSQL";
+
+/// Serves packages to the package resolver from the indexer's own `sum_packages` table.
+pub(crate) struct DbPackageStore(pub Db);
+
+#[async_trait::async_trait]
+impl PackageStore for DbPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> Result<Arc<Package>> {
+        let Self(db) = self;
+        let store_error = |e: anyhow::Error| Error::Store {
+            store: STORE,
+            error: e.to_string(),
+        };
+
+        let mut conn = db.connect().await.map_err(store_error)?;
+        let bytes: Option<Vec<u8>> = sum_packages::table
+            .select(sum_packages::move_package)
+            .filter(sum_packages::package_id.eq(id.to_vec()))
+            .get_result(&mut conn)
+            .await
+            .optional()
+            .map_err(|e| store_error(e.into()))?;
+
+        let Some(bytes) = bytes else {
+            return Err(Error::PackageNotFound(id));
+        };
+
+        let move_package: MovePackage = bcs::from_bytes(&bytes)?;
+        Ok(Arc::new(Package::read_from_package(&move_package)?))
+    }
+}
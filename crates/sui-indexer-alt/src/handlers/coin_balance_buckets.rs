@@ -159,6 +159,8 @@ impl Processor for CoinBalanceBuckets {
 
 #[async_trait::async_trait]
 impl Handler for CoinBalanceBuckets {
+    type Store = db::Db;
+
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = true;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
@@ -282,12 +284,16 @@ pub(crate) fn get_coin_owner(object: &Object) -> Option<(StoredCoinOwnerKind, Su
     }
 }
 
-pub(crate) fn get_coin_balance_bucket(coin: &Object) -> anyhow::Result<i16> {
+pub(crate) fn get_coin_balance(coin: &Object) -> anyhow::Result<u64> {
     let Some(coin) = coin.as_coin_maybe() else {
         // TODO: We should make this an invariant violation.
         bail!("Failed to deserialize Coin for {}", coin.id());
     };
-    let balance = coin.balance.value();
+    Ok(coin.balance.value())
+}
+
+pub(crate) fn get_coin_balance_bucket(coin: &Object) -> anyhow::Result<i16> {
+    let balance = get_coin_balance(coin)?;
     if balance == 0 {
         return Ok(0);
     }
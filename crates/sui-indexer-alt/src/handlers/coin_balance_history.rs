@@ -0,0 +1,314 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::{
+    db,
+    pipeline::{concurrent::Handler, Processor},
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        full_checkpoint_content::CheckpointData,
+        TypeTag,
+    },
+    FieldCount,
+};
+use sui_indexer_alt_schema::{
+    objects::{StoredCoinBalanceHistory, StoredCoinOwnerKind},
+    schema::coin_balance_history,
+};
+
+use crate::history_pruning::prune_history;
+
+use super::coin_balance_buckets::{get_coin_balance, get_coin_owner};
+
+/// This handler is used to track the exact balances of address-owned coins over time, so that an
+/// address's balance can be calculated at the latest checkpoint, or at past checkpoints. Whenever a coin object's presence,
+/// owner or balance changes, we insert a new row into the `coin_balance_history` table. A Delete
+/// record is inserted when a coin object is no longer present or no longer owned by an address.
+pub(crate) struct CoinBalanceHistory;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ProcessedCoinBalance {
+    pub object_id: ObjectID,
+    pub cp_sequence_number: u64,
+    pub change: CoinBalanceChangeKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CoinBalanceChangeKind {
+    Insert {
+        owner_kind: StoredCoinOwnerKind,
+        owner_id: SuiAddress,
+        coin_type: TypeTag,
+        balance: u64,
+    },
+    Delete,
+}
+
+impl Processor for CoinBalanceHistory {
+    const NAME: &'static str = "coin_balance_history";
+    type Value = ProcessedCoinBalance;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let checkpoint_input_objects = checkpoint.checkpoint_input_objects();
+        let latest_live_output_objects: BTreeMap<_, _> = checkpoint
+            .latest_live_output_objects()
+            .into_iter()
+            .map(|o| (o.id(), o))
+            .collect();
+
+        let mut values: BTreeMap<ObjectID, Self::Value> = BTreeMap::new();
+        for (object_id, input_object) in checkpoint_input_objects.iter() {
+            // Coins that were owned by an address prior to the checkpoint, but have since been
+            // deleted or wrapped.
+            if !input_object.is_coin()
+                || get_coin_owner(input_object).is_none()
+                || latest_live_output_objects.contains_key(object_id)
+            {
+                continue;
+            }
+
+            values.insert(
+                *object_id,
+                ProcessedCoinBalance {
+                    object_id: *object_id,
+                    cp_sequence_number,
+                    change: CoinBalanceChangeKind::Delete,
+                },
+            );
+        }
+
+        for (object_id, output_object) in latest_live_output_objects.iter() {
+            let Some(coin_type) = output_object.coin_type_maybe() else {
+                continue;
+            };
+
+            let (input_balance, input_owner) = match checkpoint_input_objects.get(object_id) {
+                Some(input_object) => (
+                    Some(get_coin_balance(input_object)?),
+                    get_coin_owner(input_object),
+                ),
+                None => (None, None),
+            };
+
+            let output_balance = get_coin_balance(output_object)?;
+            let output_owner = get_coin_owner(output_object);
+
+            match (input_owner, output_owner) {
+                // The coin stopped being owned by an address (it became shared or immutable),
+                // which is treated the same as it being deleted.
+                (Some(_), None) => {
+                    values.insert(
+                        *object_id,
+                        ProcessedCoinBalance {
+                            object_id: *object_id,
+                            cp_sequence_number,
+                            change: CoinBalanceChangeKind::Delete,
+                        },
+                    );
+                }
+
+                // The coin is owned by an address after the checkpoint, and it was either created
+                // or unwrapped, or its owner or balance changed.
+                (_, Some((owner_kind, owner_id)))
+                    if input_owner != output_owner || input_balance != Some(output_balance) =>
+                {
+                    values.insert(
+                        *object_id,
+                        ProcessedCoinBalance {
+                            object_id: *object_id,
+                            cp_sequence_number,
+                            change: CoinBalanceChangeKind::Insert {
+                                owner_kind,
+                                owner_id,
+                                coin_type,
+                                balance: output_balance,
+                            },
+                        },
+                    );
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(values.into_values().collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for CoinBalanceHistory {
+    type Store = db::Db;
+
+    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
+        let values = values
+            .iter()
+            .map(|v| v.try_into())
+            .collect::<Result<Vec<StoredCoinBalanceHistory>>>()?;
+
+        Ok(diesel::insert_into(coin_balance_history::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+
+    async fn prune(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut db::Connection<'_>,
+    ) -> Result<usize> {
+        prune_history("coin_balance_history", from, to_exclusive, conn).await
+    }
+}
+
+impl FieldCount for ProcessedCoinBalance {
+    const FIELD_COUNT: usize = StoredCoinBalanceHistory::FIELD_COUNT;
+}
+
+impl TryInto<StoredCoinBalanceHistory> for &ProcessedCoinBalance {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<StoredCoinBalanceHistory> {
+        match &self.change {
+            CoinBalanceChangeKind::Insert {
+                owner_kind,
+                owner_id,
+                coin_type,
+                balance,
+            } => {
+                let serialized_coin_type = bcs::to_bytes(&coin_type)
+                    .map_err(|_| anyhow!("Failed to serialize type for {}", self.object_id))?;
+                Ok(StoredCoinBalanceHistory {
+                    object_id: self.object_id.to_vec(),
+                    cp_sequence_number: self.cp_sequence_number as i64,
+                    owner_kind: Some(*owner_kind),
+                    owner_id: Some(owner_id.to_vec()),
+                    coin_type: Some(serialized_coin_type),
+                    balance: Some(*balance as i64),
+                })
+            }
+            CoinBalanceChangeKind::Delete => Ok(StoredCoinBalanceHistory {
+                object_id: self.object_id.to_vec(),
+                cp_sequence_number: self.cp_sequence_number as i64,
+                owner_kind: None,
+                owner_id: None,
+                coin_type: None,
+                balance: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::QueryDsl;
+    use sui_indexer_alt_framework::{
+        types::{gas_coin::GAS, test_checkpoint_data_builder::TestCheckpointDataBuilder},
+        Indexer,
+    };
+    use sui_indexer_alt_schema::MIGRATIONS;
+
+    use super::*;
+
+    async fn get_all_coin_balance_history(
+        conn: &mut db::Connection<'_>,
+    ) -> Vec<StoredCoinBalanceHistory> {
+        coin_balance_history::table
+            .order_by((
+                coin_balance_history::object_id,
+                coin_balance_history::cp_sequence_number,
+            ))
+            .load(conn)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_process_coin_balance_history() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+        let handler = CoinBalanceHistory;
+
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_sui_object(0, 100)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let values = handler.process(&Arc::new(checkpoint)).unwrap();
+        assert_eq!(
+            values,
+            vec![ProcessedCoinBalance {
+                object_id: TestCheckpointDataBuilder::derive_object_id(0),
+                cp_sequence_number: 0,
+                change: CoinBalanceChangeKind::Insert {
+                    owner_kind: StoredCoinOwnerKind::Fastpath,
+                    owner_id: TestCheckpointDataBuilder::derive_address(0),
+                    coin_type: GAS::type_tag(),
+                    balance: 100,
+                },
+            }],
+        );
+        CoinBalanceHistory::commit(&values, &mut conn)
+            .await
+            .unwrap();
+
+        // Transferring part of the balance to another address records the new balance of the
+        // original coin (even though its balance bucket has not changed), and the new coin.
+        builder = builder
+            .start_transaction(0)
+            .transfer_coin_balance(0, 1, 1, 10)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let values = handler.process(&Arc::new(checkpoint)).unwrap();
+        assert_eq!(values.len(), 2);
+        CoinBalanceHistory::commit(&values, &mut conn)
+            .await
+            .unwrap();
+
+        builder = builder
+            .start_transaction(1)
+            .delete_object(1)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let values = handler.process(&Arc::new(checkpoint)).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].change, CoinBalanceChangeKind::Delete);
+        CoinBalanceHistory::commit(&values, &mut conn)
+            .await
+            .unwrap();
+
+        let object0 = TestCheckpointDataBuilder::derive_object_id(0).to_vec();
+        let object1 = TestCheckpointDataBuilder::derive_object_id(1).to_vec();
+        let mut history: Vec<_> = get_all_coin_balance_history(&mut conn)
+            .await
+            .into_iter()
+            .map(|h| (h.object_id, h.cp_sequence_number, h.balance))
+            .collect();
+        history.sort();
+
+        let mut expected = vec![
+            (object0.clone(), 0, Some(100)),
+            (object0.clone(), 1, Some(90)),
+            (object1.clone(), 1, Some(10)),
+            (object1.clone(), 2, None),
+        ];
+        expected.sort();
+        assert_eq!(history, expected);
+
+        // Pruning up to checkpoint 2 removes the superseded balance for the first coin, and all
+        // records for the deleted coin.
+        let rows_pruned = handler.prune(0, 3, &mut conn).await.unwrap();
+        assert_eq!(rows_pruned, 3);
+
+        let history = get_all_coin_balance_history(&mut conn).await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].object_id, object0);
+        assert_eq!(history[0].balance, Some(90));
+    }
+}
@@ -54,8 +54,11 @@ impl Processor for EvStructInst {
 
 #[async_trait::async_trait]
 impl Handler for EvStructInst {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
+    const PRUNE_CLEARS_CHECKPOINTS: bool = true;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
         Ok(diesel::insert_into(ev_struct_inst::table)
@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Context, Result};
+use diesel::{
+    sql_query,
+    sql_types::{BigInt, Text},
+};
+use diesel_async::RunQueryDsl;
+use move_core_types::language_storage::StructTag;
+use sui_indexer_alt_framework::{
+    db,
+    pipeline::{concurrent::Handler, Processor},
+    types::full_checkpoint_content::CheckpointData,
+};
+
+use crate::event_tables::{EventRow, EventTable};
+
+/// Writes events of the types configured in `[event_tables]` to the tables derived from their
+/// layouts, one row per event.
+pub(crate) struct EvTables {
+    tables: BTreeMap<StructTag, EventTable>,
+}
+
+impl EvTables {
+    pub(crate) fn new(tables: Vec<EventTable>) -> Self {
+        Self {
+            tables: tables.into_iter().map(|t| (t.type_.clone(), t)).collect(),
+        }
+    }
+}
+
+impl Processor for EvTables {
+    const NAME: &'static str = "ev_tables";
+
+    type Value = EventRow;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let CheckpointData {
+            transactions,
+            checkpoint_summary,
+            ..
+        } = checkpoint.as_ref();
+
+        let mut values = vec![];
+        let cp_sequence_number = checkpoint_summary.sequence_number;
+        let first_tx = checkpoint_summary.network_total_transactions as usize - transactions.len();
+
+        for (i, tx) in transactions.iter().enumerate() {
+            let tx_sequence_number = (first_tx + i) as u64;
+            for (j, ev) in tx.events.iter().flat_map(|evs| evs.data.iter().enumerate()) {
+                let Some(table) = self.tables.get(&ev.type_) else {
+                    continue;
+                };
+
+                values.push(
+                    table
+                        .row(
+                            cp_sequence_number,
+                            tx_sequence_number,
+                            j as u64,
+                            &ev.contents,
+                        )
+                        .with_context(|| {
+                            format!("Failed to decode event ({tx_sequence_number}, {j})")
+                        })?,
+                );
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for EvTables {
+    type Store = db::Db;
+
+    const MIN_EAGER_ROWS: usize = 100;
+    const MAX_PENDING_ROWS: usize = 10000;
+    const PRUNE_CLEARS_CHECKPOINTS: bool = true;
+
+    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
+        // Rows are sent to each table as a single JSON array, which Postgres converts into the
+        // table's row type.
+        let mut tables: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+        for row in values {
+            tables.entry(&row.table).or_default().push(&row.values);
+        }
+
+        let mut affected = 0;
+        for (table, rows) in tables {
+            let rows = serde_json::to_string(&rows)
+                .with_context(|| format!("Failed to serialize rows for {table}"))?;
+
+            affected += sql_query(format!(
+                r#"
+                INSERT INTO "{table}"
+                SELECT * FROM jsonb_populate_recordset(NULL::"{table}", $1::JSONB)
+                ON CONFLICT DO NOTHING
+                "#
+            ))
+            .bind::<Text, _>(rows)
+            .execute(conn)
+            .await?;
+        }
+
+        Ok(affected)
+    }
+
+    async fn prune(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut db::Connection<'_>,
+    ) -> Result<usize> {
+        let mut affected = 0;
+        for table in self.tables.values() {
+            affected += sql_query(format!(
+                r#"
+                DELETE FROM "{}"
+                WHERE cp_sequence_number >= $1 AND cp_sequence_number < $2
+                "#,
+                table.name,
+            ))
+            .bind::<BigInt, _>(from as i64)
+            .bind::<BigInt, _>(to_exclusive as i64)
+            .execute(conn)
+            .await?;
+        }
+
+        Ok(affected)
+    }
+}
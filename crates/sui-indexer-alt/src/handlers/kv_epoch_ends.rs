@@ -121,6 +121,8 @@ impl Processor for KvEpochEnds {
 
 #[async_trait::async_trait]
 impl Handler for KvEpochEnds {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 1;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
@@ -54,6 +54,8 @@ impl Processor for KvFeatureFlags {
 
 #[async_trait::async_trait]
 impl Handler for KvFeatureFlags {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 1;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod coin_balance_buckets;
+pub(crate) mod coin_balance_history;
 pub(crate) mod ev_emit_mod;
 pub(crate) mod ev_struct_inst;
+pub(crate) mod ev_tables;
 pub(crate) mod kv_checkpoints;
 pub(crate) mod kv_epoch_ends;
 pub(crate) mod kv_epoch_starts;
@@ -12,6 +14,7 @@ pub(crate) mod kv_objects;
 pub(crate) mod kv_protocol_configs;
 pub(crate) mod kv_transactions;
 pub(crate) mod obj_info;
+pub(crate) mod obj_info_history;
 pub(crate) mod obj_versions;
 pub(crate) mod sum_displays;
 pub(crate) mod sum_packages;
@@ -38,61 +38,74 @@ impl Processor for ObjInfo {
     // TODO: Add tests for this function and the pruner.
     fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
         let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
-        let checkpoint_input_objects = checkpoint.checkpoint_input_objects();
-        let latest_live_output_objects = checkpoint
-            .latest_live_output_objects()
-            .into_iter()
-            .map(|o| (o.id(), o))
-            .collect::<BTreeMap<_, _>>();
-        let mut values: BTreeMap<ObjectID, Self::Value> = BTreeMap::new();
-        let mut prune_info = PruningInfo::new();
-        for object_id in checkpoint_input_objects.keys() {
-            if !latest_live_output_objects.contains_key(object_id) {
-                // If an input object is not in the latest live output objects, it must have been deleted
-                // or wrapped in this checkpoint. We keep an entry for it in the table.
-                // This is necessary when we query objects and iterating over them, so that we don't
-                // include the object in the result if it was deleted.
-                values.insert(
-                    *object_id,
-                    ProcessedObjInfo {
-                        cp_sequence_number,
-                        update: ProcessedObjInfoUpdate::Delete(*object_id),
-                    },
-                );
-                prune_info.add_deleted_object(*object_id);
-            }
-        }
-        for (object_id, object) in latest_live_output_objects.iter() {
-            // If an object is newly created/unwrapped in this checkpoint, or if the owner changed,
-            // we need to insert an entry for it in the table.
-            let should_insert = match checkpoint_input_objects.get(object_id) {
-                Some(input_object) => input_object.owner() != object.owner(),
-                None => true,
-            };
-            if should_insert {
-                values.insert(
-                    *object_id,
-                    ProcessedObjInfo {
-                        cp_sequence_number,
-                        update: ProcessedObjInfoUpdate::Insert((*object).clone()),
-                    },
-                );
-                // We do not need to prune if the object was created in this checkpoint,
-                // because this object would not have been in the table prior to this checkpoint.
-                if checkpoint_input_objects.contains_key(object_id) {
-                    prune_info.add_mutated_object(*object_id);
-                }
-            }
-        }
+        let (values, prune_info) = obj_info_updates(checkpoint);
         self.pruning_lookup_table
             .insert(cp_sequence_number, prune_info);
 
-        Ok(values.into_values().collect())
+        Ok(values)
     }
 }
 
+/// The changes to object type and owner information in `checkpoint`, as rows to be written to
+/// `obj_info`, along with the information needed to prune the rows that they supersede.
+pub(crate) fn obj_info_updates(
+    checkpoint: &CheckpointData,
+) -> (Vec<ProcessedObjInfo>, PruningInfo) {
+    let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+    let checkpoint_input_objects = checkpoint.checkpoint_input_objects();
+    let latest_live_output_objects = checkpoint
+        .latest_live_output_objects()
+        .into_iter()
+        .map(|o| (o.id(), o))
+        .collect::<BTreeMap<_, _>>();
+    let mut values: BTreeMap<ObjectID, ProcessedObjInfo> = BTreeMap::new();
+    let mut prune_info = PruningInfo::new();
+    for object_id in checkpoint_input_objects.keys() {
+        if !latest_live_output_objects.contains_key(object_id) {
+            // If an input object is not in the latest live output objects, it must have been deleted
+            // or wrapped in this checkpoint. We keep an entry for it in the table.
+            // This is necessary when we query objects and iterating over them, so that we don't
+            // include the object in the result if it was deleted.
+            values.insert(
+                *object_id,
+                ProcessedObjInfo {
+                    cp_sequence_number,
+                    update: ProcessedObjInfoUpdate::Delete(*object_id),
+                },
+            );
+            prune_info.add_deleted_object(*object_id);
+        }
+    }
+    for (object_id, object) in latest_live_output_objects.iter() {
+        // If an object is newly created/unwrapped in this checkpoint, or if the owner changed,
+        // we need to insert an entry for it in the table.
+        let should_insert = match checkpoint_input_objects.get(object_id) {
+            Some(input_object) => input_object.owner() != object.owner(),
+            None => true,
+        };
+        if should_insert {
+            values.insert(
+                *object_id,
+                ProcessedObjInfo {
+                    cp_sequence_number,
+                    update: ProcessedObjInfoUpdate::Insert((*object).clone()),
+                },
+            );
+            // We do not need to prune if the object was created in this checkpoint,
+            // because this object would not have been in the table prior to this checkpoint.
+            if checkpoint_input_objects.contains_key(object_id) {
+                prune_info.add_mutated_object(*object_id);
+            }
+        }
+    }
+
+    (values.into_values().collect(), prune_info)
+}
+
 #[async_trait::async_trait]
 impl Handler for ObjInfo {
+    type Store = db::Db;
+
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = true;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
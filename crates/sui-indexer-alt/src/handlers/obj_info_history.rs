@@ -0,0 +1,171 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::{
+    db,
+    pipeline::{concurrent::Handler, Processor},
+    types::full_checkpoint_content::CheckpointData,
+};
+use sui_indexer_alt_schema::{
+    objects::{StoredObjInfo, StoredObjInfoHistory},
+    schema::obj_info_history,
+};
+
+use crate::history_pruning::prune_history;
+
+use super::obj_info::{obj_info_updates, ProcessedObjInfo};
+
+/// Tracks the same changes to object type and owner information as the `obj_info` pipeline, but
+/// writes them to a table with its own retention, to serve queries about the objects that an
+/// address owned at checkpoints further in the past than `obj_info` keeps.
+pub(crate) struct ObjInfoHistory;
+
+impl Processor for ObjInfoHistory {
+    const NAME: &'static str = "obj_info_history";
+    type Value = ProcessedObjInfo;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        // This table is pruned based on its contents, so the pruning information is not needed.
+        let (values, _) = obj_info_updates(checkpoint);
+        Ok(values)
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for ObjInfoHistory {
+    type Store = db::Db;
+
+    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
+        let stored = values
+            .iter()
+            .map(|v| {
+                let info: StoredObjInfo = v.try_into()?;
+                Ok(StoredObjInfoHistory::from(info))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(diesel::insert_into(obj_info_history::table)
+            .values(stored)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+
+    async fn prune(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut db::Connection<'_>,
+    ) -> Result<usize> {
+        prune_history("obj_info_history", from, to_exclusive, conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::QueryDsl;
+    use sui_indexer_alt_framework::{
+        types::test_checkpoint_data_builder::TestCheckpointDataBuilder, Indexer,
+    };
+    use sui_indexer_alt_schema::{objects::StoredOwnerKind, MIGRATIONS};
+
+    use super::*;
+
+    async fn get_all_obj_info_history(conn: &mut db::Connection<'_>) -> Vec<StoredObjInfoHistory> {
+        obj_info_history::table
+            .order_by((
+                obj_info_history::object_id,
+                obj_info_history::cp_sequence_number,
+            ))
+            .load(conn)
+            .await
+            .unwrap()
+    }
+
+    /// Process the next checkpoint from `builder` and commit its rows.
+    async fn commit_checkpoint(
+        builder: &mut TestCheckpointDataBuilder,
+        conn: &mut db::Connection<'_>,
+    ) {
+        let checkpoint = Arc::new(builder.build_checkpoint());
+        let values = ObjInfoHistory.process(&checkpoint).unwrap();
+        ObjInfoHistory::commit(&values, conn).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_history_and_pruning() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+
+        let object0 = TestCheckpointDataBuilder::derive_object_id(0);
+        let object1 = TestCheckpointDataBuilder::derive_object_id(1);
+        let addr0 = TestCheckpointDataBuilder::derive_address(0);
+        let addr1 = TestCheckpointDataBuilder::derive_address(1);
+
+        // Checkpoint 0: Address 0 creates objects 0 and 1.
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .create_owned_object(1)
+            .finish_transaction();
+        commit_checkpoint(&mut builder, &mut conn).await;
+
+        // Checkpoint 1: Object 0 is transferred to address 1.
+        builder = builder
+            .start_transaction(0)
+            .transfer_object(0, 1)
+            .finish_transaction();
+        commit_checkpoint(&mut builder, &mut conn).await;
+
+        // Checkpoint 2: Object 1 is deleted.
+        builder = builder
+            .start_transaction(0)
+            .delete_object(1)
+            .finish_transaction();
+        commit_checkpoint(&mut builder, &mut conn).await;
+
+        // Every change is kept until it is pruned.
+        let history = get_all_obj_info_history(&mut conn).await;
+        let rows: Vec<_> = history
+            .iter()
+            .map(|h| {
+                (
+                    h.object_id.clone(),
+                    h.cp_sequence_number,
+                    h.owner_id.clone(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (object0.to_vec(), 0, Some(addr0.to_vec())),
+                (object0.to_vec(), 1, Some(addr1.to_vec())),
+                (object1.to_vec(), 0, Some(addr0.to_vec())),
+                (object1.to_vec(), 2, None),
+            ],
+        );
+
+        // Pruning checkpoint 0 does not remove anything, because those rows are still needed to
+        // answer queries about checkpoint 1.
+        let pruned = ObjInfoHistory.prune(0, 1, &mut conn).await.unwrap();
+        assert_eq!(pruned, 0);
+
+        // Pruning checkpoints 1 and 2 removes the first version of object 0, and every record of
+        // object 1, which no longer exists.
+        let pruned = ObjInfoHistory.prune(1, 3, &mut conn).await.unwrap();
+        assert_eq!(pruned, 3);
+
+        let history = get_all_obj_info_history(&mut conn).await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].object_id, object0.to_vec());
+        assert_eq!(history[0].cp_sequence_number, 1);
+        assert_eq!(history[0].owner_kind, Some(StoredOwnerKind::Address));
+        assert_eq!(history[0].owner_id, Some(addr1.to_vec()));
+    }
+}
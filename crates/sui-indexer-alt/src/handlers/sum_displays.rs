@@ -60,6 +60,8 @@ impl Processor for SumDisplays {
 
 #[async_trait::async_trait]
 impl Handler for SumDisplays {
+    type Store = db::Db;
+
     type Batch = BTreeMap<Vec<u8>, Self::Value>;
 
     fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
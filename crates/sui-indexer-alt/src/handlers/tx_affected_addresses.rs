@@ -7,15 +7,15 @@ use std::sync::Arc;
 use anyhow::Result;
 use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
-use itertools::Itertools;
 use sui_indexer_alt_framework::{
     db,
     models::cp_sequence_numbers::tx_interval,
     pipeline::{concurrent::Handler, Processor},
-    types::{full_checkpoint_content::CheckpointData, object::Owner},
+    types::full_checkpoint_content::CheckpointData,
 };
 use sui_indexer_alt_schema::{
-    schema::tx_affected_addresses, transactions::StoredTxAffectedAddress,
+    schema::tx_affected_addresses,
+    transactions::{affected_addresses, StoredTxAffectedAddress},
 };
 
 pub(crate) struct TxAffectedAddresses;
@@ -38,24 +38,14 @@ impl Processor for TxAffectedAddresses {
         for (i, tx) in transactions.iter().enumerate() {
             let tx_sequence_number = (first_tx + i) as i64;
             let sender = tx.transaction.sender_address();
-            let payer = tx.transaction.gas_owner();
-            let recipients = tx.effects.all_changed_objects().into_iter().filter_map(
-                |(_object_ref, owner, _write_kind)| match owner {
-                    Owner::AddressOwner(address) => Some(address),
-                    _ => None,
-                },
-            );
-
-            let affected_addresses: Vec<StoredTxAffectedAddress> = recipients
-                .chain(vec![sender, payer])
-                .unique()
-                .map(|a| StoredTxAffectedAddress {
+            let data = tx.transaction.transaction_data();
+            values.extend(affected_addresses(data, &tx.effects).into_iter().map(|a| {
+                StoredTxAffectedAddress {
                     tx_sequence_number,
                     affected: a.to_vec(),
                     sender: sender.to_vec(),
-                })
-                .collect();
-            values.extend(affected_addresses);
+                }
+            }));
         }
 
         Ok(values)
@@ -64,8 +54,11 @@ impl Processor for TxAffectedAddresses {
 
 #[async_trait::async_trait]
 impl Handler for TxAffectedAddresses {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
+    const PRUNE_CLEARS_CHECKPOINTS: bool = true;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
         Ok(diesel::insert_into(tx_affected_addresses::table)
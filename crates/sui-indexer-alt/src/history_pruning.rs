@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use diesel::{sql_query, sql_types::BigInt};
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::db;
+
+/// Prune a history table, keyed on `(object_id, cp_sequence_number)`, whose rows record the state
+/// of an object from a given checkpoint onwards, and where a row with a `NULL` `owner_kind` records
+/// that the object was deleted (or otherwise stopped being tracked) at that checkpoint.
+///
+/// After pruning checkpoints `[from, to_exclusive)`, the table can still answer queries about any
+/// checkpoint at or after `to_exclusive`: For each object that has a row in the pruned range, all
+/// its rows before the latest one in that range are deleted (they have been superseded), and if
+/// that latest row is a deletion record, it is deleted as well (the object no longer exists).
+///
+/// Unlike consistent pruning, this works purely from the contents of the table, so it does not rely
+/// on the indexer having processed the checkpoints being pruned since it last started. This makes
+/// it suitable for tables with retentions that span multiple restarts of the indexer.
+pub(crate) async fn prune_history(
+    table: &str,
+    from: u64,
+    to_exclusive: u64,
+    conn: &mut db::Connection<'_>,
+) -> Result<usize> {
+    let superseded = format!(
+        "
+        WITH latest(object_id, cp_sequence_number) AS (
+            SELECT DISTINCT ON (object_id)
+                object_id,
+                cp_sequence_number
+            FROM {table}
+            WHERE cp_sequence_number >= $1
+              AND cp_sequence_number < $2
+            ORDER BY object_id, cp_sequence_number DESC
+        )
+        DELETE FROM {table} h
+        USING latest l
+        WHERE h.object_id = l.object_id
+          AND h.cp_sequence_number < l.cp_sequence_number
+        "
+    );
+
+    let deleted = format!(
+        "
+        DELETE FROM {table}
+        WHERE cp_sequence_number >= $1
+          AND cp_sequence_number < $2
+          AND owner_kind IS NULL
+        "
+    );
+
+    let superseded = sql_query(superseded)
+        .bind::<BigInt, _>(from as i64)
+        .bind::<BigInt, _>(to_exclusive as i64)
+        .execute(conn)
+        .await?;
+
+    let deleted = sql_query(deleted)
+        .bind::<BigInt, _>(from as i64)
+        .bind::<BigInt, _>(to_exclusive as i64)
+        .execute(conn)
+        .await?;
+
+    Ok(superseded + deleted)
+}
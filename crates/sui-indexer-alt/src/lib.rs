@@ -3,15 +3,17 @@
 
 use bootstrap::bootstrap;
 use config::{IndexerConfig, PipelineLayer};
+use event_tables::EventTable;
 use handlers::{
-    coin_balance_buckets::CoinBalanceBuckets, ev_emit_mod::EvEmitMod, ev_struct_inst::EvStructInst,
+    coin_balance_buckets::CoinBalanceBuckets, coin_balance_history::CoinBalanceHistory,
+    ev_emit_mod::EvEmitMod, ev_struct_inst::EvStructInst, ev_tables::EvTables,
     kv_checkpoints::KvCheckpoints, kv_epoch_ends::KvEpochEnds, kv_epoch_starts::KvEpochStarts,
     kv_feature_flags::KvFeatureFlags, kv_objects::KvObjects,
     kv_protocol_configs::KvProtocolConfigs, kv_transactions::KvTransactions, obj_info::ObjInfo,
-    obj_versions::ObjVersions, sum_displays::SumDisplays, sum_packages::SumPackages,
-    tx_affected_addresses::TxAffectedAddresses, tx_affected_objects::TxAffectedObjects,
-    tx_balance_changes::TxBalanceChanges, tx_calls::TxCalls, tx_digests::TxDigests,
-    tx_kinds::TxKinds,
+    obj_info_history::ObjInfoHistory, obj_versions::ObjVersions, sum_displays::SumDisplays,
+    sum_packages::SumPackages, tx_affected_addresses::TxAffectedAddresses,
+    tx_affected_objects::TxAffectedObjects, tx_balance_changes::TxBalanceChanges,
+    tx_calls::TxCalls, tx_digests::TxDigests, tx_kinds::TxKinds,
 };
 use prometheus::Registry;
 use sui_indexer_alt_framework::{
@@ -21,12 +23,13 @@ use sui_indexer_alt_framework::{
     pipeline::{
         concurrent::{ConcurrentConfig, PrunerConfig},
         sequential::SequentialConfig,
-        CommitterConfig,
+        CommitterConfig, Processor,
     },
-    Indexer, IndexerArgs,
+    Indexer, IndexerArgs, Mode,
 };
 use sui_indexer_alt_schema::MIGRATIONS;
 use tokio_util::sync::CancellationToken;
+use tracing::warn;
 use url::Url;
 
 pub mod args;
@@ -35,7 +38,9 @@ pub mod benchmark;
 pub(crate) mod bootstrap;
 pub mod config;
 pub(crate) mod consistent_pruning;
+pub(crate) mod event_tables;
 pub(crate) mod handlers;
+pub(crate) mod history_pruning;
 
 pub async fn setup_indexer(
     database_url: Url,
@@ -54,9 +59,11 @@ pub async fn setup_indexer(
     let IndexerConfig {
         ingestion,
         consistency,
+        history,
         committer,
         pruner,
         pipeline,
+        event_tables,
         extra: _,
     } = indexer_config.finish();
 
@@ -64,9 +71,11 @@ pub async fn setup_indexer(
         sum_displays,
         sum_packages,
         coin_balance_buckets,
+        coin_balance_history,
         cp_sequence_numbers,
         ev_emit_mod,
         ev_struct_inst,
+        ev_tables,
         kv_checkpoints,
         kv_epoch_ends,
         kv_epoch_starts,
@@ -75,6 +84,7 @@ pub async fn setup_indexer(
         kv_protocol_configs,
         kv_transactions,
         obj_info,
+        obj_info_history,
         obj_versions,
         tx_affected_addresses,
         tx_affected_objects,
@@ -87,11 +97,19 @@ pub async fn setup_indexer(
 
     let ingestion = ingestion.finish(IngestionConfig::default());
     let consistency = consistency.finish(PrunerConfig::default());
+    let history = history.finish(PrunerConfig::default());
     let committer = committer.finish(CommitterConfig::default());
     let pruner = pruner.finish(PrunerConfig::default());
 
     let retry_interval = ingestion.retry_interval();
 
+    let mode = indexer_args.mode;
+    let backfill_range = indexer_args
+        .first_checkpoint
+        .zip(indexer_args.last_checkpoint);
+    let runs_ev_tables = indexer_args.pipeline.is_empty()
+        || indexer_args.pipeline.iter().any(|p| p == EvTables::NAME);
+
     let mut indexer = Indexer::new(
         database_url,
         db_args,
@@ -112,12 +130,13 @@ pub async fn setup_indexer(
     //  - Combining shared and per-pipeline configurations.
     //  - Registering the pipeline with the indexer.
     //
-    // There are three kinds of pipeline, each with their own macro: `add_concurrent`,
-    // `add_sequential`, and `add_consistent`. `add_concurrent` and `add_sequential` map directly
-    // to `Indexer::concurrent_pipeline` and `Indexer::sequential_pipeline` respectively while
-    // `add_consistent` is a special case that generates both a sequential "summary" pipeline and a
-    // `concurrent` "write-ahead log" pipeline, with their configuration based on the supplied
-    // ConsistencyConfig.
+    // There are four kinds of pipeline, each with their own macro: `add_concurrent`,
+    // `add_sequential`, `add_consistent` and `add_history`. `add_concurrent` and `add_sequential`
+    // map directly to `Indexer::concurrent_pipeline` and `Indexer::sequential_pipeline`
+    // respectively while `add_consistent` is a special case that generates both a sequential
+    // "summary" pipeline and a `concurrent` "write-ahead log" pipeline, with their configuration
+    // based on the supplied ConsistencyConfig. `add_history` registers a concurrent pipeline whose
+    // pruner is configured by the shared history configuration.
 
     macro_rules! add_consistent {
         ($handler:expr, $config:expr) => {
@@ -128,6 +147,26 @@ pub async fn setup_indexer(
                         ConcurrentConfig {
                             committer: layer.finish(committer.clone()),
                             pruner: Some(consistency.clone()),
+                            sharding: None,
+                            failure: Default::default(),
+                        },
+                    )
+                    .await?
+            }
+        };
+    }
+
+    macro_rules! add_history {
+        ($handler:expr, $config:expr) => {
+            if let Some(layer) = $config {
+                indexer
+                    .concurrent_pipeline(
+                        $handler,
+                        ConcurrentConfig {
+                            committer: layer.finish(committer.clone()),
+                            pruner: Some(history.clone()),
+                            sharding: None,
+                            failure: Default::default(),
                         },
                     )
                     .await?
@@ -144,6 +183,8 @@ pub async fn setup_indexer(
                         layer.finish(ConcurrentConfig {
                             committer: committer.clone(),
                             pruner: Some(pruner.clone()),
+                            sharding: None,
+                            failure: Default::default(),
                         }),
                     )
                     .await?
@@ -179,6 +220,10 @@ pub async fn setup_indexer(
     add_consistent!(CoinBalanceBuckets::default(), coin_balance_buckets);
     add_consistent!(ObjInfo::default(), obj_info);
 
+    // History pipelines
+    add_history!(CoinBalanceHistory, coin_balance_history);
+    add_history!(ObjInfoHistory, obj_info_history);
+
     // Summary tables (without write-ahead log)
     add_sequential!(SumDisplays, sum_displays);
     add_sequential!(SumPackages, sum_packages);
@@ -200,5 +245,28 @@ pub async fn setup_indexer(
     add_concurrent!(TxDigests, tx_digests);
     add_concurrent!(TxKinds, tx_kinds);
 
+    // Tables derived from event layouts, which need to be set up before their pipeline starts, and
+    // must not be missing events that the pipeline will not go back for.
+    if ev_tables.is_some() {
+        if event_tables.is_empty() {
+            warn!("Skipping pipeline ev_tables: No event tables configured");
+        } else {
+            let tables = EventTable::setup(indexer.db(), event_tables).await?;
+            let names = EventTable::names(&tables);
+            if runs_ev_tables && mode == Mode::Index {
+                EventTable::ensure_complete(indexer.db(), &tables).await?;
+            }
+
+            add_concurrent!(EvTables::new(tables), ev_tables);
+
+            // Backfilling the pipeline fills in the events that tables added later were missing.
+            if let (Mode::Backfill, Some((first, last))) = (mode, backfill_range) {
+                if runs_ev_tables {
+                    EventTable::backfilled(indexer.db(), names, first, last).await?;
+                }
+            }
+        }
+    }
+
     Ok(indexer)
 }
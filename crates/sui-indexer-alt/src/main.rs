@@ -14,7 +14,11 @@ use sui_indexer_alt::config::IndexerConfig;
 use sui_indexer_alt::config::Merge;
 use sui_indexer_alt::setup_indexer;
 use sui_indexer_alt_framework::db::reset_database;
+use sui_indexer_alt_framework::db::Db;
+use sui_indexer_alt_framework::store::DeadLetterConnection;
 use sui_indexer_alt_framework::Indexer;
+use sui_indexer_alt_framework::IndexerArgs;
+use sui_indexer_alt_framework::Mode;
 use sui_indexer_alt_metrics::MetricsService;
 use sui_indexer_alt_schema::MIGRATIONS;
 use tokio::fs;
@@ -73,6 +77,159 @@ async fn main() -> Result<()> {
             let _ = h_metrics.await;
         }
 
+        Command::Backfill {
+            database_url,
+            db_args,
+            client_args,
+            metrics_args,
+            pipeline,
+            first_checkpoint,
+            last_checkpoint,
+            config,
+        } => {
+            let indexer_config = read_config(&config).await?;
+            info!(
+                pipeline,
+                first_checkpoint,
+                last_checkpoint,
+                "Starting backfill with config: {:?}",
+                indexer_config
+            );
+
+            let cancel = CancellationToken::new();
+
+            let registry = Registry::new_custom(Some("indexer_alt".into()), None)
+                .context("Failed to create Prometheus registry.")?;
+
+            let metrics = MetricsService::new(metrics_args, registry, cancel.child_token());
+
+            let indexer_args = IndexerArgs {
+                first_checkpoint: Some(first_checkpoint),
+                last_checkpoint: Some(last_checkpoint),
+                pipeline: vec![pipeline],
+                mode: Mode::Backfill,
+                ..Default::default()
+            };
+
+            let h_indexer = setup_indexer(
+                database_url,
+                db_args,
+                indexer_args,
+                client_args,
+                indexer_config,
+                true,
+                metrics.registry(),
+                cancel.child_token(),
+            )
+            .await?
+            .run()
+            .await
+            .context("Failed to start backfill")?;
+
+            let h_metrics = metrics.run().await?;
+
+            // The backfill stops once it has re-indexed the last checkpoint.
+            let _ = h_indexer.await;
+            cancel.cancel();
+            let _ = h_metrics.await;
+        }
+
+        Command::Rewind {
+            database_url,
+            db_args,
+            client_args,
+            pipeline,
+            to_checkpoint,
+            config,
+        } => {
+            let indexer_config = read_config(&config).await?;
+
+            let indexer_args = IndexerArgs {
+                pipeline: vec![pipeline],
+                mode: Mode::Rewind { to_checkpoint },
+                ..Default::default()
+            };
+
+            // Pipelines are rewound as they are added to the indexer, running it just checks that
+            // the requested pipeline was found.
+            setup_indexer(
+                database_url,
+                db_args,
+                indexer_args,
+                client_args,
+                indexer_config,
+                true,
+                &Registry::new(),
+                CancellationToken::new(),
+            )
+            .await?
+            .run()
+            .await
+            .context("Failed to rewind pipeline")?
+            .await?;
+        }
+
+        Command::ListDeadLetters {
+            database_url,
+            db_args,
+            pipeline,
+        } => {
+            let db = Db::for_read(database_url, db_args)
+                .await
+                .context("Failed to connect to database")?;
+
+            let mut conn = db.connect().await?;
+            let dead_letters = conn
+                .dead_letters(pipeline.as_deref())
+                .await
+                .context("Failed to list dead letters")?;
+
+            for dead_letter in dead_letters {
+                println!(
+                    "{} {} ({} at {}): {}",
+                    dead_letter.pipeline,
+                    dead_letter.cp_sequence_number,
+                    dead_letter.stage,
+                    dead_letter.created_at,
+                    dead_letter.error,
+                );
+            }
+        }
+
+        Command::ReplayDeadLetters {
+            database_url,
+            db_args,
+            client_args,
+            pipeline,
+            config,
+        } => {
+            let indexer_config = read_config(&config).await?;
+
+            let indexer_args = IndexerArgs {
+                pipeline: vec![pipeline],
+                mode: Mode::Replay,
+                ..Default::default()
+            };
+
+            // Dead letters are replayed as pipelines are added to the indexer, running it just
+            // checks that the requested pipeline was found.
+            setup_indexer(
+                database_url,
+                db_args,
+                indexer_args,
+                client_args,
+                indexer_config,
+                true,
+                &Registry::new(),
+                CancellationToken::new(),
+            )
+            .await?
+            .run()
+            .await
+            .context("Failed to replay dead letters")?
+            .await?;
+        }
+
         Command::GenerateConfig => {
             let config = IndexerConfig::example();
             let config_toml = toml::to_string_pretty(&config)
@@ -65,7 +65,7 @@ async fn test_get_transaction_block() -> Result<(), anyhow::Error> {
             .await?;
         let tx = cluster
             .wallet
-            .sign_transaction(&transaction_bytes.to_data()?);
+            .sign_transaction(&transaction_bytes.to_data()?)?;
 
         let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
 
@@ -139,7 +139,7 @@ async fn test_get_raw_transaction() -> Result<(), anyhow::Error> {
         .await?;
     let tx = cluster
         .wallet
-        .sign_transaction(&transaction_bytes.to_data()?);
+        .sign_transaction(&transaction_bytes.to_data()?)?;
     let original_sender_signed_data = tx.data().clone();
 
     let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
@@ -197,7 +197,7 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
                 .transaction_builder()
                 .transfer_object(address, oref.object_id, Some(gas_id), 1_000_000, address)
                 .await?;
-            let tx = cluster.wallet.sign_transaction(&data);
+            let tx = cluster.wallet.sign_transaction(&data)?;
 
             let response = client
                 .quorum_driver_api()
@@ -420,7 +420,7 @@ async fn test_query_transaction_blocks() -> Result<(), anyhow::Error> {
     let pt = pt_builer.finish();
 
     let tx_data = TransactionData::new_programmable(signer, vec![gas], pt, 10_000_000, 1000);
-    let signed_data = cluster.wallet.sign_transaction(&tx_data);
+    let signed_data = cluster.wallet.sign_transaction(&tx_data)?;
     let _response = client
         .quorum_driver_api()
         .execute_transaction_block(
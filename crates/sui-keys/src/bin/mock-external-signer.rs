@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An external signer for tests, that signs with the keys in a plaintext keystore file:
+//!
+//!   mock-external-signer <KEYSTORE_PATH>
+//!
+//! See `sui_keys::external` for the protocol.
+
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use sui_keys::external::{SignerKey, SignerRequest, SignerResponse};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_types::crypto::{EncodeDecodeBase64, Signature};
+
+fn main() -> Result<(), anyhow::Error> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("Usage: mock-external-signer <KEYSTORE_PATH>"))?;
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    let request: SignerRequest = serde_json::from_str(&line).context("Invalid request")?;
+
+    let response = match handle(&PathBuf::from(path), request) {
+        Ok(response) => response,
+        Err(e) => SignerResponse::Error(e.to_string()),
+    };
+
+    println!("{}", serde_json::to_string(&response)?);
+    Ok(())
+}
+
+fn handle(path: &PathBuf, request: SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+    let keystore = FileBasedKeystore::new(path)?;
+    Ok(match request {
+        SignerRequest::Keys => SignerResponse::Keys(
+            keystore
+                .aliases()
+                .into_iter()
+                .map(|alias| SignerKey {
+                    public_key: alias.public_key_base64.clone(),
+                    alias: Some(alias.alias.clone()),
+                })
+                .collect(),
+        ),
+
+        SignerRequest::Sign {
+            address, message, ..
+        } => {
+            let message = Base64::decode(&message).map_err(|e| anyhow!("Invalid message: {e}"))?;
+            let key = keystore.get_key(&address)?;
+            SignerResponse::Signature(Signature::new_hashed(&message, key).encode_base64())
+        }
+    })
+}
@@ -0,0 +1,290 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{anyhow, bail, ensure};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Version of the encrypted keystore file format.
+const VERSION: u32 = 1;
+
+const CIPHER: &str = "aes-256-gcm";
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const SALT_LENGTH: usize = 32;
+
+/// The largest scrypt cost parameter that keystores can be encrypted with, or opened with. This
+/// bounds the memory (`128 * r * 2^log_n` bytes, 1GiB at most) and time it takes to derive a key,
+/// so that a keystore file can't be crafted to exhaust them.
+pub const MAX_LOG_N: u8 = 20;
+
+/// Fixed scrypt block size and parallelism; keystores specifying other values are rejected because
+/// they scale memory and time like `log_n`.
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Parameters for encrypting a keystore.
+#[derive(Clone, Copy, Debug)]
+pub struct EncryptionParams {
+    /// The scrypt cost parameter (the KDF performs `2^log_n` iterations).
+    pub log_n: u8,
+    /// How long keys stay unlocked after the passphrase has been entered, or `None` if they
+    /// should stay unlocked for as long as the keystore is loaded.
+    pub unlock_timeout: Option<Duration>,
+}
+
+/// The key derivation function used to turn a passphrase into an encryption key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "algorithm", rename_all = "lowercase")]
+pub enum Kdf {
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+}
+
+/// The contents of an encrypted keystore file. The ciphertext is the encryption of the same JSON
+/// array of Base64-encoded keys that a plaintext keystore file contains.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EncryptedKeystoreFile {
+    pub version: u32,
+    pub kdf: Kdf,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock_timeout_secs: Option<u64>,
+}
+
+/// An encryption key derived from a passphrase, along with the parameters used to derive it, so
+/// that the keystore can be re-encrypted without asking for the passphrase again.
+pub struct DerivedKey {
+    kdf: Kdf,
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+/// The parts of an encrypted keystore file that are not encrypted, but that are authenticated
+/// along with the ciphertext (as associated data), so that they can't be tampered with (e.g. to
+/// extend the unlock timeout) without the keystore failing to open.
+#[derive(Serialize)]
+struct Header<'a> {
+    version: u32,
+    kdf: &'a Kdf,
+    cipher: &'a str,
+    unlock_timeout_secs: Option<u64>,
+}
+
+impl Default for EncryptionParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            unlock_timeout: Some(Duration::from_secs(15 * 60)),
+        }
+    }
+}
+
+impl DerivedKey {
+    /// Derive a key from `passphrase`, with a fresh salt.
+    pub fn new(passphrase: &str, params: &EncryptionParams) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+
+        let kdf = Kdf::Scrypt {
+            log_n: params.log_n,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: Base64::encode(salt),
+        };
+
+        Self::derive(passphrase, &kdf)
+    }
+
+    /// Derive a key from `passphrase`, using the parameters from an existing keystore.
+    pub fn derive(passphrase: &str, kdf: &Kdf) -> Result<Self, anyhow::Error> {
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        match kdf {
+            Kdf::Scrypt { log_n, r, p, salt } => {
+                ensure!(
+                    *log_n <= MAX_LOG_N,
+                    "scrypt cost parameter {log_n} exceeds maximum {MAX_LOG_N}",
+                );
+
+                ensure!(
+                    *r == SCRYPT_R && *p == SCRYPT_P,
+                    "Unsupported scrypt parameters r = {r}, p = {p}, expected r = {SCRYPT_R}, \
+                     p = {SCRYPT_P}",
+                );
+
+                let salt = Base64::decode(salt).map_err(|e| anyhow!("Invalid salt: {e}"))?;
+                let params = scrypt::Params::new(*log_n, *r, *p)
+                    .map_err(|e| anyhow!("Invalid scrypt parameters: {e}"))?;
+                scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key[..])
+                    .map_err(|e| anyhow!("Failed to derive key: {e}"))?;
+            }
+        }
+
+        Ok(Self {
+            kdf: kdf.clone(),
+            key,
+        })
+    }
+
+    /// Encrypt `plaintext` with this key, and a fresh nonce.
+    pub fn seal(
+        &self,
+        plaintext: &[u8],
+        unlock_timeout: Option<Duration>,
+    ) -> Result<EncryptedKeystoreFile, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut file = EncryptedKeystoreFile {
+            version: VERSION,
+            kdf: self.kdf.clone(),
+            cipher: CIPHER.to_string(),
+            nonce: Base64::encode(nonce),
+            ciphertext: String::new(),
+            unlock_timeout_secs: unlock_timeout.map(|t| t.as_secs()),
+        };
+
+        let payload = Payload {
+            msg: plaintext,
+            aad: &file.header()?,
+        };
+
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| anyhow!("Failed to encrypt keystore"))?;
+
+        file.ciphertext = Base64::encode(ciphertext);
+        Ok(file)
+    }
+
+    /// Decrypt the contents of `file`, which must have been encrypted with this key.
+    pub fn open(&self, file: &EncryptedKeystoreFile) -> Result<Vec<u8>, anyhow::Error> {
+        ensure!(
+            file.version == VERSION,
+            "Unsupported encrypted keystore version: {}",
+            file.version,
+        );
+
+        if file.cipher != CIPHER {
+            bail!("Unsupported keystore cipher: {}", file.cipher);
+        }
+
+        let nonce = Base64::decode(&file.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid nonce length");
+
+        let ciphertext =
+            Base64::decode(&file.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: &file.header()?,
+        };
+
+        self.cipher()
+            .decrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| anyhow!("Incorrect passphrase, or the keystore has been corrupted"))
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key[..]))
+    }
+}
+
+impl EncryptedKeystoreFile {
+    /// The unlock timeout the keystore was encrypted with.
+    pub fn unlock_timeout(&self) -> Option<Duration> {
+        self.unlock_timeout_secs.map(Duration::from_secs)
+    }
+
+    /// The serialized [Header], authenticated alongside the ciphertext.
+    fn header(&self) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(serde_json::to_vec(&Header {
+            version: self.version,
+            kdf: &self.kdf,
+            cipher: &self.cipher,
+            unlock_timeout_secs: self.unlock_timeout_secs,
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: EncryptionParams = EncryptionParams {
+        log_n: 4,
+        unlock_timeout: None,
+    };
+
+    #[test]
+    fn seal_and_open() {
+        let key = DerivedKey::new("correct horse", &PARAMS).unwrap();
+        let file = key.seal(b"secret", None).unwrap();
+        assert_ne!(Base64::decode(&file.ciphertext).unwrap(), b"secret");
+
+        // The same passphrase re-derives the same key from the file's parameters.
+        let key = DerivedKey::derive("correct horse", &file.kdf).unwrap();
+        assert_eq!(key.open(&file).unwrap(), b"secret");
+
+        let wrong = DerivedKey::derive("battery staple", &file.kdf).unwrap();
+        assert!(wrong.open(&file).is_err());
+    }
+
+    #[test]
+    fn fresh_salt_and_nonce() {
+        let a = DerivedKey::new("passphrase", &PARAMS).unwrap();
+        let b = DerivedKey::new("passphrase", &PARAMS).unwrap();
+        assert_ne!(a.kdf, b.kdf);
+
+        let x = a.seal(b"secret", None).unwrap();
+        let y = a.seal(b"secret", None).unwrap();
+        assert_ne!(x.nonce, y.nonce);
+        assert_ne!(x.ciphertext, y.ciphertext);
+    }
+
+    #[test]
+    fn tampered_header() {
+        let key = DerivedKey::new("passphrase", &PARAMS).unwrap();
+        let file = key.seal(b"secret", Some(Duration::from_secs(60))).unwrap();
+        assert_eq!(key.open(&file).unwrap(), b"secret");
+
+        // The unlock timeout is stored in plaintext, but can't be changed without invalidating the
+        // ciphertext.
+        let mut tampered = file.clone();
+        tampered.unlock_timeout_secs = None;
+        assert!(key.open(&tampered).is_err());
+
+        let mut tampered = file.clone();
+        tampered.unlock_timeout_secs = Some(u64::MAX);
+        assert!(key.open(&tampered).is_err());
+    }
+
+    #[test]
+    fn max_log_n() {
+        let params = EncryptionParams {
+            log_n: MAX_LOG_N + 1,
+            ..PARAMS
+        };
+        assert!(DerivedKey::new("passphrase", &params).is_err());
+
+        let key = DerivedKey::new("passphrase", &PARAMS).unwrap();
+        let mut file = key.seal(b"secret", None).unwrap();
+        let Kdf::Scrypt { log_n, .. } = &mut file.kdf;
+        *log_n = u8::MAX;
+        assert!(DerivedKey::derive("passphrase", &file.kdf).is_err());
+    }
+}
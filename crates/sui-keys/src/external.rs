@@ -0,0 +1,309 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protocol for signing with keys held by an external signer process, e.g. one that fronts an
+//! HSM or a remote signing service.
+//!
+//! Every request spawns the signer's command, writes the request to its stdin as a single line
+//! of JSON, and reads a single JSON response from its stdout. Anything the signer writes to
+//! stderr is passed through, so it can be used to prompt for confirmation. Signers that don't
+//! respond within their timeout are killed, and the request fails.
+//!
+//! ```text
+//! > {"method":"keys"}
+//! < {"keys":[{"public_key":"<Base64 flag || pk>","alias":"hsm-key"}]}
+//!
+//! > {"method":"sign","address":"0x...","message":"<Base64>","intent_message":"<Base64>"}
+//! < {"signature":"<Base64 flag || sig || pk>"}
+//!
+//! < {"error":"<message>"}
+//! ```
+//!
+//! The signer signs `message` exactly as a `SuiKeyPair` would (for transactions, this is the
+//! digest of the intent message). `intent_message` carries the BCS-encoded intent message that
+//! was hashed, when there is one, so the signer can decode it and apply its own policies.
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// How long a signer is given to respond to a request by default. This is generous, because the
+/// signer may be waiting for a person to confirm the request.
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// How often to check whether the signer has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How to run an external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSigner {
+    /// The signer's executable.
+    pub command: String,
+    /// Arguments passed to the signer on every request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// How long the signer is given to respond to each request, in seconds, before it is killed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the keys the signer can sign with.
+    Keys,
+    /// Sign `message` with the key for `address`.
+    Sign {
+        address: SuiAddress,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        intent_message: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    Keys(Vec<SignerKey>),
+    Signature(String),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SignerKey {
+    /// Base64 encoded `flag || pk`.
+    pub public_key: String,
+    /// An alias for the key. Keys without one are given a random alias when the keystore is
+    /// loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+impl ExternalSigner {
+    pub fn new(command: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            command: command.into(),
+            args,
+            timeout_secs: None,
+        }
+    }
+
+    /// How long the signer is given to respond to each request.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    /// Ask the signer for the public keys it holds, and their aliases.
+    pub fn keys(&self) -> Result<Vec<(PublicKey, Option<String>)>, anyhow::Error> {
+        let SignerResponse::Keys(keys) = self.call(&SignerRequest::Keys)? else {
+            bail!("Unexpected response from external signer {}", self.command);
+        };
+
+        keys.into_iter()
+            .map(|key| {
+                let pk = PublicKey::decode_base64(&key.public_key).map_err(|e| {
+                    anyhow!(
+                        "Invalid public key {} from external signer: {e}",
+                        key.public_key
+                    )
+                })?;
+                Ok((pk, key.alias))
+            })
+            .collect()
+    }
+
+    /// Ask the signer to sign `message` with the key for `address`.
+    pub fn sign(
+        &self,
+        address: &SuiAddress,
+        message: &[u8],
+        intent_message: Option<&[u8]>,
+    ) -> Result<Signature, anyhow::Error> {
+        let request = SignerRequest::Sign {
+            address: *address,
+            message: Base64::encode(message),
+            intent_message: intent_message.map(Base64::encode),
+        };
+
+        let SignerResponse::Signature(signature) = self.call(&request)? else {
+            bail!("Unexpected response from external signer {}", self.command);
+        };
+
+        Signature::decode_base64(&signature)
+            .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))
+    }
+
+    /// Run the signer for a single request, killing it if it doesn't respond within its timeout.
+    ///
+    /// This blocks until the signer responds. When it is called from within a tokio runtime, the
+    /// request is run on the runtime's blocking thread pool, rather than on the calling thread. A
+    /// multi-threaded runtime is told to move its other tasks off the calling thread while it
+    /// waits, but a current-thread runtime can't make progress until the signer responds.
+    pub fn call(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let Ok(handle) = Handle::try_current() else {
+            return self.run(request);
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let signer = self.clone();
+        let request = request.clone();
+        handle.spawn_blocking(move || {
+            let _ = tx.send(signer.run(&request));
+        });
+
+        let recv = || {
+            rx.recv()
+                .unwrap_or_else(|_| bail!("External signer {} was interrupted", self.command))
+        };
+
+        match handle.runtime_flavor() {
+            RuntimeFlavor::MultiThread => tokio::task::block_in_place(recv),
+            _ => recv(),
+        }
+    }
+
+    fn run(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Cannot start external signer {}", self.command))?;
+
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+
+        // Write the request and read the response on a separate thread, so that this thread can
+        // time the signer out even if it never reads its stdin, or blocks on a full stdout.
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Cannot write to external signer {}", self.command))?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("Cannot read from external signer {}", self.command))?;
+        let command = self.command.clone();
+        let io = thread::spawn(move || {
+            stdin
+                .write_all(&line)
+                .with_context(|| format!("Cannot write to external signer {command}"))?;
+            drop(stdin);
+
+            let mut output = vec![];
+            stdout
+                .read_to_end(&mut output)
+                .with_context(|| format!("Cannot read from external signer {command}"))?;
+            Ok::<_, anyhow::Error>(output)
+        });
+
+        let deadline = Instant::now() + self.timeout();
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .with_context(|| format!("Cannot wait for external signer {}", self.command))?
+            {
+                break status;
+            }
+
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!(
+                    "External signer {} did not respond within {:?}",
+                    self.command,
+                    self.timeout(),
+                );
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        if !status.success() {
+            bail!("External signer {} failed: {}", self.command, status);
+        }
+
+        let stdout = io
+            .join()
+            .map_err(|_| anyhow!("Cannot read from external signer {}", self.command))??;
+
+        let response: SignerResponse = serde_json::from_slice(&stdout).with_context(|| {
+            format!(
+                "Cannot deserialize response from external signer {}",
+                self.command
+            )
+        })?;
+
+        match response {
+            SignerResponse::Error(e) => bail!("External signer {} failed: {e}", self.command),
+            response => Ok(response),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        let signer = ExternalSigner {
+            timeout_secs: Some(1),
+            ..ExternalSigner::new("sleep", vec!["10".to_string()])
+        };
+
+        let start = Instant::now();
+        let err = signer.call(&SignerRequest::Keys).unwrap_err();
+        assert!(err.to_string().contains("did not respond"), "{err}");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn timeout_unread_request() {
+        let signer = ExternalSigner {
+            timeout_secs: Some(1),
+            ..ExternalSigner::new("sleep", vec!["10".to_string()])
+        };
+
+        // A request that is too large to fit in the pipe to a signer that never reads it.
+        let request = SignerRequest::Sign {
+            address: SuiAddress::ZERO,
+            message: Base64::encode(vec![0u8; 1 << 20]),
+            intent_message: None,
+        };
+
+        let start = Instant::now();
+        let err = signer.call(&request).unwrap_err();
+        assert!(err.to_string().contains("did not respond"), "{err}");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn timeout_current_thread() {
+        let signer = ExternalSigner {
+            timeout_secs: Some(1),
+            ..ExternalSigner::new("sleep", vec!["10".to_string()])
+        };
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        let err = runtime
+            .block_on(async { signer.call(&SignerRequest::Keys) })
+            .unwrap_err();
+        assert!(err.to_string().contains("did not respond"), "{err}");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,11 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{DerivedKey, EncryptedKeystoreFile, EncryptionParams};
+use crate::external::ExternalSigner;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
+use fastcrypto::error::FastCryptoResult;
+use fastcrypto::hash::HashFunction;
 use rand::{rngs::StdRng, SeedableRng};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -15,19 +19,25 @@ use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write as _};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
-    enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    enum_dispatch, DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme,
+    SuiKeyPair, SuiSignature,
 };
+use zeroize::Zeroizing;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedFileKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -141,6 +151,99 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(file) => {
+                writeln!(writer, "Keystore Type : Encrypted File")?;
+                write!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(f, "{}", writer)
+            }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Command : {:?}", external.signer.command)?;
+                write!(f, "{}", writer)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Keystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        enum Repr {
+            File(PathBuf),
+            InMem(InMemKeystore),
+            Encrypted(PathBuf),
+            External(ExternalSigner),
+        }
+
+        // A file-based keystore may have been encrypted in place since the config that refers to
+        // it was written, so detect its format rather than trusting the variant.
+        match Repr::deserialize(deserializer)? {
+            Repr::File(path) => Keystore::open(&path).map_err(D::Error::custom),
+            Repr::InMem(keystore) => Ok(Keystore::InMem(keystore)),
+            Repr::Encrypted(path) => EncryptedFileKeystore::new(&path)
+                .map(Keystore::Encrypted)
+                .map_err(D::Error::custom),
+            Repr::External(signer) => Ok(Keystore::External(ExternalKeystore::new(signer))),
+        }
+    }
+}
+
+impl Keystore {
+    /// Open the keystore file at `path`, as an encrypted keystore if it has been encrypted, and
+    /// as a plaintext keystore otherwise.
+    pub fn open(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        Ok(if is_encrypted_keystore(path) {
+            Keystore::Encrypted(EncryptedFileKeystore::new(path)?)
+        } else {
+            Keystore::File(FileBasedKeystore::new(path)?)
+        })
+    }
+
+    /// Whether the keystore's private keys are inaccessible until it is unlocked.
+    pub fn is_locked(&mut self) -> bool {
+        match self {
+            Keystore::File(_) | Keystore::InMem(_) | Keystore::External(_) => false,
+            Keystore::Encrypted(keystore) => keystore.is_locked(),
+        }
+    }
+
+    /// Unlock the keystore's private keys with `passphrase`. Keystores that are not encrypted are
+    /// always unlocked.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(_) | Keystore::InMem(_) | Keystore::External(_) => Ok(()),
+            Keystore::Encrypted(keystore) => keystore.unlock(passphrase),
+        }
+    }
+
+    /// Encrypt a plaintext file-based keystore in place, with a key derived from `passphrase`.
+    /// The keystore stays unlocked afterwards.
+    pub fn encrypt(
+        &mut self,
+        passphrase: &str,
+        params: EncryptionParams,
+    ) -> Result<&EncryptedFileKeystore, anyhow::Error> {
+        let path = match self {
+            Keystore::File(FileBasedKeystore {
+                path: Some(path), ..
+            }) => path.clone(),
+            Keystore::File(_) | Keystore::InMem(_) | Keystore::External(_) => {
+                bail!("Only keystores that are saved to a file can be encrypted")
+            }
+            Keystore::Encrypted(keystore) => {
+                bail!("Keystore {} is already encrypted", keystore.path.display())
+            }
+        };
+
+        *self = Keystore::Encrypted(EncryptedFileKeystore::encrypt(&path, passphrase, params)?);
+        match self {
+            Keystore::Encrypted(keystore) => Ok(keystore),
+            _ => unreachable!(),
         }
     }
 }
@@ -309,13 +412,7 @@ impl FileBasedKeystore {
             let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
             })?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (SuiAddress::from(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
+            decode_keys(&kp_strings)
                 .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
         } else {
             BTreeMap::new()
@@ -326,34 +423,7 @@ impl FileBasedKeystore {
         aliases_path.set_extension("aliases");
 
         let aliases = if aliases_path.exists() {
-            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
-                format!(
-                    "Cannot open aliases file in keystore: {}",
-                    aliases_path.display()
-                )
-            })?);
-
-            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
-                format!(
-                    "Cannot deserialize aliases file in keystore: {}",
-                    aliases_path.display(),
-                )
-            })?;
-
-            aliases
-                .into_iter()
-                .map(|alias| {
-                    let key = PublicKey::decode_base64(&alias.public_key_base64);
-                    key.map(|k| (Into::<SuiAddress>::into(&k), alias))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| {
-                    anyhow!(
-                        "Invalid aliases file in keystore: {}. {}",
-                        aliases_path.display(),
-                        e
-                    )
-                })?
+            read_aliases(&aliases_path)?
         } else if keys.is_empty() {
             BTreeMap::new()
         } else {
@@ -441,6 +511,619 @@ impl FileBasedKeystore {
     }
 }
 
+/// A file-based keystore whose private keys are encrypted at rest, using a key derived from a
+/// passphrase. Aliases and public keys are kept in plaintext in the aliases file, so addresses
+/// can be listed without the passphrase, but signing requires the keystore to be unlocked first.
+pub struct EncryptedFileKeystore {
+    aliases: BTreeMap<SuiAddress, Alias>,
+    encrypted: EncryptedKeystoreFile,
+    unlocked: Option<UnlockedKeys>,
+    path: PathBuf,
+}
+
+struct UnlockedKeys {
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    key: DerivedKey,
+    since: Instant,
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.path.to_str().unwrap_or(""))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        let alias = self.create_alias(alias)?;
+        let public_key_base64 = keypair.public().encode_base64();
+        self.unlocked_mut()?.keys.insert(address, keypair);
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64,
+            },
+        );
+        self.save()?;
+        Ok(())
+    }
+
+    /// Return an array of `Alias`, consisting of every alias and its corresponding public key.
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    /// Return an array of `Alias`, consisting of every alias and its corresponding public key.
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    /// Public keys are read from the aliases file, so they are available while locked.
+    fn keys(&self) -> Vec<PublicKey> {
+        self.aliases
+            .values()
+            .filter_map(|alias| PublicKey::decode_base64(&alias.public_key_base64).ok())
+            .collect()
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// Get the alias if it exists, or return an error if it does not exist.
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        match self.unlocked()?.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+
+    /// Updates an old alias to the new alias and saves it to the alias file.
+    /// If the new_alias is None, it will generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save_aliases()?;
+        Ok(new_alias_name)
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Load the encrypted keystore at `path`. The keystore starts off locked.
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let reader = BufReader::new(
+            File::open(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?,
+        );
+        let encrypted: EncryptedKeystoreFile =
+            serde_json::from_reader(reader).with_context(|| {
+                format!(
+                    "Cannot deserialize the encrypted keystore file: {}",
+                    path.display()
+                )
+            })?;
+
+        let mut aliases_path = path.clone();
+        aliases_path.set_extension("aliases");
+        let aliases = if aliases_path.exists() {
+            read_aliases(&aliases_path)?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            aliases,
+            encrypted,
+            unlocked: None,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Encrypt the plaintext keystore at `path` in place, with a key derived from `passphrase`.
+    /// If there is no keystore at `path` yet, an empty encrypted keystore is created. The
+    /// returned keystore is unlocked.
+    pub fn encrypt(
+        path: &PathBuf,
+        passphrase: &str,
+        params: EncryptionParams,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            !is_encrypted_keystore(path),
+            "Keystore {} is already encrypted",
+            path.display(),
+        );
+
+        let plaintext = FileBasedKeystore::new(path)?;
+        let key = DerivedKey::new(passphrase, &params)?;
+        let encrypted = seal_keys(path, &key, &plaintext.keys, params.unlock_timeout)?;
+
+        // Keep a copy of the plaintext keystore until the encrypted keystore that replaces it has
+        // been read back, so that the keys are not lost if it was not written correctly.
+        let backup = path.exists().then(|| with_suffix(path, "bak"));
+        if let Some(backup) = &backup {
+            fs::copy(path, backup).with_context(|| {
+                format!("Cannot back up the keystore file to: {}", backup.display())
+            })?;
+        }
+
+        // Write the aliases first, so that the public keys are never only in the encrypted file.
+        plaintext.save_aliases()?;
+        write_encrypted_keystore(path, &encrypted)?;
+
+        let verified = EncryptedFileKeystore::new(path)
+            .and_then(|written| written.decrypt(&key))
+            .is_ok_and(|keys| keys.keys().eq(plaintext.keys.keys()));
+
+        if !verified {
+            let kept = backup
+                .map(|b| format!(", the plaintext keystore has been kept at: {}", b.display()))
+                .unwrap_or_default();
+            bail!(
+                "Failed to verify the encrypted keystore {}{kept}",
+                path.display()
+            );
+        }
+
+        if let Some(backup) = &backup {
+            fs::remove_file(backup).with_context(|| {
+                format!("Cannot remove the keystore backup: {}", backup.display())
+            })?;
+        }
+
+        Ok(Self {
+            aliases: plaintext.aliases,
+            encrypted,
+            unlocked: Some(UnlockedKeys {
+                keys: plaintext.keys,
+                key,
+                since: Instant::now(),
+            }),
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Decrypt the private keys with `passphrase`. They stay accessible until the keystore's
+    /// unlock timeout elapses, or it is explicitly locked again.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        let key = DerivedKey::derive(passphrase, &self.encrypted.kdf)?;
+        let keys = self.decrypt(&key)?;
+        self.unlocked = Some(UnlockedKeys {
+            keys,
+            key,
+            since: Instant::now(),
+        });
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The unlock timeout the keystore was encrypted with.
+    pub fn unlock_timeout(&self) -> Option<Duration> {
+        self.encrypted.unlock_timeout()
+    }
+
+    /// Forget the decrypted private keys.
+    pub fn lock(&mut self) {
+        self.unlocked = None;
+    }
+
+    /// Whether the private keys are inaccessible until the keystore is unlocked. If the unlock
+    /// timeout has elapsed, the decrypted private keys are forgotten.
+    pub fn is_locked(&mut self) -> bool {
+        self.lock_if_expired();
+        self.unlocked.is_none()
+    }
+
+    pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
+        let aliases_store = serde_json::to_string_pretty(
+            &self.aliases.values().collect::<Vec<_>>(),
+        )
+        .with_context(|| {
+            format!(
+                "Cannot serialize aliases to file in keystore: {}",
+                self.path.display()
+            )
+        })?;
+
+        let mut aliases_path = self.path.clone();
+        aliases_path.set_extension("aliases");
+        fs::write(aliases_path, aliases_store)?;
+        Ok(())
+    }
+
+    /// Re-encrypts the private keys, in the same format as `FileBasedKeystore::save_keystore`,
+    /// with the key they were unlocked with and a fresh nonce.
+    pub fn save_keystore(&mut self) -> Result<(), anyhow::Error> {
+        self.lock_if_expired();
+        let unlocked = self.unlocked()?;
+        let encrypted = seal_keys(
+            &self.path,
+            &unlocked.key,
+            &unlocked.keys,
+            self.encrypted.unlock_timeout(),
+        )?;
+
+        write_encrypted_keystore(&self.path, &encrypted)?;
+        self.encrypted = encrypted;
+        Ok(())
+    }
+
+    pub fn save(&mut self) -> Result<(), anyhow::Error> {
+        self.save_aliases()?;
+        self.save_keystore()?;
+        Ok(())
+    }
+
+    /// Decrypt the private keys with `key`.
+    fn decrypt(&self, key: &DerivedKey) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+        let plaintext = Zeroizing::new(key.open(&self.encrypted)?);
+        let kp_strings: Zeroizing<Vec<String>> =
+            Zeroizing::new(serde_json::from_slice(&plaintext).with_context(|| {
+                format!(
+                    "Cannot deserialize the keystore file: {}",
+                    self.path.display()
+                )
+            })?);
+
+        decode_keys(&kp_strings)
+            .map_err(|e| anyhow!("Invalid keystore file: {}. {}", self.path.display(), e))
+    }
+
+    fn unlocked(&self) -> Result<&UnlockedKeys, anyhow::Error> {
+        match &self.unlocked {
+            Some(unlocked) if !self.expired(unlocked) => Ok(unlocked),
+            _ => bail!(
+                "Keystore {} is locked. Unlock it with its passphrase to access private keys",
+                self.path.display(),
+            ),
+        }
+    }
+
+    fn unlocked_mut(&mut self) -> Result<&mut UnlockedKeys, anyhow::Error> {
+        self.lock_if_expired();
+        self.unlocked()?;
+        self.unlocked
+            .as_mut()
+            .ok_or_else(|| anyhow!("Keystore {} is locked", self.path.display()))
+    }
+
+    /// Forget the decrypted private keys if the unlock timeout has elapsed. Accessors that only
+    /// borrow the keystore refuse to use expired keys, but cannot drop them.
+    fn lock_if_expired(&mut self) {
+        if self.unlocked.as_ref().is_some_and(|u| self.expired(u)) {
+            self.lock();
+        }
+    }
+
+    fn expired(&self, unlocked: &UnlockedKeys) -> bool {
+        self.encrypted
+            .unlock_timeout()
+            .is_some_and(|timeout| unlocked.since.elapsed() >= timeout)
+    }
+}
+
+/// A keystore whose keys are held by an external signer process (see [`crate::external`]).
+/// Private keys never leave the signer: signing requests are forwarded to it, and they can't be
+/// read or added through the keystore.
+///
+/// The signer is only asked for its keys the first time they are needed, so that loading a config
+/// that refers to it does not run it. If that fails, the keystore behaves as if it is empty, and
+/// the failure is reported by every operation that can return an error.
+pub struct ExternalKeystore {
+    signer: ExternalSigner,
+    loaded: OnceLock<Result<SignerKeys, String>>,
+}
+
+/// The public keys held by an external signer, and their aliases.
+#[derive(Default)]
+struct SignerKeys {
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.signer.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ExternalKeystore::new(ExternalSigner::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let signature = self
+            .signer
+            .sign(address, msg, None)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        // Make sure the signer used the right key, to fail early rather than when the signature
+        // is rejected by the network.
+        let keys = self
+            .loaded()
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        match keys.keys.get(address) {
+            Some(pk) if signature.public_key_bytes() == pk.as_ref() => Ok(signature),
+            _ => Err(signature::Error::from_source(format!(
+                "External signer signed with the wrong key for address: [{address}]"
+            ))),
+        }
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes = bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?;
+        let mut hasher = DefaultHash::default();
+        hasher.update(&bytes);
+        let digest = hasher.finalize().digest;
+
+        let signature = self
+            .signer
+            .sign(address, &digest, Some(&bytes))
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "Invalid signature from external signer: {e}"
+                ))
+            })?;
+
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!(
+            "Keys can't be added to the external signer {}, add them to the signer itself",
+            self.signer.command
+        )
+    }
+
+    /// Return an array of `Alias`, consisting of every alias and its corresponding public key.
+    fn aliases(&self) -> Vec<&Alias> {
+        self.loaded()
+            .map(|keys| keys.aliases.values().collect())
+            .unwrap_or_default()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.loaded()
+            .map(|keys| keys.aliases.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Return an array of `Alias`, consisting of every alias and its corresponding public key.
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        let _ = self.loaded();
+        match self.loaded.get_mut() {
+            Some(Ok(keys)) => keys.aliases.values_mut().collect(),
+            _ => vec![],
+        }
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.loaded()
+            .map(|keys| keys.keys.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.loaded()?
+            .aliases
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// Get the alias if it exists, or return an error if it does not exist.
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.loaded()?.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if self.loaded()?.keys.contains_key(address) {
+            bail!(
+                "The private key for address [{address}] is held by the external signer {}",
+                self.signer.command
+            )
+        } else {
+            bail!("Cannot find key for address: [{address}]")
+        }
+    }
+
+    /// Aliases are set by the signer, so they can't be updated through the keystore.
+    fn update_alias(
+        &mut self,
+        _old_alias: &str,
+        _new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        bail!(
+            "Aliases of keys in the external signer {} are set by the signer",
+            self.signer.command
+        )
+    }
+}
+
+impl ExternalKeystore {
+    /// A keystore for the keys held by `signer`. The signer is not run until its keys are needed.
+    pub fn new(signer: ExternalSigner) -> Self {
+        Self {
+            signer,
+            loaded: OnceLock::new(),
+        }
+    }
+
+    pub fn signer(&self) -> &ExternalSigner {
+        &self.signer
+    }
+
+    /// The keys held by the signer, asking the signer for them if this is the first time they
+    /// are needed.
+    fn loaded(&self) -> Result<&SignerKeys, anyhow::Error> {
+        self.loaded
+            .get_or_init(|| SignerKeys::load(&self.signer).map_err(|e| format!("{e:#}")))
+            .as_ref()
+            .map_err(|e| anyhow!("Cannot load keys from external signer: {e}"))
+    }
+}
+
+impl SignerKeys {
+    /// Ask `signer` for its keys. Keys that the signer doesn't give an alias are given a random
+    /// one, which only lasts as long as the keystore is loaded.
+    fn load(signer: &ExternalSigner) -> Result<Self, anyhow::Error> {
+        let mut loaded = Self::default();
+        for (pk, alias) in signer.keys()? {
+            let names: HashSet<_> = loaded.aliases.values().map(|a| a.alias.clone()).collect();
+            let alias = match alias {
+                Some(a) if names.contains(&a) => {
+                    bail!("Alias {a} is used for more than one key by the external signer")
+                }
+                Some(a) => validate_alias(&a)?,
+                None => random_name(&names),
+            };
+
+            let address = SuiAddress::from(&pk);
+            loaded.aliases.insert(
+                address,
+                Alias {
+                    alias,
+                    public_key_base64: pk.encode_base64(),
+                },
+            );
+            loaded.keys.insert(address, pk);
+        }
+
+        Ok(loaded)
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     aliases: BTreeMap<SuiAddress, Alias>,
@@ -593,6 +1276,105 @@ impl InMemKeystore {
     }
 }
 
+/// Whether the keystore file at `path` is encrypted. Encrypted keystores are stored as a JSON
+/// object, while plaintext keystores are stored as a JSON array of keys.
+fn is_encrypted_keystore(path: &Path) -> bool {
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .is_some_and(|value| value.is_object())
+}
+
+fn decode_keys(kp_strings: &[String]) -> FastCryptoResult<BTreeMap<SuiAddress, SuiKeyPair>> {
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = SuiKeyPair::decode_base64(kpstr);
+            key.map(|k| (SuiAddress::from(&k.public()), k))
+        })
+        .collect()
+}
+
+fn read_aliases(aliases_path: &Path) -> Result<BTreeMap<SuiAddress, Alias>, anyhow::Error> {
+    let reader = BufReader::new(File::open(aliases_path).with_context(|| {
+        format!(
+            "Cannot open aliases file in keystore: {}",
+            aliases_path.display()
+        )
+    })?);
+
+    let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+        format!(
+            "Cannot deserialize aliases file in keystore: {}",
+            aliases_path.display(),
+        )
+    })?;
+
+    aliases
+        .into_iter()
+        .map(|alias| {
+            let key = PublicKey::decode_base64(&alias.public_key_base64);
+            key.map(|k| (Into::<SuiAddress>::into(&k), alias))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map_err(|e| {
+            anyhow!(
+                "Invalid aliases file in keystore: {}. {}",
+                aliases_path.display(),
+                e
+            )
+        })
+}
+
+/// Encrypt `keys` in the same format as `FileBasedKeystore::save_keystore`.
+fn seal_keys(
+    path: &Path,
+    key: &DerivedKey,
+    keys: &BTreeMap<SuiAddress, SuiKeyPair>,
+    unlock_timeout: Option<Duration>,
+) -> Result<EncryptedKeystoreFile, anyhow::Error> {
+    let plaintext =
+        serde_json::to_vec(&keys.values().map(|k| k.encode_base64()).collect::<Vec<_>>())
+            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
+    key.seal(&plaintext, unlock_timeout)
+}
+
+/// Write `encrypted` to `path`, without leaving a partially written keystore behind if that
+/// fails: It is written to a temporary file next to `path`, flushed to disk, and then renamed over
+/// `path`.
+fn write_encrypted_keystore(
+    path: &Path,
+    encrypted: &EncryptedKeystoreFile,
+) -> Result<(), anyhow::Error> {
+    let store = serde_json::to_string_pretty(encrypted)
+        .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
+
+    let tmp_path = with_suffix(path, "tmp");
+    let mut file = File::create(&tmp_path)
+        .with_context(|| format!("Cannot create keystore file: {}", tmp_path.display()))?;
+    file.write_all(store.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Cannot replace keystore file: {}", path.display()))?;
+
+    // Flush the rename as well, where the platform supports syncing directories.
+    if let Some(dir) = path.parent().and_then(|dir| File::open(dir).ok()) {
+        let _ = dir.sync_all();
+    }
+
+    Ok(())
+}
+
+/// `path` with `.{suffix}` appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
@@ -606,7 +1388,12 @@ fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
 
 #[cfg(test)]
 mod tests {
-    use crate::keystore::validate_alias;
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use crate::encryption::EncryptionParams;
+    use crate::keystore::{validate_alias, EncryptedFileKeystore};
 
     #[test]
     fn validate_alias_test() {
@@ -625,4 +1412,21 @@ mod tests {
         assert!(validate_alias("^A").is_err());
         assert!(validate_alias("-A").is_err());
     }
+    #[test]
+    fn unlock_timeout_drops_keys_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sui.keystore");
+        let params = EncryptionParams {
+            log_n: 4,
+            unlock_timeout: Some(Duration::from_secs(1)),
+        };
+
+        let mut keystore = EncryptedFileKeystore::encrypt(&path, "passphrase", params).unwrap();
+        assert!(!keystore.is_locked());
+        assert!(keystore.unlocked.is_some());
+
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(keystore.is_locked());
+        assert!(keystore.unlocked.is_none());
+    }
 }
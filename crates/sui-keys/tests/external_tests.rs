@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for keystores backed by an external signer. These use the `mock-external-signer` binary,
+//! so they need the `test-utils` feature:
+//!
+//!   cargo test -p sui-keys --features test-utils
+
+use shared_crypto::intent::Intent;
+use sui_keys::external::ExternalSigner;
+use sui_keys::key_derive::generate_new_key;
+use sui_keys::keystore::{AccountKeystore, ExternalKeystore, FileBasedKeystore, Keystore};
+use sui_types::crypto::SignatureScheme;
+use tempfile::TempDir;
+
+fn mock_external_signer(keystore_path: &std::path::Path) -> ExternalSigner {
+    ExternalSigner::new(
+        env!("CARGO_BIN_EXE_mock-external-signer"),
+        vec![keystore_path.to_str().unwrap().to_string()],
+    )
+}
+
+#[test]
+fn external_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("signer.keystore");
+    let mut signer_keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (ed25519, _, _) = signer_keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, Some("a".to_string()), None, None)
+        .unwrap();
+    let (secp256k1, _, _) = signer_keystore
+        .generate_and_add_new_key(
+            SignatureScheme::Secp256k1,
+            Some("b".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let mut keystore = Keystore::from(ExternalKeystore::new(mock_external_signer(&keystore_path)));
+    assert_eq!(signer_keystore.addresses(), keystore.addresses());
+    assert_eq!(
+        ed25519,
+        *keystore.get_address_by_alias("a".to_string()).unwrap()
+    );
+    assert_eq!("b", keystore.get_alias_by_address(&secp256k1).unwrap());
+
+    // Signatures from the signer are the same as signing with the keys directly.
+    for address in [ed25519, secp256k1] {
+        let expected = signer_keystore
+            .sign_secure(&address, &"msg", Intent::sui_transaction())
+            .unwrap();
+        let actual = keystore
+            .sign_secure(&address, &"msg", Intent::sui_transaction())
+            .unwrap();
+        assert_eq!(expected, actual);
+
+        let expected = signer_keystore.sign_hashed(&address, b"msg").unwrap();
+        let actual = keystore.sign_hashed(&address, b"msg").unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    // Private keys never leave the signer, and keys can't be added through the keystore.
+    assert!(keystore.get_key(&ed25519).is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    // The signer reports errors for keys it doesn't hold.
+    let (unknown, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    assert!(keystore
+        .sign_secure(&unknown, &"msg", Intent::sui_transaction())
+        .is_err());
+}
+
+#[test]
+fn external_keystore_config_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("signer.keystore");
+    let mut signer_keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    signer_keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let signer = mock_external_signer(&keystore_path);
+    let config = serde_json::json!({
+        "External": { "command": signer.command, "args": signer.args }
+    });
+
+    let keystore: Keystore = serde_json::from_value(config.clone()).unwrap();
+    assert!(matches!(keystore, Keystore::External(_)));
+    assert_eq!(signer_keystore.addresses(), keystore.addresses());
+    assert_eq!(config, serde_json::to_value(&keystore).unwrap());
+
+    // Loading the keystore does not run the signer, so signers that can't be run are only
+    // reported once their keys are needed.
+    let config = serde_json::json!({ "External": { "command": "/does/not/exist" } });
+    let keystore: Keystore = serde_json::from_value(config).unwrap();
+    assert!(keystore.addresses().is_empty());
+    assert!(keystore.get_address_by_alias("a".to_string()).is_err());
+
+    let (address, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    assert!(keystore
+        .sign_secure(&address, &"msg", Intent::sui_transaction())
+        .is_err());
+}
@@ -3,9 +3,12 @@
 
 use std::fs;
 use std::str::FromStr;
+use std::time::Duration;
 
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
+use shared_crypto::intent::Intent;
+use sui_keys::encryption::{DerivedKey, EncryptionParams, Kdf, MAX_LOG_N};
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
@@ -279,3 +282,170 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+/// Cheap KDF parameters, so that tests don't spend their time deriving keys.
+const TEST_PARAMS: EncryptionParams = EncryptionParams {
+    log_n: 4,
+    unlock_timeout: None,
+};
+
+#[test]
+fn encrypt_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, Some("a".to_string()), None, None)
+        .unwrap();
+    keystore
+        .generate_and_add_new_key(
+            SignatureScheme::Secp256k1,
+            Some("b".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let addresses = keystore.addresses();
+    let private_keys: Vec<_> = addresses
+        .iter()
+        .map(|a| keystore.get_key(a).unwrap().encode_base64())
+        .collect();
+
+    keystore.encrypt("passphrase", TEST_PARAMS).unwrap();
+    assert!(!keystore.is_locked());
+    assert!(keystore.to_string().contains("Encrypted"));
+
+    // Private keys are no longer in the keystore file in plaintext.
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    for key in &private_keys {
+        assert!(!contents.contains(key.as_str()));
+    }
+
+    // The plaintext backup and the temporary file are cleaned up once the encrypted keystore has
+    // been written and verified.
+    assert!(!temp_dir.path().join("sui.keystore.bak").exists());
+    assert!(!temp_dir.path().join("sui.keystore.tmp").exists());
+
+    // Re-opening the keystore detects that it is encrypted, and starts off locked, but addresses
+    // and aliases are still available.
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+    assert_eq!(addresses, keystore.addresses());
+    assert_eq!(vec!["a", "b"], {
+        let mut names = keystore.alias_names();
+        names.sort();
+        names
+    });
+    assert!(keystore.get_key(&addresses[0]).is_err());
+    assert!(keystore
+        .sign_secure(&addresses[0], &"msg", Intent::sui_transaction())
+        .is_err());
+
+    assert!(keystore.unlock("wrong passphrase").is_err());
+    assert!(keystore.is_locked());
+
+    keystore.unlock("passphrase").unwrap();
+    assert!(!keystore.is_locked());
+    for (address, key) in addresses.iter().zip(&private_keys) {
+        assert_eq!(key, &keystore.get_key(address).unwrap().encode_base64());
+    }
+    assert!(keystore
+        .sign_secure(&addresses[0], &"msg", Intent::sui_transaction())
+        .is_ok());
+
+    // A keystore can't be encrypted twice.
+    assert!(keystore.encrypt("passphrase", TEST_PARAMS).is_err());
+}
+
+#[test]
+fn encrypted_keystore_add_key_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    keystore.encrypt("passphrase", TEST_PARAMS).unwrap();
+    assert!(keystore.addresses().is_empty());
+
+    // Adding a key needs the keystore to be unlocked, but renaming aliases does not.
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    keystore.unlock("passphrase").unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, Some("a".to_string()), None, None)
+        .unwrap();
+
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    keystore.update_alias("a", Some("b")).unwrap();
+
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    assert_eq!(vec![address], keystore.addresses());
+    assert_eq!("b", keystore.get_alias_by_address(&address).unwrap());
+    keystore.unlock("passphrase").unwrap();
+    assert!(keystore.get_key(&address).is_ok());
+}
+
+#[test]
+fn encrypted_keystore_unlock_timeout_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let params = EncryptionParams {
+        unlock_timeout: Some(Duration::from_secs(1)),
+        ..TEST_PARAMS
+    };
+    keystore.encrypt("passphrase", params).unwrap();
+    assert!(keystore.get_key(&address).is_ok());
+
+    std::thread::sleep(Duration::from_millis(1100));
+    assert!(keystore.is_locked());
+    assert!(keystore.get_key(&address).is_err());
+
+    keystore.unlock("passphrase").unwrap();
+    assert!(keystore.get_key(&address).is_ok());
+}
+
+#[test]
+fn encrypted_keystore_config_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::open(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    // Configs written before the keystore was encrypted still refer to it as a file keystore.
+    let config = serde_json::to_string(&keystore).unwrap();
+    keystore.encrypt("passphrase", TEST_PARAMS).unwrap();
+
+    let mut keystore: Keystore = serde_json::from_str(&config).unwrap();
+    assert!(matches!(keystore, Keystore::Encrypted(_)));
+    assert!(keystore.is_locked());
+
+    let config = serde_json::to_string(&keystore).unwrap();
+    let keystore: Keystore = serde_json::from_str(&config).unwrap();
+    assert!(matches!(keystore, Keystore::Encrypted(_)));
+}
+
+#[test]
+fn encrypted_keystore_kdf_bounds_test() {
+    let kdf = |log_n, r, p| Kdf::Scrypt {
+        log_n,
+        r,
+        p,
+        salt: "c2FsdA==".to_string(),
+    };
+
+    assert!(DerivedKey::derive("passphrase", &kdf(4, 8, 1)).is_ok());
+
+    // Keystore files can't ask for more memory or time to open than keystores are encrypted with.
+    assert!(DerivedKey::derive("passphrase", &kdf(MAX_LOG_N + 1, 8, 1)).is_err());
+    assert!(DerivedKey::derive("passphrase", &kdf(4, 1 << 20, 1)).is_err());
+    assert!(DerivedKey::derive("passphrase", &kdf(4, 8, 1 << 20)).is_err());
+}
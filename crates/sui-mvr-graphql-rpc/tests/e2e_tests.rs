@@ -717,7 +717,8 @@ async fn test_payload_using_vars_mutation_passes() {
         .network
         .validator_fullnode_handle
         .wallet
-        .sign_transaction(&tx);
+        .sign_transaction(&tx)
+        .unwrap();
     let (tx_bytes, sigs) = signed_tx.to_tx_bytes_and_signatures();
     let tx_bytes = tx_bytes.encoded();
     let sigs = sigs.iter().map(|sig| sig.encoded()).collect::<Vec<_>>();
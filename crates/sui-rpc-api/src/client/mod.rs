@@ -5,6 +5,7 @@ mod response_ext;
 pub use response_ext::ResponseExt;
 
 use tap::Pipe;
+use tokio_stream::{Stream, StreamExt};
 use tonic::metadata::MetadataMap;
 
 use crate::field_mask::FieldMaskUtil;
@@ -13,6 +14,8 @@ use crate::proto::node::v2::{
     EffectsFinality, ExecuteTransactionResponse, GetCheckpointResponse, GetFullCheckpointResponse,
     GetObjectResponse,
 };
+use crate::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+use crate::proto::node::v2alpha::SubscribeCheckpointsRequest;
 use crate::proto::types::Bcs;
 use crate::proto::TryFromProtoError;
 use prost_types::FieldMask;
@@ -23,6 +26,21 @@ use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSeque
 use sui_types::object::Object;
 use sui_types::transaction::Transaction;
 
+/// The fields of `GetFullCheckpointResponse` needed to reconstruct a [CheckpointData].
+const FULL_CHECKPOINT_READ_MASK: [&str; 8] = [
+    "summary_bcs",
+    "signature",
+    "contents_bcs",
+    "transactions.transaction_bcs",
+    "transactions.effects_bcs",
+    "transactions.events_bcs",
+    "transactions.input_objects.object_bcs",
+    "transactions.output_objects.object_bcs",
+];
+
+/// Full checkpoints can be much larger than tonic's default decoding limit.
+const MAX_CHECKPOINT_DECODING_SIZE: usize = 64 * 1024 * 1024;
+
 pub type Result<T, E = tonic::Status> = std::result::Result<T, E>;
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
@@ -122,22 +140,12 @@ impl Client {
         let request = crate::proto::node::v2::GetFullCheckpointRequest {
             sequence_number: Some(sequence_number),
             digest: None,
-            read_mask: FieldMask::from_paths([
-                "summary_bcs",
-                "signature",
-                "contents_bcs",
-                "transactions.transaction_bcs",
-                "transactions.effects_bcs",
-                "transactions.events_bcs",
-                "transactions.input_objects.object_bcs",
-                "transactions.output_objects.object_bcs",
-            ])
-            .pipe(Some),
+            read_mask: FieldMask::from_paths(FULL_CHECKPOINT_READ_MASK).pipe(Some),
         };
 
         let (metadata, response, _extentions) = self
             .raw_client()
-            .max_decoding_message_size(64 * 1024 * 1024)
+            .max_decoding_message_size(MAX_CHECKPOINT_DECODING_SIZE)
             .get_full_checkpoint(request)
             .await?
             .into_parts();
@@ -146,6 +154,36 @@ impl Client {
             .map_err(|e| status_from_error_with_metadata(e, metadata))
     }
 
+    /// Subscribe to the checkpoints that the node executes, starting from the next checkpoint it
+    /// executes after the subscription is established.
+    ///
+    /// The stream ends (possibly after yielding an error) if the node drops the subscription, in
+    /// which case checkpoints executed before re-subscribing need to be fetched with
+    /// [Client::get_full_checkpoint].
+    pub async fn subscribe_checkpoints(
+        &self,
+    ) -> Result<impl Stream<Item = Result<CheckpointData>> + Send + 'static> {
+        let request = SubscribeCheckpointsRequest {
+            read_mask: FieldMask::from_paths(FULL_CHECKPOINT_READ_MASK).pipe(Some),
+            filter: None,
+        };
+
+        let (metadata, stream, _extentions) =
+            SubscriptionServiceClient::with_interceptor(self.channel.clone(), self.auth.clone())
+                .max_decoding_message_size(MAX_CHECKPOINT_DECODING_SIZE)
+                .subscribe_checkpoints(request)
+                .await?
+                .into_parts();
+
+        Ok(stream.map(move |response| {
+            response?
+                .checkpoint
+                .ok_or_else(|| TryFromProtoError::missing("checkpoint"))
+                .and_then(checkpoint_data_try_from_proto)
+                .map_err(|e| status_from_error_with_metadata(e, metadata.clone()))
+        }))
+    }
+
     pub async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
         self.get_object_internal(object_id, None).await
     }
@@ -35,6 +35,10 @@ impl RpcError {
             details: None,
         }
     }
+
+    pub fn code(&self) -> Code {
+        self.code
+    }
 }
 
 impl From<RpcError> for tonic::Status {
@@ -58,6 +62,15 @@ impl From<RpcError> for tonic::Status {
     }
 }
 
+impl From<RpcError> for crate::proto::node::v2alpha::BatchGetError {
+    fn from(value: RpcError) -> Self {
+        Self {
+            code: Some(value.code.into()),
+            message: value.message,
+        }
+    }
+}
+
 impl From<sui_types::storage::error::Error> for RpcError {
     fn from(value: sui_types::storage::error::Error) -> Self {
         Self {
@@ -11,6 +11,7 @@ use tonic::{
 };
 use tower::{Service, ServiceExt};
 
+use crate::subscription::CheckpointFilter;
 use crate::subscription::SubscriptionServiceHandle;
 
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -155,7 +156,14 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
         &self,
         request: tonic::Request<crate::proto::node::v2alpha::SubscribeCheckpointsRequest>,
     ) -> Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
-        let read_mask = request.into_inner().read_mask.unwrap_or_default();
+        let request = request.into_inner();
+        let read_mask = request.read_mask.unwrap_or_default();
+        let filter = request
+            .filter
+            .as_ref()
+            .map(CheckpointFilter::try_from_proto)
+            .transpose()
+            .map_err(|e| tonic::Status::from(crate::RpcError::from(e)))?;
 
         let Some(mut receiver) = self.register_subscription().await else {
             return Err(tonic::Status::unavailable(
@@ -165,15 +173,19 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
 
         let response = Box::pin(async_stream::stream! {
             while let Some(checkpoint) = receiver.recv().await {
-                let Some(cursor) = checkpoint.sequence_number else {
+                let Some(cursor) = checkpoint.response.sequence_number else {
                     yield Err(tonic::Status::internal("unable to determine cursor"));
                     break;
                 };
 
-                let checkpoint = apply_checkpoint_read_mask(&read_mask, &checkpoint);
+                let mut masked = apply_checkpoint_read_mask(&read_mask, &checkpoint.response);
+                if let Some(filter) = &filter {
+                    filter.apply(&checkpoint.data, &mut masked);
+                }
+
                 let response = SubscribeCheckpointsResponse {
                     cursor: Some(cursor),
-                    checkpoint: Some(checkpoint),
+                    checkpoint: Some(masked),
                 };
 
                 yield Ok(response);
@@ -445,4 +457,122 @@ impl crate::proto::node::v2alpha::node_service_server::NodeService for crate::Rp
 
         Ok(tonic::Response::new(response))
     }
+
+    type ListTransactionsStream = crate::service::transactions::list::ListTransactionsStream;
+
+    async fn list_transactions(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::ListTransactionsRequest>,
+    ) -> std::result::Result<tonic::Response<Self::ListTransactionsStream>, tonic::Status> {
+        self.list_transactions(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    type ListEventsStream = crate::service::events::ListEventsStream;
+
+    async fn list_events(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::ListEventsRequest>,
+    ) -> std::result::Result<tonic::Response<Self::ListEventsStream>, tonic::Status> {
+        self.list_events(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_balance(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::GetBalanceRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::GetBalanceResponse>,
+        tonic::Status,
+    > {
+        self.get_balance(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_balances(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::ListBalancesRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::ListBalancesResponse>,
+        tonic::Status,
+    > {
+        self.list_balances(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_owned_coins(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::ListOwnedCoinsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::ListOwnedCoinsResponse>,
+        tonic::Status,
+    > {
+        self.list_owned_coins(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_package(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::GetPackageRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::GetPackageResponse>,
+        tonic::Status,
+    > {
+        self.get_package(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_datatype(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::GetDatatypeRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::GetDatatypeResponse>,
+        tonic::Status,
+    > {
+        self.get_datatype(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_function(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::GetFunctionRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::GetFunctionResponse>,
+        tonic::Status,
+    > {
+        self.get_function(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn batch_get_objects(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::BatchGetObjectsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::BatchGetObjectsResponse>,
+        tonic::Status,
+    > {
+        self.batch_get_objects(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn batch_get_transactions(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::BatchGetTransactionsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::BatchGetTransactionsResponse>,
+        tonic::Status,
+    > {
+        self.batch_get_transactions(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
 }
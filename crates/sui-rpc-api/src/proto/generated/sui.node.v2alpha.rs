@@ -223,6 +223,714 @@ pub struct AccountObject {
     #[prost(message, optional, tag = "4")]
     pub object_type: ::core::option::Option<super::super::types::StructTag>,
 }
+/// Request message for `NodeService.ListTransactions`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTransactionsRequest {
+    /// Required. The first checkpoint in the range to scan, inclusive.
+    #[prost(uint64, optional, tag = "1")]
+    pub start_checkpoint: ::core::option::Option<u64>,
+    /// The last checkpoint in the range to scan, inclusive.
+    /// If unspecified, defaults to the latest executed checkpoint.
+    /// At most `1000` checkpoints can be scanned by a single request with a filter,
+    /// or `100` without one or if the node has no indexes.
+    #[prost(uint64, optional, tag = "2")]
+    pub end_checkpoint: ::core::option::Option<u64>,
+    /// Optional. Only transactions that match every criteria set in the filter
+    /// will be returned.
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<TransactionFilter>,
+    /// Optional. Mask for specifiying which parts of each
+    /// `sui.node.v2.FullCheckpointTransaction` should be returned.
+    ///
+    /// If no read_mask is provided, defaults to `digest`.
+    #[prost(message, optional, tag = "4")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+/// Response message for `NodeService.ListTransactions`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTransactionsResponse {
+    /// Required. The sequence number of the checkpoint that includes this
+    /// transaction.
+    #[prost(uint64, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<u64>,
+    /// The requested data for this transaction.
+    #[prost(message, optional, tag = "2")]
+    pub transaction: ::core::option::Option<super::v2::FullCheckpointTransaction>,
+}
+/// Criteria for selecting transactions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionFilter {
+    /// Select transactions sent by this address.
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<super::super::types::Address>,
+    /// Select transactions that were sent by, or that created, mutated or
+    /// transferred an object to, this address.
+    #[prost(message, optional, tag = "2")]
+    pub affected_address: ::core::option::Option<super::super::types::Address>,
+    /// Select transactions that call a matching Move function.
+    #[prost(message, optional, tag = "3")]
+    pub move_function: ::core::option::Option<MoveFunctionFilter>,
+}
+/// Criteria for selecting a Move function. Functions can be selected by
+/// package, by package and module, or by package, module and function name.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MoveFunctionFilter {
+    /// Required. The package containing the function.
+    #[prost(message, optional, tag = "1")]
+    pub package: ::core::option::Option<super::super::types::ObjectId>,
+    /// Name of the module containing the function.
+    #[prost(string, optional, tag = "2")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the function. If this is set then `module` must also be set.
+    #[prost(string, optional, tag = "3")]
+    pub function: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Request message for `NodeService.ListEvents`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsRequest {
+    /// Required. The first checkpoint in the range to scan, inclusive.
+    #[prost(uint64, optional, tag = "1")]
+    pub start_checkpoint: ::core::option::Option<u64>,
+    /// The last checkpoint in the range to scan, inclusive.
+    /// If unspecified, defaults to the latest executed checkpoint.
+    /// At most `1000` checkpoints can be scanned by a single request with a filter,
+    /// or `100` without one or if the node has no indexes.
+    #[prost(uint64, optional, tag = "2")]
+    pub end_checkpoint: ::core::option::Option<u64>,
+    /// Optional. Only events that match every criteria set in the filter will be
+    /// returned.
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<EventFilter>,
+    /// Optional. Mask for specifiying which parts of the `ListEventsResponse`
+    /// should be returned.
+    ///
+    /// If no read_mask is provided, defaults to `transaction_digest,event`.
+    #[prost(message, optional, tag = "4")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+/// Response message for `NodeService.ListEvents`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsResponse {
+    /// Required. The sequence number of the checkpoint that includes the
+    /// transaction that emitted this event.
+    #[prost(uint64, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<u64>,
+    /// Required. The position of this event within the events emitted by its
+    /// transaction.
+    #[prost(uint64, optional, tag = "2")]
+    pub event_index: ::core::option::Option<u64>,
+    /// The digest of the transaction that emitted this event.
+    #[prost(message, optional, tag = "3")]
+    pub transaction_digest: ::core::option::Option<super::super::types::Digest>,
+    /// The event.
+    #[prost(message, optional, tag = "4")]
+    pub event: ::core::option::Option<super::super::types::Event>,
+    /// The event encoded as BCS.
+    #[prost(message, optional, tag = "5")]
+    pub event_bcs: ::core::option::Option<super::super::types::Bcs>,
+}
+/// Criteria for selecting events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventFilter {
+    /// Select events emitted by transactions sent by this address.
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<super::super::types::Address>,
+    /// Select events by type. This can be a package address (`0x2`), a module
+    /// (`0x2::coin`), or a struct type (`0x2::coin::CoinMetadata`). Struct types
+    /// without type parameters match every instantiation of that type.
+    #[prost(string, optional, tag = "2")]
+    pub event_type: ::core::option::Option<::prost::alloc::string::String>,
+    /// Select events emitted by transactions that call a matching Move function.
+    #[prost(message, optional, tag = "3")]
+    pub move_function: ::core::option::Option<MoveFunctionFilter>,
+}
+/// Request message for `NodeService.GetBalance`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceRequest {
+    /// Required. The address of the account that owns the coins.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// Required. The coin type to request the balance of.
+    #[prost(message, optional, tag = "2")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+}
+/// Response message for `NodeService.GetBalance`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceResponse {
+    /// Required. The balance of the requested coin type.
+    #[prost(message, optional, tag = "1")]
+    pub balance: ::core::option::Option<Balance>,
+}
+/// The balance of a coin type held by an account.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Balance {
+    /// Required. The coin type.
+    #[prost(message, optional, tag = "1")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+    /// Required. The sum of the values of all of the coins of this type owned by
+    /// the account.
+    #[prost(uint64, optional, tag = "2")]
+    pub balance: ::core::option::Option<u64>,
+    /// Required. The number of coins of this type owned by the account.
+    #[prost(uint64, optional, tag = "3")]
+    pub coin_count: ::core::option::Option<u64>,
+}
+/// Request message for `NodeService.ListBalances`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesRequest {
+    /// Required. The address of the account that owns the coins.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The maximum number of balances to return. The service may return fewer than this value.
+    /// If unspecified, at most `50` entries will be returned.
+    /// The maximum value is `1000`; values above `1000` will be coerced to `1000`.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListBalances` call.
+    /// Provide this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListBalances` must
+    /// match the call that provided the page token.
+    #[prost(string, optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Response message for `NodeService.ListBalances`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesResponse {
+    /// Page of balances held by the specified account.
+    #[prost(message, repeated, tag = "1")]
+    pub balances: ::prost::alloc::vec::Vec<Balance>,
+    /// A token, which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(string, optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Request message for `NodeService.ListOwnedCoins`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedCoinsRequest {
+    /// Required. The address of the account that owns the coins.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// Optional. Only return coins of this type.
+    #[prost(message, optional, tag = "2")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+    /// The maximum number of coins to return. The service may return fewer than this value.
+    /// If unspecified, at most `50` entries will be returned.
+    /// The maximum value is `1000`; values above `1000` will be coerced to `1000`.
+    #[prost(uint32, optional, tag = "3")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListOwnedCoins` call.
+    /// Provide this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListOwnedCoins` must
+    /// match the call that provided the page token.
+    #[prost(string, optional, tag = "4")]
+    pub page_token: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Response message for `NodeService.ListOwnedCoins`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedCoinsResponse {
+    /// Page of coins owned by the specified account.
+    #[prost(message, repeated, tag = "1")]
+    pub coins: ::prost::alloc::vec::Vec<OwnedCoin>,
+    /// A token, which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(string, optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// A coin owned by an account.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OwnedCoin {
+    /// Required. ObjectId of the coin.
+    #[prost(message, optional, tag = "1")]
+    pub object_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. Version of the coin.
+    #[prost(uint64, optional, tag = "2")]
+    pub version: ::core::option::Option<u64>,
+    /// Required. The type of the coin, e.g. `0x2::sui::SUI` for a
+    /// `0x2::coin::Coin<0x2::sui::SUI>`.
+    #[prost(message, optional, tag = "3")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+    /// Required. The value of the coin.
+    #[prost(uint64, optional, tag = "4")]
+    pub balance: ::core::option::Option<u64>,
+}
+/// Request message for `NodeService.GetPackage`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPackageRequest {
+    /// Required. The storage id of the package.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+}
+/// Response message for `NodeService.GetPackage`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPackageResponse {
+    /// Required. The requested package.
+    #[prost(message, optional, tag = "1")]
+    pub package: ::core::option::Option<Package>,
+}
+/// Request message for `NodeService.GetDatatype`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatatypeRequest {
+    /// Required. The storage id of the package the datatype is defined in.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. The name of the module the datatype is defined in.
+    #[prost(string, optional, tag = "2")]
+    pub module_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The name of the datatype.
+    #[prost(string, optional, tag = "3")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Response message for `NodeService.GetDatatype`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatatypeResponse {
+    /// Required. The requested datatype.
+    #[prost(message, optional, tag = "1")]
+    pub datatype: ::core::option::Option<DatatypeDescriptor>,
+}
+/// Request message for `NodeService.GetFunction`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetFunctionRequest {
+    /// Required. The storage id of the package the function is defined in.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. The name of the module the function is defined in.
+    #[prost(string, optional, tag = "2")]
+    pub module_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The name of the function.
+    #[prost(string, optional, tag = "3")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Response message for `NodeService.GetFunction`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetFunctionResponse {
+    /// Required. The requested function.
+    #[prost(message, optional, tag = "1")]
+    pub function: ::core::option::Option<FunctionDescriptor>,
+}
+/// A Move package.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Package {
+    /// Required. The id the package is stored at on-chain.
+    #[prost(message, optional, tag = "1")]
+    pub storage_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. The id of the first version of the package. Types and functions
+    /// defined in this package are referred to by this id at runtime.
+    #[prost(message, optional, tag = "2")]
+    pub original_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. The version of the package.
+    #[prost(uint64, optional, tag = "3")]
+    pub version: ::core::option::Option<u64>,
+    /// The modules defined in the package, ordered by name.
+    #[prost(message, repeated, tag = "4")]
+    pub modules: ::prost::alloc::vec::Vec<Module>,
+}
+/// A Move module.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Module {
+    /// Required. The name of the module.
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The structs and enums defined in the module, ordered by name.
+    #[prost(message, repeated, tag = "2")]
+    pub datatypes: ::prost::alloc::vec::Vec<DatatypeDescriptor>,
+    /// The functions defined in the module, ordered by name.
+    #[prost(message, repeated, tag = "3")]
+    pub functions: ::prost::alloc::vec::Vec<FunctionDescriptor>,
+}
+/// The normalized signature of a Move struct or enum.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatatypeDescriptor {
+    /// Required. The fully qualified name of the datatype, e.g. `0x2::coin::Coin`,
+    /// using the id of the package that first defined it.
+    #[prost(string, optional, tag = "1")]
+    pub type_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The storage id of the package that first defined the datatype.
+    #[prost(message, optional, tag = "2")]
+    pub defining_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Required. The name of the module the datatype is defined in.
+    #[prost(string, optional, tag = "3")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The name of the datatype.
+    #[prost(string, optional, tag = "4")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The abilities of the datatype.
+    #[prost(enumeration = "Ability", repeated, tag = "5")]
+    pub abilities: ::prost::alloc::vec::Vec<i32>,
+    /// The type parameters of the datatype.
+    #[prost(message, repeated, tag = "6")]
+    pub type_parameters: ::prost::alloc::vec::Vec<TypeParameter>,
+    /// Required. Whether the datatype is a struct or an enum.
+    #[prost(enumeration = "DatatypeKind", optional, tag = "7")]
+    pub kind: ::core::option::Option<i32>,
+    /// The fields of a struct. Empty for enums.
+    #[prost(message, repeated, tag = "8")]
+    pub fields: ::prost::alloc::vec::Vec<FieldDescriptor>,
+    /// The variants of an enum. Empty for structs.
+    #[prost(message, repeated, tag = "9")]
+    pub variants: ::prost::alloc::vec::Vec<VariantDescriptor>,
+}
+/// A type parameter of a Move datatype or function.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TypeParameter {
+    /// The abilities that a type argument must have.
+    #[prost(enumeration = "Ability", repeated, tag = "1")]
+    pub constraints: ::prost::alloc::vec::Vec<i32>,
+    /// Required. Whether the type parameter is declared as `phantom`. Always
+    /// `false` for the type parameters of functions.
+    #[prost(bool, optional, tag = "2")]
+    pub is_phantom: ::core::option::Option<bool>,
+}
+/// A field of a Move struct or enum variant.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldDescriptor {
+    /// Required. The name of the field.
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The position of the field in its struct or variant.
+    #[prost(uint32, optional, tag = "2")]
+    pub position: ::core::option::Option<u32>,
+    /// Required. The type of the field.
+    #[prost(message, optional, tag = "3")]
+    pub r#type: ::core::option::Option<OpenSignatureBody>,
+}
+/// A variant of a Move enum.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VariantDescriptor {
+    /// Required. The name of the variant.
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The position of the variant in its enum.
+    #[prost(uint32, optional, tag = "2")]
+    pub position: ::core::option::Option<u32>,
+    /// The fields of the variant.
+    #[prost(message, repeated, tag = "3")]
+    pub fields: ::prost::alloc::vec::Vec<FieldDescriptor>,
+}
+/// The normalized signature of a Move function.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FunctionDescriptor {
+    /// Required. The name of the function.
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The visibility of the function.
+    #[prost(enumeration = "Visibility", optional, tag = "2")]
+    pub visibility: ::core::option::Option<i32>,
+    /// Required. Whether the function is marked `entry`.
+    #[prost(bool, optional, tag = "3")]
+    pub is_entry: ::core::option::Option<bool>,
+    /// The type parameters of the function.
+    #[prost(message, repeated, tag = "4")]
+    pub type_parameters: ::prost::alloc::vec::Vec<TypeParameter>,
+    /// The types of the function's parameters.
+    #[prost(message, repeated, tag = "5")]
+    pub parameters: ::prost::alloc::vec::Vec<OpenSignature>,
+    /// The types of the function's return values.
+    #[prost(message, repeated, tag = "6")]
+    pub returns: ::prost::alloc::vec::Vec<OpenSignature>,
+}
+/// The type of a function parameter or return value, which may be a reference.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenSignature {
+    /// Whether the type is an immutable or mutable reference. Unset if the type
+    /// is not a reference.
+    #[prost(enumeration = "Reference", optional, tag = "1")]
+    pub reference: ::core::option::Option<i32>,
+    /// Required. The type, or the type being referenced.
+    #[prost(message, optional, tag = "2")]
+    pub body: ::core::option::Option<OpenSignatureBody>,
+}
+/// A Move type that may refer to the type parameters of the datatype or
+/// function it appears in.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenSignatureBody {
+    /// Required. The kind of type.
+    #[prost(enumeration = "TypeKind", optional, tag = "1")]
+    pub kind: ::core::option::Option<i32>,
+    /// The fully qualified name of the datatype, e.g. `0x2::coin::Coin`. Only set
+    /// for `DATATYPE` types. Refers to the datatype's package by its runtime
+    /// (original) id.
+    #[prost(string, optional, tag = "2")]
+    pub type_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The type arguments of a `DATATYPE` type, or the element type of a
+    /// `VECTOR` type.
+    #[prost(message, repeated, tag = "3")]
+    pub type_parameter_instantiation: ::prost::alloc::vec::Vec<OpenSignatureBody>,
+    /// The index of the type parameter. Only set for `TYPE_PARAMETER` types.
+    #[prost(uint32, optional, tag = "4")]
+    pub type_parameter: ::core::option::Option<u32>,
+}
+/// Request message for `NodeService.BatchGetObjects`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchGetObjectsRequest {
+    /// The objects to request. At most `1000` objects can be requested at once.
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<ObjectRequest>,
+    /// Mask specifying which fields to read for each object.
+    /// If no mask is specified, defaults to `object_id,version,digest`.
+    #[prost(message, optional, tag = "2")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+/// An object requested as part of a `NodeService.BatchGetObjects` call.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ObjectRequest {
+    /// Required. The `ObjectId` of the requested object.
+    #[prost(message, optional, tag = "1")]
+    pub object_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Request a specific version of the object.
+    /// If no version is specified, and the object is live, then the latest
+    /// version of the object is returned.
+    #[prost(uint64, optional, tag = "2")]
+    pub version: ::core::option::Option<u64>,
+}
+/// Response message for `NodeService.BatchGetObjects`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchGetObjectsResponse {
+    /// The requested objects, in the same order as the requests.
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<GetObjectResult>,
+}
+/// The result of requesting a single object as part of a batch.
+///
+/// Exactly one of `object` or `error` is set.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetObjectResult {
+    /// The requested object.
+    #[prost(message, optional, tag = "1")]
+    pub object: ::core::option::Option<super::v2::GetObjectResponse>,
+    /// The error encountered while reading the object, e.g. if it was not found.
+    #[prost(message, optional, tag = "2")]
+    pub error: ::core::option::Option<BatchGetError>,
+}
+/// Request message for `NodeService.BatchGetTransactions`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchGetTransactionsRequest {
+    /// The digests of the requested transactions. At most `1000` transactions can
+    /// be requested at once.
+    #[prost(message, repeated, tag = "1")]
+    pub digests: ::prost::alloc::vec::Vec<super::super::types::Digest>,
+    /// Mask specifying which fields to read for each transaction.
+    /// If no mask is specified, defaults to `digest`.
+    #[prost(message, optional, tag = "2")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+/// Response message for `NodeService.BatchGetTransactions`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchGetTransactionsResponse {
+    /// The requested transactions, in the same order as the requested digests.
+    #[prost(message, repeated, tag = "1")]
+    pub transactions: ::prost::alloc::vec::Vec<GetTransactionResult>,
+}
+/// The result of requesting a single transaction as part of a batch.
+///
+/// Exactly one of `transaction` or `error` is set.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTransactionResult {
+    /// The requested transaction.
+    #[prost(message, optional, tag = "1")]
+    pub transaction: ::core::option::Option<super::v2::GetTransactionResponse>,
+    /// The error encountered while reading the transaction, e.g. if it was not
+    /// found.
+    #[prost(message, optional, tag = "2")]
+    pub error: ::core::option::Option<BatchGetError>,
+}
+/// An error encountered while reading a single item of a batch request.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchGetError {
+    /// Required. The `google.rpc.Code` of the error, e.g. `5` (`NOT_FOUND`).
+    #[prost(int32, optional, tag = "1")]
+    pub code: ::core::option::Option<i32>,
+    /// A developer-facing description of the error.
+    #[prost(string, optional, tag = "2")]
+    pub message: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// An ability of a Move type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Ability {
+    Unknown = 0,
+    Copy = 1,
+    Drop = 2,
+    Store = 3,
+    Key = 4,
+}
+impl Ability {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "ABILITY_UNKNOWN",
+            Self::Copy => "COPY",
+            Self::Drop => "DROP",
+            Self::Store => "STORE",
+            Self::Key => "KEY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ABILITY_UNKNOWN" => Some(Self::Unknown),
+            "COPY" => Some(Self::Copy),
+            "DROP" => Some(Self::Drop),
+            "STORE" => Some(Self::Store),
+            "KEY" => Some(Self::Key),
+            _ => None,
+        }
+    }
+}
+/// The kind of a Move datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DatatypeKind {
+    Unknown = 0,
+    Struct = 1,
+    Enum = 2,
+}
+impl DatatypeKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "DATATYPE_KIND_UNKNOWN",
+            Self::Struct => "STRUCT",
+            Self::Enum => "ENUM",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DATATYPE_KIND_UNKNOWN" => Some(Self::Unknown),
+            "STRUCT" => Some(Self::Struct),
+            "ENUM" => Some(Self::Enum),
+            _ => None,
+        }
+    }
+}
+/// The visibility of a Move function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Visibility {
+    Unknown = 0,
+    Private = 1,
+    Public = 2,
+    Friend = 3,
+}
+impl Visibility {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "VISIBILITY_UNKNOWN",
+            Self::Private => "PRIVATE",
+            Self::Public => "PUBLIC",
+            Self::Friend => "FRIEND",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "VISIBILITY_UNKNOWN" => Some(Self::Unknown),
+            "PRIVATE" => Some(Self::Private),
+            "PUBLIC" => Some(Self::Public),
+            "FRIEND" => Some(Self::Friend),
+            _ => None,
+        }
+    }
+}
+/// The kind of a Move reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Reference {
+    Unknown = 0,
+    Immutable = 1,
+    Mutable = 2,
+}
+impl Reference {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "REFERENCE_UNKNOWN",
+            Self::Immutable => "IMMUTABLE",
+            Self::Mutable => "MUTABLE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REFERENCE_UNKNOWN" => Some(Self::Unknown),
+            "IMMUTABLE" => Some(Self::Immutable),
+            "MUTABLE" => Some(Self::Mutable),
+            _ => None,
+        }
+    }
+}
+/// The kind of a Move type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TypeKind {
+    Unknown = 0,
+    Address = 1,
+    Bool = 2,
+    U8 = 3,
+    U16 = 4,
+    U32 = 5,
+    U64 = 6,
+    U128 = 7,
+    U256 = 8,
+    Vector = 9,
+    Datatype = 10,
+    TypeParameter = 11,
+}
+impl TypeKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "TYPE_KIND_UNKNOWN",
+            Self::Address => "ADDRESS",
+            Self::Bool => "BOOL",
+            Self::U8 => "U8",
+            Self::U16 => "U16",
+            Self::U32 => "U32",
+            Self::U64 => "U64",
+            Self::U128 => "U128",
+            Self::U256 => "U256",
+            Self::Vector => "VECTOR",
+            Self::Datatype => "DATATYPE",
+            Self::TypeParameter => "TYPE_PARAMETER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TYPE_KIND_UNKNOWN" => Some(Self::Unknown),
+            "ADDRESS" => Some(Self::Address),
+            "BOOL" => Some(Self::Bool),
+            "U8" => Some(Self::U8),
+            "U16" => Some(Self::U16),
+            "U32" => Some(Self::U32),
+            "U64" => Some(Self::U64),
+            "U128" => Some(Self::U128),
+            "U256" => Some(Self::U256),
+            "VECTOR" => Some(Self::Vector),
+            "DATATYPE" => Some(Self::Datatype),
+            "TYPE_PARAMETER" => Some(Self::TypeParameter),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod node_service_client {
     #![allow(
@@ -498,20 +1206,289 @@ pub mod node_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod node_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with NodeServiceServer.
-    #[async_trait]
+        /// Stream the transactions from a range of checkpoints that match the provided filter.
+        ///
+        /// Transactions are returned in the order they were executed in.
+        pub async fn list_transactions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ListTransactionsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/ListTransactions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2alpha.NodeService", "ListTransactions"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Stream the events from a range of checkpoints that match the provided filter.
+        ///
+        /// Events are returned in the order they were emitted in.
+        pub async fn list_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ListEventsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/ListEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "ListEvents"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Request the balance of a single coin type held by an account.
+        pub async fn get_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/GetBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "GetBalance"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// List the balances of all of the coin types held by an account.
+        pub async fn list_balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/ListBalances",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "ListBalances"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// List the coins owned by an account.
+        pub async fn list_owned_coins(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListOwnedCoinsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOwnedCoinsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/ListOwnedCoins",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2alpha.NodeService", "ListOwnedCoins"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Request the normalized signatures of all of the datatypes and functions
+        /// defined in a Move package.
+        pub async fn get_package(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPackageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPackageResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/GetPackage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "GetPackage"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Request the normalized signature of a single Move struct or enum.
+        pub async fn get_datatype(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetDatatypeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDatatypeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/GetDatatype",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "GetDatatype"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Request the normalized signature of a single Move function.
+        pub async fn get_function(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetFunctionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetFunctionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/GetFunction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "GetFunction"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Request information for a batch of objects.
+        ///
+        /// Objects that can't be found are reported individually in the response
+        /// rather than failing the whole request.
+        pub async fn batch_get_objects(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BatchGetObjectsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BatchGetObjectsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/BatchGetObjects",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2alpha.NodeService", "BatchGetObjects"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Request information for a batch of transactions.
+        ///
+        /// Transactions that can't be found are reported individually in the
+        /// response rather than failing the whole request.
+        pub async fn batch_get_transactions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BatchGetTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BatchGetTransactionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/BatchGetTransactions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2alpha.NodeService", "BatchGetTransactions"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod node_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with NodeServiceServer.
+    #[async_trait]
     pub trait NodeService: std::marker::Send + std::marker::Sync + 'static {
         /// Request information for the specified coin type.
         async fn get_coin_info(
@@ -564,6 +1541,109 @@ pub mod node_service_server {
             tonic::Response<super::ResolveTransactionResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the ListTransactions method.
+        type ListTransactionsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ListTransactionsResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Stream the transactions from a range of checkpoints that match the provided filter.
+        ///
+        /// Transactions are returned in the order they were executed in.
+        async fn list_transactions(
+            &self,
+            request: tonic::Request<super::ListTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ListTransactionsStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the ListEvents method.
+        type ListEventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ListEventsResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Stream the events from a range of checkpoints that match the provided filter.
+        ///
+        /// Events are returned in the order they were emitted in.
+        async fn list_events(
+            &self,
+            request: tonic::Request<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ListEventsStream>,
+            tonic::Status,
+        >;
+        /// Request the balance of a single coin type held by an account.
+        async fn get_balance(
+            &self,
+            request: tonic::Request<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        >;
+        /// List the balances of all of the coin types held by an account.
+        async fn list_balances(
+            &self,
+            request: tonic::Request<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        >;
+        /// List the coins owned by an account.
+        async fn list_owned_coins(
+            &self,
+            request: tonic::Request<super::ListOwnedCoinsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOwnedCoinsResponse>,
+            tonic::Status,
+        >;
+        /// Request the normalized signatures of all of the datatypes and functions
+        /// defined in a Move package.
+        async fn get_package(
+            &self,
+            request: tonic::Request<super::GetPackageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPackageResponse>,
+            tonic::Status,
+        >;
+        /// Request the normalized signature of a single Move struct or enum.
+        async fn get_datatype(
+            &self,
+            request: tonic::Request<super::GetDatatypeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDatatypeResponse>,
+            tonic::Status,
+        >;
+        /// Request the normalized signature of a single Move function.
+        async fn get_function(
+            &self,
+            request: tonic::Request<super::GetFunctionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetFunctionResponse>,
+            tonic::Status,
+        >;
+        /// Request information for a batch of objects.
+        ///
+        /// Objects that can't be found are reported individually in the response
+        /// rather than failing the whole request.
+        async fn batch_get_objects(
+            &self,
+            request: tonic::Request<super::BatchGetObjectsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BatchGetObjectsResponse>,
+            tonic::Status,
+        >;
+        /// Request information for a batch of transactions.
+        ///
+        /// Transactions that can't be found are reported individually in the
+        /// response rather than failing the whole request.
+        async fn batch_get_transactions(
+            &self,
+            request: tonic::Request<super::BatchGetTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BatchGetTransactionsResponse>,
+            tonic::Status,
+        >;
     }
     /// Service for reading data from a Sui Full node.
     #[derive(Debug)]
@@ -644,23 +1724,298 @@ pub mod node_service_server {
             match req.uri().path() {
                 "/sui.node.v2alpha.NodeService/GetCoinInfo" => {
                     #[allow(non_camel_case_types)]
-                    struct GetCoinInfoSvc<T: NodeService>(pub Arc<T>);
+                    struct GetCoinInfoSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetCoinInfoRequest>
+                    for GetCoinInfoSvc<T> {
+                        type Response = super::GetCoinInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCoinInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_coin_info(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetCoinInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/ListDynamicFields" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListDynamicFieldsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListDynamicFieldsRequest>
+                    for ListDynamicFieldsSvc<T> {
+                        type Response = super::ListDynamicFieldsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListDynamicFieldsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_dynamic_fields(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListDynamicFieldsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/ListAccountObjects" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListAccountObjectsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListAccountObjectsRequest>
+                    for ListAccountObjectsSvc<T> {
+                        type Response = super::ListAccountObjectsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListAccountObjectsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_account_objects(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListAccountObjectsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/GetProtocolConfig" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetProtocolConfigSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetProtocolConfigRequest>
+                    for GetProtocolConfigSvc<T> {
+                        type Response = super::GetProtocolConfigResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetProtocolConfigRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_protocol_config(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetProtocolConfigSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/GetGasInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGasInfoSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetGasInfoRequest>
+                    for GetGasInfoSvc<T> {
+                        type Response = super::GetGasInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGasInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_gas_info(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetGasInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/SimulateTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct SimulateTransactionSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::SimulateTransactionRequest>
+                    for SimulateTransactionSvc<T> {
+                        type Response = super::SimulateTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SimulateTransactionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::simulate_transaction(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SimulateTransactionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/ResolveTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResolveTransactionSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::GetCoinInfoRequest>
-                    for GetCoinInfoSvc<T> {
-                        type Response = super::GetCoinInfoResponse;
+                    > tonic::server::UnaryService<super::ResolveTransactionRequest>
+                    for ResolveTransactionSvc<T> {
+                        type Response = super::ResolveTransactionResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetCoinInfoRequest>,
+                            request: tonic::Request<super::ResolveTransactionRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::get_coin_info(&inner, request).await
+                                <T as NodeService>::resolve_transaction(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -671,7 +2026,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetCoinInfoSvc(inner);
+                        let method = ResolveTransactionSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -687,26 +2042,117 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/ListDynamicFields" => {
+                "/sui.node.v2alpha.NodeService/ListTransactions" => {
                     #[allow(non_camel_case_types)]
-                    struct ListDynamicFieldsSvc<T: NodeService>(pub Arc<T>);
+                    struct ListTransactionsSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::ListDynamicFieldsRequest>
-                    for ListDynamicFieldsSvc<T> {
-                        type Response = super::ListDynamicFieldsResponse;
+                    > tonic::server::ServerStreamingService<super::ListTransactionsRequest>
+                    for ListTransactionsSvc<T> {
+                        type Response = super::ListTransactionsResponse;
+                        type ResponseStream = T::ListTransactionsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTransactionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_transactions(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTransactionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/ListEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEventsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::ServerStreamingService<super::ListEventsRequest>
+                    for ListEventsSvc<T> {
+                        type Response = super::ListEventsResponse;
+                        type ResponseStream = T::ListEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/GetBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBalanceSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetBalanceRequest>
+                    for GetBalanceSvc<T> {
+                        type Response = super::GetBalanceResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListDynamicFieldsRequest>,
+                            request: tonic::Request<super::GetBalanceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::list_dynamic_fields(&inner, request)
-                                    .await
+                                <T as NodeService>::get_balance(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -717,7 +2163,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ListDynamicFieldsSvc(inner);
+                        let method = GetBalanceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -733,26 +2179,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/ListAccountObjects" => {
+                "/sui.node.v2alpha.NodeService/ListBalances" => {
                     #[allow(non_camel_case_types)]
-                    struct ListAccountObjectsSvc<T: NodeService>(pub Arc<T>);
+                    struct ListBalancesSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::ListAccountObjectsRequest>
-                    for ListAccountObjectsSvc<T> {
-                        type Response = super::ListAccountObjectsResponse;
+                    > tonic::server::UnaryService<super::ListBalancesRequest>
+                    for ListBalancesSvc<T> {
+                        type Response = super::ListBalancesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListAccountObjectsRequest>,
+                            request: tonic::Request<super::ListBalancesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::list_account_objects(&inner, request)
-                                    .await
+                                <T as NodeService>::list_balances(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -763,7 +2208,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ListAccountObjectsSvc(inner);
+                        let method = ListBalancesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -779,26 +2224,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/GetProtocolConfig" => {
+                "/sui.node.v2alpha.NodeService/ListOwnedCoins" => {
                     #[allow(non_camel_case_types)]
-                    struct GetProtocolConfigSvc<T: NodeService>(pub Arc<T>);
+                    struct ListOwnedCoinsSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::GetProtocolConfigRequest>
-                    for GetProtocolConfigSvc<T> {
-                        type Response = super::GetProtocolConfigResponse;
+                    > tonic::server::UnaryService<super::ListOwnedCoinsRequest>
+                    for ListOwnedCoinsSvc<T> {
+                        type Response = super::ListOwnedCoinsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetProtocolConfigRequest>,
+                            request: tonic::Request<super::ListOwnedCoinsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::get_protocol_config(&inner, request)
-                                    .await
+                                <T as NodeService>::list_owned_coins(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -809,7 +2253,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetProtocolConfigSvc(inner);
+                        let method = ListOwnedCoinsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -825,25 +2269,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/GetGasInfo" => {
+                "/sui.node.v2alpha.NodeService/GetPackage" => {
                     #[allow(non_camel_case_types)]
-                    struct GetGasInfoSvc<T: NodeService>(pub Arc<T>);
+                    struct GetPackageSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::GetGasInfoRequest>
-                    for GetGasInfoSvc<T> {
-                        type Response = super::GetGasInfoResponse;
+                    > tonic::server::UnaryService<super::GetPackageRequest>
+                    for GetPackageSvc<T> {
+                        type Response = super::GetPackageResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetGasInfoRequest>,
+                            request: tonic::Request<super::GetPackageRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::get_gas_info(&inner, request).await
+                                <T as NodeService>::get_package(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -854,7 +2298,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetGasInfoSvc(inner);
+                        let method = GetPackageSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -870,26 +2314,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/SimulateTransaction" => {
+                "/sui.node.v2alpha.NodeService/GetDatatype" => {
                     #[allow(non_camel_case_types)]
-                    struct SimulateTransactionSvc<T: NodeService>(pub Arc<T>);
+                    struct GetDatatypeSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::SimulateTransactionRequest>
-                    for SimulateTransactionSvc<T> {
-                        type Response = super::SimulateTransactionResponse;
+                    > tonic::server::UnaryService<super::GetDatatypeRequest>
+                    for GetDatatypeSvc<T> {
+                        type Response = super::GetDatatypeResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SimulateTransactionRequest>,
+                            request: tonic::Request<super::GetDatatypeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::simulate_transaction(&inner, request)
-                                    .await
+                                <T as NodeService>::get_datatype(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -900,7 +2343,7 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SimulateTransactionSvc(inner);
+                        let method = GetDatatypeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -916,26 +2359,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2alpha.NodeService/ResolveTransaction" => {
+                "/sui.node.v2alpha.NodeService/GetFunction" => {
                     #[allow(non_camel_case_types)]
-                    struct ResolveTransactionSvc<T: NodeService>(pub Arc<T>);
+                    struct GetFunctionSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::ResolveTransactionRequest>
-                    for ResolveTransactionSvc<T> {
-                        type Response = super::ResolveTransactionResponse;
+                    > tonic::server::UnaryService<super::GetFunctionRequest>
+                    for GetFunctionSvc<T> {
+                        type Response = super::GetFunctionResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ResolveTransactionRequest>,
+                            request: tonic::Request<super::GetFunctionRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::resolve_transaction(&inner, request)
-                                    .await
+                                <T as NodeService>::get_function(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -946,7 +2388,97 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ResolveTransactionSvc(inner);
+                        let method = GetFunctionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/BatchGetObjects" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchGetObjectsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::BatchGetObjectsRequest>
+                    for BatchGetObjectsSvc<T> {
+                        type Response = super::BatchGetObjectsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchGetObjectsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::batch_get_objects(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BatchGetObjectsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.NodeService/BatchGetTransactions" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchGetTransactionsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::BatchGetTransactionsRequest>
+                    for BatchGetTransactionsSvc<T> {
+                        type Response = super::BatchGetTransactionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchGetTransactionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::batch_get_transactions(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BatchGetTransactionsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1007,6 +2539,13 @@ pub struct SubscribeCheckpointsRequest {
     /// SubscribeCheckpointsResponse should be returned.
     #[prost(message, optional, tag = "3")]
     pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+    /// Optional. Only the transactions and events that match the filter will be
+    /// included in each checkpoint, after which the `read_mask` is applied.
+    ///
+    /// Checkpoints without any matching transactions are still returned so that
+    /// the stream of cursors remains without gaps.
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<CheckpointFilter>,
 }
 /// Response message for SubscriptionService.SubscribeCheckpoints
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1019,6 +2558,33 @@ pub struct SubscribeCheckpointsResponse {
     #[prost(message, optional, tag = "2")]
     pub checkpoint: ::core::option::Option<super::v2::GetFullCheckpointResponse>,
 }
+/// Criteria for selecting the transactions and events of a checkpoint.
+///
+/// A transaction is selected if it matches any of the criteria that are set.
+/// If no criteria are set, every transaction is selected.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointFilter {
+    /// Select transactions that were sent by, or that created, mutated or
+    /// transferred an object to, any of these addresses.
+    #[prost(message, repeated, tag = "1")]
+    pub affected_addresses: ::prost::alloc::vec::Vec<super::super::types::Address>,
+    /// Select transactions that call any matching Move function.
+    #[prost(message, repeated, tag = "2")]
+    pub move_functions: ::prost::alloc::vec::Vec<MoveFunctionFilter>,
+    /// Select transactions that emitted an event of any of these types. Types
+    /// are given in the same format as `EventFilter.event_type`.
+    ///
+    /// If a transaction is selected only because of the events it emitted,
+    /// only its matching events are returned, in both its `events` and
+    /// `events_bcs`. Transactions selected by any of the other criteria are
+    /// returned with all of their events.
+    #[prost(string, repeated, tag = "3")]
+    pub event_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Select transactions that used or produced an object of any of these
+    /// types. Types are given in the same format as `EventFilter.event_type`.
+    #[prost(string, repeated, tag = "4")]
+    pub object_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 /// Generated client implementations.
 pub mod subscription_service_client {
     #![allow(
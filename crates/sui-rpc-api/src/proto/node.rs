@@ -367,3 +367,38 @@ impl ExecuteTransactionResponse {
         Ok(())
     }
 }
+
+//
+// ListTransactionsRequest
+//
+
+impl v2alpha::ListTransactionsRequest {
+    pub const READ_MASK_DEFAULT: &str = "digest";
+}
+
+//
+// ListEventsRequest
+//
+
+impl v2alpha::ListEventsRequest {
+    pub const READ_MASK_DEFAULT: &str = "transaction_digest,event";
+}
+
+//
+// ListEventsResponse
+//
+
+impl v2alpha::ListEventsResponse {
+    pub fn validate_read_mask(read_mask: &FieldMask) -> Result<(), &str> {
+        for path in &read_mask.paths {
+            match path.as_str() {
+                "transaction_digest" | "event" | "event_bcs" => {}
+                path => {
+                    return Err(path);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proto::node::v2alpha::Balance;
+use crate::proto::node::v2alpha::GetBalanceRequest;
+use crate::proto::node::v2alpha::GetBalanceResponse;
+use crate::proto::node::v2alpha::ListBalancesRequest;
+use crate::proto::node::v2alpha::ListBalancesResponse;
+use crate::proto::node::v2alpha::ListOwnedCoinsRequest;
+use crate::proto::node::v2alpha::ListOwnedCoinsResponse;
+use crate::proto::node::v2alpha::OwnedCoin;
+use crate::Result;
+use crate::RpcError;
+use crate::RpcService;
+use move_core_types::language_storage::TypeTag;
+use sui_sdk_types::Address;
+use sui_sdk_types::ObjectId;
+use sui_types::storage::AccountOwnedObjectInfo;
+use sui_types::storage::BalanceInfo;
+use sui_types::sui_sdk_types_conversions::type_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::type_tag_sdk_to_core;
+use tap::Pipe;
+
+impl RpcService {
+    pub fn get_balance(&self, request: GetBalanceRequest) -> Result<GetBalanceResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcError::not_found)?;
+
+        let owner = parse_owner(request.owner.as_ref())?;
+        let coin_type = request
+            .coin_type
+            .as_ref()
+            .ok_or_else(|| RpcError::new(tonic::Code::InvalidArgument, "missing coin_type"))?
+            .pipe(parse_coin_type)?;
+
+        let balance = indexes
+            .get_balance(&owner.into(), &coin_type)?
+            .unwrap_or_default();
+
+        Ok(GetBalanceResponse {
+            balance: Some(balance_to_proto(coin_type, balance)?),
+        })
+    }
+
+    pub fn list_balances(&self, request: ListBalancesRequest) -> Result<ListBalancesResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcError::not_found)?;
+
+        let owner = parse_owner(request.owner.as_ref())?;
+        let page_size = request
+            .page_size
+            .map(|s| (s as usize).clamp(1, 1000))
+            .unwrap_or(50);
+        let page_token = request
+            .page_token
+            .map(|token| decode_balances_page_token(&token))
+            .transpose()?;
+
+        // Balances that have dropped to zero are only removed from the index during compaction.
+        let mut balances: Vec<_> = indexes
+            .balance_iter(&owner.into(), page_token)?
+            .filter(|(_, balance)| balance.coin_count > 0)
+            .take(page_size + 1)
+            .collect();
+
+        let next_page_token = if balances.len() > page_size {
+            // SAFETY: We've already verified that balances is greater than limit, which is
+            // gaurenteed to be >= 1.
+            let (coin_type, _) = balances.pop().unwrap();
+            Some(encode_balances_page_token(&coin_type)?)
+        } else {
+            None
+        };
+
+        Ok(ListBalancesResponse {
+            balances: balances
+                .into_iter()
+                .map(|(coin_type, balance)| balance_to_proto(coin_type, balance))
+                .collect::<Result<_>>()?,
+            next_page_token,
+        })
+    }
+
+    pub fn list_owned_coins(
+        &self,
+        request: ListOwnedCoinsRequest,
+    ) -> Result<ListOwnedCoinsResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcError::not_found)?;
+
+        let owner = parse_owner(request.owner.as_ref())?;
+        let coin_type = request
+            .coin_type
+            .as_ref()
+            .map(parse_coin_type)
+            .transpose()?;
+        let page_size = request
+            .page_size
+            .map(|s| (s as usize).clamp(1, 1000))
+            .unwrap_or(50);
+        let page_token = request
+            .page_token
+            .map(|token| decode_coins_page_token(&token))
+            .transpose()?;
+
+        // Coins of a specific type are listed from an index keyed on their owner and type, so that
+        // listing them does not require iterating over all the owner's other objects.
+        let cursor = page_token.map(Into::into);
+        let coins: Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_> = match &coin_type {
+            Some(coin_type) => indexes.owned_coins_iter(owner.into(), coin_type, cursor)?,
+            None => Box::new(
+                indexes
+                    .account_owned_objects_info_iter(owner.into(), cursor)?
+                    .filter(|info| info.type_.is_coin()),
+            ),
+        };
+
+        let mut coins = coins.take(page_size + 1).collect::<Vec<_>>();
+
+        let next_page_token = if coins.len() > page_size {
+            // SAFETY: We've already verified that coins is greater than limit, which is
+            // gaurenteed to be >= 1.
+            coins
+                .pop()
+                .unwrap()
+                .object_id
+                .pipe(ObjectId::from)
+                .pipe(encode_coins_page_token)
+                .pipe(Some)
+        } else {
+            None
+        };
+
+        Ok(ListOwnedCoinsResponse {
+            coins: coins
+                .into_iter()
+                .map(owned_coin_to_proto)
+                .collect::<Result<_>>()?,
+            next_page_token,
+        })
+    }
+}
+
+fn balance_to_proto(coin_type: TypeTag, balance: BalanceInfo) -> Result<Balance> {
+    Ok(Balance {
+        coin_type: Some(type_tag_core_to_sdk(coin_type)?.into()),
+        balance: Some(balance.balance),
+        coin_count: Some(balance.coin_count),
+    })
+}
+
+fn owned_coin_to_proto(info: AccountOwnedObjectInfo) -> Result<OwnedCoin> {
+    let coin_type = info
+        .type_
+        .coin_type_maybe()
+        .ok_or_else(|| RpcError::new(tonic::Code::Internal, "object is not a coin"))?;
+
+    Ok(OwnedCoin {
+        object_id: Some(ObjectId::from(info.object_id).into()),
+        version: Some(info.version.into()),
+        coin_type: Some(type_tag_core_to_sdk(coin_type)?.into()),
+        balance: info.balance,
+    })
+}
+
+fn parse_owner(owner: Option<&crate::proto::types::Address>) -> Result<Address> {
+    owner
+        .ok_or_else(|| RpcError::new(tonic::Code::InvalidArgument, "missing owner"))?
+        .try_into()
+        .map_err(|e| RpcError::new(tonic::Code::InvalidArgument, format!("invalid owner: {e}")))
+}
+
+fn parse_coin_type(coin_type: &crate::proto::types::TypeTag) -> Result<TypeTag> {
+    sui_sdk_types::TypeTag::try_from(coin_type)
+        .map_err(|e| e.to_string())
+        .and_then(|coin_type| type_tag_sdk_to_core(coin_type).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            RpcError::new(
+                tonic::Code::InvalidArgument,
+                format!("invalid coin_type: {e}"),
+            )
+        })
+}
+
+fn decode_balances_page_token(page_token: &str) -> Result<TypeTag> {
+    use base64::prelude::BASE64_STANDARD;
+    use base64::Engine;
+
+    BASE64_STANDARD
+        .decode(page_token)
+        .ok()
+        .and_then(|bytes| bcs::from_bytes(&bytes).ok())
+        .ok_or_else(|| RpcError::new(tonic::Code::InvalidArgument, "invalid page_token"))
+}
+
+fn encode_balances_page_token(page_token: &TypeTag) -> Result<String> {
+    use base64::prelude::BASE64_STANDARD;
+    use base64::Engine;
+
+    Ok(BASE64_STANDARD.encode(bcs::to_bytes(page_token)?))
+}
+
+fn decode_coins_page_token(page_token: &str) -> Result<ObjectId> {
+    use base64::prelude::BASE64_STANDARD;
+    use base64::Engine;
+
+    BASE64_STANDARD
+        .decode(page_token)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(ObjectId::new)
+        .ok_or_else(|| RpcError::new(tonic::Code::InvalidArgument, "invalid page_token"))
+}
+
+fn encode_coins_page_token(page_token: ObjectId) -> String {
+    use base64::prelude::BASE64_STANDARD;
+    use base64::Engine;
+
+    BASE64_STANDARD.encode(page_token.as_bytes())
+}
@@ -17,12 +17,38 @@ use crate::Result;
 use crate::RpcError;
 use crate::RpcService;
 use prost_types::FieldMask;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
 use sui_sdk_types::CheckpointContents;
 use sui_sdk_types::CheckpointDigest;
 use sui_sdk_types::CheckpointSequenceNumber;
 use sui_sdk_types::SignedCheckpointSummary;
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::storage::ObjectKey;
+use sui_types::storage::TransactionIndexKey;
+use sui_types::storage::TransactionIndexKind;
 use tap::Pipe;
 
+/// The maximum number of checkpoints that a single streaming `List*` request can cover, when its
+/// filter can be served from the RPC indexes.
+const MAX_CHECKPOINT_RANGE: u64 = 1000;
+
+/// The maximum number of checkpoints that a single streaming `List*` request can cover, when every
+/// transaction in those checkpoints needs to be loaded and checked against its filter.
+const MAX_SCANNED_CHECKPOINT_RANGE: u64 = 100;
+
+/// The maximum number of transactions loaded by each read from the RPC indexes.
+const INDEX_BATCH_SIZE: usize = 50;
+
+pub(crate) type CheckpointTransactionStream = Pin<
+    Box<
+        dyn tokio_stream::Stream<Item = Result<(CheckpointSequenceNumber, CheckpointTransaction)>>
+            + Send,
+    >,
+>;
+
 impl RpcService {
     pub fn get_checkpoint(&self, request: GetCheckpointRequest) -> Result<GetCheckpointResponse> {
         let checkpoint_id =
@@ -171,30 +197,320 @@ impl RpcService {
             .inner()
             .get_checkpoint_data(verified_summary, checkpoint_contents)?;
 
-        checkpoint_data_to_full_checkpoint_response(checkpoint, &read_mask)
+        checkpoint_data_to_full_checkpoint_response(&checkpoint, &read_mask)
+    }
+
+    /// Stream the transactions in checkpoints `start_checkpoint` to `end_checkpoint` (inclusive,
+    /// defaulting to the latest checkpoint), along with the checkpoint each one is in.
+    ///
+    /// If `index` is set and the RPC indexes are available, only the transactions it selects are
+    /// loaded. Otherwise every transaction in the range is loaded, so the range must be shorter.
+    /// Input and output objects are only loaded if `with_objects` is set. Reads are performed on
+    /// the blocking thread pool.
+    pub(crate) fn checkpoint_transactions(
+        &self,
+        start_checkpoint: Option<u64>,
+        end_checkpoint: Option<u64>,
+        index: Option<TransactionIndexKind>,
+        with_objects: bool,
+    ) -> Result<CheckpointTransactionStream> {
+        let index = index.filter(|_| self.reader.inner().indexes().is_some());
+        let checkpoints = self.checkpoint_range(
+            start_checkpoint,
+            end_checkpoint,
+            index.is_some(),
+            with_objects,
+        )?;
+
+        let service = self.clone();
+        let stream = async_stream::stream! {
+            let Some(kind) = index else {
+                for sequence_number in checkpoints {
+                    let service = service.clone();
+                    let checkpoint =
+                        match spawn_blocking(move || service.checkpoint_data(sequence_number)).await
+                        {
+                            Ok(checkpoint) => checkpoint,
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        };
+
+                    for transaction in checkpoint.transactions {
+                        yield Ok((sequence_number, transaction));
+                    }
+                }
+
+                return;
+            };
+
+            let to_checkpoint = *checkpoints.end();
+            let mut cursor = Some(TransactionIndexKey {
+                kind,
+                checkpoint: *checkpoints.start(),
+                transaction_index: 0,
+            });
+
+            while let Some(from) = cursor {
+                let service = service.clone();
+                let batch = spawn_blocking(move || {
+                    service.indexed_transactions(from, to_checkpoint, with_objects)
+                });
+
+                let (transactions, next) = match batch.await {
+                    Ok(batch) => batch,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                for transaction in transactions {
+                    yield Ok(transaction);
+                }
+
+                cursor = next;
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Resolve the range of checkpoints to read for a streaming `List*` request.
+    ///
+    /// `end_checkpoint` defaults to, and is capped at, the latest executed checkpoint, so the
+    /// returned range may be empty if `start_checkpoint` has not been executed yet.
+    fn checkpoint_range(
+        &self,
+        start_checkpoint: Option<u64>,
+        end_checkpoint: Option<u64>,
+        indexed: bool,
+        with_objects: bool,
+    ) -> Result<RangeInclusive<CheckpointSequenceNumber>> {
+        let start = start_checkpoint.ok_or_else(|| {
+            FieldViolation::new("start_checkpoint")
+                .with_description("missing start_checkpoint")
+                .with_reason(ErrorReason::FieldMissing)
+        })?;
+
+        let latest = self.reader.inner().get_latest_checkpoint()?.sequence_number;
+        let end = end_checkpoint.unwrap_or(latest);
+
+        if start > end {
+            return Err(FieldViolation::new("end_checkpoint")
+                .with_description("end_checkpoint must not be less than start_checkpoint")
+                .with_reason(ErrorReason::FieldInvalid)
+                .into());
+        }
+
+        let max_range = if indexed {
+            MAX_CHECKPOINT_RANGE
+        } else {
+            MAX_SCANNED_CHECKPOINT_RANGE
+        };
+
+        if end - start >= max_range {
+            return Err(FieldViolation::new("end_checkpoint")
+                .with_description(format!(
+                    "at most {max_range} checkpoints can be listed by a single request with this \
+                     filter"
+                ))
+                .with_reason(ErrorReason::FieldInvalid)
+                .into());
+        }
+
+        // Scanning a checkpoint loads its full contents, including objects, so all of it needs to
+        // still be available. Transactions read from the indexes only need their objects if they
+        // were asked for.
+        let lowest_available = if indexed && !with_objects {
+            self.reader.inner().get_lowest_available_checkpoint()?
+        } else {
+            self.reader
+                .inner()
+                .get_lowest_available_checkpoint_objects()?
+        };
+
+        if start < lowest_available {
+            return Err(RpcError::new(
+                tonic::Code::NotFound,
+                format!("data from requested checkpoint {start} has been pruned"),
+            ));
+        }
+
+        Ok(start..=end.min(latest))
+    }
+
+    /// Load up to `INDEX_BATCH_SIZE` of the transactions selected by `from.kind`, starting at
+    /// `from` and ending at `to_checkpoint`. Also returns the key to continue from, if there are
+    /// more transactions to load.
+    fn indexed_transactions(
+        &self,
+        from: TransactionIndexKey,
+        to_checkpoint: CheckpointSequenceNumber,
+        with_objects: bool,
+    ) -> Result<(
+        Vec<(CheckpointSequenceNumber, CheckpointTransaction)>,
+        Option<TransactionIndexKey>,
+    )> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcError::not_found)?;
+
+        let mut entries: Vec<_> = indexes
+            .transactions_iter(from, to_checkpoint)?
+            .take(INDEX_BATCH_SIZE + 1)
+            .collect();
+
+        let next = if entries.len() > INDEX_BATCH_SIZE {
+            entries.pop().map(|(key, _)| key)
+        } else {
+            None
+        };
+
+        let digests: Vec<_> = entries.iter().map(|(_, digest)| *digest).collect();
+        let transactions = self.transactions(&digests, with_objects)?;
+
+        let checkpoints = entries.into_iter().map(|(key, _)| key.checkpoint);
+        Ok((checkpoints.zip(transactions).collect(), next))
+    }
+
+    /// Load the transactions with the given digests, along with their effects and events, and
+    /// their input and output objects if `with_objects` is set.
+    fn transactions(
+        &self,
+        digests: &[TransactionDigest],
+        with_objects: bool,
+    ) -> Result<Vec<CheckpointTransaction>> {
+        let store = self.reader.inner();
+        let transactions = store.multi_get_transactions(digests);
+        let effects = store.multi_get_transaction_effects(digests);
+
+        let load_objects = |keys: Vec<ObjectKey>, digest: &TransactionDigest| {
+            store
+                .multi_get_objects_by_key(&keys)
+                .into_iter()
+                .zip(&keys)
+                .map(|(object, key)| {
+                    object.ok_or_else(|| {
+                        anyhow::anyhow!("missing object {key:?} from transaction {digest}")
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        digests
+            .iter()
+            .zip(transactions)
+            .zip(effects)
+            .map(|((digest, transaction), effects)| -> Result<_> {
+                let transaction =
+                    transaction.ok_or_else(|| anyhow::anyhow!("missing transaction {digest}"))?;
+                let effects = effects
+                    .ok_or_else(|| anyhow::anyhow!("missing effects for transaction {digest}"))?;
+
+                let events = effects
+                    .events_digest()
+                    .map(|events_digest| {
+                        store.get_events(events_digest).ok_or_else(|| {
+                            anyhow::anyhow!("missing events for transaction {digest}")
+                        })
+                    })
+                    .transpose()?;
+
+                let (input_objects, output_objects) = if with_objects {
+                    let input_keys = effects
+                        .modified_at_versions()
+                        .into_iter()
+                        .map(|(object_id, version)| ObjectKey(object_id, version))
+                        .collect();
+                    let output_keys = effects
+                        .all_changed_objects()
+                        .into_iter()
+                        .map(|(object_ref, _, _)| ObjectKey::from(object_ref))
+                        .collect();
+                    (
+                        load_objects(input_keys, digest)?,
+                        load_objects(output_keys, digest)?,
+                    )
+                } else {
+                    (vec![], vec![])
+                };
+
+                Ok(CheckpointTransaction {
+                    transaction: (*transaction).clone().into(),
+                    effects,
+                    events,
+                    input_objects,
+                    output_objects,
+                })
+            })
+            .collect()
+    }
+
+    /// Load the full contents of the checkpoint with the given sequence number.
+    pub(crate) fn checkpoint_data(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<sui_types::full_checkpoint_content::CheckpointData> {
+        let checkpoint_id = CheckpointId::SequenceNumber(sequence_number);
+
+        let verified_summary = self
+            .reader
+            .inner()
+            .get_checkpoint_by_sequence_number(sequence_number)
+            .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+
+        let checkpoint_contents = self
+            .reader
+            .inner()
+            .get_checkpoint_contents_by_digest(&verified_summary.content_digest)
+            .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+
+        self.reader
+            .inner()
+            .get_checkpoint_data(verified_summary, checkpoint_contents)
+            .map_err(Into::into)
     }
 }
 
+/// Run a blocking read from the store on the blocking thread pool.
+async fn spawn_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(anyhow::Error::from)?
+}
+
+/// Convert `checkpoint` into its proto representation, populating the fields selected by
+/// `read_mask`. The checkpoint is borrowed, so that callers can hold on to it (e.g. to evaluate
+/// subscription filters against it), and only the parts selected by the read mask are copied.
 pub(crate) fn checkpoint_data_to_full_checkpoint_response(
     sui_types::full_checkpoint_content::CheckpointData {
         checkpoint_summary,
         checkpoint_contents,
         transactions,
-    }: sui_types::full_checkpoint_content::CheckpointData,
+    }: &sui_types::full_checkpoint_content::CheckpointData,
     read_mask: &FieldMaskTree,
 ) -> Result<GetFullCheckpointResponse> {
     let sequence_number = checkpoint_summary.sequence_number;
     let digest: CheckpointDigest = checkpoint_summary.digest().to_owned().into();
-    let (summary, signature) = checkpoint_summary.into_data_and_sig();
+    let summary = checkpoint_summary.data();
+    let signature = checkpoint_summary.auth_sig();
 
     let summary_bcs = read_mask
         .contains("summary_bcs")
-        .then(|| bcs::to_bytes(&summary))
+        .then(|| bcs::to_bytes(summary))
         .transpose()?
         .map(Into::into);
     let contents_bcs = read_mask
         .contains("contents_bcs")
-        .then(|| bcs::to_bytes(&checkpoint_contents))
+        .then(|| bcs::to_bytes(checkpoint_contents))
         .transpose()?
         .map(Into::into);
 
@@ -202,7 +518,7 @@ pub(crate) fn checkpoint_data_to_full_checkpoint_response(
         .subtree("transactions")
         .map(|read_mask| {
             transactions
-                .into_iter()
+                .iter()
                 .map(|transaction| transaction_to_checkpoint_transaction(transaction, &read_mask))
                 .collect::<Result<_>>()
         })
@@ -216,17 +532,17 @@ pub(crate) fn checkpoint_data_to_full_checkpoint_response(
         digest: read_mask.contains("digest").then(|| digest.into()),
         summary: read_mask
             .contains("summary")
-            .then(|| sui_sdk_types::CheckpointSummary::try_from(summary))
+            .then(|| sui_sdk_types::CheckpointSummary::try_from(summary.clone()))
             .transpose()?
             .map(Into::into),
         summary_bcs,
 
         signature: read_mask
             .contains("signature")
-            .then(|| sui_sdk_types::ValidatorAggregatedSignature::from(signature).into()),
+            .then(|| sui_sdk_types::ValidatorAggregatedSignature::from(signature.clone()).into()),
         contents: read_mask
             .contains("contents")
-            .then(|| sui_sdk_types::CheckpointContents::try_from(checkpoint_contents))
+            .then(|| sui_sdk_types::CheckpointContents::try_from(checkpoint_contents.clone()))
             .transpose()?
             .map(Into::into),
         contents_bcs,
@@ -236,36 +552,36 @@ pub(crate) fn checkpoint_data_to_full_checkpoint_response(
     .pipe(Ok)
 }
 
-fn transaction_to_checkpoint_transaction(
+pub(crate) fn transaction_to_checkpoint_transaction(
     sui_types::full_checkpoint_content::CheckpointTransaction {
         transaction,
         effects,
         events,
         input_objects,
         output_objects,
-    }: sui_types::full_checkpoint_content::CheckpointTransaction,
+    }: &sui_types::full_checkpoint_content::CheckpointTransaction,
     read_mask: &FieldMaskTree,
 ) -> Result<FullCheckpointTransaction> {
     let digest = read_mask
         .contains("digest")
         .then(|| sui_sdk_types::TransactionDigest::from(transaction.digest().to_owned()).into());
-    let transaction = transaction.into_data().into_inner().intent_message.value;
+    let transaction = transaction.data().transaction_data();
     let transaction_bcs = read_mask
         .contains("transaction_bcs")
-        .then(|| Bcs::serialize(&transaction))
+        .then(|| Bcs::serialize(transaction))
         .transpose()?;
     let transaction = read_mask
         .contains("transaction")
-        .then(|| sui_sdk_types::Transaction::try_from(transaction))
+        .then(|| sui_sdk_types::Transaction::try_from(transaction.clone()))
         .transpose()?
         .map(Into::into);
     let effects_bcs = read_mask
         .contains("effects_bcs")
-        .then(|| Bcs::serialize(&effects))
+        .then(|| Bcs::serialize(effects))
         .transpose()?;
     let effects = read_mask
         .contains("effects")
-        .then(|| sui_sdk_types::TransactionEffects::try_from(effects))
+        .then(|| sui_sdk_types::TransactionEffects::try_from(effects.clone()))
         .transpose()?
         .map(Into::into);
     let events_bcs = read_mask
@@ -275,7 +591,11 @@ fn transaction_to_checkpoint_transaction(
         .transpose()?;
     let events = read_mask
         .contains("events")
-        .then(|| events.map(sui_sdk_types::TransactionEvents::try_from))
+        .then(|| {
+            events
+                .clone()
+                .map(sui_sdk_types::TransactionEvents::try_from)
+        })
         .flatten()
         .transpose()?
         .map(Into::into);
@@ -284,7 +604,7 @@ fn transaction_to_checkpoint_transaction(
         .subtree("input_objects")
         .map(|read_mask| {
             input_objects
-                .into_iter()
+                .iter()
                 .map(|object| object_to_object_response(object, &read_mask))
                 .collect::<Result<_>>()
         })
@@ -295,7 +615,7 @@ fn transaction_to_checkpoint_transaction(
         .subtree("output_objects")
         .map(|read_mask| {
             output_objects
-                .into_iter()
+                .iter()
                 .map(|object| object_to_object_response(object, &read_mask))
                 .collect::<Result<_>>()
         })
@@ -317,7 +637,7 @@ fn transaction_to_checkpoint_transaction(
 }
 
 fn object_to_object_response(
-    object: sui_types::object::Object,
+    object: &sui_types::object::Object,
     read_mask: &FieldMaskTree,
 ) -> Result<FullCheckpointObject> {
     let object_id = read_mask
@@ -332,11 +652,11 @@ fn object_to_object_response(
 
     let object_bcs = read_mask
         .contains("object_bcs")
-        .then(|| Bcs::serialize(&object))
+        .then(|| Bcs::serialize(object))
         .transpose()?;
     let object = read_mask
         .contains("object")
-        .then(|| sui_sdk_types::Object::try_from(object))
+        .then(|| sui_sdk_types::Object::try_from(object.clone()))
         .transpose()?
         .map(Into::into);
 
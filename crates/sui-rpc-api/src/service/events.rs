@@ -0,0 +1,184 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::pin::Pin;
+
+use crate::field_mask::FieldMaskTree;
+use crate::field_mask::FieldMaskUtil;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::node::v2alpha::ListEventsRequest;
+use crate::proto::node::v2alpha::ListEventsResponse;
+use crate::proto::types::Bcs;
+use crate::service::filter::parse_address;
+use crate::service::filter::MoveFunctionFilter;
+use crate::service::filter::TypeFilter;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcService;
+use prost_types::FieldMask;
+use sui_types::base_types::SuiAddress;
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::storage::TransactionIndexKind;
+use sui_types::transaction::TransactionDataAPI;
+
+pub type ListEventsStream =
+    Pin<Box<dyn tokio_stream::Stream<Item = Result<ListEventsResponse, tonic::Status>> + Send>>;
+
+impl RpcService {
+    pub fn list_events(&self, request: ListEventsRequest) -> Result<ListEventsStream> {
+        let filter = request
+            .filter
+            .as_ref()
+            .map(EventFilter::try_from_proto)
+            .transpose()?
+            .unwrap_or_default();
+
+        let read_mask = request
+            .read_mask
+            .unwrap_or_else(|| FieldMask::from_str(ListEventsRequest::READ_MASK_DEFAULT));
+        ListEventsResponse::validate_read_mask(&read_mask).map_err(|path| {
+            FieldViolation::new("read_mask")
+                .with_description(format!("invalid read_mask path: {path}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })?;
+        let read_mask = FieldMaskTree::from(read_mask);
+
+        let transactions = self.checkpoint_transactions(
+            request.start_checkpoint,
+            request.end_checkpoint,
+            filter.index(),
+            false,
+        )?;
+
+        let stream = async_stream::stream! {
+            for await transaction in transactions {
+                let (sequence_number, transaction) = match transaction {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        yield Err(e.into());
+                        break;
+                    }
+                };
+
+                if !filter.matches_transaction(&transaction) {
+                    continue;
+                }
+
+                let Some(events) = transaction.events else {
+                    continue;
+                };
+
+                let digest = *transaction.transaction.digest();
+                for (index, event) in events.data.into_iter().enumerate() {
+                    if !filter.matches_event(&event) {
+                        continue;
+                    }
+
+                    yield event_to_response(sequence_number, digest, index, event, &read_mask)
+                        .map_err(Into::into);
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
+
+fn event_to_response(
+    checkpoint: u64,
+    transaction_digest: sui_types::digests::TransactionDigest,
+    event_index: usize,
+    event: Event,
+    read_mask: &FieldMaskTree,
+) -> Result<ListEventsResponse> {
+    let event = sui_sdk_types::Event::try_from(event)?;
+
+    let event_bcs = read_mask
+        .contains("event_bcs")
+        .then(|| Bcs::serialize(&event))
+        .transpose()?;
+
+    Ok(ListEventsResponse {
+        checkpoint: Some(checkpoint),
+        event_index: Some(event_index as u64),
+        transaction_digest: read_mask
+            .contains("transaction_digest")
+            .then(|| sui_sdk_types::TransactionDigest::from(transaction_digest).into()),
+        event: read_mask.contains("event").then(|| event.into()),
+        event_bcs,
+    })
+}
+
+/// Criteria for selecting events, parsed from an `EventFilter` request message. An event matches
+/// if it meets every criteria that is set.
+#[derive(Default)]
+struct EventFilter {
+    sender: Option<SuiAddress>,
+    event_type: Option<TypeFilter>,
+    move_function: Option<MoveFunctionFilter>,
+}
+
+impl EventFilter {
+    fn try_from_proto(
+        filter: &crate::proto::node::v2alpha::EventFilter,
+    ) -> Result<Self, FieldViolation> {
+        Ok(Self {
+            sender: filter
+                .sender
+                .as_ref()
+                .map(|sender| parse_address("filter.sender", sender))
+                .transpose()?,
+            event_type: filter
+                .event_type
+                .as_deref()
+                .map(|event_type| TypeFilter::try_from_proto("filter.event_type", event_type))
+                .transpose()?,
+            move_function: filter
+                .move_function
+                .as_ref()
+                .map(|f| MoveFunctionFilter::try_from_proto("filter.move_function", f))
+                .transpose()?,
+        })
+    }
+
+    /// The RPC index to read candidate transactions from, before checking their events against
+    /// the rest of the filter.
+    fn index(&self) -> Option<TransactionIndexKind> {
+        self.sender
+            .map(TransactionIndexKind::Sender)
+            .or_else(|| {
+                self.move_function
+                    .as_ref()
+                    .map(|f| TransactionIndexKind::MoveCallPackage(f.package()))
+            })
+            .or_else(|| {
+                self.event_type
+                    .as_ref()
+                    .map(|t| TransactionIndexKind::EventPackage(t.package()))
+            })
+    }
+
+    /// Whether events emitted by `transaction` could match this filter, based on the criteria
+    /// that apply to the transaction as a whole.
+    fn matches_transaction(&self, transaction: &CheckpointTransaction) -> bool {
+        if let Some(move_function) = &self.move_function {
+            if !move_function.matches_transaction(transaction) {
+                return false;
+            }
+        }
+
+        self.sender
+            .is_none_or(|sender| transaction.transaction.transaction_data().sender() == sender)
+    }
+
+    fn matches_event(&self, event: &Event) -> bool {
+        if self.sender.is_some_and(|sender| event.sender != sender) {
+            return false;
+        }
+
+        self.event_type
+            .as_ref()
+            .is_none_or(|event_type| event_type.matches(&event.type_))
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::ErrorReason;
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::ObjectID;
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::object::Owner;
+use sui_types::parse_sui_struct_tag;
+use sui_types::transaction::TransactionDataAPI;
+use tap::Pipe;
+
+/// Criteria for selecting transactions by the Move functions they call.
+pub(crate) struct MoveFunctionFilter {
+    package: ObjectID,
+    module: Option<String>,
+    function: Option<String>,
+}
+
+/// Criteria for selecting events or objects by their type.
+pub(crate) enum TypeFilter {
+    /// Types defined in this package.
+    Package(ObjectID),
+
+    /// Types defined in this module.
+    Module(ObjectID, String),
+
+    /// This type. If the type has no type parameters, any instantiation of the type matches.
+    Type(StructTag),
+}
+
+impl MoveFunctionFilter {
+    pub(crate) fn try_from_proto(
+        field: &str,
+        filter: &crate::proto::node::v2alpha::MoveFunctionFilter,
+    ) -> Result<Self, FieldViolation> {
+        let package = filter
+            .package
+            .as_ref()
+            .ok_or_else(|| {
+                FieldViolation::new(format!("{field}.package"))
+                    .with_description("missing package")
+                    .with_reason(ErrorReason::FieldMissing)
+            })?
+            .pipe(sui_sdk_types::ObjectId::try_from)
+            .map_err(|e| {
+                FieldViolation::new(format!("{field}.package"))
+                    .with_description(format!("invalid package: {e}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+            })?
+            .into();
+
+        if filter.function.is_some() && filter.module.is_none() {
+            return Err(FieldViolation::new(format!("{field}.module"))
+                .with_description("module must be set when function is set")
+                .with_reason(ErrorReason::FieldMissing));
+        }
+
+        Ok(Self {
+            package,
+            module: filter.module.clone(),
+            function: filter.function.clone(),
+        })
+    }
+
+    /// The package whose functions this filter selects calls to.
+    pub(crate) fn package(&self) -> ObjectID {
+        self.package
+    }
+
+    /// Whether any of the `MoveCall` commands in `transaction` call a matching function.
+    pub(crate) fn matches_transaction(&self, transaction: &CheckpointTransaction) -> bool {
+        transaction
+            .transaction
+            .transaction_data()
+            .move_calls()
+            .into_iter()
+            .any(|(package, module, function)| {
+                *package == self.package
+                    && self.module.as_ref().is_none_or(|m| m == module)
+                    && self.function.as_ref().is_none_or(|f| f == function)
+            })
+    }
+}
+
+impl TypeFilter {
+    /// Parse a type filter from the string in `field`: a package address (`0x2`), a module
+    /// (`0x2::coin`), or a struct type (`0x2::coin::Coin`).
+    pub(crate) fn try_from_proto(field: &str, type_: &str) -> Result<Self, FieldViolation> {
+        type_.parse().map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid type: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })
+    }
+
+    /// The package that types selected by this filter are defined in.
+    pub(crate) fn package(&self) -> ObjectID {
+        match self {
+            Self::Package(package) | Self::Module(package, _) => *package,
+            Self::Type(tag) => tag.address.into(),
+        }
+    }
+
+    pub(crate) fn matches(&self, type_: &StructTag) -> bool {
+        match self {
+            Self::Package(package) => ObjectID::from(type_.address) == *package,
+            Self::Module(package, module) => {
+                ObjectID::from(type_.address) == *package && type_.module.as_str() == module
+            }
+            Self::Type(tag) if tag.type_params.is_empty() => {
+                tag.address == type_.address && tag.module == type_.module && tag.name == type_.name
+            }
+            Self::Type(tag) => tag == type_,
+        }
+    }
+}
+
+impl FromStr for TypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.splitn(3, "::").collect();
+        Ok(match parts.as_slice() {
+            [package] => Self::Package(ObjectID::from_str(package)?),
+            [package, module] => Self::Module(ObjectID::from_str(package)?, module.to_string()),
+            _ => Self::Type(parse_sui_struct_tag(s)?),
+        })
+    }
+}
+
+pub(crate) fn parse_address(
+    field: &str,
+    address: &crate::proto::types::Address,
+) -> Result<SuiAddress, FieldViolation> {
+    sui_sdk_types::Address::try_from(address)
+        .map(Into::into)
+        .map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid address: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })
+}
+
+/// Whether `transaction` was sent by `address`, or modified, created or transferred an object
+/// owned by `address`. Only needs the transaction's data and effects, not its objects.
+pub(crate) fn affects_address(transaction: &CheckpointTransaction, address: SuiAddress) -> bool {
+    transaction.transaction.transaction_data().sender() == address
+        || transaction
+            .effects
+            .old_object_metadata()
+            .iter()
+            .any(|(_, owner)| is_owned_by(owner, address))
+        || transaction
+            .effects
+            .all_changed_objects()
+            .iter()
+            .any(|(_, owner, _)| is_owned_by(owner, address))
+}
+
+fn is_owned_by(owner: &Owner, address: SuiAddress) -> bool {
+    match owner {
+        Owner::AddressOwner(owner) => *owner == address,
+        Owner::ConsensusV2 { authenticator, .. } => *authenticator.as_single_owner() == address,
+        Owner::ObjectOwner(_) | Owner::Shared { .. } | Owner::Immutable => false,
+    }
+}
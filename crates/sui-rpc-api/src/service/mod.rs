@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod accounts;
+mod balances;
 pub(crate) mod checkpoints;
 mod coin_info;
 mod committee;
+pub(crate) mod events;
+pub(crate) mod filter;
 mod gas_info;
 pub(crate) mod health;
 mod info;
+mod move_package;
 pub(crate) mod objects;
 mod protocol_config;
 pub(crate) mod transactions;
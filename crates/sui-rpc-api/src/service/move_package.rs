@@ -0,0 +1,372 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::node::v2alpha::Ability;
+use crate::proto::node::v2alpha::DatatypeDescriptor;
+use crate::proto::node::v2alpha::DatatypeKind;
+use crate::proto::node::v2alpha::FieldDescriptor;
+use crate::proto::node::v2alpha::FunctionDescriptor;
+use crate::proto::node::v2alpha::GetDatatypeRequest;
+use crate::proto::node::v2alpha::GetDatatypeResponse;
+use crate::proto::node::v2alpha::GetFunctionRequest;
+use crate::proto::node::v2alpha::GetFunctionResponse;
+use crate::proto::node::v2alpha::GetPackageRequest;
+use crate::proto::node::v2alpha::GetPackageResponse;
+use crate::proto::node::v2alpha::Module;
+use crate::proto::node::v2alpha::OpenSignature;
+use crate::proto::node::v2alpha::OpenSignatureBody;
+use crate::proto::node::v2alpha::Package;
+use crate::proto::node::v2alpha::Reference;
+use crate::proto::node::v2alpha::TypeKind;
+use crate::proto::node::v2alpha::TypeParameter;
+use crate::proto::node::v2alpha::VariantDescriptor;
+use crate::proto::node::v2alpha::Visibility;
+use crate::service::objects::ObjectNotFoundError;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcError;
+use crate::RpcService;
+use move_binary_format::file_format::AbilitySet;
+use sui_package_resolver::DataDef;
+use sui_package_resolver::FunctionDef;
+use sui_package_resolver::MoveData;
+use sui_sdk_types::ObjectId;
+use tap::Pipe;
+
+impl RpcService {
+    pub fn get_package(&self, request: GetPackageRequest) -> Result<GetPackageResponse> {
+        let package_id = parse_package_id(request.package_id.as_ref())?;
+        let (move_package, package) = self.read_package(package_id)?;
+
+        let modules = package
+            .modules()
+            .iter()
+            .map(|(name, module)| module_to_proto(name, module))
+            .collect::<Result<_>>()?;
+
+        Ok(GetPackageResponse {
+            package: Some(Package {
+                storage_id: Some(ObjectId::from(move_package.id()).into()),
+                original_id: Some(ObjectId::from(move_package.original_package_id()).into()),
+                version: Some(move_package.version().value()),
+                modules,
+            }),
+        })
+    }
+
+    pub fn get_datatype(&self, request: GetDatatypeRequest) -> Result<GetDatatypeResponse> {
+        let package_id = parse_package_id(request.package_id.as_ref())?;
+        let module_name = required_name("module_name", request.module_name)?;
+        let name = required_name("name", request.name)?;
+
+        let (_, package) = self.read_package(package_id)?;
+        let module = package
+            .module(&module_name)
+            .map_err(|_| ModuleNotFoundError(package_id, module_name.clone()))?;
+
+        let datatype = module
+            .data_def(&name)
+            .map_err(package_error)?
+            .ok_or_else(|| {
+                RpcError::new(
+                    tonic::Code::NotFound,
+                    format!("Datatype {package_id}::{module_name}::{name} not found"),
+                )
+            })?
+            .pipe(|def| datatype_to_proto(&module_name, &name, def));
+
+        Ok(GetDatatypeResponse {
+            datatype: Some(datatype),
+        })
+    }
+
+    pub fn get_function(&self, request: GetFunctionRequest) -> Result<GetFunctionResponse> {
+        let package_id = parse_package_id(request.package_id.as_ref())?;
+        let module_name = required_name("module_name", request.module_name)?;
+        let name = required_name("name", request.name)?;
+
+        let (_, package) = self.read_package(package_id)?;
+        let module = package
+            .module(&module_name)
+            .map_err(|_| ModuleNotFoundError(package_id, module_name.clone()))?;
+
+        let function = module
+            .function_def(&name)
+            .map_err(package_error)?
+            .ok_or_else(|| {
+                RpcError::new(
+                    tonic::Code::NotFound,
+                    format!("Function {package_id}::{module_name}::{name} not found"),
+                )
+            })?
+            .pipe(|def| function_to_proto(&name, def));
+
+        Ok(GetFunctionResponse {
+            function: Some(function),
+        })
+    }
+
+    /// Load the package stored at `package_id`, returning both its on-chain representation and
+    /// its deserialized form.
+    fn read_package(
+        &self,
+        package_id: ObjectId,
+    ) -> Result<(
+        sui_types::move_package::MovePackage,
+        sui_package_resolver::Package,
+    )> {
+        let object = self
+            .reader
+            .inner()
+            .get_object(&package_id.into())
+            .ok_or_else(|| ObjectNotFoundError::new(package_id))?;
+
+        let move_package = object.data.try_as_package().cloned().ok_or_else(|| {
+            RpcError::new(
+                tonic::Code::InvalidArgument,
+                format!("Object {package_id} is not a package"),
+            )
+        })?;
+
+        let package = sui_package_resolver::Package::read_from_package(&move_package)
+            .map_err(package_error)?;
+
+        Ok((move_package, package))
+    }
+}
+
+#[derive(Debug)]
+pub struct ModuleNotFoundError(ObjectId, String);
+
+impl std::fmt::Display for ModuleNotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Module {}::{} not found", self.0, self.1)
+    }
+}
+
+impl std::error::Error for ModuleNotFoundError {}
+
+impl From<ModuleNotFoundError> for crate::RpcError {
+    fn from(value: ModuleNotFoundError) -> Self {
+        Self::new(tonic::Code::NotFound, value.to_string())
+    }
+}
+
+fn parse_package_id(package_id: Option<&crate::proto::types::ObjectId>) -> Result<ObjectId> {
+    package_id
+        .ok_or_else(|| {
+            FieldViolation::new("package_id")
+                .with_description("missing package_id")
+                .with_reason(ErrorReason::FieldMissing)
+        })?
+        .pipe(ObjectId::try_from)
+        .map_err(|e| {
+            FieldViolation::new("package_id")
+                .with_description(format!("invalid package_id: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+                .into()
+        })
+}
+
+fn required_name(field: &str, name: Option<String>) -> Result<String> {
+    name.ok_or_else(|| {
+        FieldViolation::new(field)
+            .with_description(format!("missing {field}"))
+            .with_reason(ErrorReason::FieldMissing)
+            .into()
+    })
+}
+
+/// Failures to deserialize a package that has already been published are internal errors.
+fn package_error(error: sui_package_resolver::error::Error) -> RpcError {
+    RpcError::new(tonic::Code::Internal, error.to_string())
+}
+
+fn module_to_proto(name: &str, module: &sui_package_resolver::Module) -> Result<Module> {
+    let datatypes = module
+        .datatypes(None, None)
+        .filter_map(|datatype| {
+            module
+                .data_def(datatype)
+                .transpose()
+                .map(|def| def.map(|def| datatype_to_proto(name, datatype, def)))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(package_error)?;
+
+    let functions = module
+        .functions(None, None)
+        .filter_map(|function| {
+            module
+                .function_def(function)
+                .transpose()
+                .map(|def| def.map(|def| function_to_proto(function, def)))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(package_error)?;
+
+    Ok(Module {
+        name: Some(name.to_owned()),
+        datatypes,
+        functions,
+    })
+}
+
+fn datatype_to_proto(module: &str, name: &str, def: DataDef) -> DatatypeDescriptor {
+    let DataDef {
+        defining_id,
+        abilities,
+        type_params,
+        data,
+    } = def;
+
+    let (kind, fields, variants) = match data {
+        MoveData::Struct(fields) => (DatatypeKind::Struct, fields_to_proto(fields), vec![]),
+        MoveData::Enum(variants) => {
+            let variants = variants
+                .into_iter()
+                .enumerate()
+                .map(|(position, variant)| VariantDescriptor {
+                    name: Some(variant.name),
+                    position: Some(position as u32),
+                    fields: fields_to_proto(variant.signatures),
+                })
+                .collect();
+            (DatatypeKind::Enum, vec![], variants)
+        }
+    };
+
+    DatatypeDescriptor {
+        type_name: Some(format!(
+            "{}::{module}::{name}",
+            defining_id.to_canonical_display(true)
+        )),
+        defining_id: Some(ObjectId::new(defining_id.into_bytes()).into()),
+        module: Some(module.to_owned()),
+        name: Some(name.to_owned()),
+        abilities: abilities_to_proto(abilities),
+        type_parameters: type_params
+            .into_iter()
+            .map(|param| TypeParameter {
+                constraints: abilities_to_proto(param.constraints),
+                is_phantom: Some(param.is_phantom),
+            })
+            .collect(),
+        kind: Some(kind.into()),
+        fields,
+        variants,
+    }
+}
+
+fn fields_to_proto(
+    fields: Vec<(String, sui_package_resolver::OpenSignatureBody)>,
+) -> Vec<FieldDescriptor> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(position, (name, type_))| FieldDescriptor {
+            name: Some(name),
+            position: Some(position as u32),
+            r#type: Some(open_signature_body_to_proto(&type_)),
+        })
+        .collect()
+}
+
+fn function_to_proto(name: &str, def: FunctionDef) -> FunctionDescriptor {
+    use move_binary_format::file_format::Visibility as V;
+
+    let visibility = match def.visibility {
+        V::Private => Visibility::Private,
+        V::Public => Visibility::Public,
+        V::Friend => Visibility::Friend,
+    };
+
+    FunctionDescriptor {
+        name: Some(name.to_owned()),
+        visibility: Some(visibility.into()),
+        is_entry: Some(def.is_entry),
+        type_parameters: def
+            .type_params
+            .into_iter()
+            .map(|constraints| TypeParameter {
+                constraints: abilities_to_proto(constraints),
+                is_phantom: Some(false),
+            })
+            .collect(),
+        parameters: def.parameters.iter().map(open_signature_to_proto).collect(),
+        returns: def.return_.iter().map(open_signature_to_proto).collect(),
+    }
+}
+
+fn abilities_to_proto(abilities: AbilitySet) -> Vec<i32> {
+    use move_binary_format::file_format::Ability as A;
+
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            A::Copy => Ability::Copy,
+            A::Drop => Ability::Drop,
+            A::Store => Ability::Store,
+            A::Key => Ability::Key,
+        })
+        .map(Into::into)
+        .collect()
+}
+
+fn open_signature_to_proto(signature: &sui_package_resolver::OpenSignature) -> OpenSignature {
+    use sui_package_resolver::Reference as R;
+
+    OpenSignature {
+        reference: signature.ref_.map(|reference| {
+            match reference {
+                R::Immutable => Reference::Immutable,
+                R::Mutable => Reference::Mutable,
+            }
+            .into()
+        }),
+        body: Some(open_signature_body_to_proto(&signature.body)),
+    }
+}
+
+fn open_signature_body_to_proto(
+    body: &sui_package_resolver::OpenSignatureBody,
+) -> OpenSignatureBody {
+    use sui_package_resolver::OpenSignatureBody as S;
+
+    let mut message = OpenSignatureBody::default();
+    let kind = match body {
+        S::Address => TypeKind::Address,
+        S::Bool => TypeKind::Bool,
+        S::U8 => TypeKind::U8,
+        S::U16 => TypeKind::U16,
+        S::U32 => TypeKind::U32,
+        S::U64 => TypeKind::U64,
+        S::U128 => TypeKind::U128,
+        S::U256 => TypeKind::U256,
+        S::Vector(element) => {
+            message.type_parameter_instantiation = vec![open_signature_body_to_proto(element)];
+            TypeKind::Vector
+        }
+        S::Datatype(key, type_params) => {
+            message.type_name = Some(format!(
+                "{}::{}::{}",
+                key.package.to_canonical_display(true),
+                key.module,
+                key.name,
+            ));
+            message.type_parameter_instantiation = type_params
+                .iter()
+                .map(open_signature_body_to_proto)
+                .collect();
+            TypeKind::Datatype
+        }
+        S::TypeParameter(index) => {
+            message.type_parameter = Some((*index).into());
+            TypeKind::TypeParameter
+        }
+    };
+
+    message.kind = Some(kind.into());
+    message
+}
@@ -6,7 +6,10 @@ use crate::field_mask::FieldMaskUtil;
 use crate::proto::google::rpc::bad_request::FieldViolation;
 use crate::proto::node::v2::GetObjectRequest;
 use crate::proto::node::v2::GetObjectResponse;
+use crate::proto::node::v2alpha::BatchGetObjectsRequest;
+use crate::proto::node::v2alpha::BatchGetObjectsResponse;
 use crate::proto::node::v2alpha::DynamicField;
+use crate::proto::node::v2alpha::GetObjectResult;
 use crate::proto::node::v2alpha::ListDynamicFieldsRequest;
 use crate::proto::node::v2alpha::ListDynamicFieldsResponse;
 use crate::ErrorReason;
@@ -14,6 +17,7 @@ use crate::Result;
 use crate::RpcError;
 use crate::RpcService;
 use prost_types::FieldMask;
+use sui_sdk_types::Object;
 use sui_sdk_types::ObjectId;
 use sui_sdk_types::TypeTag;
 use sui_sdk_types::Version;
@@ -33,57 +37,131 @@ impl RpcService {
             read_mask,
         }: GetObjectRequest,
     ) -> Result<GetObjectResponse> {
-        let object_id = object_id
-            .ok_or_else(|| {
-                FieldViolation::new("object_id")
-                    .with_description("missing object_id")
-                    .with_reason(ErrorReason::FieldMissing)
-            })?
-            .pipe_ref(ObjectId::try_from)
-            .map_err(|e| {
-                FieldViolation::new("object_id")
-                    .with_description(format!("invalid object_id: {e}"))
-                    .with_reason(ErrorReason::FieldInvalid)
-            })?;
+        let object_id = parse_object_id("object_id", object_id.as_ref())?;
+        let read_mask = object_read_mask(read_mask)?;
+
+        self.read_object(object_id, version)
+            .and_then(|object| object_to_response(object, &read_mask))
+    }
 
-        let read_mask =
-            read_mask.unwrap_or_else(|| FieldMask::from_str(GetObjectRequest::READ_MASK_DEFAULT));
-        GetObjectResponse::validate_read_mask(&read_mask).map_err(|path| {
-            FieldViolation::new("read_mask")
-                .with_description(format!("invalid read_mask path: {path}"))
+    pub fn batch_get_objects(
+        &self,
+        BatchGetObjectsRequest {
+            requests,
+            read_mask,
+        }: BatchGetObjectsRequest,
+    ) -> Result<BatchGetObjectsResponse> {
+        if requests.len() > MAX_BATCH_SIZE {
+            return Err(FieldViolation::new("requests")
+                .with_description(format!(
+                    "too many objects requested: {} > {MAX_BATCH_SIZE}",
+                    requests.len()
+                ))
                 .with_reason(ErrorReason::FieldInvalid)
-        })?;
-        let read_mask = FieldMaskTree::from(read_mask);
+                .into());
+        }
 
-        let object = if let Some(version) = version {
+        let requests = requests
+            .iter()
+            .enumerate()
+            .map(|(i, request)| {
+                parse_object_id(
+                    &format!("requests[{i}].object_id"),
+                    request.object_id.as_ref(),
+                )
+                .map(|object_id| (object_id, request.version))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let read_mask = object_read_mask(read_mask)?;
+
+        let objects = requests
+            .into_iter()
+            .map(|(object_id, version)| {
+                let result = match self.read_object(object_id, version) {
+                    Ok(object) => GetObjectResult {
+                        object: Some(object_to_response(object, &read_mask)?),
+                        error: None,
+                    },
+                    Err(e) if e.code() == tonic::Code::NotFound => GetObjectResult {
+                        object: None,
+                        error: Some(e.into()),
+                    },
+                    Err(e) => return Err(e),
+                };
+
+                Ok(result)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(BatchGetObjectsResponse { objects })
+    }
+
+    fn read_object(&self, object_id: ObjectId, version: Option<Version>) -> Result<Object> {
+        if let Some(version) = version {
             self.reader
                 .get_object_with_version(object_id, version)?
-                .ok_or_else(|| ObjectNotFoundError::new_with_version(object_id, version))?
+                .ok_or_else(|| ObjectNotFoundError::new_with_version(object_id, version).into())
         } else {
             self.reader
                 .get_object(object_id)?
-                .ok_or_else(|| ObjectNotFoundError::new(object_id))?
-        };
-
-        let object_bcs = read_mask
-            .contains("object_bcs")
-            .then(|| bcs::to_bytes(&object))
-            .transpose()?
-            .map(Into::into);
-
-        GetObjectResponse {
-            object_id: read_mask
-                .contains("object_id")
-                .then(|| object.object_id().into()),
-            version: read_mask.contains("version").then_some(object.version()),
-            digest: read_mask.contains("digest").then(|| object.digest().into()),
-            object: read_mask.contains("object").then(|| object.into()),
-            object_bcs,
+                .ok_or_else(|| ObjectNotFoundError::new(object_id).into())
         }
-        .pipe(Ok)
     }
 }
 
+/// The maximum number of items that can be requested by a single batch request.
+pub(crate) const MAX_BATCH_SIZE: usize = 1000;
+
+fn parse_object_id(
+    field: &str,
+    object_id: Option<&crate::proto::types::ObjectId>,
+) -> Result<ObjectId> {
+    object_id
+        .ok_or_else(|| {
+            FieldViolation::new(field)
+                .with_description(format!("missing {field}"))
+                .with_reason(ErrorReason::FieldMissing)
+        })?
+        .pipe(ObjectId::try_from)
+        .map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid {field}: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+                .into()
+        })
+}
+
+fn object_read_mask(read_mask: Option<FieldMask>) -> Result<FieldMaskTree> {
+    let read_mask =
+        read_mask.unwrap_or_else(|| FieldMask::from_str(GetObjectRequest::READ_MASK_DEFAULT));
+    GetObjectResponse::validate_read_mask(&read_mask).map_err(|path| {
+        FieldViolation::new("read_mask")
+            .with_description(format!("invalid read_mask path: {path}"))
+            .with_reason(ErrorReason::FieldInvalid)
+    })?;
+
+    Ok(FieldMaskTree::from(read_mask))
+}
+
+fn object_to_response(object: Object, read_mask: &FieldMaskTree) -> Result<GetObjectResponse> {
+    let object_bcs = read_mask
+        .contains("object_bcs")
+        .then(|| bcs::to_bytes(&object))
+        .transpose()?
+        .map(Into::into);
+
+    GetObjectResponse {
+        object_id: read_mask
+            .contains("object_id")
+            .then(|| object.object_id().into()),
+        version: read_mask.contains("version").then_some(object.version()),
+        digest: read_mask.contains("digest").then(|| object.digest().into()),
+        object: read_mask.contains("object").then(|| object.into()),
+        object_bcs,
+    }
+    .pipe(Ok)
+}
+
 #[derive(Debug)]
 pub struct ObjectNotFoundError {
     object_id: ObjectId,
@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::pin::Pin;
+
+use crate::field_mask::FieldMaskTree;
+use crate::field_mask::FieldMaskUtil;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::node::v2::FullCheckpointTransaction;
+use crate::proto::node::v2alpha::ListTransactionsRequest;
+use crate::proto::node::v2alpha::ListTransactionsResponse;
+use crate::service::checkpoints::transaction_to_checkpoint_transaction;
+use crate::service::filter::affects_address;
+use crate::service::filter::parse_address;
+use crate::service::filter::MoveFunctionFilter;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcService;
+use prost_types::FieldMask;
+use sui_types::base_types::SuiAddress;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
+use sui_types::storage::TransactionIndexKind;
+use sui_types::transaction::TransactionDataAPI;
+
+pub type ListTransactionsStream = Pin<
+    Box<dyn tokio_stream::Stream<Item = Result<ListTransactionsResponse, tonic::Status>> + Send>,
+>;
+
+impl RpcService {
+    pub fn list_transactions(
+        &self,
+        request: ListTransactionsRequest,
+    ) -> Result<ListTransactionsStream> {
+        let filter = request
+            .filter
+            .as_ref()
+            .map(TransactionFilter::try_from_proto)
+            .transpose()?
+            .unwrap_or_default();
+
+        let read_mask = request
+            .read_mask
+            .unwrap_or_else(|| FieldMask::from_str(ListTransactionsRequest::READ_MASK_DEFAULT));
+        for path in &read_mask.paths {
+            if !FullCheckpointTransaction::validate_field_path(path) {
+                return Err(FieldViolation::new("read_mask")
+                    .with_description(format!("invalid read_mask path: {path}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+                    .into());
+            }
+        }
+        let read_mask = FieldMaskTree::from(read_mask);
+
+        let with_objects = read_mask.subtree("input_objects").is_some()
+            || read_mask.subtree("output_objects").is_some();
+
+        let transactions = self.checkpoint_transactions(
+            request.start_checkpoint,
+            request.end_checkpoint,
+            filter.index(),
+            with_objects,
+        )?;
+
+        let stream = async_stream::stream! {
+            for await transaction in transactions {
+                let (sequence_number, transaction) = match transaction {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        yield Err(e.into());
+                        break;
+                    }
+                };
+
+                if !filter.matches(&transaction) {
+                    continue;
+                }
+
+                yield transaction_to_checkpoint_transaction(&transaction, &read_mask)
+                    .map(|transaction| ListTransactionsResponse {
+                        checkpoint: Some(sequence_number),
+                        transaction: Some(transaction),
+                    })
+                    .map_err(Into::into);
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Criteria for selecting transactions, parsed from a `TransactionFilter` request message. A
+/// transaction matches if it meets every criteria that is set.
+#[derive(Default)]
+struct TransactionFilter {
+    sender: Option<SuiAddress>,
+    affected_address: Option<SuiAddress>,
+    move_function: Option<MoveFunctionFilter>,
+}
+
+impl TransactionFilter {
+    fn try_from_proto(
+        filter: &crate::proto::node::v2alpha::TransactionFilter,
+    ) -> Result<Self, FieldViolation> {
+        Ok(Self {
+            sender: filter
+                .sender
+                .as_ref()
+                .map(|sender| parse_address("filter.sender", sender))
+                .transpose()?,
+            affected_address: filter
+                .affected_address
+                .as_ref()
+                .map(|address| parse_address("filter.affected_address", address))
+                .transpose()?,
+            move_function: filter
+                .move_function
+                .as_ref()
+                .map(|f| MoveFunctionFilter::try_from_proto("filter.move_function", f))
+                .transpose()?,
+        })
+    }
+
+    /// The RPC index to read candidate transactions from, before checking them against the rest
+    /// of the filter.
+    fn index(&self) -> Option<TransactionIndexKind> {
+        self.sender
+            .map(TransactionIndexKind::Sender)
+            .or(self
+                .affected_address
+                .map(TransactionIndexKind::AffectedAddress))
+            .or_else(|| {
+                self.move_function
+                    .as_ref()
+                    .map(|f| TransactionIndexKind::MoveCallPackage(f.package()))
+            })
+    }
+
+    fn matches(&self, transaction: &CheckpointTransaction) -> bool {
+        let data = transaction.transaction.transaction_data();
+
+        if self.sender.is_some_and(|sender| data.sender() != sender) {
+            return false;
+        }
+
+        if self
+            .affected_address
+            .is_some_and(|address| !affects_address(transaction, address))
+        {
+            return false;
+        }
+
+        if let Some(move_function) = &self.move_function {
+            if !move_function.matches_transaction(transaction) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -2,9 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metrics::SubscriptionMetrics;
+use crate::proto::google::rpc::bad_request::FieldViolation;
 use crate::proto::node::v2::GetFullCheckpointResponse;
+use crate::proto::types::Bcs;
+use crate::service::filter::affects_address;
+use crate::service::filter::parse_address;
+use crate::service::filter::MoveFunctionFilter;
+use crate::service::filter::TypeFilter;
+use move_core_types::language_storage::StructTag;
 use std::sync::Arc;
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::TransactionEvents;
 use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::error;
@@ -17,7 +27,26 @@ const SUBSCRIPTION_CHANNEL_SIZE: usize = 256;
 const MAX_SUBSCRIBERS: usize = 1024;
 
 struct SubscriptionRequest {
-    sender: oneshot::Sender<mpsc::Receiver<Arc<GetFullCheckpointResponse>>>,
+    sender: oneshot::Sender<mpsc::Receiver<Arc<SubscriptionCheckpoint>>>,
+}
+
+/// A checkpoint, as it is sent to subscribers.
+pub struct SubscriptionCheckpoint {
+    /// The checkpoint's data, which subscription filters are evaluated against.
+    pub data: Arc<CheckpointData>,
+
+    /// The checkpoint with all of its fields populated, which read masks are applied to.
+    pub response: GetFullCheckpointResponse,
+}
+
+/// Criteria for selecting the transactions and events of the checkpoints sent to a subscriber,
+/// parsed from a `CheckpointFilter` request message. A transaction is selected if it meets any of
+/// the criteria.
+pub(crate) struct CheckpointFilter {
+    affected_addresses: Vec<SuiAddress>,
+    move_functions: Vec<MoveFunctionFilter>,
+    event_types: Vec<TypeFilter>,
+    object_types: Vec<TypeFilter>,
 }
 
 #[derive(Clone)]
@@ -28,7 +57,7 @@ pub struct SubscriptionServiceHandle {
 impl SubscriptionServiceHandle {
     pub async fn register_subscription(
         &self,
-    ) -> Option<mpsc::Receiver<Arc<GetFullCheckpointResponse>>> {
+    ) -> Option<mpsc::Receiver<Arc<SubscriptionCheckpoint>>> {
         let (sender, reciever) = oneshot::channel();
         let request = SubscriptionRequest { sender };
         self.sender.send(request).await.ok()?;
@@ -43,7 +72,7 @@ pub struct SubscriptionService {
     // Expectation is that checkpoints are recieved in-order
     checkpoint_mailbox: mpsc::Receiver<CheckpointData>,
     mailbox: mpsc::Receiver<SubscriptionRequest>,
-    subscribers: Vec<mpsc::Sender<Arc<GetFullCheckpointResponse>>>,
+    subscribers: Vec<mpsc::Sender<Arc<SubscriptionCheckpoint>>>,
 
     metrics: SubscriptionMetrics,
 }
@@ -120,12 +149,16 @@ impl SubscriptionService {
             self.metrics.last_recieved_checkpoint.set(sequence_number);
         }
 
+        let checkpoint = Arc::new(checkpoint);
         let checkpoint =
             match crate::service::checkpoints::checkpoint_data_to_full_checkpoint_response(
-                checkpoint,
+                &checkpoint,
                 &crate::field_mask::FieldMaskTree::new_wildcard(),
             ) {
-                Ok(checkpoint) => Arc::new(checkpoint),
+                Ok(response) => Arc::new(SubscriptionCheckpoint {
+                    data: checkpoint,
+                    response,
+                }),
                 Err(e) => {
                     error!("unable to convert checkpoint to proto: {e:?}");
                     return;
@@ -173,3 +206,254 @@ impl SubscriptionService {
         }
     }
 }
+
+impl CheckpointFilter {
+    pub(crate) fn try_from_proto(
+        filter: &crate::proto::node::v2alpha::CheckpointFilter,
+    ) -> Result<Self, FieldViolation> {
+        Ok(Self {
+            affected_addresses: filter
+                .affected_addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| {
+                    parse_address(&format!("filter.affected_addresses[{i}]"), address)
+                })
+                .collect::<Result<_, _>>()?,
+            move_functions: filter
+                .move_functions
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    MoveFunctionFilter::try_from_proto(&format!("filter.move_functions[{i}]"), f)
+                })
+                .collect::<Result<_, _>>()?,
+            event_types: filter
+                .event_types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| TypeFilter::try_from_proto(&format!("filter.event_types[{i}]"), t))
+                .collect::<Result<_, _>>()?,
+            object_types: filter
+                .object_types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| TypeFilter::try_from_proto(&format!("filter.object_types[{i}]"), t))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.affected_addresses.is_empty()
+            && self.move_functions.is_empty()
+            && self.event_types.is_empty()
+            && self.object_types.is_empty()
+    }
+
+    /// Remove the transactions that don't match this filter from `checkpoint`. Transactions that
+    /// are only selected because of the events they emitted are stripped of the events that don't
+    /// match, in both their `events` and `events_bcs`. Transactions selected by any of the other
+    /// criteria keep all of their events.
+    ///
+    /// `checkpoint` is expected to be `data` converted to proto, with a read mask applied, so its
+    /// transactions are either absent or in the same order as `data`'s.
+    pub(crate) fn apply(&self, data: &CheckpointData, checkpoint: &mut GetFullCheckpointResponse) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut transactions = data.transactions.iter();
+        checkpoint.transactions.retain_mut(|response| {
+            let Some(transaction) = transactions.next() else {
+                return false;
+            };
+
+            if self.matches_transaction(transaction) {
+                return true;
+            }
+
+            let Some(events) = &transaction.events else {
+                return false;
+            };
+
+            let matches: Vec<_> = events
+                .data
+                .iter()
+                .map(|e| self.matches_event_type(&e.type_))
+                .collect();
+
+            if !matches.contains(&true) {
+                return false;
+            }
+
+            if let Some(response) = &mut response.events {
+                let mut matches = matches.iter();
+                response
+                    .events
+                    .retain(|_| matches.next().copied().unwrap_or(false));
+            }
+
+            if response.events_bcs.is_some() {
+                let events = TransactionEvents {
+                    data: events
+                        .data
+                        .iter()
+                        .zip(&matches)
+                        .filter(|(_, matches)| **matches)
+                        .map(|(event, _)| event.clone())
+                        .collect(),
+                };
+
+                // If the matching events can't be re-encoded, none of them are sent, rather than
+                // all of them.
+                response.events_bcs = Bcs::serialize(&events).ok();
+            }
+
+            true
+        });
+    }
+
+    /// Whether `transaction` is selected by any criteria other than the types of events it
+    /// emitted.
+    fn matches_transaction(&self, transaction: &CheckpointTransaction) -> bool {
+        self.affected_addresses
+            .iter()
+            .any(|address| affects_address(transaction, *address))
+            || self
+                .move_functions
+                .iter()
+                .any(|f| f.matches_transaction(transaction))
+            || transaction
+                .input_objects
+                .iter()
+                .chain(&transaction.output_objects)
+                .filter_map(|object| object.struct_tag())
+                .any(|type_| self.object_types.iter().any(|f| f.matches(&type_)))
+    }
+
+    fn matches_event_type(&self, type_: &StructTag) -> bool {
+        self.event_types.iter().any(|f| f.matches(type_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_mask::FieldMaskTree;
+    use crate::proto::node::v2::FullCheckpointTransaction;
+    use crate::service::checkpoints::checkpoint_data_to_full_checkpoint_response;
+    use move_core_types::identifier::Identifier;
+    use std::str::FromStr;
+    use sui_types::base_types::ObjectID;
+    use sui_types::event::Event;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+
+    fn event(type_: &str) -> Event {
+        let type_ = StructTag::from_str(type_).unwrap();
+        Event {
+            package_id: ObjectID::from(type_.address),
+            transaction_module: Identifier::new("m").unwrap(),
+            sender: SuiAddress::ZERO,
+            type_,
+            contents: vec![],
+        }
+    }
+
+    fn filter(affected_addresses: Vec<SuiAddress>, event_types: &[&str]) -> CheckpointFilter {
+        CheckpointFilter {
+            affected_addresses,
+            move_functions: vec![],
+            event_types: event_types
+                .iter()
+                .map(|t| TypeFilter::try_from_proto("event_types", t).unwrap())
+                .collect(),
+            object_types: vec![],
+        }
+    }
+
+    /// A checkpoint containing one transaction from each of `senders`, all emitting the same
+    /// events, and the full response for it.
+    fn checkpoint(senders: &[u8]) -> (CheckpointData, GetFullCheckpointResponse) {
+        let mut builder = TestCheckpointDataBuilder::new(1);
+        for sender in senders {
+            builder = builder
+                .start_transaction(*sender)
+                .with_events(vec![event("0x42::m::A"), event("0x42::m::B")])
+                .finish_transaction();
+        }
+
+        let data = builder.build_checkpoint();
+        let response =
+            checkpoint_data_to_full_checkpoint_response(&data, &FieldMaskTree::new_wildcard())
+                .unwrap();
+
+        (data, response)
+    }
+
+    /// The names of the types of the events in `transaction`'s `events` and `events_bcs`.
+    fn event_names(transaction: &FullCheckpointTransaction) -> (Vec<String>, Vec<String>) {
+        let events = transaction
+            .events
+            .as_ref()
+            .unwrap()
+            .events
+            .iter()
+            .map(|e| {
+                let name = e.event_type.as_ref().unwrap().name.as_ref().unwrap();
+                name.identifier.clone().unwrap()
+            })
+            .collect();
+
+        let events_bcs: TransactionEvents = transaction
+            .events_bcs
+            .as_ref()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        let events_bcs = events_bcs
+            .data
+            .iter()
+            .map(|e| e.type_.name.to_string())
+            .collect();
+
+        (events, events_bcs)
+    }
+
+    fn names(names: &[&str]) -> (Vec<String>, Vec<String>) {
+        let names: Vec<_> = names.iter().map(|n| n.to_string()).collect();
+        (names.clone(), names)
+    }
+
+    #[test]
+    fn event_types_trim_events_and_events_bcs() {
+        let (data, mut response) = checkpoint(&[0]);
+        filter(vec![], &["0x42::m::A"]).apply(&data, &mut response);
+
+        let [transaction] = &response.transactions[..] else {
+            panic!(
+                "Expected one transaction, got {}",
+                response.transactions.len()
+            );
+        };
+
+        assert_eq!(event_names(transaction), names(&["A"]));
+    }
+
+    #[test]
+    fn event_types_dont_trim_transactions_selected_otherwise() {
+        let (data, mut response) = checkpoint(&[0, 1]);
+        let address = TestCheckpointDataBuilder::derive_address(1);
+        filter(vec![address], &["0x42::m::A"]).apply(&data, &mut response);
+
+        let [by_event, by_address] = &response.transactions[..] else {
+            panic!(
+                "Expected two transactions, got {}",
+                response.transactions.len()
+            );
+        };
+
+        assert_eq!(event_names(by_event), names(&["A"]));
+        assert_eq!(event_names(by_address), names(&["A", "B"]));
+    }
+}
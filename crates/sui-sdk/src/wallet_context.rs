@@ -3,7 +3,7 @@
 
 use crate::sui_client_config::SuiClientConfig;
 use crate::SuiClient;
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use shared_crypto::intent::Intent;
 use std::collections::BTreeSet;
 use std::path::Path;
@@ -278,15 +278,16 @@ impl WalletContext {
         self.config.keystore.add_key(alias, keypair).unwrap();
     }
 
-    /// Sign a transaction with a key currently managed by the WalletContext
-    pub fn sign_transaction(&self, data: &TransactionData) -> Transaction {
+    /// Sign a transaction with a key currently managed by the WalletContext. Fails if the sender's
+    /// key is not in the keystore, or if the keystore is encrypted and has not been unlocked.
+    pub fn sign_transaction(&self, data: &TransactionData) -> Result<Transaction, anyhow::Error> {
         let sig = self
             .config
             .keystore
             .sign_secure(&data.sender(), data, Intent::sui_transaction())
-            .unwrap();
+            .with_context(|| format!("Failed to sign transaction from {}", data.sender()))?;
         // TODO: To support sponsored transaction, we should also look at the gas owner.
-        Transaction::from_data(data.clone(), vec![sig])
+        Ok(Transaction::from_data(data.clone(), vec![sig]))
     }
 
     /// Execute a transaction and wait for it to be locally executed on the fullnode.
@@ -878,7 +878,7 @@ pub async fn upgrade_package_with_wallet(
             .await
             .unwrap();
 
-        context.sign_transaction(&data)
+        context.sign_transaction(&data).unwrap()
     };
 
     let resp = context.execute_transaction_must_succeed(transaction).await;
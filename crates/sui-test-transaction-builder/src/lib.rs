@@ -484,7 +484,7 @@ pub async fn batch_make_transfer_transactions(
                 gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
                 gas_price,
             );
-            let tx = context.sign_transaction(&data);
+            let tx = context.sign_transaction(&data).unwrap();
             res.push(tx);
         }
     }
@@ -498,11 +498,13 @@ pub async fn make_transfer_sui_transaction(
 ) -> Transaction {
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .transfer_sui(amount, recipient.unwrap_or(sender))
-            .build(),
-    )
+    context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .transfer_sui(amount, recipient.unwrap_or(sender))
+                .build(),
+        )
+        .unwrap()
 }
 
 pub async fn make_staking_transaction(
@@ -514,21 +516,25 @@ pub async fn make_staking_transaction(
     let gas_object = accounts_and_objs[0].1[0];
     let stake_object = accounts_and_objs[0].1[1];
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .call_staking(stake_object, validator_address)
-            .build(),
-    )
+    context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .call_staking(stake_object, validator_address)
+                .build(),
+        )
+        .unwrap()
 }
 
 pub async fn make_publish_transaction(context: &WalletContext, path: PathBuf) -> Transaction {
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .publish(path)
-            .build(),
-    )
+    context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish(path)
+                .build(),
+        )
+        .unwrap()
 }
 
 pub async fn make_publish_transaction_with_deps(
@@ -537,21 +543,25 @@ pub async fn make_publish_transaction_with_deps(
 ) -> Transaction {
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .publish_with_deps(path)
-            .build(),
-    )
+    context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish_with_deps(path)
+                .build(),
+        )
+        .unwrap()
 }
 
 pub async fn publish_package(context: &WalletContext, path: PathBuf) -> ObjectRef {
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .publish(path)
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish(path)
+                .build(),
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
     get_new_package_obj_from_response(&resp).unwrap()
 }
@@ -560,11 +570,13 @@ pub async fn publish_package(context: &WalletContext, path: PathBuf) -> ObjectRe
 pub async fn publish_basics_package(context: &WalletContext) -> ObjectRef {
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .publish_examples("basics")
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish_examples("basics")
+                .build(),
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
     get_new_package_obj_from_response(&resp).unwrap()
 }
@@ -577,11 +589,13 @@ pub async fn publish_basics_package_and_make_counter(
     let package_ref = publish_basics_package(context).await;
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    let counter_creation_txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .call_counter_create(package_ref.0)
-            .build(),
-    );
+    let counter_creation_txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .call_counter_create(package_ref.0)
+                .build(),
+        )
+        .unwrap();
     let resp = context
         .execute_transaction_must_succeed(counter_creation_txn)
         .await;
@@ -617,11 +631,13 @@ pub async fn increment_counter(
             .unwrap()
     };
     let rgp = context.get_reference_gas_price().await.unwrap();
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, rgp)
-            .call_counter_increment(package_id, counter_id, initial_shared_version)
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, rgp)
+                .call_counter_increment(package_id, counter_id, initial_shared_version)
+                .build(),
+        )
+        .unwrap();
     context.execute_transaction_must_succeed(txn).await
 }
 
@@ -660,11 +676,13 @@ pub async fn emit_new_random_u128(
         mutable: false,
     });
 
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, rgp)
-            .move_call(package_id, "random", "new", vec![random_call_arg])
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, rgp)
+                .move_call(package_id, "random", "new", vec![random_call_arg])
+                .build(),
+        )
+        .unwrap();
     context.execute_transaction_must_succeed(txn).await
 }
 
@@ -676,11 +694,13 @@ pub async fn publish_nfts_package(
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let gas_id = gas_object.0;
     let gas_price = context.get_reference_gas_price().await.unwrap();
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, gas_price)
-            .publish_examples("nft")
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish_examples("nft")
+                .build(),
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
     let package_id = get_new_package_obj_from_response(&resp).unwrap().0;
     (package_id, gas_id, resp.digest)
@@ -696,11 +716,13 @@ pub async fn create_nft(
     let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
     let rgp = context.get_reference_gas_price().await.unwrap();
 
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas_object, rgp)
-            .call_nft_create(package_id)
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, rgp)
+                .call_nft_create(package_id)
+                .build(),
+        )
+        .unwrap();
     let resp = context.execute_transaction_must_succeed(txn).await;
 
     let object_id = resp
@@ -729,10 +751,12 @@ pub async fn delete_nft(
         .unwrap()
         .unwrap_or_else(|| panic!("Expect {sender} to have at least one gas object"));
     let rgp = context.get_reference_gas_price().await.unwrap();
-    let txn = context.sign_transaction(
-        &TestTransactionBuilder::new(sender, gas, rgp)
-            .call_nft_delete(package_id, nft_to_delete)
-            .build(),
-    );
+    let txn = context
+        .sign_transaction(
+            &TestTransactionBuilder::new(sender, gas, rgp)
+                .call_nft_delete(package_id, nft_to_delete)
+                .build(),
+        )
+        .unwrap();
     context.execute_transaction_must_succeed(txn).await
 }
@@ -623,6 +623,15 @@ pub trait RpcIndexes: Send + Sync {
         cursor: Option<ObjectID>,
     ) -> Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>>;
 
+    /// Iterate over the coins of type `coin_type` owned by `owner`, in object ID order, starting
+    /// from the coin `cursor` (inclusive) if provided.
+    fn owned_coins_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &TypeTag,
+        cursor: Option<ObjectID>,
+    ) -> Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>>;
+
     fn dynamic_field_iter(
         &self,
         parent: ObjectID,
@@ -630,6 +639,24 @@ pub trait RpcIndexes: Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    fn get_balance(&self, owner: &SuiAddress, coin_type: &TypeTag) -> Result<Option<BalanceInfo>>;
+
+    /// Iterate over the balances of every coin type owned by `owner`, starting from the coin type
+    /// `cursor` (inclusive) if provided.
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<Box<dyn Iterator<Item = (TypeTag, BalanceInfo)> + '_>>;
+
+    /// Iterate over the transactions matching `from.kind`, in execution order, starting at `from`
+    /// (inclusive) and ending with the last matching transaction in `to_checkpoint` (inclusive).
+    fn transactions_iter(
+        &self,
+        from: TransactionIndexKey,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Box<dyn Iterator<Item = (TransactionIndexKey, TransactionDigest)> + '_>>;
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -637,6 +664,8 @@ pub struct AccountOwnedObjectInfo {
     pub object_id: ObjectID,
     pub version: SequenceNumber,
     pub type_: MoveObjectType,
+    /// The balance of the object, if it is a `0x2::coin::Coin<T>`.
+    pub balance: Option<u64>,
 }
 
 #[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -670,6 +699,34 @@ pub struct DynamicFieldIndexInfo {
     pub dynamic_object_id: Option<ObjectID>,
 }
 
+/// The total balance of, and number of, the coins of a single type owned by an address.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub struct BalanceInfo {
+    pub balance: u64,
+    pub coin_count: u64,
+}
+
+/// A property of a transaction that transactions can be looked up by.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TransactionIndexKind {
+    /// Transactions sent by this address.
+    Sender(SuiAddress),
+    /// Transactions sent by this address, or that modified or created an object owned by it.
+    AffectedAddress(SuiAddress),
+    /// Transactions with a `MoveCall` command calling a function in this package.
+    MoveCallPackage(ObjectID),
+    /// Transactions that emitted an event whose type is defined in this package.
+    EventPackage(ObjectID),
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct TransactionIndexKey {
+    pub kind: TransactionIndexKind,
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Position of the transaction within its checkpoint.
+    pub transaction_index: u64,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
 pub struct CoinInfo {
     pub coin_metadata_object_id: Option<ObjectID>,
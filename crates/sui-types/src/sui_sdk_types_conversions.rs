@@ -84,6 +84,7 @@ bcs_convert_impl!(
 );
 bcs_convert_impl!(crate::signature::GenericSignature, UserSignature);
 bcs_convert_impl!(crate::effects::TransactionEvents, TransactionEvents);
+bcs_convert_impl!(crate::event::Event, Event);
 bcs_convert_impl!(crate::transaction::Command, Command);
 
 impl<const T: bool> From<crate::crypto::AuthorityQuorumSignInfo<T>>
@@ -6,6 +6,8 @@ use crate::{
     client_ptb::ptb::PTB,
     displays::Pretty,
     key_identity::{get_identity_address, KeyIdentity},
+    keytool::unlock_keystore,
+    multisig_commands::{MultiSigCommand, MultiSigProposalStatus},
     upgrade_compatibility::check_compatibility,
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
@@ -240,6 +242,14 @@ pub enum SuiClientCommands {
         opts: OptsWithGas,
     },
 
+    /// Propose, sign and execute transactions from a MultiSig address. Create the proposal from
+    /// the output of any client command run with --serialize-unsigned-transaction.
+    #[clap(name = "multisig")]
+    MultiSig {
+        #[clap(subcommand)]
+        cmd: MultiSigCommand,
+    },
+
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word length can be
@@ -1470,6 +1480,7 @@ impl SuiClientCommands {
                 derivation_path,
                 word_length,
             } => {
+                unlock_keystore(&mut context.config.keystore)?;
                 let (address, phrase, scheme) = context.config.keystore.generate_and_add_new_key(
                     key_scheme,
                     alias.clone(),
@@ -1720,6 +1731,7 @@ impl SuiClientCommands {
                 ptb.execute(context).await?;
                 SuiClientCommandResult::NoOutput
             }
+            SuiClientCommands::MultiSig { cmd } => cmd.execute(context).await?,
         };
         Ok(ret.prerender_clever_errors(context).await)
     }
@@ -2176,6 +2188,9 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
+            SuiClientCommandResult::MultiSigProposal(status) => {
+                write!(writer, "{}", status)?;
+            }
             SuiClientCommandResult::ChainIdentifier(ci) => {
                 writeln!(writer, "{}", ci)?;
             }
@@ -2413,6 +2428,7 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::DevInspect(_)
             | SuiClientCommandResult::Envs(_, _)
             | SuiClientCommandResult::Gas(_)
+            | SuiClientCommandResult::MultiSigProposal(_)
             | SuiClientCommandResult::NewAddress(_)
             | SuiClientCommandResult::NewEnv(_)
             | SuiClientCommandResult::NoOutput
@@ -2563,6 +2579,7 @@ pub enum SuiClientCommandResult {
     DevInspect(DevInspectResults),
     Envs(Vec<SuiEnv>, Option<String>),
     Gas(Vec<GasCoin>),
+    MultiSigProposal(MultiSigProposalStatus),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
     NoOutput,
@@ -2986,6 +3003,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             tx_data,
         ))
     } else {
+        unlock_keystore(&mut context.config.keystore)?;
         let signature = context.config.keystore.sign_secure(
             &tx_data.sender(),
             &tx_data,
@@ -56,6 +56,10 @@ pub const SOME: &str = "some";
 pub const NONE: &str = "none";
 pub const GAS: &str = "gas";
 
+// Object annotations
+pub const RECEIVING: &str = "receiving";
+pub const IMMUTABLE: &str = "imm";
+
 pub const KEYWORDS: &[&str] = &[
     ADDRESS, BOOL, VECTOR, SOME, NONE, GAS, U8, U16, U32, U64, U128, U256,
 ];
@@ -163,6 +167,23 @@ pub enum Argument {
     String(String),
     Vector(Vec<Spanned<Argument>>),
     Option(Spanned<Option<Box<Argument>>>),
+    Object(ObjectInput),
+}
+
+/// An object ID, annotated with how it should be passed as an input to the transaction, overriding
+/// what would otherwise be inferred from the object's owner and the parameter it is passed to.
+///
+/// Only the version of an object can be pinned: its digest is always the one reported for that
+/// version by the RPC node.
+#[derive(Debug, Clone)]
+pub struct ObjectInput {
+    pub id: NumericalAddress,
+    /// Pass the object at this version, rather than its latest version (`@0x..#version`).
+    pub version: Option<u64>,
+    /// Pass a shared object by immutable reference (`@0x..:imm`).
+    pub immutable: bool,
+    /// Pass an address-owned object as a `Receiving<T>` argument (`receiving(@0x..)`).
+    pub receiving: bool,
 }
 
 impl Argument {
@@ -249,7 +270,13 @@ impl Argument {
                     MoveValue::Vector(vec![])
                 }
             }
-            (Argument::Identifier(_) | Argument::VariableAccess(_, _) | Argument::Gas, _) => {
+            (
+                Argument::Identifier(_)
+                | Argument::VariableAccess(_, _)
+                | Argument::Gas
+                | Argument::Object(_),
+                _,
+            ) => {
                 error!(loc, "Unable to convert '{self}' to non-object value.")
             }
             (arg, tag) => error!(loc, "Unable to serialize '{arg}' as a {tag} value"),
@@ -293,7 +320,10 @@ impl Argument {
                     MoveValue::Vector(vec![])
                 }
             }
-            Argument::Identifier(_) | Argument::VariableAccess(_, _) | Argument::Gas => {
+            Argument::Identifier(_)
+            | Argument::VariableAccess(_, _)
+            | Argument::Gas
+            | Argument::Object(_) => {
                 error!(loc, "Unable to convert '{self}' to non-object value.")
             }
         })
@@ -362,7 +392,32 @@ impl fmt::Display for Argument {
                 Some(v) => write!(f, "some({v})"),
                 None => write!(f, "none"),
             },
+            Argument::Object(o) => write!(f, "{o}"),
+        }
+    }
+}
+
+impl fmt::Display for ObjectInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.receiving {
+            write!(f, "{RECEIVING}(")?;
         }
+
+        write!(f, "@{}", self.id)?;
+
+        if let Some(version) = self.version {
+            write!(f, "#{version}")?;
+        }
+
+        if self.immutable {
+            write!(f, ":{IMMUTABLE}")?;
+        }
+
+        if self.receiving {
+            write!(f, ")")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -4,7 +4,7 @@
 use crate::{
     client_commands::{compile_package, upgrade_package},
     client_ptb::{
-        ast::{Argument as PTBArg, ASSIGN, GAS_BUDGET},
+        ast::{Argument as PTBArg, ObjectInput, ASSIGN, GAS_BUDGET, IMMUTABLE, RECEIVING},
         error::{PTBError, PTBResult, Span, Spanned},
     },
     err, error, sp,
@@ -30,7 +30,7 @@ use sui_json_rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiRawData};
 use sui_move::manage_package::resolve_lock_file_path;
 use sui_sdk::apis::ReadApi;
 use sui_types::{
-    base_types::{is_primitive_type_tag, ObjectID, TxContext, TxContextKind},
+    base_types::{is_primitive_type_tag, ObjectID, SequenceNumber, TxContext, TxContextKind},
     move_package::MovePackage,
     object::Owner,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
@@ -75,6 +75,17 @@ trait Resolver<'a>: Send {
         obj_id: ObjectID,
     ) -> PTBResult<Tx::Argument>;
 
+    /// Resolve an object ID that has been annotated with how it should be passed as an input.
+    /// Annotations only make sense for object arguments, so by default this is an error.
+    async fn resolve_object_input(
+        &mut self,
+        _builder: &mut PTBBuilder<'a>,
+        loc: Span,
+        input: ObjectInput,
+    ) -> PTBResult<Tx::Argument> {
+        error!(loc, "Unable to convert '{input}' to non-object value.")
+    }
+
     fn re_resolve(&self) -> bool {
         false
     }
@@ -105,28 +116,46 @@ impl ToObject {
             is_mut,
         }
     }
-}
 
-#[async_trait]
-impl<'a> Resolver<'a> for ToObject {
-    async fn resolve_object_id(
-        &mut self,
-        builder: &mut PTBBuilder<'a>,
+    /// Add `obj` to the transaction as an object input. `input` holds the annotations the object
+    /// was written with, if it had any, which are checked against the object's ownership.
+    fn object_arg(
+        &self,
+        builder: &mut PTBBuilder<'_>,
         loc: Span,
-        obj_id: ObjectID,
+        obj: SuiObjectData,
+        input: Option<&ObjectInput>,
     ) -> PTBResult<Tx::Argument> {
-        // Get the object from the reader to get metadata about the object.
-        let obj = builder.get_object(obj_id, loc).await?;
+        let obj_id = obj.object_id;
         let owner = obj
             .owner
             .clone()
             .ok_or_else(|| err!(loc, "Unable to get owner info for object {obj_id}"))?;
         let object_ref = obj.object_ref();
+        let receiving = input.is_some_and(|i| i.receiving);
+        let immutable = input.is_some_and(|i| i.immutable);
+        let pinned = input.is_some_and(|i| i.version.is_some());
+
         // Depending on the ownership of the object, we resolve it to different types of object
         // arguments for the transaction.
         let obj_arg = match owner {
             Owner::AddressOwner(_) if self.is_receiving => ObjectArg::Receiving(object_ref),
+            _ if receiving => {
+                error!(loc => help: {
+                    "Only address-owned objects can be passed as '{RECEIVING}' arguments."
+                }, "Cannot receive object {obj_id}")
+            }
+            Owner::AddressOwner(_) if immutable => {
+                error!(loc => help: {
+                    "':{IMMUTABLE}' can only be used to pass shared objects immutably."
+                }, "Cannot pass address-owned object {obj_id} immutably")
+            }
             Owner::Immutable | Owner::AddressOwner(_) => ObjectArg::ImmOrOwnedObject(object_ref),
+            Owner::Shared { .. } | Owner::ConsensusV2 { .. } if pinned => {
+                error!(loc => help: {
+                    "Shared objects are always passed at their latest version."
+                }, "Cannot pass shared object {obj_id} at a specific version")
+            }
             Owner::Shared {
                 initial_shared_version,
             }
@@ -147,6 +176,50 @@ impl<'a> Resolver<'a> for ToObject {
         // Insert the correct object arg that we built above into the transaction.
         builder.ptb.obj(obj_arg).map_err(|e| err!(loc, "{e}"))
     }
+}
+
+#[async_trait]
+impl<'a> Resolver<'a> for ToObject {
+    async fn resolve_object_id(
+        &mut self,
+        builder: &mut PTBBuilder<'a>,
+        loc: Span,
+        obj_id: ObjectID,
+    ) -> PTBResult<Tx::Argument> {
+        // Get the object from the reader to get metadata about the object.
+        let obj = builder.get_object(obj_id, loc).await?;
+        self.object_arg(builder, loc, obj, None)
+    }
+
+    async fn resolve_object_input(
+        &mut self,
+        builder: &mut PTBBuilder<'a>,
+        loc: Span,
+        input: ObjectInput,
+    ) -> PTBResult<Tx::Argument> {
+        // Objects taken by mutable reference or by value must be passed mutably, so an object
+        // that has been annotated as immutable can't be passed to them.
+        if input.immutable && self.is_mut {
+            error!(loc => help: {
+                "':{IMMUTABLE}' objects can only be passed to parameters taken by immutable \
+                 reference."
+            }, "Cannot pass '{input}' mutably")
+        }
+
+        let obj_id = ObjectID::from_address(input.id.into_inner());
+        let obj = match input.version {
+            Some(version) => {
+                let version = SequenceNumber::from_u64(version);
+                builder.get_past_object(obj_id, version, loc).await?
+            }
+            None => builder.get_object(obj_id, loc).await?,
+        };
+
+        // Annotations override what was inferred from the parameter the object is passed to.
+        let ctx = ToObject::new(self.is_receiving || input.receiving, self.is_mut);
+
+        ctx.object_arg(builder, loc, obj, Some(&input))
+    }
 
     // We always re-resolve object IDs to object arguments if we need it mutably -- we could have
     // added it earlier as an immutable argument.
@@ -688,6 +761,7 @@ impl<'a> PTBBuilder<'a> {
                 let object_id = ObjectID::from_address(addr.into_inner());
                 ctx.resolve_object_id(self, arg_loc, object_id).await
             }
+            PTBArg::Object(input) => ctx.resolve_object_input(self, arg_loc, input).await,
             PTBArg::VariableAccess(head, fields) => {
                 // Since keystore aliases can contain dots, we need to resolve these/disambiguate
                 // them as best as possible here.
@@ -775,6 +849,28 @@ impl<'a> PTBBuilder<'a> {
         Ok(res)
     }
 
+    /// Fetch the `SuiObjectData` for an object ID at a specific version -- this is used for
+    /// resolving objects whose version has been pinned.
+    async fn get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        obj_loc: Span,
+    ) -> PTBResult<SuiObjectData> {
+        let res = self
+            .reader
+            .try_get_parsed_past_object(
+                object_id,
+                version,
+                SuiObjectDataOptions::new().with_type().with_owner(),
+            )
+            .await
+            .map_err(|e| err!(obj_loc, "{e}"))?
+            .into_object()
+            .map_err(|e| err!(obj_loc, "{e}"))?;
+        Ok(res)
+    }
+
     /// Create a "did you mean" message for an identifier with the context of our different binding
     /// environments.
     fn did_you_mean_identifier(&self, ident: &str) -> Option<String> {
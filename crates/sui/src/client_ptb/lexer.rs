@@ -236,9 +236,11 @@ impl<'l, I: Iterator<Item = &'l str>> Iterator for Lexer<'l, I> {
 
             sp!(_, "'" | "\"") => self.string(c),
 
+            sp!(_, "#") => token!(T::Hash),
+
             sp!(_, ":") => 'colon: {
                 let Some(sp) = self.eat_prefix("::") else {
-                    break 'colon self.unexpected(c);
+                    break 'colon token!(T::Colon);
                 };
 
                 sp.map(|src| Lexeme(T::ColonColon, src))
@@ -475,9 +477,15 @@ mod tests {
     }
 
     #[test]
-    fn unexpected_colon() {
-        let unexpected = vec!["hello: world"];
-        insta::assert_debug_snapshot!(lex(unexpected));
+    fn tokenize_colon() {
+        let colon = vec!["hello: world"];
+        insta::assert_debug_snapshot!(lex(colon));
+    }
+
+    #[test]
+    fn tokenize_object_annotations() {
+        let objects = vec!["@0x1#42:imm"];
+        insta::assert_debug_snapshot!(lex(objects));
     }
 
     #[test]
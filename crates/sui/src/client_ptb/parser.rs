@@ -19,7 +19,9 @@ use crate::{
 };
 
 use super::{
-    ast::{self as A, is_keyword, Argument, ModuleAccess, ParsedPTBCommand, ParsedProgram},
+    ast::{
+        self as A, is_keyword, Argument, ModuleAccess, ObjectInput, ParsedPTBCommand, ParsedProgram,
+    },
     error::{PTBError, PTBResult, Span, Spanned},
     lexer::Lexer,
     token::{Lexeme, Token},
@@ -416,7 +418,28 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 self.parse_number(sp.wrap(&number))?
             }
 
-            L(T::At, _) => self.parse_address_literal()?.map(V::Address),
+            L(T::At, _) => self.parse_object(false)?,
+
+            L(T::Ident, A::RECEIVING) => {
+                self.bump();
+
+                // `receiving` is only an annotation when it is applied to an object ID, otherwise
+                // it is treated as a regular variable.
+                let sp!(_, L(T::LParen, _)) = self.peek() else {
+                    return self.parse_fields(sp.wrap(A::RECEIVING.to_owned()));
+                };
+
+                self.bump();
+                let sp!(_, object) = self.parse_object(true).map_err(|e| {
+                    e.with_help(format!(
+                        "Only object IDs can be received, e.g., {}(@0x1)",
+                        A::RECEIVING,
+                    ))
+                })?;
+
+                let sp!(end_sp, _) = self.expect(T::RParen)?;
+                sp.widen(end_sp).wrap(object)
+            }
 
             L(T::Ident, A::NONE) => {
                 self.bump();
@@ -578,8 +601,16 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         use Token as T;
 
         let sp!(start_sp, L(_, ident)) = self.expect(T::Ident)?;
-        let ident = start_sp.wrap(ident.to_owned());
+        self.parse_fields(start_sp.wrap(ident.to_owned()))
+    }
+
+    /// Parse the (possibly empty) chain of fields following the identifier `ident`, which has
+    /// already been consumed.
+    fn parse_fields(&mut self, ident: Spanned<String>) -> PTBResult<Spanned<Argument>> {
+        use Lexeme as L;
+        use Token as T;
 
+        let start_sp = ident.span;
         let sp!(_, L(T::Dot, _)) = self.peek() else {
             return Ok(start_sp.wrap(Argument::Identifier(ident.value)));
         };
@@ -699,6 +730,78 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         })
     }
 
+    /// Parse an object ID literal, followed by optional annotations for how it should be passed
+    /// as an input: `#<version>` to pass it at a specific version, and `:imm` to pass a shared
+    /// object immutably. Object IDs without annotations (that are not being `receiving`) are
+    /// parsed as plain addresses.
+    fn parse_object(&mut self, receiving: bool) -> PTBResult<Spanned<Argument>> {
+        use Lexeme as L;
+        use Token as T;
+
+        let sp!(start_sp, id) = self.parse_address_literal()?;
+        let mut end_sp = start_sp;
+        let mut version = None;
+        let mut immutable = false;
+
+        loop {
+            match self.peek() {
+                sp!(_, L(T::Hash, _)) if version.is_none() => {
+                    self.bump();
+
+                    let sp!(sp, lexeme) = self.peek();
+                    let number = match lexeme {
+                        L(T::Number, number) => number.to_owned(),
+                        L(T::HexNumber, number) => format!("0x{number}"),
+                        unexpected => error!(
+                            sp => help: { "Object versions are numbers, e.g., @0x1#42" },
+                            "Unexpected {unexpected}",
+                        ),
+                    };
+
+                    self.bump();
+                    match parse_u64(&number) {
+                        Ok((v, _)) => version = Some(v),
+                        Err(e) => error!(sp, "Invalid object version: {e}"),
+                    }
+
+                    end_sp = sp;
+                }
+
+                sp!(_, L(T::Colon, _)) if !immutable => {
+                    self.bump();
+
+                    let sp!(sp, lexeme) = self.peek();
+                    let L(T::Ident, A::IMMUTABLE) = lexeme else {
+                        error!(
+                            sp => help: {
+                                "Use ':{}' to pass a shared object immutably", A::IMMUTABLE
+                            },
+                            "Unexpected {lexeme}",
+                        );
+                    };
+
+                    self.bump();
+                    immutable = true;
+                    end_sp = sp;
+                }
+
+                _ => break,
+            }
+        }
+
+        let sp = start_sp.widen(end_sp);
+        Ok(if version.is_none() && !immutable && !receiving {
+            sp.wrap(Argument::Address(id))
+        } else {
+            sp.wrap(Argument::Object(ObjectInput {
+                id,
+                version,
+                immutable,
+                receiving,
+            }))
+        })
+    }
+
     // Parse an array of arguments. Each element of the array is separated by a comma.
     fn parse_array(&mut self) -> PTBResult<Spanned<Vec<Spanned<Argument>>>> {
         use Lexeme as L;
@@ -827,6 +930,50 @@ mod tests {
         insta::assert_debug_snapshot!(parsed);
     }
 
+    #[test]
+    fn test_parse_object_annotations() {
+        let parse = |input: &str| {
+            let x = shlex::split(input).unwrap();
+            let mut parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+            parser.parse_argument().map(|sp!(_, arg)| arg)
+        };
+
+        let object = |input: &str| match parse(input) {
+            Ok(Argument::Object(o)) => (o.version, o.immutable, o.receiving),
+            other => panic!("Expected an annotated object for {input:?}, got: {other:?}"),
+        };
+
+        assert_eq!(object("@0x1:imm"), (None, true, false));
+        assert_eq!(object("@0x1#42"), (Some(42), false, false));
+        assert_eq!(object("@0x1#0x2a:imm"), (Some(42), true, false));
+        assert_eq!(object("@0x1:imm#42"), (Some(42), true, false));
+        assert_eq!(object("receiving(@0x1)"), (None, false, true));
+        assert_eq!(object("receiving(@0x1#7)"), (Some(7), false, true));
+
+        // Unannotated object IDs are still plain addresses, and `receiving` is only an annotation
+        // when it is applied to an object ID.
+        assert!(matches!(parse("@0x1"), Ok(Argument::Address(_))));
+        assert!(matches!(parse("receiving"), Ok(Argument::Identifier(i)) if i == "receiving"));
+        assert!(matches!(
+            parse("receiving.0"),
+            Ok(Argument::VariableAccess(_, fields)) if fields.len() == 1,
+        ));
+
+        for invalid in [
+            "@0x1:mut",
+            "@0x1#",
+            "@0x1#foo",
+            "receiving(foo)",
+            "receiving(@0x1",
+        ] {
+            let x = shlex::split(invalid).unwrap();
+            let result = ProgramParser::new(x.iter().map(|x| x.as_str()))
+                .unwrap()
+                .parse_argument();
+            assert!(result.is_err(), "Expected {invalid:?} to fail to parse");
+        }
+    }
+
     #[test]
     fn test_parse_args_invalid() {
         let inputs = vec![
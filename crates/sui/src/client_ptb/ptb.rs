@@ -7,6 +7,7 @@ use crate::{
         ast::{ParsedProgram, Program},
         builder::PTBBuilder,
         error::{build_error_reports, PTBError},
+        script::expand_scripts,
         token::{Lexeme, Token},
     },
     displays::Pretty,
@@ -53,10 +54,13 @@ impl PTB {
             ptb_description().print_help().unwrap();
             return Ok(());
         }
-        let source_string = to_source_string(self.args.clone());
+
+        // Expand any scripts into the commands they contain, before tokenizing.
+        let args = expand_scripts(self.args)?;
+        let source_string = to_source_string(args.clone());
 
         // Tokenize once to detect help flags
-        let tokens = self.args.iter().map(|s| s.as_str());
+        let tokens = args.iter().map(|s| s.as_str());
         for sp!(_, lexeme) in Lexer::new(tokens.clone()).into_iter().flatten() {
             match lexeme {
                 Lexeme(Token::Command, "help") => return Ok(ptb_description().print_long_help()?),
@@ -397,6 +401,34 @@ pub fn ptb_description() -> clap::Command {
             --"preview"
             "Preview the list of PTB transactions instead of executing them."
         ))
+        .arg(arg!(
+            --"script" <PATH>
+            "Run the PTB commands in a script file, in place of this argument."
+        ).long_help(
+            "Run the PTB commands in a script file, in place of this argument. Scripts contain \
+            PTB commands written as they would be on the command line, and can also contain:\
+            \n - comments, starting with #,\
+            \n - parameters, declared with `param NAME [DEFAULT]`, set with --param, and \
+            referred to as $NAME or ${NAME},\
+            \n - loops, `for NAME in [VALUES] { ... }` or `for NAME in LO..HI { ... }`, that \
+            repeat their body for each value, bound to $NAME,\
+            \n - `include PATH`, to run the commands of another script (relative to this one).\
+            \nDirectives must be the first thing on their line.\
+            \n\nExamples:\
+            \n --script airdrop.ptb --param recipients \"[@0x1, @0x2]\" --gas-budget 10000000\
+            \n\nWhere airdrop.ptb contains:\
+            \n param amount 1000\
+            \n param recipients\
+            \n for r in $recipients {\
+            \n   --split-coins gas [$amount]\
+            \n   --assign coin\
+            \n   --transfer-objects [coin] $r\
+            \n }"
+        ).value_hint(ValueHint::FilePath))
+        .arg(arg!(
+            --"param" <PARAM>
+            "Set the value of a parameter declared by a script passed with --script."
+        ).value_names(["NAME", "VALUE"]))
         .arg(arg!(
             --"serialize-unsigned-transaction"
             "Instead of executing the transaction, serialize the bcs bytes of the unsigned \
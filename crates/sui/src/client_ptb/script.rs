@@ -0,0 +1,559 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for PTB script files (conventionally with a `.ptb` extension), passed to `sui client
+//! ptb` with `--script <PATH>`.
+//!
+//! A script is a sequence of PTB commands, written the same way as on the command line, with a
+//! few additions that are all resolved before the commands are parsed, so that a script lowers to
+//! exactly the shell tokens that could have been passed to `sui client ptb` directly:
+//!
+//! - Comments, starting with `#` and running to the end of the line.
+//! - Parameters, declared with `param <NAME> [DEFAULT]`, whose values are passed on the command
+//!   line with `--param <NAME> <VALUE>`, and referred to as `$NAME` or `${NAME}` (`$$` is a
+//!   literal `$`).
+//! - Loops, `for <NAME> in <VALUES> { ... }`, that repeat their body once for each element of a
+//!   list (`[@0x1, @0x2]`) or range (`0..3`), binding it to `$NAME`.
+//! - Includes, `include <PATH>`, that splice in the commands from another script, resolved
+//!   relative to the including script.
+//!
+//! Directives (`param`, `for`, `include` and the closing `}` of a loop) must be the first token on
+//! their line. Every other line is split into shell tokens and forwarded as-is, after parameters
+//! have been substituted, so commands can span multiple lines.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+
+pub const SCRIPT: &str = "--script";
+pub const PARAM: &str = "--param";
+
+const PARAM_DIRECTIVE: &str = "param";
+const INCLUDE_DIRECTIVE: &str = "include";
+const FOR_DIRECTIVE: &str = "for";
+const END_DIRECTIVE: &str = "}";
+
+/// A line from a script, after it has been split into shell tokens.
+#[derive(Debug)]
+struct Line {
+    number: usize,
+    item: Item,
+}
+
+#[derive(Debug)]
+enum Item {
+    /// Shell tokens to forward to the PTB parser, once parameters have been substituted.
+    Tokens(Vec<String>),
+
+    /// `param <NAME> [DEFAULT]`
+    Param {
+        name: String,
+        default: Option<String>,
+    },
+
+    /// `include <PATH>`
+    Include(String),
+
+    /// `for <NAME> in <VALUES> { <BODY> }`
+    For {
+        name: String,
+        values: Vec<String>,
+        body: Vec<Line>,
+    },
+}
+
+/// State accumulated while expanding scripts.
+struct Expander {
+    /// Parameter values passed on the command line.
+    args: BTreeMap<String, String>,
+
+    /// Values of all the parameters declared so far.
+    params: BTreeMap<String, String>,
+
+    /// Values bound by the loops currently being expanded, which shadow parameters.
+    locals: BTreeMap<String, String>,
+
+    /// The scripts currently being expanded, to detect cyclic includes.
+    stack: Vec<PathBuf>,
+
+    output: Vec<String>,
+}
+
+/// Expand every `--script <PATH>` in `args` into the shell tokens for the commands in that script,
+/// using the values for parameters passed in `args` as `--param <NAME> <VALUE>`. All other
+/// arguments are left as they are.
+pub fn expand_scripts(args: Vec<String>) -> Result<Vec<String>> {
+    let mut params = BTreeMap::new();
+    let mut scripts = false;
+    let mut rest = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            SCRIPT => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| anyhow!("Expected a path to a PTB script after {SCRIPT}"))?;
+                scripts = true;
+                rest.push((true, path));
+            }
+
+            PARAM => {
+                let (Some(name), Some(value)) = (args.next(), args.next()) else {
+                    bail!("Expected a name and a value after {PARAM}");
+                };
+
+                ensure!(is_ident(&name), "Invalid parameter name '{name}'");
+                ensure!(
+                    params.insert(name.clone(), value).is_none(),
+                    "Parameter '{name}' passed more than once",
+                );
+            }
+
+            _ => rest.push((false, arg)),
+        }
+    }
+
+    if !scripts {
+        ensure!(
+            params.is_empty(),
+            "{PARAM} can only be used together with {SCRIPT}"
+        );
+        return Ok(rest.into_iter().map(|(_, arg)| arg).collect());
+    }
+
+    let mut expander = Expander {
+        args: params,
+        params: BTreeMap::new(),
+        locals: BTreeMap::new(),
+        stack: vec![],
+        output: vec![],
+    };
+
+    for (is_script, arg) in rest {
+        if is_script {
+            expander.include(Path::new(&arg))?;
+        } else {
+            expander.output.push(arg);
+        }
+    }
+
+    if let Some(name) = expander
+        .args
+        .keys()
+        .find(|name| !expander.params.contains_key(*name))
+    {
+        bail!("Unknown parameter '{name}', it is not declared by any script");
+    }
+
+    Ok(expander.output)
+}
+
+impl Expander {
+    /// Expand the script at `path` into the output.
+    fn include(&mut self, path: &Path) -> Result<()> {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Cannot find PTB script {}", path.display()))?;
+
+        ensure!(
+            !self.stack.contains(&path),
+            "PTB script {} includes itself",
+            path.display(),
+        );
+
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read PTB script {}", path.display()))?;
+
+        let mut lines = source.lines().enumerate().map(|(i, l)| (i + 1, l));
+        let script = parse_block(&path, &mut lines, None)?;
+
+        self.stack.push(path);
+        self.expand(&script)?;
+        self.stack.pop();
+        Ok(())
+    }
+
+    fn expand(&mut self, lines: &[Line]) -> Result<()> {
+        for Line { number, item } in lines {
+            let file = self.current().display().to_string();
+            let at = |e: String| anyhow!("{file}:{number}: {e}");
+
+            match item {
+                Item::Tokens(tokens) => {
+                    for token in tokens {
+                        let token = self.substitute(token).map_err(at)?;
+                        self.output.push(token);
+                    }
+                }
+
+                Item::Param { name, default } => {
+                    if self.params.contains_key(name) {
+                        continue;
+                    }
+
+                    let value = match (self.args.get(name), default) {
+                        (Some(value), _) => value.clone(),
+                        (None, Some(default)) => self.substitute(default).map_err(at)?,
+                        (None, None) => {
+                            return Err(at(format!(
+                                "Missing value for parameter '{name}', \
+                                 pass it with {PARAM} {name} <VALUE>",
+                            )))
+                        }
+                    };
+
+                    self.params.insert(name.clone(), value);
+                }
+
+                Item::Include(path) => {
+                    let path = self.substitute(path).map_err(at)?;
+                    let dir = self.current().parent().unwrap_or(Path::new(""));
+                    let path = dir.join(path);
+                    self.include(&path).map_err(|e| at(format!("{e:#}")))?;
+                }
+
+                Item::For { name, values, body } => {
+                    let values = values
+                        .iter()
+                        .map(|v| self.substitute(v))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(at)?;
+                    let values = loop_values(&values.join(" ")).map_err(at)?;
+
+                    let shadowed = self.locals.remove(name);
+                    for value in values {
+                        self.locals.insert(name.clone(), value);
+                        self.expand(body)?;
+                    }
+
+                    self.locals.remove(name);
+                    if let Some(shadowed) = shadowed {
+                        self.locals.insert(name.clone(), shadowed);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace references to parameters and loop variables in `token` with their values.
+    fn substitute(&self, token: &str) -> Result<String, String> {
+        let mut output = String::new();
+        let mut rest = token;
+
+        while let Some(ix) = rest.find('$') {
+            output.push_str(&rest[..ix]);
+            rest = &rest[ix + 1..];
+
+            if let Some(after) = rest.strip_prefix('$') {
+                output.push('$');
+                rest = after;
+                continue;
+            }
+
+            let (name, after) = if let Some(braced) = rest.strip_prefix('{') {
+                let end = braced
+                    .find('}')
+                    .ok_or_else(|| format!("Unterminated '${{' in '{token}'"))?;
+                (&braced[..end], &braced[end + 1..])
+            } else {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            };
+
+            if !is_ident(name) {
+                return Err(format!("Expected a parameter name after '$' in '{token}'"));
+            }
+
+            let value = self
+                .locals
+                .get(name)
+                .or_else(|| self.params.get(name))
+                .ok_or_else(|| format!("Undefined parameter '${name}'"))?;
+
+            output.push_str(value);
+            rest = after;
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    fn current(&self) -> &Path {
+        self.stack.last().expect("Expanding outside of a script")
+    }
+}
+
+/// Parse lines from the script at `path` until the end of the enclosing block. If `open` is set,
+/// the block is the body of the loop starting on that line, which must be closed by a `}`,
+/// otherwise the block is the whole script.
+fn parse_block<'l>(
+    path: &Path,
+    lines: &mut impl Iterator<Item = (usize, &'l str)>,
+    open: Option<usize>,
+) -> Result<Vec<Line>> {
+    let mut block = vec![];
+    let at = |number: usize, msg: &str| anyhow!("{}:{number}: {msg}", path.display());
+
+    while let Some((number, line)) = lines.next() {
+        let tokens = shlex::split(line).ok_or_else(|| at(number, "Unterminated quote"))?;
+        let Some(first) = tokens.first() else {
+            continue;
+        };
+
+        let item = match first.as_str() {
+            END_DIRECTIVE => {
+                if tokens.len() != 1 {
+                    return Err(at(number, "Expected '}' on a line of its own"));
+                } else if open.is_none() {
+                    return Err(at(number, "Unexpected '}' outside of a loop"));
+                } else {
+                    return Ok(block);
+                }
+            }
+
+            PARAM_DIRECTIVE => match &tokens[1..] {
+                [name] if is_ident(name) => Item::Param {
+                    name: name.clone(),
+                    default: None,
+                },
+
+                [name, default] if is_ident(name) => Item::Param {
+                    name: name.clone(),
+                    default: Some(default.clone()),
+                },
+
+                _ => return Err(at(number, "Expected 'param <NAME> [DEFAULT]'")),
+            },
+
+            INCLUDE_DIRECTIVE => match &tokens[1..] {
+                [path] => Item::Include(path.clone()),
+                _ => return Err(at(number, "Expected 'include <PATH>'")),
+            },
+
+            FOR_DIRECTIVE => match &tokens[1..] {
+                [name, in_, values @ .., brace]
+                    if is_ident(name) && in_ == "in" && brace == "{" && !values.is_empty() =>
+                {
+                    Item::For {
+                        name: name.clone(),
+                        values: values.to_vec(),
+                        body: parse_block(path, lines, Some(number))?,
+                    }
+                }
+
+                _ => return Err(at(number, "Expected 'for <NAME> in <VALUES> {'")),
+            },
+
+            _ => Item::Tokens(tokens),
+        };
+
+        block.push(Line { number, item });
+    }
+
+    if let Some(open) = open {
+        return Err(at(open, "Loop is missing a closing '}'"));
+    }
+
+    Ok(block)
+}
+
+/// The values a loop iterates over: Either the elements of a list (`[a, b, c]`), or the numbers in
+/// a half-open range (`lo..hi`).
+fn loop_values(values: &str) -> Result<Vec<String>, String> {
+    let values = values.trim();
+
+    if let Some((lo, hi)) = values.split_once("..") {
+        if let (Ok(lo), Ok(hi)) = (lo.trim().parse::<u64>(), hi.trim().parse::<u64>()) {
+            return Ok((lo..hi).map(|i| i.to_string()).collect());
+        }
+    }
+
+    let Some(inner) = values.strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+        return Err(format!(
+            "Expected a list, like [a, b], or a range, like 0..3, to loop over, found '{values}'"
+        ));
+    };
+
+    // Split on the commas that are not nested inside another list, call, or string.
+    let mut elements = vec![];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (ix, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '(' | '<') => depth += 1,
+            (None, ']' | ')' | '>') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                elements.push(&inner[start..ix]);
+                start = ix + 1;
+            }
+            _ => {}
+        }
+    }
+    elements.push(&inner[start..]);
+
+    Ok(elements
+        .into_iter()
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        shlex::split(s).unwrap()
+    }
+
+    fn script(dir: &Path, name: &str, contents: &str) -> String {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_no_script() {
+        let input = args("--split-coins gas [1000] --assign c --gas-budget 1");
+        assert_eq!(expand_scripts(input.clone()).unwrap(), input);
+
+        let err = expand_scripts(args("--param x 1 --preview")).unwrap_err();
+        assert!(err.to_string().contains("--script"), "{err}");
+    }
+
+    #[test]
+    fn test_comments_and_params() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = script(
+            dir.path(),
+            "pay.ptb",
+            r#"
+            # Pay a recipient.
+            param amount 1000
+            param recipient
+
+            --split-coins gas [$amount] # the coin to send
+            --assign coin
+            --transfer-objects [coin] ${recipient}
+            --move-call pkg::m::f "$$not_a_param"
+            "#,
+        );
+
+        let expanded = expand_scripts(args(&format!(
+            "--script {path} --param recipient @0x1 --preview"
+        )))
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            args(
+                "--split-coins gas [1000] --assign coin --transfer-objects [coin] @0x1 \
+                 --move-call pkg::m::f '$not_a_param' --preview"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_loops() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = script(
+            dir.path(),
+            "airdrop.ptb",
+            r#"
+            param recipients
+            for r in $recipients {
+                for i in 0..2 {
+                    --split-coins gas [${i}00]
+                    --assign c
+                    --transfer-objects [c] $r
+                }
+            }
+            "#,
+        );
+
+        let expanded = expand_scripts(args(&format!(
+            "--script {path} --param recipients '[@0x1, @0x2]'"
+        )))
+        .unwrap();
+
+        let mut expected = vec![];
+        for r in ["@0x1", "@0x2"] {
+            for amount in ["000", "100"] {
+                expected.extend(args(&format!(
+                    "--split-coins gas [{amount}] --assign c --transfer-objects [c] {r}"
+                )));
+            }
+        }
+
+        assert_eq!(expanded, expected);
+    }
+
+    #[test]
+    fn test_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("lib")).unwrap();
+        script(
+            dir.path(),
+            "lib/send.ptb",
+            "--split-coins gas [$amount]\n--assign c\n--transfer-objects [c] $to",
+        );
+
+        let path = script(
+            dir.path(),
+            "main.ptb",
+            "param amount 5\nfor to in [@0x1] {\n  include lib/send.ptb\n}",
+        );
+
+        assert_eq!(
+            expand_scripts(args(&format!("--script {path}"))).unwrap(),
+            args("--split-coins gas [5] --assign c --transfer-objects [c] @0x1"),
+        );
+
+        let path = script(dir.path(), "cycle.ptb", "include cycle.ptb");
+        let err = expand_scripts(args(&format!("--script {path}"))).unwrap_err();
+        assert!(err.to_string().contains("includes itself"), "{err}");
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = script(dir.path(), "err.ptb", "param x\n--assign a $x");
+
+        let err = expand_scripts(args(&format!("--script {path}"))).unwrap_err();
+        assert!(err.to_string().contains(":1: Missing value"), "{err}");
+
+        let err =
+            expand_scripts(args(&format!("--script {path} --param x 1 --param y 2"))).unwrap_err();
+        assert!(err.to_string().contains("Unknown parameter 'y'"), "{err}");
+
+        let path = script(dir.path(), "undef.ptb", "\n--assign a $y");
+        let err = expand_scripts(args(&format!("--script {path}"))).unwrap_err();
+        assert!(err.to_string().contains(":2: Undefined parameter"), "{err}");
+
+        let path = script(dir.path(), "open.ptb", "for x in 0..3 {\n--assign a $x");
+        let err = expand_scripts(args(&format!("--script {path}"))).unwrap_err();
+        assert!(err.to_string().contains(":1: Loop is missing"), "{err}");
+    }
+}
@@ -27,6 +27,10 @@ pub enum Token {
     String,
     /// ::
     ColonColon,
+    /// :
+    Colon,
+    /// #
+    Hash,
     /// ,
     Comma,
     /// [
@@ -92,6 +96,8 @@ impl fmt::Display for Lexeme<'_> {
             T::HexNumber => write!(f, "hexadecimal number '0x{}'", self.1),
             T::String => write!(f, "string {:?}", self.1),
             T::ColonColon => write!(f, "'::'"),
+            T::Colon => write!(f, "':'"),
+            T::Hash => write!(f, "'#'"),
             T::Comma => write!(f, "','"),
             T::LBracket => write!(f, "'['"),
             T::RBracket => write!(f, "']'"),
@@ -121,6 +127,8 @@ impl fmt::Display for Token {
             T::HexNumber => write!(f, "a hexadecimal number"),
             T::String => write!(f, "a string"),
             T::ColonColon => write!(f, "'::'"),
+            T::Colon => write!(f, "':'"),
+            T::Hash => write!(f, "'#'"),
             T::Comma => write!(f, "','"),
             T::LBracket => write!(f, "'['"),
             T::RBracket => write!(f, "']'"),
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use aws_sdk_kms::{
     primitives::Blob,
     types::{MessageType, SigningAlgorithmSpec},
@@ -24,6 +24,7 @@ use fastcrypto_zkp::bn254::zk_login::{fetch_jwks, OIDCProvider, ZkLoginInputs};
 use fastcrypto_zkp::bn254::zk_login::{JwkId, JWK};
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use im::hashmap::HashMap as ImHashMap;
+use inquire::Password;
 use json_to_table::{json_to_table, Orientation};
 use num_bigint::BigUint;
 use rand::rngs::StdRng;
@@ -36,6 +37,8 @@ use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+use sui_keys::encryption::EncryptionParams;
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
@@ -60,10 +63,15 @@ use tabled::builder::Builder;
 use tabled::settings::Rotate;
 use tabled::settings::{object::Rows, Modify, Width};
 use tracing::info;
+use zeroize::Zeroizing;
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
 
+/// Environment variable to read the passphrase of an encrypted keystore from, instead of
+/// prompting for it.
+pub const SUI_KEYSTORE_PASSPHRASE: &str = "SUI_KEYSTORE_PASSPHRASE";
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 #[clap(rename_all = "kebab-case")]
@@ -102,6 +110,15 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Encrypt the private keys in the Sui CLI Keystore with a passphrase, in place. Aliases and
+    /// public keys stay readable, but commands that need a private key will ask for the
+    /// passphrase (or read it from the SUI_KEYSTORE_PASSPHRASE environment variable).
+    Encrypt {
+        /// How many seconds keys stay unlocked for after the passphrase has been entered, in
+        /// long-running sessions like `sui console`. Pass 0 to keep them unlocked until exit.
+        #[clap(long, default_value = "900")]
+        unlock_timeout: u64,
+    },
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -322,6 +339,13 @@ pub struct DecodedMultiSigOutput {
     sig_verify_result: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptOutput {
+    keystore_path: PathBuf,
+    unlock_timeout_secs: Option<u64>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DecodeOrVerifyTxOutput {
@@ -461,6 +485,7 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    Encrypt(EncryptOutput),
     Error(String),
     Generate(Key),
     Import(Key),
@@ -481,6 +506,10 @@ pub enum CommandOutput {
 
 impl KeyToolCommand {
     pub async fn execute(self, keystore: &mut Keystore) -> Result<CommandOutput, anyhow::Error> {
+        if self.requires_private_keys() {
+            unlock_keystore(keystore)?;
+        }
+
         let cmd_result = Ok(match self {
             KeyToolCommand::Alias {
                 old_alias,
@@ -587,6 +616,20 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::Encrypt { unlock_timeout } => {
+                let passphrase = read_new_passphrase()?;
+                let params = EncryptionParams {
+                    unlock_timeout: (unlock_timeout > 0)
+                        .then(|| Duration::from_secs(unlock_timeout)),
+                    ..Default::default()
+                };
+
+                let encrypted = keystore.encrypt(&passphrase, params)?;
+                CommandOutput::Encrypt(EncryptOutput {
+                    keystore_path: encrypted.path().to_path_buf(),
+                    unlock_timeout_secs: encrypted.unlock_timeout().map(|t| t.as_secs()),
+                })
+            }
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -1298,6 +1341,54 @@ impl KeyToolCommand {
 
         cmd_result
     }
+
+    /// Whether the command needs to read or add private keys, and so needs an encrypted
+    /// keystore to be unlocked first.
+    fn requires_private_keys(&self) -> bool {
+        matches!(
+            self,
+            KeyToolCommand::Export { .. }
+                | KeyToolCommand::Import { .. }
+                | KeyToolCommand::Sign { .. }
+                | KeyToolCommand::ZkLoginSignAndExecuteTx { .. }
+                | KeyToolCommand::ZkLoginEnterToken { .. }
+        )
+    }
+}
+
+/// Unlock `keystore` if it is encrypted and locked, reading the passphrase from the
+/// `SUI_KEYSTORE_PASSPHRASE` environment variable if it is set, and prompting for it otherwise.
+pub fn unlock_keystore(keystore: &mut Keystore) -> Result<(), anyhow::Error> {
+    if !keystore.is_locked() {
+        return Ok(());
+    }
+
+    let passphrase = Zeroizing::new(match std::env::var(SUI_KEYSTORE_PASSPHRASE) {
+        Ok(passphrase) => passphrase,
+        Err(_) => Password::new("Enter the keystore passphrase:")
+            .without_confirmation()
+            .prompt()?,
+    });
+
+    keystore.unlock(&passphrase)
+}
+
+/// Read a passphrase to encrypt a keystore with, from the `SUI_KEYSTORE_PASSPHRASE`
+/// environment variable if it is set, and by prompting for it (twice) otherwise.
+fn read_new_passphrase() -> Result<Zeroizing<String>, anyhow::Error> {
+    let passphrase = Zeroizing::new(match std::env::var(SUI_KEYSTORE_PASSPHRASE) {
+        Ok(passphrase) => passphrase,
+        Err(_) => Password::new("Enter a passphrase for the keystore:")
+            .with_custom_confirmation_message("Confirm the passphrase:")
+            .with_custom_confirmation_error_message("The passphrases don't match.")
+            .prompt()?,
+    });
+
+    if passphrase.is_empty() {
+        bail!("The keystore passphrase cannot be empty");
+    }
+
+    Ok(passphrase)
 }
 
 impl From<&SuiKeyPair> for Key {
@@ -13,6 +13,7 @@ pub mod genesis_ceremony;
 pub mod genesis_inspector;
 pub mod key_identity;
 pub mod keytool;
+pub mod multisig_commands;
 pub mod shell;
 pub mod sui_commands;
 pub mod upgrade_compatibility;
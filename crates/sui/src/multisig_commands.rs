@@ -0,0 +1,413 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context};
+use clap::*;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiSignature};
+use sui_types::digests::TransactionDigest;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+
+use crate::client_commands::SuiClientCommandResult;
+use crate::key_identity::{get_identity_address, KeyIdentity};
+use crate::keytool::unlock_keystore;
+
+/// Coordinate signing a transaction from a MultiSig address. A proposal file holds the unsigned
+/// transaction, the MultiSig public key, and the signatures collected so far, and can be passed
+/// between signers until enough weight has signed to execute it.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum MultiSigCommand {
+    /// Create a proposal file for a transaction whose sender is the MultiSig address defined by
+    /// `pks`, `weights` and `threshold`.
+    Propose {
+        /// Path to write the proposal to.
+        proposal: PathBuf,
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string.
+        /// This is the output of sui client commands using --serialize-unsigned-transaction.
+        #[clap(long)]
+        tx_bytes: String,
+        /// The public keys (`flag || pk` in Base64) of the MultiSig's members.
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        /// The weight of each public key, in the same order as `pks`.
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        /// The total weight of signatures needed to execute the transaction.
+        #[clap(long)]
+        threshold: ThresholdUnit,
+    },
+    /// Sign the proposal's transaction with a key in the local keystore, and add the signature to
+    /// the proposal.
+    Sign {
+        /// Path to the proposal.
+        proposal: PathBuf,
+        /// The address (or its alias) of the key to sign with. Defaults to the first key in the
+        /// keystore that is a member of the MultiSig and has not signed yet.
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+    },
+    /// Show which members have signed the proposal, and the weight collected so far.
+    Status {
+        /// Path to the proposal.
+        proposal: PathBuf,
+    },
+    /// Combine the proposal's signatures into a MultiSig signature and execute the transaction.
+    /// Fails if the signatures collected so far don't meet the threshold.
+    Execute {
+        /// Path to the proposal.
+        proposal: PathBuf,
+    },
+}
+
+/// The contents of a proposal file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposal {
+    /// BCS serialized transaction data, as Base64.
+    pub tx_bytes: String,
+    /// Public keys of the MultiSig's members, as Base64 `flag || pk`.
+    pub pks: Vec<String>,
+    pub weights: Vec<WeightUnit>,
+    pub threshold: ThresholdUnit,
+    /// Signatures collected so far, as Base64 `flag || sig || pk`.
+    pub signatures: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposalStatus {
+    pub multisig_address: SuiAddress,
+    pub tx_digest: TransactionDigest,
+    pub members: Vec<MultiSigMember>,
+    pub weight: ThresholdUnit,
+    pub threshold: ThresholdUnit,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigMember {
+    pub address: SuiAddress,
+    pub public_base64_key: String,
+    pub weight: WeightUnit,
+    pub signed: bool,
+}
+
+impl MultiSigCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            MultiSigCommand::Propose {
+                proposal,
+                tx_bytes,
+                pks,
+                weights,
+                threshold,
+            } => {
+                ensure!(
+                    !proposal.exists(),
+                    "Proposal file {} already exists",
+                    proposal.display(),
+                );
+
+                let tx_data = decode_tx_data(&tx_bytes)?;
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let multisig_proposal = MultiSigProposal::new(&tx_data, &multisig_pk)?;
+                multisig_proposal.write(&proposal)?;
+                SuiClientCommandResult::MultiSigProposal(multisig_proposal.status()?)
+            }
+
+            MultiSigCommand::Sign { proposal, address } => {
+                let mut multisig_proposal = MultiSigProposal::read(&proposal)?;
+                let tx_data = multisig_proposal.tx_data()?;
+                let multisig_pk = multisig_proposal.multisig_pk()?;
+
+                let signer = match address {
+                    Some(address) => get_identity_address(Some(address), context)?,
+                    None => {
+                        let signed = multisig_proposal.signers()?;
+                        multisig_pk
+                            .pubkeys()
+                            .iter()
+                            .map(|(pk, _)| SuiAddress::from(pk))
+                            .find(|address| {
+                                !signed.contains(address)
+                                    && context.config.keystore.addresses().contains(address)
+                            })
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "No key in the keystore can add a signature to the proposal"
+                                )
+                            })?
+                    }
+                };
+
+                unlock_keystore(&mut context.config.keystore)?;
+                let signature = context.config.keystore.sign_secure(
+                    &signer,
+                    &tx_data,
+                    Intent::sui_transaction(),
+                )?;
+
+                multisig_proposal.add_signature(signature)?;
+                multisig_proposal.write(&proposal)?;
+                SuiClientCommandResult::MultiSigProposal(multisig_proposal.status()?)
+            }
+
+            MultiSigCommand::Status { proposal } => SuiClientCommandResult::MultiSigProposal(
+                MultiSigProposal::read(&proposal)?.status()?,
+            ),
+
+            MultiSigCommand::Execute { proposal } => {
+                let transaction = MultiSigProposal::read(&proposal)?.transaction()?;
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
+        })
+    }
+}
+
+impl MultiSigProposal {
+    /// Create a proposal with no signatures yet. The transaction's sender must be the MultiSig
+    /// address for `multisig_pk`.
+    pub fn new(
+        tx_data: &TransactionData,
+        multisig_pk: &MultiSigPublicKey,
+    ) -> Result<Self, anyhow::Error> {
+        let multisig_address = SuiAddress::from(multisig_pk);
+        ensure!(
+            tx_data.sender() == multisig_address,
+            "Transaction sender {} is not the MultiSig address {multisig_address}",
+            tx_data.sender(),
+        );
+
+        let (pks, weights) = multisig_pk
+            .pubkeys()
+            .iter()
+            .map(|(pk, weight)| (pk.encode_base64(), *weight))
+            .unzip();
+
+        Ok(Self {
+            tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+            pks,
+            weights,
+            threshold: *multisig_pk.threshold(),
+            signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read proposal file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Cannot deserialize proposal file {}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Cannot write proposal file {}", path.display()))
+    }
+
+    pub fn tx_data(&self) -> Result<TransactionData, anyhow::Error> {
+        decode_tx_data(&self.tx_bytes)
+    }
+
+    pub fn multisig_pk(&self) -> Result<MultiSigPublicKey, anyhow::Error> {
+        let pks = self
+            .pks
+            .iter()
+            .map(|pk| {
+                PublicKey::decode_base64(pk).map_err(|e| anyhow!("Invalid public key {pk}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MultiSigPublicKey::new(
+            pks,
+            self.weights.clone(),
+            self.threshold,
+        )?)
+    }
+
+    pub fn signatures(&self) -> Result<Vec<Signature>, anyhow::Error> {
+        self.signatures
+            .iter()
+            .map(|sig| {
+                Signature::decode_base64(sig).map_err(|e| anyhow!("Invalid signature {sig}: {e}"))
+            })
+            .collect()
+    }
+
+    /// Addresses of the members who have signed so far.
+    pub fn signers(&self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        self.signatures()?
+            .into_iter()
+            .map(|sig| Ok(SuiAddress::from(&signature_public_key(&sig)?)))
+            .collect()
+    }
+
+    /// Add `signature` to the proposal, replacing any earlier signature from the same member. The
+    /// signature must be from a member of the MultiSig, over the proposal's transaction.
+    pub fn add_signature(&mut self, signature: Signature) -> Result<(), anyhow::Error> {
+        let multisig_pk = self.multisig_pk()?;
+        let pk = signature_public_key(&signature)?;
+        let signer = SuiAddress::from(&pk);
+        ensure!(
+            multisig_pk.get_index(&pk).is_some(),
+            "{signer} is not a member of MultiSig {}",
+            SuiAddress::from(&multisig_pk),
+        );
+
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), self.tx_data()?);
+        signature
+            .verify_secure(&intent_msg, signer, signature.scheme())
+            .map_err(|e| anyhow!("Invalid signature from {signer}: {e}"))?;
+
+        let mut signatures = self.signatures()?;
+        signatures.retain(|sig| {
+            signature_public_key(sig)
+                .map(|other| other != pk)
+                .unwrap_or(true)
+        });
+        signatures.push(signature);
+        self.signatures = signatures.iter().map(|sig| sig.encode_base64()).collect();
+        Ok(())
+    }
+
+    pub fn status(&self) -> Result<MultiSigProposalStatus, anyhow::Error> {
+        let multisig_pk = self.multisig_pk()?;
+        let signers = self.signers()?;
+
+        let members: Vec<_> = multisig_pk
+            .pubkeys()
+            .iter()
+            .map(|(pk, weight)| {
+                let address = SuiAddress::from(pk);
+                MultiSigMember {
+                    address,
+                    public_base64_key: pk.encode_base64(),
+                    weight: *weight,
+                    signed: signers.contains(&address),
+                }
+            })
+            .collect();
+
+        let weight = members
+            .iter()
+            .filter(|m| m.signed)
+            .map(|m| m.weight as ThresholdUnit)
+            .sum();
+
+        Ok(MultiSigProposalStatus {
+            multisig_address: SuiAddress::from(&multisig_pk),
+            tx_digest: self.tx_data()?.digest(),
+            members,
+            weight,
+            threshold: *multisig_pk.threshold(),
+        })
+    }
+
+    /// Combine the signatures collected so far into a MultiSig signed transaction.
+    pub fn transaction(&self) -> Result<Transaction, anyhow::Error> {
+        let status = self.status()?;
+        if status.weight < status.threshold {
+            bail!(
+                "The proposal has collected a weight of {}, but needs {} to be executed",
+                status.weight,
+                status.threshold,
+            );
+        }
+
+        // Signatures need to be in the same order as their public keys in the MultiSig.
+        let multisig_pk = self.multisig_pk()?;
+        let mut signatures = self.signatures()?;
+        signatures.sort_by_key(|sig| {
+            signature_public_key(sig)
+                .ok()
+                .and_then(|pk| multisig_pk.get_index(&pk))
+        });
+
+        let multisig = MultiSig::combine(
+            signatures
+                .into_iter()
+                .map(GenericSignature::Signature)
+                .collect(),
+            multisig_pk,
+        )?;
+
+        Ok(Transaction::from_generic_sig_data(
+            self.tx_data()?,
+            vec![GenericSignature::MultiSig(multisig)],
+        ))
+    }
+}
+
+impl MultiSigProposalStatus {
+    pub fn is_ready(&self) -> bool {
+        self.weight >= self.threshold
+    }
+}
+
+impl Display for MultiSigProposalStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut builder = TableBuilder::default();
+        builder.set_header(["address", "weight", "signed"]);
+        for member in &self.members {
+            builder.push_record([
+                member.address.to_string(),
+                member.weight.to_string(),
+                if member.signed { "yes" } else { "no" }.to_string(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        table.with(TablePanel::header(format!(
+            "MultiSig {}, transaction {}",
+            self.multisig_address, self.tx_digest
+        )));
+        table.with(TablePanel::footer(if self.is_ready() {
+            format!(
+                "Collected weight {} of {}, ready to execute",
+                self.weight, self.threshold
+            )
+        } else {
+            format!(
+                "Collected weight {} of {}, needs more signatures",
+                self.weight, self.threshold
+            )
+        }));
+
+        write!(f, "{}", table)
+    }
+}
+
+fn decode_tx_data(tx_bytes: &str) -> Result<TransactionData, anyhow::Error> {
+    let bytes = Base64::decode(tx_bytes).map_err(|_| anyhow!("Invalid Base64 encoding"))?;
+    bcs::from_bytes(&bytes).map_err(|_| {
+        anyhow!(
+            "Failed to parse tx bytes, check if it matches the output of sui client commands \
+             with --serialize-unsigned-transaction"
+        )
+    })
+}
+
+fn signature_public_key(signature: &Signature) -> Result<PublicKey, anyhow::Error> {
+    Ok(GenericSignature::Signature(signature.clone()).to_public_key()?)
+}
@@ -28,6 +28,7 @@ use sui_types::{
 use tap::tap::TapOptional;
 
 use crate::fire_drill::get_gas_obj_ref;
+use crate::keytool::unlock_keystore;
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
@@ -285,6 +286,7 @@ impl SuiValidatorCommand {
             } => {
                 let dir = std::env::current_dir()?;
                 let protocol_key_file_name = dir.join("protocol.key");
+                unlock_keystore(&mut context.config.keystore)?;
                 let account_key = match context.config.keystore.get_key(&sui_address)? {
                     SuiKeyPair::Ed25519(account_key) => SuiKeyPair::Ed25519(account_key.copy()),
                     _ => panic!(
@@ -575,7 +577,8 @@ impl SuiValidatorCommand {
                         serialized_unsigned_transaction: Some(serialized_data),
                     }
                 } else {
-                    let tx = context.sign_transaction(&tx_data);
+                    unlock_keystore(&mut context.config.keystore)?;
+                    let tx = context.sign_transaction(&tx_data)?;
                     let response = context.execute_transaction_must_succeed(tx).await;
                     println!(
                         "Committee registration successful. Transaction digest: {}",
@@ -653,7 +656,8 @@ impl SuiValidatorCommand {
                         serialized_unsigned_transaction: Some(serialized_data),
                     }
                 } else {
-                    let tx = context.sign_transaction(&tx_data);
+                    unlock_keystore(&mut context.config.keystore)?;
+                    let tx = context.sign_transaction(&tx_data)?;
                     let response = context.execute_transaction_must_succeed(tx).await;
                     println!(
                         "Update Bridge validator node URL successful. Transaction digest: {}",
@@ -864,6 +868,7 @@ async fn call_0x5(
     let sender = context.active_address()?;
     let tx_data =
         construct_unsigned_0x5_txn(context, sender, function, call_args, gas_budget).await?;
+    unlock_keystore(&mut context.config.keystore)?;
     let signature =
         context
             .config
@@ -12,13 +12,16 @@ use std::env;
 use std::str::FromStr;
 
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
 use sui::client_ptb::ptb::PTB;
 use sui::key_identity::{get_identity_address, KeyIdentity};
+use sui::multisig_commands::MultiSigCommand;
 use sui::sui_commands::IndexerArgs;
 use sui_sdk::SuiClient;
-use sui_test_transaction_builder::batch_make_transfer_transactions;
+use sui_test_transaction_builder::{batch_make_transfer_transactions, TestTransactionBuilder};
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::object::Owner;
 use sui_types::transaction::{
     TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
@@ -3098,6 +3101,114 @@ async fn test_execute_signed_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_multisig_proposal() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let addresses = test_cluster.wallet.get_addresses();
+    let pks: Vec<_> = addresses[..3]
+        .iter()
+        .map(|a| {
+            test_cluster
+                .wallet
+                .config
+                .keystore
+                .get_key(a)
+                .unwrap()
+                .public()
+        })
+        .collect();
+
+    // A 2-of-3 MultiSig, made up of keys in the wallet.
+    let multisig_pk = MultiSigPublicKey::new(pks.clone(), vec![1, 1, 1], 2)?;
+    let multisig_address = SuiAddress::from(&multisig_pk);
+    let gas = test_cluster
+        .fund_address_and_return_gas(rgp, Some(20_000_000_000), multisig_address)
+        .await;
+    let tx_data = TestTransactionBuilder::new(multisig_address, gas, rgp)
+        .transfer_sui(Some(1000), addresses[0])
+        .build();
+
+    let temp_dir = TempDir::new()?;
+    let proposal = temp_dir.path().join("proposal.json");
+    let context = &mut test_cluster.wallet;
+
+    let weight = |result: SuiClientCommandResult| match result {
+        SuiClientCommandResult::MultiSigProposal(status) => status.weight,
+        _ => panic!("Expected a MultiSig proposal status"),
+    };
+
+    let resp = SuiClientCommands::MultiSig {
+        cmd: MultiSigCommand::Propose {
+            proposal: proposal.clone(),
+            tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+            pks,
+            weights: vec![1, 1, 1],
+            threshold: 2,
+        },
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(weight(resp), 0);
+
+    // Signing twice with the same key doesn't add any weight.
+    for _ in 0..2 {
+        let resp = SuiClientCommands::MultiSig {
+            cmd: MultiSigCommand::Sign {
+                proposal: proposal.clone(),
+                address: Some(KeyIdentity::Address(addresses[1])),
+            },
+        }
+        .execute(context)
+        .await?;
+        assert_eq!(weight(resp), 1);
+    }
+
+    let execute = SuiClientCommands::MultiSig {
+        cmd: MultiSigCommand::Execute {
+            proposal: proposal.clone(),
+        },
+    }
+    .execute(context)
+    .await;
+    assert!(execute.is_err());
+
+    // Without an address, the first member that hasn't signed yet signs.
+    SuiClientCommands::MultiSig {
+        cmd: MultiSigCommand::Sign {
+            proposal: proposal.clone(),
+            address: None,
+        },
+    }
+    .execute(context)
+    .await?;
+
+    let resp = SuiClientCommands::MultiSig {
+        cmd: MultiSigCommand::Status {
+            proposal: proposal.clone(),
+        },
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::MultiSigProposal(status) = resp else {
+        panic!("Expected a MultiSig proposal status");
+    };
+    assert_eq!(status.weight, 2);
+    assert_eq!(status.multisig_address, multisig_address);
+    assert!(status.members[0].signed && status.members[1].signed && !status.members[2].signed);
+
+    let resp = SuiClientCommands::MultiSig {
+        cmd: MultiSigCommand::Execute { proposal },
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::TransactionBlock(response) = resp else {
+        panic!("Expected a transaction block response");
+    };
+    assert!(response.status_ok().unwrap());
+    Ok(())
+}
+
 #[sim_test]
 async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
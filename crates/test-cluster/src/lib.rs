@@ -581,14 +581,14 @@ impl TestCluster {
     }
 
     pub fn sign_transaction(&self, tx_data: &TransactionData) -> Transaction {
-        self.wallet.sign_transaction(tx_data)
+        self.wallet.sign_transaction(tx_data).unwrap()
     }
 
     pub async fn sign_and_execute_transaction(
         &self,
         tx_data: &TransactionData,
     ) -> SuiTransactionBlockResponse {
-        let tx = self.wallet.sign_transaction(tx_data);
+        let tx = self.wallet.sign_transaction(tx_data).unwrap();
         self.execute_transaction(tx).await
     }
 
@@ -711,11 +711,13 @@ impl TestCluster {
     ) -> ObjectRef {
         let context = &self.wallet;
         let (sender, gas) = context.get_one_gas_object().await.unwrap().unwrap();
-        let tx = context.sign_transaction(
-            &TestTransactionBuilder::new(sender, gas, rgp)
-                .transfer_sui(amount, funding_address)
-                .build(),
-        );
+        let tx = context
+            .sign_transaction(
+                &TestTransactionBuilder::new(sender, gas, rgp)
+                    .transfer_sui(amount, funding_address)
+                    .build(),
+            )
+            .unwrap();
         context.execute_transaction_must_succeed(tx).await;
 
         context
@@ -1565,6 +1565,27 @@ impl DBBatch {
             .observe(total as f64);
         Ok(self)
     }
+
+    /// merges a range of (key, operand) pairs given as an iterator, using the merge operator
+    /// configured for the table's column family. Operands are serialized like values.
+    pub fn partial_merge_batch<J: Borrow<K>, K: Serialize, U: Borrow<V>, V: Serialize>(
+        &mut self,
+        db: &DBMap<K, V>,
+        operands: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<&mut Self, TypedStoreError> {
+        if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        operands
+            .into_iter()
+            .try_for_each::<_, Result<_, TypedStoreError>>(|(k, o)| {
+                let k_buf = be_fix_int_ser(k.borrow())?;
+                let o_buf = bcs::to_bytes(o.borrow()).map_err(typed_store_err_from_bcs_err)?;
+                self.batch.merge_cf(&db.cf(), k_buf, o_buf);
+                Ok(())
+            })?;
+        Ok(self)
+    }
 }
 
 pub struct DBTransaction<'a> {
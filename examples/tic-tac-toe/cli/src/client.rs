@@ -364,7 +364,7 @@ impl Client {
         );
 
         let data = self.build_tx_data(player, builder.finish()).await?;
-        let tx = self.wallet.sign_transaction(&data);
+        let tx = self.wallet.sign_transaction(&data)?;
         self.execute_transaction(tx).await?;
         Ok(())
     }
@@ -445,7 +445,7 @@ impl Client {
         );
 
         let data = self.build_tx_data(player, builder.finish()).await?;
-        let tx = self.wallet.sign_transaction(&data);
+        let tx = self.wallet.sign_transaction(&data)?;
         self.execute_transaction(tx).await?;
         Ok(())
     }
@@ -479,7 +479,7 @@ impl Client {
         );
 
         let data = self.build_tx_data(player, builder.finish()).await?;
-        let tx = self.wallet.sign_transaction(&data);
+        let tx = self.wallet.sign_transaction(&data)?;
         let SuiTransactionBlockResponse {
             object_changes: Some(object_changes),
             ..
@@ -552,7 +552,7 @@ impl Client {
 
     /// Execute a PTB, expecting it to create a shared or owned Game, and return its ObjectID.
     async fn execute_for_game(&self, data: TransactionData) -> Result<ObjectID> {
-        let tx = self.wallet.sign_transaction(&data);
+        let tx = self.wallet.sign_transaction(&data)?;
         let SuiTransactionBlockResponse {
             object_changes: Some(object_changes),
             ..